@@ -104,9 +104,18 @@ pub struct CacheFlags {
   pub files: Vec<String>,
 }
 
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum CheckReporterKind {
+  #[default]
+  Pretty,
+  Sarif,
+  Checkstyle,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct CheckFlags {
   pub files: Vec<String>,
+  pub reporter: CheckReporterKind,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -117,6 +126,28 @@ pub struct CompileFlags {
   pub target: Option<String>,
   pub no_terminal: bool,
   pub include: Vec<String>,
+  pub list_contents: bool,
+  pub compress: bool,
+  pub inspect_binary: bool,
+  pub sbom: Option<SbomFormat>,
+}
+
+/// The format of the software bill of materials of `deno compile --sbom`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SbomFormat {
+  CycloneDx,
+  Spdx,
+}
+
+impl SbomFormat {
+  /// The extension of the file the document is written to next to the
+  /// executable.
+  pub fn file_extension(&self) -> &'static str {
+    match self {
+      SbomFormat::CycloneDx => "cdx.json",
+      SbomFormat::Spdx => "spdx.json",
+    }
+  }
 }
 
 impl CompileFlags {
@@ -200,7 +231,11 @@ pub struct FmtFlags {
   pub prose_wrap: Option<String>,
   pub no_semicolons: Option<bool>,
   pub watch: Option<WatchFlags>,
+  pub unstable_css: bool,
+  pub unstable_html: bool,
+  pub unstable_component: bool,
   pub unstable_yaml: bool,
+  pub unstable_template_tags: bool,
 }
 
 impl FmtFlags {
@@ -214,6 +249,7 @@ impl FmtFlags {
 pub struct InitFlags {
   pub dir: Option<String>,
   pub lib: bool,
+  pub template: Option<String>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -231,11 +267,25 @@ pub struct InstallFlagsGlobal {
   pub force: bool,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InstallFlagsGlobalList {
+  pub root: Option<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InstallFlagsGlobalUpgrade {
+  /// Only upgrade the executable with this name, otherwise all of them.
+  pub name: Option<String>,
+  pub root: Option<String>,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum InstallKind {
   #[allow(unused)]
   Local(Option<AddFlags>),
   Global(InstallFlagsGlobal),
+  GlobalList(InstallFlagsGlobalList),
+  GlobalUpgrade(InstallFlagsGlobalUpgrade),
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -270,6 +320,16 @@ pub struct UninstallFlags {
   pub kind: UninstallKind,
 }
 
+#[derive(Clone, Copy, Default, Debug, Eq, PartialEq)]
+pub enum LintReporterKind {
+  #[default]
+  Pretty,
+  Json,
+  Compact,
+  Sarif,
+  Checkstyle,
+}
+
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct LintFlags {
   pub files: FileFlags,
@@ -280,6 +340,9 @@ pub struct LintFlags {
   pub maybe_rules_exclude: Option<Vec<String>>,
   pub json: bool,
   pub compact: bool,
+  pub reporter: Option<LintReporterKind>,
+  pub baseline: Option<String>,
+  pub baseline_write: bool,
   pub watch: Option<WatchFlags>,
 }
 
@@ -323,6 +386,8 @@ pub struct ServeFlags {
   pub watch: Option<WatchFlagsWithPaths>,
   pub port: u16,
   pub host: String,
+  /// Number of worker processes sharing the port.
+  pub parallel: Option<NonZeroUsize>,
 }
 
 impl ServeFlags {
@@ -333,6 +398,7 @@ impl ServeFlags {
       watch: None,
       port,
       host: host.to_owned(),
+      parallel: None,
     }
   }
 }
@@ -356,6 +422,18 @@ pub struct WatchFlagsWithPaths {
 pub struct TaskFlags {
   pub cwd: Option<String>,
   pub task: Option<String>,
+  pub recursive: bool,
+  pub filter: Option<String>,
+  pub shell: TaskShell,
+}
+
+/// The shell that runs the scripts of `deno task`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum TaskShell {
+  /// The cross-platform shell built into Deno.
+  #[default]
+  Deno,
+  Bash,
 }
 
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
@@ -1559,6 +1637,12 @@ Unless --reload is specified, this command will not re-download already cached d
           .conflicts_with("no-remote")
           .hide(true)
       )
+      .arg(
+        Arg::new("reporter")
+          .long("reporter")
+          .help("Select reporter to use for type errors. Defaults to 'pretty'")
+          .value_parser(["pretty", "sarif", "checkstyle"])
+      )
       .arg(
         Arg::new("file")
           .num_args(1..)
@@ -1591,23 +1675,96 @@ Cross-compiling to different target architectures is supported using the
 `--target` flag. On the first invocation with deno will download proper
 binary and cache it in $DENO_DIR. The aarch64-apple-darwin target is not
 supported in canary.
+
+Use the `--inspect-binary` flag to audit an existing executable. It prints the
+permissions, environment variables and other options the executable was
+compiled with, as well as the embedded modules and files. When combined with
+`--output <dir>`, the modules and files are also extracted to that directory:
+
+  deno compile --inspect-binary ./file_server
+  deno compile --inspect-binary --output ./file_server_contents ./file_server
+
+Use the `--sbom` flag to generate a software bill of materials listing the jsr,
+npm and remote modules embedded in the executable. It's written next to the
+executable and embedded in it, where `--inspect-binary --sbom` prints it back:
+
+  deno compile --sbom --output file_server jsr:@std/http/file-server
+  deno compile --inspect-binary --sbom ./file_server
 ",
     )
     .defer(|cmd| {
       runtime_args(cmd, true, false)
       .arg(check_arg(true))
+      .arg(no_code_cache_arg())
       .arg(
         Arg::new("include")
           .long("include")
-          .help("Additional module to include in the module graph")
+          .help("Additional module or file to include in the executable")
           .long_help(
-            "Includes an additional module in the compiled executable's module
-    graph. Use this flag if a dynamically imported module or a web worker main
-    module fails to load in the executable. This flag can be passed multiple
-    times, to include multiple additional modules.",
+            "Includes an additional module, file or directory in the compiled
+    executable. Remote modules and local script files are added to the
+    executable's module graph. Use this if a dynamically imported module or a
+    web worker main module fails to load in the executable. Any other file,
+    directory or glob is embedded in the executable's virtual file system and
+    can be read at runtime at the same path relative to the entrypoint, for
+    example via `Deno.readFile(import.meta.dirname + \"/static/index.html\")`.
+    This flag can be passed multiple times.",
           )
           .action(ArgAction::Append)
-          .value_hint(ValueHint::FilePath),
+          .value_hint(ValueHint::AnyPath),
+      )
+      .arg(
+        Arg::new("list-contents")
+          .long("list-contents")
+          .help("List the modules and files embedded in the executable")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("compress")
+          .long("compress")
+          .help("Compress the modules and files embedded in the executable")
+          .long_help(
+            "Compresses the modules and files embedded in the executable with
+    zstd. This makes executables that bundle npm packages or many files much
+    smaller, at the cost of decompressing the modules on startup and each file
+    the first time it is read.",
+          )
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("inspect-binary")
+          .long("inspect-binary")
+          .help("Show the contents of an executable produced by deno compile")
+          .long_help(
+            "Shows the options, modules and files of an executable produced by
+    deno compile, which is passed instead of a script. When combined with
+    `--output <dir>`, the modules and files are extracted to that directory.",
+          )
+          .conflicts_with_all([
+            "include",
+            "list-contents",
+            "compress",
+            "target",
+            "no-terminal",
+          ])
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("sbom")
+          .long("sbom")
+          .value_name("FORMAT")
+          .help("Generate a software bill of materials for the executable")
+          .long_help(
+            "Generates a software bill of materials in the CycloneDX (default) or
+    SPDX format, listing the jsr, npm and remote modules embedded in the
+    executable with their versions and the integrity hashes from the lockfile.
+    It's written to `<output>.cdx.json` or `<output>.spdx.json` and embedded
+    in the executable. With `--inspect-binary`, prints the embedded document.",
+          )
+          .num_args(0..=1)
+          .require_equals(true)
+          .default_missing_value("cyclonedx")
+          .value_parser(["cyclonedx", "spdx"]),
       )
       .arg(
         Arg::new("output")
@@ -1997,7 +2154,8 @@ Ignore formatting a file by adding an ignore comment at the top of the file:
             // prefer using ts for formatting instead of js because ts works in more scenarios
             .default_value("ts")
             .value_parser([
-              "ts", "tsx", "js", "jsx", "md", "json", "jsonc", "yml", "yaml",
+              "ts", "tsx", "js", "jsx", "md", "json", "jsonc", "css", "scss",
+              "sass", "less", "html", "svelte", "vue", "astro", "yml", "yaml",
               "ipynb",
             ]),
         )
@@ -2075,6 +2233,27 @@ Ignore formatting a file by adding an ignore comment at the top of the file:
               "Don't use semicolons except where necessary. Defaults to false.",
             ),
         )
+        .arg(
+          Arg::new("unstable-css")
+            .long("unstable-css")
+            .help("Enable formatting CSS, SCSS, Sass and Less files.")
+            .value_parser(FalseyValueParser::new())
+            .action(ArgAction::SetTrue),
+        )
+        .arg(
+          Arg::new("unstable-html")
+            .long("unstable-html")
+            .help("Enable formatting HTML files.")
+            .value_parser(FalseyValueParser::new())
+            .action(ArgAction::SetTrue),
+        )
+        .arg(
+          Arg::new("unstable-component")
+            .long("unstable-component")
+            .help("Enable formatting Svelte, Vue and Astro files.")
+            .value_parser(FalseyValueParser::new())
+            .action(ArgAction::SetTrue),
+        )
         .arg(
           Arg::new("unstable-yaml")
             .long("unstable-yaml")
@@ -2082,12 +2261,44 @@ Ignore formatting a file by adding an ignore comment at the top of the file:
             .value_parser(FalseyValueParser::new())
             .action(ArgAction::SetTrue),
         )
+        .arg(
+          Arg::new("unstable-template-tags")
+            .long("unstable-template-tags")
+            .help(
+              "Enable formatting html, css, sql and graphql tagged templates.",
+            )
+            .value_parser(FalseyValueParser::new())
+            .action(ArgAction::SetTrue),
+        )
     })
 }
 
 fn init_subcommand() -> Command {
   Command::new("init")
     .about("Initialize a new project")
+    .long_about(
+"Initialize a new project in the current directory or the provided directory.
+
+  deno init
+  deno init my_project
+  deno init --lib my_library
+
+To start from a project template, use --template. The built-in templates are
+'serve' (an HTTP server with tests), 'workspace' (a workspace with two members)
+and 'cli' (a command line tool that can be compiled into an executable):
+
+  deno init --template=serve my_server
+
+A template can also be a package on JSR, a local directory or a URL to a
+.tar.gz archive:
+
+  deno init --template=jsr:@scope/template my_project
+  deno init --template=./templates/app my_project
+  deno init --template=https://example.com/template.tar.gz my_project
+
+Occurrences of {{name}} and {{author}} in the paths and text files of a template
+are replaced with the project's directory name and the author from the
+GIT_AUTHOR_NAME or USER environment variable.")
     .defer(|cmd| {
       cmd
         .arg(
@@ -2101,6 +2312,14 @@ fn init_subcommand() -> Command {
             .required(false)
             .action(ArgAction::SetTrue),
         )
+        .arg(
+          Arg::new("template")
+            .long("template")
+            .help("Create the project from a template: serve, workspace, cli, a jsr: package, a directory or a tarball URL")
+            .value_name("TEMPLATE")
+            .require_equals(true)
+            .conflicts_with("lib"),
+        )
     })
 }
 
@@ -2166,7 +2385,7 @@ fn install_args(cmd: Command, deno_future: bool) -> Command {
   } else {
     cmd.arg(
       Arg::new("cmd")
-        .required(true)
+        .required_unless_present_any(["list", "upgrade"])
         .num_args(1..)
         .value_hint(ValueHint::FilePath),
     )
@@ -2199,6 +2418,21 @@ fn install_args(cmd: Command, deno_future: bool) -> Command {
         .help("Install a package or script as a globally available executable")
         .action(ArgAction::SetTrue),
     )
+    .arg(
+      Arg::new("list")
+        .long("list")
+        .help("List the executables installed in the installation root")
+        .conflicts_with_all(["cmd", "name", "force", "upgrade"])
+        .action(ArgAction::SetTrue),
+    )
+    .arg(
+      Arg::new("upgrade")
+        .long("upgrade")
+        .help("Reinstall executables at the newest compatible version")
+        .value_name("NAME")
+        .num_args(0..=1)
+        .conflicts_with_all(["cmd", "name", "force"]),
+    )
     .arg(env_file_arg())
 }
 
@@ -2247,7 +2481,18 @@ The installation root is determined, in order of precedence:
   - DENO_INSTALL_ROOT environment variable
   - $HOME/.deno
 
-These must be added to the path manually if required.")
+These must be added to the path manually if required.
+
+Every executable installed this way is recorded in the installation root. To
+show them along with where they were installed from, use --list:
+
+  deno install --list
+
+To reinstall them at the newest version matching their original specifier, use
+--upgrade, optionally with the name of a single executable:
+
+  deno install --upgrade
+  deno install --upgrade serve")
     .defer(|cmd| {
       let cmd = runtime_args(cmd, true, true).arg(check_arg(true)).arg(allow_scripts_arg());
       install_args(cmd, true)
@@ -2284,7 +2529,18 @@ The installation root is determined, in order of precedence:
   - DENO_INSTALL_ROOT environment variable
   - $HOME/.deno
 
-These must be added to the path manually if required.")
+These must be added to the path manually if required.
+
+Every executable installed this way is recorded in the installation root. To
+show them along with where they were installed from, use --list:
+
+  deno install --list
+
+To reinstall them at the newest version matching their original specifier, use
+--upgrade, optionally with the name of a single executable:
+
+  deno install --upgrade
+  deno install --upgrade serve")
     .defer(|cmd| {
       let cmd = runtime_args(cmd, true, true).arg(check_arg(true));
       install_args(cmd, false)
@@ -2402,6 +2658,12 @@ Names of rules to ignore must be specified after ignore comment.
 Ignore linting a file by adding an ignore comment at the top of the file:
 
   // deno-lint-ignore-file
+
+Record the current diagnostics in a baseline file, so that only new ones get
+reported when enabling a rule in an existing codebase:
+
+  deno lint --baseline-write
+  deno lint
 ",
     )
     .defer(|cmd| {
@@ -2469,6 +2731,28 @@ Ignore linting a file by adding an ignore comment at the top of the file:
             .action(ArgAction::SetTrue)
             .conflicts_with("json"),
         )
+        .arg(
+          Arg::new("reporter")
+            .long("reporter")
+            .help("Select reporter to use. Defaults to 'pretty'")
+            .value_parser(["pretty", "json", "compact", "sarif", "checkstyle"])
+            .conflicts_with_all(["json", "compact"]),
+        )
+        .arg(
+          Arg::new("baseline")
+            .long("baseline")
+            .require_equals(true)
+            .value_name("FILE")
+            .help("Only report diagnostics that aren't recorded in this baseline file. Defaults to 'deno-lint-baseline.json' in the workspace root when that file exists")
+            .value_hint(ValueHint::FilePath),
+        )
+        .arg(
+          Arg::new("baseline-write")
+            .long("baseline-write")
+            .help("Record the current diagnostics in the baseline file")
+            .action(ArgAction::SetTrue)
+            .conflicts_with_all(["rules", "watch"]),
+        )
         .arg(
           Arg::new("files")
             .num_args(1..)
@@ -2569,6 +2853,17 @@ fn serve_subcommand() -> Command {
         .help("The TCP address to serve on, defaulting to 0.0.0.0 (all interfaces).")
         .value_parser(serve_host_validator),
     )
+    .arg(
+      Arg::new("parallel")
+        .long("parallel")
+        .help("Run the server in multiple processes sharing the same port. Defaults to the number of available CPUs or the value in the DENO_JOBS environment variable. Only supported on Linux.")
+        .num_args(0..=1)
+        .require_equals(true)
+        .value_name("WORKERS")
+        .value_parser(value_parser!(NonZeroUsize))
+        .conflicts_with("watch")
+        .conflicts_with("hmr"),
+    )
     .arg(check_arg(false))
     .arg(watch_arg(true))
     .arg(watch_exclude_arg())
@@ -2597,7 +2892,11 @@ Start a server defined in server.ts:
 
 Start a server defined in server.ts, watching for changes and running on port 5050:
 
-  deno serve --watch --port 5050 server.ts")
+  deno serve --watch --port 5050 server.ts
+
+Start 4 server processes that share port 8000:
+
+  deno serve --parallel=4 server.ts")
 }
 
 fn task_subcommand() -> Command {
@@ -2606,7 +2905,13 @@ fn task_subcommand() -> Command {
     .long_about(
       "Run a task defined in the configuration file
 
-  deno task build",
+  deno task build
+
+Run a task in every workspace member that defines it, ordered so that members
+run after the members they import:
+
+  deno task --recursive build
+  deno task --filter='@scope/*' build",
     )
     .defer(|cmd| {
       cmd
@@ -2620,6 +2925,37 @@ fn task_subcommand() -> Command {
             .help("Specify the directory to run the task in")
             .value_hint(ValueHint::DirPath),
         )
+        .arg(
+          Arg::new("recursive")
+            .long("recursive")
+            .short('r')
+            .help("Run the task in all workspace members")
+            .action(ArgAction::SetTrue)
+            .conflicts_with_all(["cwd", "filter"]),
+        )
+        .arg(
+          Arg::new("filter")
+            .long("filter")
+            .value_name("PATTERN")
+            .help("Run the task in the workspace members with a matching name")
+            .long_help(
+              "Run the task in the workspace members with a matching package name.
+Use * as a wildcard, for example --filter='@scope/*'",
+            )
+            .conflicts_with("cwd"),
+        )
+        .arg(
+          Arg::new("shell")
+            .long("shell")
+            .value_name("SHELL")
+            .help("The shell to run the task with")
+            .long_help(
+              "The shell to run the task with. Defaults to the cross-platform shell built
+into Deno. Use 'bash' to run tasks that rely on features of bash, which then
+needs to be installed",
+            )
+            .value_parser(["deno", "bash"]),
+        )
     })
 }
 
@@ -3888,7 +4224,13 @@ fn check_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   if matches.get_flag("all") || matches.get_flag("remote") {
     flags.type_check_mode = TypeCheckMode::All;
   }
-  flags.subcommand = DenoSubcommand::Check(CheckFlags { files });
+  let reporter = match matches.remove_one::<String>("reporter").as_deref() {
+    Some("sarif") => CheckReporterKind::Sarif,
+    Some("checkstyle") => CheckReporterKind::Checkstyle,
+    Some("pretty") | None => CheckReporterKind::Pretty,
+    Some(_) => unreachable!(),
+  };
+  flags.subcommand = DenoSubcommand::Check(CheckFlags { files, reporter });
 }
 
 fn compile_parse(flags: &mut Flags, matches: &mut ArgMatches) {
@@ -3905,6 +4247,17 @@ fn compile_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     Some(f) => f.collect(),
     None => vec![],
   };
+  let list_contents = matches.get_flag("list-contents");
+  let compress = matches.get_flag("compress");
+  let inspect_binary = matches.get_flag("inspect-binary");
+  let sbom =
+    matches
+      .remove_one::<String>("sbom")
+      .map(|format| match format.as_str() {
+        "spdx" => SbomFormat::Spdx,
+        _ => SbomFormat::CycloneDx,
+      });
+  flags.code_cache_enabled = !matches.get_flag("no-code-cache");
   ext_arg_parse(flags, matches);
 
   flags.subcommand = DenoSubcommand::Compile(CompileFlags {
@@ -3914,6 +4267,10 @@ fn compile_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     target,
     no_terminal,
     include,
+    list_contents,
+    compress,
+    inspect_binary,
+    sbom,
   });
 }
 
@@ -4096,7 +4453,11 @@ fn fmt_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   let single_quote = matches.remove_one::<bool>("single-quote");
   let prose_wrap = matches.remove_one::<String>("prose-wrap");
   let no_semicolons = matches.remove_one::<bool>("no-semicolons");
+  let unstable_css = matches.get_flag("unstable-css");
+  let unstable_html = matches.get_flag("unstable-html");
+  let unstable_component = matches.get_flag("unstable-component");
   let unstable_yaml = matches.get_flag("unstable-yaml");
+  let unstable_template_tags = matches.get_flag("unstable-template-tags");
 
   flags.subcommand = DenoSubcommand::Fmt(FmtFlags {
     check: matches.get_flag("check"),
@@ -4108,7 +4469,11 @@ fn fmt_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     prose_wrap,
     no_semicolons,
     watch: watch_arg_parse(matches),
+    unstable_css,
+    unstable_html,
+    unstable_component,
     unstable_yaml,
+    unstable_template_tags,
   });
 }
 
@@ -4116,6 +4481,7 @@ fn init_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   flags.subcommand = DenoSubcommand::Init(InitFlags {
     dir: matches.remove_one::<String>("dir"),
     lib: matches.get_flag("lib"),
+    template: matches.remove_one::<String>("template"),
   });
 }
 
@@ -4140,7 +4506,23 @@ fn install_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   runtime_args_parse(flags, matches, true, true);
 
   let global = matches.get_flag("global");
-  if global || !*DENO_FUTURE {
+  if matches.get_flag("list") {
+    let root = matches.remove_one::<String>("root");
+    flags.subcommand = DenoSubcommand::Install(InstallFlags {
+      global,
+      kind: InstallKind::GlobalList(InstallFlagsGlobalList { root }),
+    });
+  } else if matches.contains_id("upgrade") {
+    let root = matches.remove_one::<String>("root");
+    let name = matches.remove_one::<String>("upgrade");
+    flags.subcommand = DenoSubcommand::Install(InstallFlags {
+      global,
+      kind: InstallKind::GlobalUpgrade(InstallFlagsGlobalUpgrade {
+        name,
+        root,
+      }),
+    });
+  } else if global || !*DENO_FUTURE {
     let root = matches.remove_one::<String>("root");
     let force = matches.get_flag("force");
     let name = matches.remove_one::<String>("name");
@@ -4227,6 +4609,18 @@ fn lint_parse(flags: &mut Flags, matches: &mut ArgMatches) {
 
   let json = matches.get_flag("json");
   let compact = matches.get_flag("compact");
+  let reporter = matches.remove_one::<String>("reporter").map(|reporter| {
+    match reporter.as_str() {
+      "pretty" => LintReporterKind::Pretty,
+      "json" => LintReporterKind::Json,
+      "compact" => LintReporterKind::Compact,
+      "sarif" => LintReporterKind::Sarif,
+      "checkstyle" => LintReporterKind::Checkstyle,
+      _ => unreachable!(),
+    }
+  });
+  let baseline = matches.remove_one::<String>("baseline");
+  let baseline_write = matches.get_flag("baseline-write");
   flags.subcommand = DenoSubcommand::Lint(LintFlags {
     files: FileFlags {
       include: files,
@@ -4239,6 +4633,9 @@ fn lint_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     maybe_rules_exclude,
     json,
     compact,
+    reporter,
+    baseline,
+    baseline_write,
     watch: watch_arg_parse(matches),
   });
 }
@@ -4351,6 +4748,17 @@ fn serve_parse(
   let host = matches
     .remove_one::<String>("host")
     .unwrap_or_else(|| "0.0.0.0".to_owned());
+  let parallel = if matches.contains_id("parallel") {
+    if let Some(value) = matches.remove_one::<NonZeroUsize>("parallel") {
+      Some(value)
+    } else if let Ok(value) = env::var("DENO_JOBS") {
+      value.parse::<NonZeroUsize>().ok()
+    } else {
+      std::thread::available_parallelism().ok()
+    }
+  } else {
+    None
+  };
 
   runtime_args_parse(flags, matches, true, true);
   // If the user didn't pass --allow-net, add this port to the network
@@ -4391,6 +4799,7 @@ fn serve_parse(
     watch: watch_arg_parse_with_paths(matches),
     port,
     host,
+    parallel,
   });
 
   Ok(())
@@ -4405,6 +4814,12 @@ fn task_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   let mut task_flags = TaskFlags {
     cwd: matches.remove_one::<String>("cwd"),
     task: None,
+    recursive: matches.get_flag("recursive"),
+    filter: matches.remove_one::<String>("filter"),
+    shell: match matches.remove_one::<String>("shell").as_deref() {
+      Some("bash") => TaskShell::Bash,
+      _ => TaskShell::Deno,
+    },
   };
 
   if let Some((task, mut matches)) = matches.remove_subcommand() {
@@ -5588,6 +6003,34 @@ mod tests {
     );
   }
 
+  #[test]
+  fn serve_parallel() {
+    let r = flags_from_vec(svec!["deno", "serve", "--parallel=4", "main.ts"]);
+    let flags = r.unwrap();
+    let DenoSubcommand::Serve(serve_flags) = flags.subcommand else {
+      panic!("expected serve subcommand");
+    };
+    assert_eq!(serve_flags.parallel, NonZeroUsize::new(4));
+    assert_eq!(serve_flags.script, "main.ts");
+
+    let r = flags_from_vec(svec!["deno", "serve", "--parallel", "main.ts"]);
+    let flags = r.unwrap();
+    let DenoSubcommand::Serve(serve_flags) = flags.subcommand else {
+      panic!("expected serve subcommand");
+    };
+    assert!(serve_flags.parallel.is_some());
+    assert_eq!(serve_flags.script, "main.ts");
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "serve",
+      "--parallel=2",
+      "--watch",
+      "main.ts"
+    ]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn has_permission() {
     let r = flags_from_vec(svec!["deno", "run", "--allow-read", "x.ts"]);
@@ -5791,7 +6234,11 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          unstable_css: false,
+          unstable_html: false,
+          unstable_component: false,
           unstable_yaml: false,
+          unstable_template_tags: false,
           watch: Default::default(),
         }),
         ext: Some("ts".to_string()),
@@ -5815,7 +6262,11 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          unstable_css: false,
+          unstable_html: false,
+          unstable_component: false,
           unstable_yaml: false,
+          unstable_template_tags: false,
           watch: Default::default(),
         }),
         ext: Some("ts".to_string()),
@@ -5839,7 +6290,11 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          unstable_css: false,
+          unstable_html: false,
+          unstable_component: false,
           unstable_yaml: false,
+          unstable_template_tags: false,
           watch: Default::default(),
         }),
         ext: Some("ts".to_string()),
@@ -5863,7 +6318,11 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          unstable_css: false,
+          unstable_html: false,
+          unstable_component: false,
           unstable_yaml: false,
+          unstable_template_tags: false,
           watch: Some(Default::default()),
         }),
         ext: Some("ts".to_string()),
@@ -5876,7 +6335,11 @@ mod tests {
       "fmt",
       "--watch",
       "--no-clear-screen",
-      "--unstable-yaml"
+      "--unstable-css",
+      "--unstable-html",
+      "--unstable-component",
+      "--unstable-yaml",
+      "--unstable-template-tags"
     ]);
     assert_eq!(
       r.unwrap(),
@@ -5893,7 +6356,11 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          unstable_css: true,
+          unstable_html: true,
+          unstable_component: true,
           unstable_yaml: true,
+          unstable_template_tags: true,
           watch: Some(WatchFlags {
             hmr: false,
             no_clear_screen: true,
@@ -5928,7 +6395,11 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          unstable_css: false,
+          unstable_html: false,
+          unstable_component: false,
           unstable_yaml: false,
+          unstable_template_tags: false,
           watch: Some(Default::default()),
         }),
         ext: Some("ts".to_string()),
@@ -5952,7 +6423,11 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          unstable_css: false,
+          unstable_html: false,
+          unstable_component: false,
           unstable_yaml: false,
+          unstable_template_tags: false,
           watch: Default::default(),
         }),
         ext: Some("ts".to_string()),
@@ -5984,7 +6459,11 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          unstable_css: false,
+          unstable_html: false,
+          unstable_component: false,
           unstable_yaml: false,
+          unstable_template_tags: false,
           watch: Some(Default::default()),
         }),
         config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
@@ -6021,7 +6500,11 @@ mod tests {
           single_quote: Some(true),
           prose_wrap: Some("never".to_string()),
           no_semicolons: Some(true),
+          unstable_css: false,
+          unstable_html: false,
+          unstable_component: false,
           unstable_yaml: false,
+          unstable_template_tags: false,
           watch: Default::default(),
         }),
         ext: Some("ts".to_string()),
@@ -6052,7 +6535,11 @@ mod tests {
           single_quote: Some(false),
           prose_wrap: None,
           no_semicolons: Some(false),
+          unstable_css: false,
+          unstable_html: false,
+          unstable_component: false,
           unstable_yaml: false,
+          unstable_template_tags: false,
           watch: Default::default(),
         }),
         ext: Some("ts".to_string()),
@@ -6079,6 +6566,9 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          reporter: None,
+          baseline: None,
+          baseline_write: false,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -6107,6 +6597,9 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          reporter: None,
+          baseline: None,
+          baseline_write: false,
           watch: Some(Default::default()),
         }),
         ..Flags::default()
@@ -6136,6 +6629,9 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          reporter: None,
+          baseline: None,
+          baseline_write: false,
           watch: Some(WatchFlags {
             hmr: false,
             no_clear_screen: true,
@@ -6167,6 +6663,9 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          reporter: None,
+          baseline: None,
+          baseline_write: false,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -6189,6 +6688,9 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          reporter: None,
+          baseline: None,
+          baseline_write: false,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -6216,6 +6718,9 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          reporter: None,
+          baseline: None,
+          baseline_write: false,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -6244,6 +6749,9 @@ mod tests {
           maybe_rules_exclude: Some(svec!["no-const-assign"]),
           json: false,
           compact: false,
+          reporter: None,
+          baseline: None,
+          baseline_write: false,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -6266,6 +6774,9 @@ mod tests {
           maybe_rules_exclude: None,
           json: true,
           compact: false,
+          reporter: None,
+          baseline: None,
+          baseline_write: false,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -6295,6 +6806,9 @@ mod tests {
           maybe_rules_exclude: None,
           json: true,
           compact: false,
+          reporter: None,
+          baseline: None,
+          baseline_write: false,
           watch: Default::default(),
         }),
         config_flag: ConfigFlag::Path("Deno.jsonc".to_string()),
@@ -6325,12 +6839,62 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: true,
+          reporter: None,
+          baseline: None,
+          baseline_write: false,
           watch: Default::default(),
         }),
         config_flag: ConfigFlag::Path("Deno.jsonc".to_string()),
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec!["deno", "lint", "--reporter=sarif"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Lint(LintFlags {
+          reporter: Some(LintReporterKind::Sarif),
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "lint", "--reporter=checkstyle", "--json"]);
+    assert_eq!(
+      r.unwrap_err().kind(),
+      clap::error::ErrorKind::ArgumentConflict
+    );
+  }
+
+  #[test]
+  fn lint_baseline() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "lint",
+      "--baseline=lint-baseline.json",
+      "--baseline-write"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Lint(LintFlags {
+          baseline: Some("lint-baseline.json".to_string()),
+          baseline_write: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "lint", "--baseline-write", "--watch"]);
+    assert_eq!(
+      r.unwrap_err().kind(),
+      clap::error::ErrorKind::ArgumentConflict
+    );
   }
 
   #[test]
@@ -6367,6 +6931,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Check(CheckFlags {
           files: svec!["script.ts"],
+          reporter: CheckReporterKind::Pretty,
         }),
         type_check_mode: TypeCheckMode::Local,
         ..Flags::default()
@@ -6380,6 +6945,7 @@ mod tests {
         Flags {
           subcommand: DenoSubcommand::Check(CheckFlags {
             files: svec!["script.ts"],
+            reporter: CheckReporterKind::Pretty,
           }),
           type_check_mode: TypeCheckMode::All,
           ..Flags::default()
@@ -6398,6 +6964,20 @@ mod tests {
         clap::error::ErrorKind::ArgumentConflict
       );
     }
+
+    let r =
+      flags_from_vec(svec!["deno", "check", "--reporter=sarif", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Check(CheckFlags {
+          files: svec!["script.ts"],
+          reporter: CheckReporterKind::Sarif,
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
   }
 
   #[test]
@@ -7759,6 +8339,66 @@ mod tests {
     );
   }
 
+  #[test]
+  fn install_list_and_upgrade() {
+    let r = flags_from_vec(svec!["deno", "install", "--list"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Install(InstallFlags {
+          kind: InstallKind::GlobalList(InstallFlagsGlobalList { root: None }),
+          global: false,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "install",
+      "-g",
+      "--upgrade",
+      "--root",
+      "/foo"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Install(InstallFlags {
+          kind: InstallKind::GlobalUpgrade(InstallFlagsGlobalUpgrade {
+            name: None,
+            root: Some("/foo".to_string()),
+          }),
+          global: true,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "install", "--upgrade", "serve"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Install(InstallFlags {
+          kind: InstallKind::GlobalUpgrade(InstallFlagsGlobalUpgrade {
+            name: Some("serve".to_string()),
+            root: None,
+          }),
+          global: false,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "install",
+      "--list",
+      "jsr:@std/http/file-server"
+    ]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn uninstall() {
     let r = flags_from_vec(svec!["deno", "uninstall", "file_server"]);
@@ -9305,9 +9945,14 @@ mod tests {
           args: vec![],
           target: None,
           no_terminal: false,
-          include: vec![]
+          include: vec![],
+          list_contents: false,
+          compress: false,
+          inspect_binary: false,
+          sbom: None,
         }),
         type_check_mode: TypeCheckMode::Local,
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
@@ -9316,7 +9961,7 @@ mod tests {
   #[test]
   fn compile_with_flags() {
     #[rustfmt::skip]
-    let r = flags_from_vec(svec!["deno", "compile", "--import-map", "import_map.json", "--no-remote", "--config", "tsconfig.json", "--no-check", "--unsafely-ignore-certificate-errors", "--reload", "--lock", "lock.json", "--lock-write", "--cert", "example.crt", "--cached-only", "--location", "https:foo", "--allow-read", "--allow-net", "--v8-flags=--help", "--seed", "1", "--no-terminal", "--output", "colors", "--env=.example.env", "https://examples.deno.land/color-logging.ts", "foo", "bar", "-p", "8080"]);
+    let r = flags_from_vec(svec!["deno", "compile", "--import-map", "import_map.json", "--no-remote", "--config", "tsconfig.json", "--no-check", "--unsafely-ignore-certificate-errors", "--reload", "--lock", "lock.json", "--lock-write", "--cert", "example.crt", "--cached-only", "--location", "https:foo", "--allow-read", "--allow-net", "--v8-flags=--help", "--seed", "1", "--no-terminal", "--no-code-cache", "--output", "colors", "--env=.example.env", "https://examples.deno.land/color-logging.ts", "foo", "bar", "-p", "8080"]);
     assert_eq!(
       r.unwrap(),
       Flags {
@@ -9327,7 +9972,11 @@ mod tests {
           args: svec!["foo", "bar", "-p", "8080"],
           target: None,
           no_terminal: true,
-          include: vec![]
+          include: vec![],
+          list_contents: false,
+          compress: false,
+          inspect_binary: false,
+          sbom: None,
         }),
         import_map_path: Some("import_map.json".to_string()),
         no_remote: true,
@@ -9353,6 +10002,158 @@ mod tests {
     );
   }
 
+  #[test]
+  fn compile_with_compress() {
+    let r = flags_from_vec(svec!["deno", "compile", "--compress", "main.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Compile(CompileFlags {
+          source_file: "main.ts".to_string(),
+          output: None,
+          args: vec![],
+          target: None,
+          no_terminal: false,
+          include: vec![],
+          list_contents: false,
+          compress: true,
+          inspect_binary: false,
+          sbom: None,
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn compile_with_include() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "compile",
+      "--include",
+      "worker.ts",
+      "--include",
+      "static/",
+      "--include",
+      "templates/*.html",
+      "--list-contents",
+      "main.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Compile(CompileFlags {
+          source_file: "main.ts".to_string(),
+          output: None,
+          args: vec![],
+          target: None,
+          no_terminal: false,
+          include: svec!["worker.ts", "static/", "templates/*.html"],
+          list_contents: true,
+          compress: false,
+          inspect_binary: false,
+          sbom: None,
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn compile_inspect_binary() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "compile",
+      "--inspect-binary",
+      "--output",
+      "contents",
+      "main"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Compile(CompileFlags {
+          source_file: "main".to_string(),
+          output: Some(String::from("contents")),
+          args: vec![],
+          target: None,
+          no_terminal: false,
+          include: vec![],
+          list_contents: false,
+          compress: false,
+          inspect_binary: true,
+          sbom: None,
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "compile",
+      "--inspect-binary",
+      "--compress",
+      "main"
+    ]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn compile_sbom() {
+    let r = flags_from_vec(svec!["deno", "compile", "--sbom", "main"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Compile(CompileFlags {
+          source_file: "main".to_string(),
+          output: None,
+          args: vec![],
+          target: None,
+          no_terminal: false,
+          include: vec![],
+          list_contents: false,
+          compress: false,
+          inspect_binary: false,
+          sbom: Some(SbomFormat::CycloneDx),
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "compile", "--sbom=spdx", "main"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Compile(CompileFlags {
+          source_file: "main".to_string(),
+          output: None,
+          args: vec![],
+          target: None,
+          no_terminal: false,
+          include: vec![],
+          list_contents: false,
+          compress: false,
+          inspect_binary: false,
+          sbom: Some(SbomFormat::Spdx),
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "compile", "--sbom=swid", "main"]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn coverage() {
     let r = flags_from_vec(svec!["deno", "coverage", "foo.json"]);
@@ -9549,6 +10350,9 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          recursive: false,
+          filter: None,
+          shell: TaskShell::Deno,
         }),
         argv: svec!["hello", "world"],
         ..Flags::default()
@@ -9562,6 +10366,9 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          recursive: false,
+          filter: None,
+          shell: TaskShell::Deno,
         }),
         ..Flags::default()
       }
@@ -9574,12 +10381,80 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: Some("foo".to_string()),
           task: Some("build".to_string()),
+          recursive: false,
+          filter: None,
+          shell: TaskShell::Deno,
         }),
         ..Flags::default()
       }
     );
   }
 
+  #[test]
+  fn task_subcommand_recursive() {
+    let r = flags_from_vec(svec!["deno", "task", "--recursive", "build"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Task(TaskFlags {
+          cwd: None,
+          task: Some("build".to_string()),
+          recursive: true,
+          filter: None,
+          shell: TaskShell::Deno,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "task", "--filter", "@scope/*", "build"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Task(TaskFlags {
+          cwd: None,
+          task: Some("build".to_string()),
+          recursive: false,
+          filter: Some("@scope/*".to_string()),
+          shell: TaskShell::Deno,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "task",
+      "--recursive",
+      "--cwd",
+      "foo",
+      "build"
+    ]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn task_subcommand_shell() {
+    let r = flags_from_vec(svec!["deno", "task", "--shell=bash", "build"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Task(TaskFlags {
+          cwd: None,
+          task: Some("build".to_string()),
+          recursive: false,
+          filter: None,
+          shell: TaskShell::Bash,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "task", "--shell=zsh", "build"]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn task_subcommand_double_hyphen() {
     let r = flags_from_vec(svec![
@@ -9598,6 +10473,9 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          recursive: false,
+          filter: None,
+          shell: TaskShell::Deno,
         }),
         argv: svec!["--", "hello", "world"],
         config_flag: ConfigFlag::Path("deno.json".to_owned()),
@@ -9614,6 +10492,9 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: Some("foo".to_string()),
           task: Some("build".to_string()),
+          recursive: false,
+          filter: None,
+          shell: TaskShell::Deno,
         }),
         argv: svec!["--", "hello", "world"],
         ..Flags::default()
@@ -9631,6 +10512,9 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          recursive: false,
+          filter: None,
+          shell: TaskShell::Deno,
         }),
         argv: svec!["--"],
         ..Flags::default()
@@ -9647,6 +10531,9 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          recursive: false,
+          filter: None,
+          shell: TaskShell::Deno,
         }),
         argv: svec!["-1", "--test"],
         ..Flags::default()
@@ -9663,6 +10550,9 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          recursive: false,
+          filter: None,
+          shell: TaskShell::Deno,
         }),
         argv: svec!["--test"],
         ..Flags::default()
@@ -9681,6 +10571,9 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          recursive: false,
+          filter: None,
+          shell: TaskShell::Deno,
         }),
         unstable_config: UnstableConfig {
           legacy_flag_enabled: true,
@@ -9701,6 +10594,9 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: None,
+          recursive: false,
+          filter: None,
+          shell: TaskShell::Deno,
         }),
         ..Flags::default()
       }
@@ -9716,6 +10612,9 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: None,
+          recursive: false,
+          filter: None,
+          shell: TaskShell::Deno,
         }),
         config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
         ..Flags::default()
@@ -9732,6 +10631,9 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: None,
+          recursive: false,
+          filter: None,
+          shell: TaskShell::Deno,
         }),
         config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
         ..Flags::default()
@@ -9912,7 +10814,8 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Init(InitFlags {
           dir: None,
-          lib: false
+          lib: false,
+          template: None
         }),
         ..Flags::default()
       }
@@ -9924,7 +10827,8 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Init(InitFlags {
           dir: Some(String::from("foo")),
-          lib: false
+          lib: false,
+          template: None
         }),
         ..Flags::default()
       }
@@ -9936,7 +10840,8 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Init(InitFlags {
           dir: None,
-          lib: false
+          lib: false,
+          template: None
         }),
         log_level: Some(Level::Error),
         ..Flags::default()
@@ -9949,7 +10854,8 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Init(InitFlags {
           dir: None,
-          lib: true
+          lib: true,
+          template: None
         }),
         ..Flags::default()
       }
@@ -9961,11 +10867,29 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Init(InitFlags {
           dir: Some(String::from("foo")),
-          lib: true
+          lib: true,
+          template: None
         }),
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec!["deno", "init", "--template=serve", "foo"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Init(InitFlags {
+          dir: Some(String::from("foo")),
+          lib: false,
+          template: Some(String::from("serve"))
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "init", "--lib", "--template=serve", "foo"]);
+    assert!(r.is_err());
   }
 
   #[test]
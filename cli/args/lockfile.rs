@@ -131,7 +131,9 @@ impl CliLockfile {
       || matches!(
         flags.subcommand,
         DenoSubcommand::Install(InstallFlags {
-          kind: InstallKind::Global(..),
+          kind: InstallKind::Global(..)
+            | InstallKind::GlobalList(..)
+            | InstallKind::GlobalUpgrade(..),
           ..
         }) | DenoSubcommand::Uninstall(_)
       )
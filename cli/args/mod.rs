@@ -118,6 +118,16 @@ pub static DENO_DISABLE_PEDANTIC_NODE_WARNINGS: Lazy<bool> = Lazy::new(|| {
 pub static DENO_FUTURE: Lazy<bool> =
   Lazy::new(|| std::env::var("DENO_FUTURE").ok().is_some());
 
+/// Set by `deno serve --parallel` on each of the worker processes it spawns.
+pub const SERVE_WORKER_ID_ENV_VAR_NAME: &str = "DENO_SERVE_WORKER_ID";
+
+/// The id of this process if it was spawned by `deno serve --parallel`.
+pub static SERVE_WORKER_ID: Lazy<Option<usize>> = Lazy::new(|| {
+  std::env::var(SERVE_WORKER_ID_ENV_VAR_NAME)
+    .ok()
+    .and_then(|id| id.parse().ok())
+});
+
 pub fn jsr_url() -> &'static Url {
   static JSR_URL: Lazy<Url> = Lazy::new(|| {
     let env_var_name = "JSR_URL";
@@ -281,7 +291,13 @@ impl BenchOptions {
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
 pub struct UnstableFmtOptions {
+  pub css: bool,
+  pub html: bool,
+  pub component: bool,
   pub yaml: bool,
+  /// Format the contents of `html`, `css`, `sql` and `gql` or `graphql`
+  /// tagged templates.
+  pub template_tags: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -314,7 +330,12 @@ impl FmtOptions {
     Self {
       options: resolve_fmt_options(fmt_flags, fmt_config.options),
       unstable: UnstableFmtOptions {
+        css: unstable.css || fmt_flags.unstable_css,
+        html: unstable.html || fmt_flags.unstable_html,
+        component: unstable.component || fmt_flags.unstable_component,
         yaml: unstable.yaml || fmt_flags.unstable_yaml,
+        template_tags: unstable.template_tags
+          || fmt_flags.unstable_template_tags,
       },
       files: fmt_config.files,
     }
@@ -405,25 +426,55 @@ impl TestOptions {
   }
 }
 
-#[derive(Clone, Copy, Default, Debug)]
-pub enum LintReporterKind {
-  #[default]
-  Pretty,
-  Json,
-  Compact,
+pub const DEFAULT_LINT_BASELINE_FILE_NAME: &str = "deno-lint-baseline.json";
+
+#[derive(Clone, Debug)]
+pub struct LintBaselineOptions {
+  pub path: PathBuf,
+  /// Record the diagnostics in the baseline instead of filtering them.
+  pub write: bool,
+}
+
+impl LintBaselineOptions {
+  pub fn resolve(
+    lint_flags: &LintFlags,
+    initial_cwd: &Path,
+    root_dir_path: &Path,
+  ) -> Result<Option<Self>, AnyError> {
+    let (path, is_default) = match &lint_flags.baseline {
+      Some(path) => (initial_cwd.join(path), false),
+      None => (root_dir_path.join(DEFAULT_LINT_BASELINE_FILE_NAME), true),
+    };
+    if lint_flags.baseline_write {
+      Ok(Some(Self { path, write: true }))
+    } else if path.is_file() {
+      Ok(Some(Self { path, write: false }))
+    } else if is_default {
+      Ok(None)
+    } else {
+      bail!(
+        "Lint baseline file not found at '{}'. Create it with --baseline-write.",
+        path.display()
+      )
+    }
+  }
 }
 
 #[derive(Clone, Debug)]
 pub struct WorkspaceLintOptions {
   pub reporter_kind: LintReporterKind,
+  pub baseline: Option<LintBaselineOptions>,
 }
 
 impl WorkspaceLintOptions {
   pub fn resolve(
     lint_config: &WorkspaceLintConfig,
     lint_flags: &LintFlags,
+    baseline: Option<LintBaselineOptions>,
   ) -> Result<Self, AnyError> {
-    let mut maybe_reporter_kind = if lint_flags.json {
+    let mut maybe_reporter_kind = if let Some(reporter) = lint_flags.reporter {
+      Some(reporter)
+    } else if lint_flags.json {
       Some(LintReporterKind::Json)
     } else if lint_flags.compact {
       Some(LintReporterKind::Compact)
@@ -437,6 +488,8 @@ impl WorkspaceLintOptions {
         Some("json") => Some(LintReporterKind::Json),
         Some("compact") => Some(LintReporterKind::Compact),
         Some("pretty") => Some(LintReporterKind::Pretty),
+        Some("sarif") => Some(LintReporterKind::Sarif),
+        Some("checkstyle") => Some(LintReporterKind::Checkstyle),
         Some(_) => {
           bail!("Invalid lint report type in config file")
         }
@@ -445,6 +498,7 @@ impl WorkspaceLintOptions {
     }
     Ok(Self {
       reporter_kind: maybe_reporter_kind.unwrap_or_default(),
+      baseline,
     })
   }
 }
@@ -1113,6 +1167,17 @@ impl CliOptions {
     }
   }
 
+  /// Whether this is one of the worker processes of `deno serve --parallel`.
+  pub fn serve_parallel(&self) -> bool {
+    matches!(
+      self.sub_command(),
+      DenoSubcommand::Serve(ServeFlags {
+        parallel: Some(_),
+        ..
+      })
+    ) && SERVE_WORKER_ID.is_some()
+  }
+
   pub fn env_file_name(&self) -> Option<&String> {
     self.flags.env_file.as_ref()
   }
@@ -1331,7 +1396,11 @@ impl CliOptions {
 
   pub fn resolve_config_unstable_fmt_options(&self) -> UnstableFmtOptions {
     UnstableFmtOptions {
+      css: self.workspace().has_unstable("fmt-css"),
+      html: self.workspace().has_unstable("fmt-html"),
+      component: self.workspace().has_unstable("fmt-component"),
       yaml: self.workspace().has_unstable("fmt-yaml"),
+      template_tags: self.workspace().has_unstable("fmt-template-tags"),
     }
   }
 
@@ -1340,7 +1409,17 @@ impl CliOptions {
     lint_flags: &LintFlags,
   ) -> Result<WorkspaceLintOptions, AnyError> {
     let lint_config = self.workspace().to_lint_config()?;
-    WorkspaceLintOptions::resolve(&lint_config, lint_flags)
+    let root_dir_path = self
+      .workspace()
+      .root_dir()
+      .to_file_path()
+      .unwrap_or_else(|_| self.initial_cwd().to_path_buf());
+    let baseline = LintBaselineOptions::resolve(
+      lint_flags,
+      self.initial_cwd(),
+      &root_dir_path,
+    )?;
+    WorkspaceLintOptions::resolve(&lint_config, lint_flags, baseline)
   }
 
   pub fn resolve_lint_options_for_members(
@@ -1653,6 +1732,14 @@ impl CliOptions {
       });
     }
 
+    if self.serve_parallel() {
+      // The workers share the port through `Deno.listen({ reusePort: true })`.
+      let net = deno_runtime::deno_net::UNSTABLE_FEATURE_NAME.to_string();
+      if !from_config_file.contains(&net) {
+        from_config_file.push(net);
+      }
+    }
+
     if !from_config_file.is_empty() {
       // collect unstable granular flags
       let mut all_valid_unstable_flags: Vec<&str> =
@@ -1665,7 +1752,11 @@ impl CliOptions {
         "sloppy-imports",
         "byonm",
         "bare-node-builtins",
+        "fmt-css",
+        "fmt-html",
+        "fmt-component",
         "fmt-yaml",
+        "fmt-template-tags",
       ]);
       // add more unstable flags to the same vector holding granular flags
       all_valid_unstable_flags.append(&mut another_unstable_flags);
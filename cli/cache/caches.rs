@@ -14,6 +14,7 @@ use super::fast_check::FAST_CHECK_CACHE_DB;
 use super::incremental::INCREMENTAL_CACHE_DB;
 use super::module_info::MODULE_INFO_CACHE_DB;
 use super::node::NODE_ANALYSIS_CACHE_DB;
+use super::task::TASK_CACHE_DB;
 
 pub struct Caches {
   dir_provider: Arc<DenoDirProvider>,
@@ -24,6 +25,7 @@ pub struct Caches {
   node_analysis_db: OnceCell<CacheDB>,
   type_checking_cache_db: OnceCell<CacheDB>,
   code_cache_db: OnceCell<CacheDB>,
+  task_cache_db: OnceCell<CacheDB>,
 }
 
 impl Caches {
@@ -37,6 +39,7 @@ impl Caches {
       node_analysis_db: Default::default(),
       type_checking_cache_db: Default::default(),
       code_cache_db: Default::default(),
+      task_cache_db: Default::default(),
     }
   }
 
@@ -139,4 +142,16 @@ impl Caches {
         .map(|dir| dir.code_cache_db_file_path()),
     )
   }
+
+  pub fn task_cache_db(&self) -> CacheDB {
+    Self::make_db(
+      &self.task_cache_db,
+      &TASK_CACHE_DB,
+      self
+        .dir_provider
+        .get_or_create()
+        .ok()
+        .map(|dir| dir.task_cache_db_file_path()),
+    )
+  }
 }
@@ -121,6 +121,18 @@ impl DenoDir {
     self.root.join("check_cache_v2")
   }
 
+  /// Path for the cache used to skip running tasks with unchanged inputs.
+  pub fn task_cache_db_file_path(&self) -> PathBuf {
+    // bump this version name to invalidate the entire cache
+    self.root.join("task_cache_v1")
+  }
+
+  /// Folder for the V8 code cache of `deno compile` executables.
+  pub fn standalone_code_cache_folder_path(&self) -> PathBuf {
+    // bump this version name to invalidate the entire cache
+    self.root.join("compile_code_cache_v1")
+  }
+
   /// Path to the registries cache, used for the lps.
   pub fn registries_folder_path(&self) -> PathBuf {
     self.root.join("registries")
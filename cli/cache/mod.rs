@@ -37,6 +37,7 @@ mod incremental;
 mod module_info;
 mod node;
 mod parsed_source;
+mod task;
 
 pub use cache_db::CacheDBHash;
 pub use caches::Caches;
@@ -54,6 +55,7 @@ pub use module_info::ModuleInfoCache;
 pub use node::NodeAnalysisCache;
 pub use parsed_source::LazyGraphSourceParser;
 pub use parsed_source::ParsedSourceCache;
+pub use task::TaskCache;
 
 /// Permissions used to save a file in the disk caches.
 pub const CACHE_PERM: u32 = 0o644;
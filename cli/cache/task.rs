@@ -0,0 +1,111 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use deno_core::error::AnyError;
+use deno_runtime::deno_webstorage::rusqlite::params;
+
+use super::cache_db::CacheDB;
+use super::cache_db::CacheDBConfiguration;
+use super::cache_db::CacheDBHash;
+use super::cache_db::CacheFailure;
+
+pub static TASK_CACHE_DB: CacheDBConfiguration = CacheDBConfiguration {
+  table_initializer: concat!(
+    "CREATE TABLE IF NOT EXISTS taskcache (",
+    "task_key TEXT PRIMARY KEY,",
+    "inputs_hash INTEGER NOT NULL",
+    ");"
+  ),
+  on_version_change: "DELETE FROM taskcache;",
+  preheat_queries: &[],
+  // If the cache fails, just ignore all caching attempts
+  on_failure: CacheFailure::Blackhole,
+};
+
+/// Cache used by `deno task` to skip running a task again when the input
+/// files it declares haven't changed since it last succeeded.
+pub struct TaskCache(CacheDB);
+
+impl TaskCache {
+  pub fn new(db: CacheDB) -> Self {
+    Self(db)
+  }
+
+  pub fn get_inputs_hash(&self, task_key: &str) -> Option<CacheDBHash> {
+    match self.get_inputs_hash_result(task_key) {
+      Ok(hash) => hash,
+      Err(err) => {
+        if cfg!(debug_assertions) {
+          panic!("Error retrieving hash: {err}");
+        } else {
+          log::debug!("Error retrieving hash: {}", err);
+          // fail silently when not debugging
+          None
+        }
+      }
+    }
+  }
+
+  fn get_inputs_hash_result(
+    &self,
+    task_key: &str,
+  ) -> Result<Option<CacheDBHash>, AnyError> {
+    let query = "SELECT inputs_hash FROM taskcache WHERE task_key=?1 LIMIT 1";
+    self.0.query_row(query, params![task_key], |row| {
+      let hash: CacheDBHash = row.get(0)?;
+      Ok(hash)
+    })
+  }
+
+  pub fn set_inputs_hash(&self, task_key: &str, inputs_hash: CacheDBHash) {
+    if let Err(err) = self.set_inputs_hash_result(task_key, inputs_hash) {
+      if cfg!(debug_assertions) {
+        panic!("Error saving hash: {err}");
+      } else {
+        log::debug!("Error saving hash: {}", err);
+      }
+    }
+  }
+
+  fn set_inputs_hash_result(
+    &self,
+    task_key: &str,
+    inputs_hash: CacheDBHash,
+  ) -> Result<(), AnyError> {
+    let sql = "
+      INSERT OR REPLACE INTO
+        taskcache (task_key, inputs_hash)
+      VALUES
+        (?1, ?2)";
+    self.0.execute(sql, params![task_key, inputs_hash])?;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  pub fn task_cache_general_use() {
+    let conn = CacheDB::in_memory(&TASK_CACHE_DB, "1.0.0");
+    let cache = TaskCache::new(conn);
+
+    assert_eq!(cache.get_inputs_hash("file:///a/#build"), None);
+    cache.set_inputs_hash("file:///a/#build", CacheDBHash::new(1));
+    assert_eq!(
+      cache.get_inputs_hash("file:///a/#build"),
+      Some(CacheDBHash::new(1))
+    );
+    cache.set_inputs_hash("file:///a/#build", CacheDBHash::new(2));
+    assert_eq!(
+      cache.get_inputs_hash("file:///a/#build"),
+      Some(CacheDBHash::new(2))
+    );
+    assert_eq!(cache.get_inputs_hash("file:///b/#build"), None);
+
+    // changing the cli version clears the cache
+    let conn = cache.0.recreate_with_version("2.0.0");
+    let cache = TaskCache::new(conn);
+    assert_eq!(cache.get_inputs_hash("file:///a/#build"), None);
+  }
+}
@@ -799,6 +799,7 @@ impl CliFactory {
       cli_options.node_ipc_fd(),
       cli_options.serve_port(),
       cli_options.serve_host(),
+      cli_options.serve_parallel(),
       cli_options.enable_future_features(),
       // TODO(bartlomieju): temporarily disabled
       // cli_options.disable_deprecated_api_warning,
@@ -845,7 +845,7 @@ pub fn get_resolution_error_bare_node_specifier(
     .filter(|specifier| deno_node::is_builtin_node_module(specifier))
 }
 
-fn get_resolution_error_bare_specifier(
+pub fn get_resolution_error_bare_specifier(
   error: &ResolutionError,
 ) -> Option<&str> {
   if let ResolutionError::InvalidSpecifier {
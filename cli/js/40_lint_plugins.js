@@ -0,0 +1,200 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+// Host for lint plugins. This runs in a bare isolate: plugins only get the
+// JavaScript built-ins and a console that writes to stderr, because stdout
+// may be carrying the JSON output of `deno lint`.
+
+const core = globalThis.Deno.core;
+const { op_lint_plugin_file } = core.ops;
+delete globalThis.Deno;
+
+function formatArg(arg) {
+  if (typeof arg === "string") {
+    return arg;
+  }
+  try {
+    return JSON.stringify(arg) ?? String(arg);
+  } catch {
+    return String(arg);
+  }
+}
+
+function print(...args) {
+  core.print(args.map(formatArg).join(" ") + "\n", true);
+}
+
+globalThis.console = {
+  log: print,
+  info: print,
+  debug: print,
+  warn: print,
+  error: print,
+};
+
+/** @type {{ code: string, docsUrl: string, create: Function }[]} */
+const rules = [];
+
+/**
+ * @param {[string, Record<string, unknown>][]} plugins specifier and module
+ * namespace of each plugin
+ */
+export function installPlugins(plugins) {
+  const codes = new Set();
+  for (const { 0: specifier, 1: namespace } of plugins) {
+    const plugin = namespace.default;
+    if (typeof plugin !== "object" || plugin === null) {
+      throw new TypeError(
+        `Lint plugin "${specifier}" must have a default export`,
+      );
+    }
+    if (typeof plugin.name !== "string" || plugin.name.length === 0) {
+      throw new TypeError(`Lint plugin "${specifier}" must have a name`);
+    }
+    if (typeof plugin.rules !== "object" || plugin.rules === null) {
+      throw new TypeError(`Lint plugin "${plugin.name}" must have rules`);
+    }
+    for (const { 0: name, 1: rule } of Object.entries(plugin.rules)) {
+      const code = `${plugin.name}/${name}`;
+      if (typeof rule?.create !== "function") {
+        throw new TypeError(`Lint rule "${code}" must have a create function`);
+      }
+      if (codes.has(code)) {
+        throw new TypeError(`Lint rule "${code}" is defined more than once`);
+      }
+      codes.add(code);
+      rules.push({
+        code,
+        docsUrl: typeof rule.docsUrl === "string" ? rule.docsUrl : specifier,
+        create: rule.create,
+      });
+    }
+  }
+}
+
+function ruleCodes() {
+  return rules.map((rule) => rule.code);
+}
+
+/**
+ * @param {{ code: string, docsUrl: string }} rule
+ * @param {{ specifier: string, source: string, start: number }} file
+ * @param {object[]} diagnostics
+ */
+function createContext(rule, file, diagnostics) {
+  const { specifier, source } = file;
+  const fileStart = file.start;
+  let bytes;
+
+  // Spans in the AST are byte positions that start counting at `fileStart`.
+  // Diagnostics are sent back as byte offsets into the file.
+  function toRange(target, what) {
+    const span = target?.node?.span ?? target?.range ?? target?.span ??
+      target;
+    if (typeof span?.start !== "number" || typeof span?.end !== "number") {
+      throw new TypeError(`${what} requires a node or a range`);
+    }
+    return { start: span.start - fileStart, end: span.end - fileStart };
+  }
+
+  return Object.freeze({
+    id: rule.code,
+    specifier,
+    source,
+    getText(nodeOrRange) {
+      const { start, end } = toRange(nodeOrRange, "getText()");
+      bytes ??= core.encode(source);
+      return core.decode(bytes.subarray(start, end));
+    },
+    report(descriptor) {
+      const { start, end } = toRange(descriptor, "report()");
+      let fix = null;
+      if (descriptor.fix !== undefined) {
+        const changes = Array.isArray(descriptor.fix)
+          ? descriptor.fix
+          : [descriptor.fix];
+        fix = {
+          description: descriptor.fixDescription ?? `Fix ${rule.code}`,
+          changes: changes.map((change) => ({
+            ...toRange(change, "A fix"),
+            text: String(change.text),
+          })),
+        };
+      }
+      diagnostics.push({
+        code: rule.code,
+        message: String(descriptor.message),
+        hint: descriptor.hint ?? null,
+        docsUrl: rule.docsUrl,
+        start,
+        end,
+        fix,
+      });
+    },
+  });
+}
+
+function callVisitors(visitors, node) {
+  if (visitors === undefined) {
+    return;
+  }
+  for (const { code, visit } of visitors) {
+    try {
+      visit(node);
+    } catch (error) {
+      throw new Error(
+        `Lint rule "${code}" failed: ${error?.stack ?? String(error)}`,
+      );
+    }
+  }
+}
+
+function traverse(value, parent, enter, exit) {
+  if (Array.isArray(value)) {
+    for (const item of value) {
+      traverse(item, parent, enter, exit);
+    }
+    return;
+  }
+  if (value === null || typeof value !== "object") {
+    return;
+  }
+  const isNode = typeof value.type === "string";
+  if (isNode) {
+    Object.defineProperty(value, "parent", { value: parent });
+    callVisitors(enter.get(value.type), value);
+  }
+  for (const key in value) {
+    if (key !== "span") {
+      traverse(value[key], isNode ? value : parent, enter, exit);
+    }
+  }
+  if (isNode) {
+    callVisitors(exit.get(value.type), value);
+  }
+}
+
+function runPlugins() {
+  const file = op_lint_plugin_file();
+  const diagnostics = [];
+  const enter = new Map();
+  const exit = new Map();
+  for (const rule of rules) {
+    const visitor = rule.create(createContext(rule, file, diagnostics)) ?? {};
+    for (const { 0: key, 1: visit } of Object.entries(visitor)) {
+      const isExit = key.endsWith(":exit");
+      const type = isExit ? key.slice(0, -":exit".length) : key;
+      const visitors = isExit ? exit : enter;
+      if (!visitors.has(type)) {
+        visitors.set(type, []);
+      }
+      visitors.get(type).push({ code: rule.code, visit });
+    }
+  }
+  if (enter.size > 0 || exit.size > 0) {
+    traverse(JSON.parse(file.ast), null, enter, exit);
+  }
+  return diagnostics;
+}
+
+globalThis[Symbol.for("Deno.lint.ruleCodes")] = ruleCodes;
+globalThis[Symbol.for("Deno.lint.runPlugins")] = runPlugins;
@@ -5,17 +5,28 @@ use super::diagnostics::DiagnosticSource;
 use super::documents::Documents;
 use super::language_server;
 use super::resolver::LspResolver;
+use super::text::get_edits;
+use super::text::LineIndex;
 use super::tsc;
 
 use crate::args::jsr_url;
+use crate::args::FmtOptionsConfig;
+use crate::tools::fmt::format_json;
 use crate::tools::lint::CliLinter;
 use deno_lint::diagnostic::LintDiagnosticRange;
 use deno_runtime::fs_util::specifier_to_file_path;
 
+use deno_ast::swc::ast;
+use deno_ast::swc::common::Spanned;
+use deno_ast::swc::visit::Visit;
+use deno_ast::swc::visit::VisitWith;
+use deno_ast::MediaType;
 use deno_ast::SourceRange;
 use deno_ast::SourceRangedForSpanned;
 use deno_ast::SourceTextInfo;
+use deno_ast::TextChange;
 use deno_core::anyhow::anyhow;
+use deno_core::anyhow::bail;
 use deno_core::error::custom_error;
 use deno_core::error::AnyError;
 use deno_core::serde::Deserialize;
@@ -44,6 +55,11 @@ use tower_lsp::lsp_types as lsp;
 use tower_lsp::lsp_types::Position;
 use tower_lsp::lsp_types::Range;
 
+/// The kind of the source code action which removes dependencies that aren't
+/// imported by any module from the import map and package.json.
+pub const REMOVE_UNUSED_DEPENDENCIES_KIND: &str =
+  "source.removeUnusedDependencies";
+
 /// Diagnostic error codes which actually are the same, and so when grouping
 /// fixes we treat them the same.
 static FIX_ALL_ERROR_CODES: Lazy<HashMap<&'static str, &'static str>> =
@@ -728,6 +744,29 @@ impl CodeActionCollection {
     Ok(())
   }
 
+  pub fn add_missing_dependency_action(
+    &mut self,
+    diagnostic: &lsp::Diagnostic,
+    config_specifier: &ModuleSpecifier,
+    value: &str,
+    edits: Vec<lsp::TextEdit>,
+  ) {
+    let file_name = config_specifier
+      .path_segments()
+      .and_then(|mut s| s.next_back())
+      .unwrap_or("deno.json");
+    self.actions.push(CodeActionKind::Deno(lsp::CodeAction {
+      title: format!("Add \"{value}\" to the imports of {file_name}."),
+      kind: Some(lsp::CodeActionKind::QUICKFIX),
+      diagnostics: Some(vec![diagnostic.clone()]),
+      edit: Some(lsp::WorkspaceEdit {
+        changes: Some(HashMap::from([(config_specifier.clone(), edits)])),
+        ..Default::default()
+      }),
+      ..Default::default()
+    }));
+  }
+
   pub fn add_deno_lint_actions(
     &mut self,
     specifier: &ModuleSpecifier,
@@ -1172,6 +1211,247 @@ pub fn source_range_to_lsp_range(
   }
 }
 
+/// Expands a byte range of a document to the statements it overlaps. The
+/// innermost statement list that contains the whole range is used, so that
+/// selecting code inside a function body doesn't select the whole function.
+pub fn get_statements_byte_range(
+  parsed_source: &deno_ast::ParsedSource,
+  range: std::ops::Range<usize>,
+) -> Option<std::ops::Range<usize>> {
+  let mut finder = StatementRangeFinder {
+    start_pos: parsed_source.text_info_lazy().range().start,
+    selection: range,
+    result: None,
+  };
+  parsed_source.program().visit_with(&mut finder);
+  finder.result
+}
+
+struct StatementRangeFinder {
+  start_pos: deno_ast::StartSourcePos,
+  selection: std::ops::Range<usize>,
+  result: Option<std::ops::Range<usize>>,
+}
+
+impl StatementRangeFinder {
+  fn visit_statement_list<T: Spanned + VisitWith<Self>>(
+    &mut self,
+    items: &[T],
+  ) {
+    let intersecting = items
+      .iter()
+      .map(|item| {
+        let range = item.range();
+        let range = range.start.as_byte_index(self.start_pos)
+          ..range.end.as_byte_index(self.start_pos);
+        (item, range)
+      })
+      .filter(|(_, range)| {
+        if self.selection.is_empty() {
+          // an empty selection touches the statement it's placed in
+          range.start <= self.selection.start
+            && self.selection.start < range.end
+        } else {
+          range.start < self.selection.end && self.selection.start < range.end
+        }
+      })
+      .collect::<Vec<_>>();
+    match intersecting.as_slice() {
+      [] => {}
+      [(item, range)]
+        if range.start <= self.selection.start
+          && self.selection.end <= range.end =>
+      {
+        self.result = Some(range.clone());
+        // look for a nested statement list that contains the selection
+        item.visit_with(self);
+      }
+      [(_, first), ..] => {
+        let (_, last) = intersecting.last().unwrap();
+        self.result = Some(first.start..last.end);
+      }
+    }
+  }
+}
+
+impl Visit for StatementRangeFinder {
+  fn visit_module_items(&mut self, items: &[ast::ModuleItem]) {
+    self.visit_statement_list(items);
+  }
+
+  fn visit_stmts(&mut self, stmts: &[ast::Stmt]) {
+    self.visit_statement_list(stmts);
+  }
+}
+
+/// Returns the import map entry which would map an unmapped bare specifier,
+/// as a `(key, value)` pair. Scoped packages are mapped to JSR and all other
+/// packages to npm. Specifiers that don't look like a package name, for
+/// example a local file missing its `./` prefix, return `None`.
+pub fn get_import_map_entry_for_bare_specifier(
+  specifier: &str,
+) -> Option<(String, String)> {
+  let mut parts = specifier.split('/');
+  let name = if specifier.starts_with('@') {
+    format!("{}/{}", parts.next()?, parts.next()?)
+  } else {
+    parts.next()?.to_string()
+  };
+  if name.contains(':')
+    || name.starts_with('.')
+    || MediaType::from_path(Path::new(&name)) != MediaType::Unknown
+  {
+    return None;
+  }
+  let req = PackageReq::from_str(&name).ok()?;
+  if req.name != name {
+    return None;
+  }
+  let value = if name.starts_with('@') {
+    format!("jsr:{name}")
+  } else {
+    format!("npm:{name}")
+  };
+  Some((name, value))
+}
+
+/// Computes the edits which add an entry to the `"imports"` of a
+/// configuration file, creating the object when it doesn't exist yet.
+pub fn get_add_import_map_entry_edits(
+  config_text: &str,
+  key: &str,
+  value: &str,
+  file_path: &Path,
+  fmt_options: &FmtOptionsConfig,
+) -> Result<Vec<lsp::TextEdit>, AnyError> {
+  use jsonc_parser::ast::ObjectProp;
+  use jsonc_parser::ast::Value;
+  let obj = parse_config_object(config_text)?;
+  let entry = format!(
+    "{}: {}",
+    serde_json::to_string(key)?,
+    serde_json::to_string(value)?
+  );
+  let text_change = match obj.get("imports") {
+    Some(ObjectProp {
+      value: Value::Object(imports),
+      ..
+    }) => {
+      if imports.get(key).is_some() {
+        bail!("The import map already contains \"{}\".", key);
+      }
+      match imports.properties.last() {
+        Some(last) => TextChange {
+          range: last.range.end..last.range.end,
+          new_text: format!(",\n{entry}"),
+        },
+        None => TextChange {
+          range: (imports.range.start + 1)..(imports.range.end - 1),
+          new_text: format!("\n{entry}\n"),
+        },
+      }
+    }
+    Some(_) => bail!("The \"imports\" of the config file is not an object."),
+    None => {
+      // adding `\n` to force the formatter to produce a multiline object
+      let imports = format!("\"imports\": {{\n{entry}\n}}");
+      match obj.properties.last() {
+        Some(last) => TextChange {
+          range: last.range.end..last.range.end,
+          new_text: format!(",\n{imports}"),
+        },
+        None => TextChange {
+          range: (obj.range.start + 1)..(obj.range.end - 1),
+          new_text: format!("\n{imports}\n"),
+        },
+      }
+    }
+  };
+  let new_text = deno_ast::apply_text_changes(config_text, vec![text_change]);
+  Ok(get_formatted_config_edits(
+    config_text,
+    new_text,
+    file_path,
+    fmt_options,
+  ))
+}
+
+/// Computes the edits which remove the entries with the provided keys from
+/// an object property of a configuration file, for example the `"imports"`
+/// of a deno.json or the `"dependencies"` of a package.json.
+pub fn get_remove_object_entries_edits(
+  config_text: &str,
+  property_name: &str,
+  keys: &HashSet<String>,
+  file_path: &Path,
+  fmt_options: &FmtOptionsConfig,
+) -> Result<Vec<lsp::TextEdit>, AnyError> {
+  use jsonc_parser::ast::ObjectProp;
+  use jsonc_parser::ast::Value;
+  let obj = parse_config_object(config_text)?;
+  let Some(ObjectProp {
+    value: Value::Object(entries),
+    ..
+  }) = obj.get(property_name)
+  else {
+    bail!(
+      "The \"{}\" of the config file is not an object.",
+      property_name
+    );
+  };
+  let kept_entries = entries
+    .properties
+    .iter()
+    .filter(|prop| !keys.contains(prop.name.as_str()))
+    .map(|prop| &config_text[prop.range.start..prop.range.end])
+    .collect::<Vec<_>>();
+  let new_text = deno_ast::apply_text_changes(
+    config_text,
+    vec![TextChange {
+      range: (entries.range.start + 1)..(entries.range.end - 1),
+      new_text: if kept_entries.is_empty() {
+        String::new()
+      } else {
+        format!("\n{}\n", kept_entries.join(",\n"))
+      },
+    }],
+  );
+  Ok(get_formatted_config_edits(
+    config_text,
+    new_text,
+    file_path,
+    fmt_options,
+  ))
+}
+
+fn parse_config_object(
+  config_text: &str,
+) -> Result<jsonc_parser::ast::Object, AnyError> {
+  use jsonc_parser::ast::Value;
+  let ast = jsonc_parser::parse_to_ast(
+    config_text,
+    &Default::default(),
+    &Default::default(),
+  )?;
+  match ast.value {
+    Some(Value::Object(obj)) => Ok(obj),
+    _ => bail!("The config file does not contain an object."),
+  }
+}
+
+fn get_formatted_config_edits(
+  config_text: &str,
+  new_text: String,
+  file_path: &Path,
+  fmt_options: &FmtOptionsConfig,
+) -> Vec<lsp::TextEdit> {
+  let new_text = format_json(file_path, &new_text, fmt_options)
+    .ok()
+    .flatten()
+    .unwrap_or(new_text);
+  get_edits(config_text, &new_text, &LineIndex::new(config_text))
+}
+
 #[cfg(test)]
 mod tests {
   use std::path::PathBuf;
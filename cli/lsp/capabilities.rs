@@ -8,6 +8,7 @@
 use deno_core::serde_json::json;
 use tower_lsp::lsp_types::*;
 
+use super::analysis::REMOVE_UNUSED_DEPENDENCIES_KIND;
 use super::refactor::ALL_KNOWN_REFACTOR_ACTION_KINDS;
 use super::semantic_tokens::get_legend;
 
@@ -20,8 +21,11 @@ fn code_action_capabilities(
     .and_then(|it| it.code_action.as_ref())
     .and_then(|it| it.code_action_literal_support.as_ref())
     .map(|_| {
-      let mut code_action_kinds =
-        vec![CodeActionKind::QUICKFIX, CodeActionKind::REFACTOR];
+      let mut code_action_kinds = vec![
+        CodeActionKind::QUICKFIX,
+        CodeActionKind::REFACTOR,
+        CodeActionKind::from(REMOVE_UNUSED_DEPENDENCIES_KIND),
+      ];
       code_action_kinds.extend(
         ALL_KNOWN_REFACTOR_ACTION_KINDS
           .iter()
@@ -37,6 +41,32 @@ fn code_action_capabilities(
     .unwrap_or(CodeActionProviderCapability::Simple(true))
 }
 
+/// Diagnostics are only provided on request when the client supports it and
+/// can be asked to pull them again, otherwise they are published.
+fn diagnostic_capabilities(
+  client_capabilities: &ClientCapabilities,
+) -> Option<DiagnosticServerCapabilities> {
+  client_capabilities
+    .text_document
+    .as_ref()?
+    .diagnostic
+    .as_ref()?;
+  let refresh_support = client_capabilities
+    .workspace
+    .as_ref()?
+    .diagnostic
+    .as_ref()?
+    .refresh_support?;
+  refresh_support.then(|| {
+    DiagnosticServerCapabilities::Options(DiagnosticOptions {
+      identifier: Some("deno".to_string()),
+      inter_file_dependencies: true,
+      workspace_diagnostics: false,
+      work_done_progress_options: Default::default(),
+    })
+  })
+}
+
 pub fn server_capabilities(
   client_capabilities: &ClientCapabilities,
 ) -> ServerCapabilities {
@@ -109,19 +139,28 @@ pub fn server_capabilities(
       resolve_provider: Some(true),
     }),
     document_formatting_provider: Some(OneOf::Left(true)),
-    document_range_formatting_provider: None,
-    document_on_type_formatting_provider: None,
+    document_range_formatting_provider: Some(OneOf::Left(true)),
+    document_on_type_formatting_provider: Some(
+      DocumentOnTypeFormattingOptions {
+        first_trigger_character: ";".to_string(),
+        more_trigger_character: Some(vec!["}".to_string()]),
+      },
+    ),
     selection_range_provider: Some(SelectionRangeProviderCapability::Simple(
       true,
     )),
     folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
     rename_provider: Some(OneOf::Left(true)),
-    document_link_provider: None,
+    document_link_provider: Some(DocumentLinkOptions {
+      resolve_provider: None,
+      work_done_progress_options: Default::default(),
+    }),
     color_provider: None,
     execute_command_provider: Some(ExecuteCommandOptions {
       commands: vec![
         "deno.cache".to_string(),
         "deno.reloadImportRegistries".to_string(),
+        "deno.organizeImportsInWorkspace".to_string(),
       ],
       ..Default::default()
     }),
@@ -143,7 +182,9 @@ pub fn server_capabilities(
       }),
       file_operations: None,
     }),
-    linked_editing_range_provider: None,
+    linked_editing_range_provider: Some(
+      LinkedEditingRangeServerCapabilities::Simple(true),
+    ),
     moniker_provider: None,
     experimental: Some(json!({
       "denoConfigTasks": true,
@@ -151,8 +192,7 @@ pub fn server_capabilities(
     })),
     inlay_hint_provider: Some(OneOf::Left(true)),
     position_encoding: None,
-    // TODO(nayeemrmn): Support pull-based diagnostics.
-    diagnostic_provider: None,
-    inline_value_provider: None,
+    diagnostic_provider: diagnostic_capabilities(client_capabilities),
+    inline_value_provider: Some(OneOf::Left(true)),
   }
 }
@@ -15,6 +15,7 @@ use crate::lsp::repl::get_repl_workspace_settings;
 
 use super::config::WorkspaceSettings;
 use super::config::SETTINGS_SECTION;
+use super::logging::lsp_warn;
 use super::lsp_custom;
 use super::testing::lsp_custom as testing_lsp_custom;
 use super::urls::LspClientUrl;
@@ -119,6 +120,16 @@ impl Client {
     });
   }
 
+  /// Asks a client that pulls diagnostics to pull them again, because they
+  /// changed.
+  pub fn send_diagnostic_refresh_request(&self) {
+    // do on a task in case the caller currently is in the lsp lock
+    let client = self.0.clone();
+    spawn(async move {
+      client.send_diagnostic_refresh_request().await;
+    });
+  }
+
   pub fn show_message(
     &self,
     message_type: lsp::MessageType,
@@ -153,6 +164,13 @@ impl OutsideLockClient {
   ) -> Result<Vec<WorkspaceSettings>, AnyError> {
     self.0.workspace_configuration(scopes).await
   }
+
+  pub async fn apply_edit(
+    &self,
+    edit: lsp::WorkspaceEdit,
+  ) -> Result<(), AnyError> {
+    self.0.apply_edit(edit).await
+  }
 }
 
 #[async_trait]
@@ -180,6 +198,7 @@ trait ClientTrait: Send + Sync {
     &self,
     params: lsp_custom::DidUpgradeCheckNotificationParams,
   );
+  async fn send_diagnostic_refresh_request(&self);
   async fn workspace_configuration(
     &self,
     scopes: Vec<Option<lsp::Url>>,
@@ -189,6 +208,7 @@ trait ClientTrait: Send + Sync {
     &self,
     registrations: Vec<lsp::Registration>,
   ) -> Result<(), AnyError>;
+  async fn apply_edit(&self, edit: lsp::WorkspaceEdit) -> Result<(), AnyError>;
 }
 
 #[derive(Clone)]
@@ -274,6 +294,16 @@ impl ClientTrait for TowerClient {
       .await
   }
 
+  async fn send_diagnostic_refresh_request(&self) {
+    if let Err(err) = self
+      .0
+      .send_request::<lsp::request::WorkspaceDiagnosticRefresh>(())
+      .await
+    {
+      lsp_warn!("Failed refreshing diagnostics: {}", err);
+    }
+  }
+
   async fn workspace_configuration(
     &self,
     scopes: Vec<Option<lsp::Url>>,
@@ -340,6 +370,25 @@ impl ClientTrait for TowerClient {
       .await
       .map_err(|err| anyhow!("{}", err))
   }
+
+  async fn apply_edit(&self, edit: lsp::WorkspaceEdit) -> Result<(), AnyError> {
+    let response = self
+      .0
+      .apply_edit(edit)
+      .await
+      .map_err(|err| anyhow!("{}", err))?;
+    if response.applied {
+      Ok(())
+    } else {
+      Err(anyhow!(
+        "Client did not apply the edit: {}",
+        response
+          .failure_reason
+          .as_deref()
+          .unwrap_or("unknown reason")
+      ))
+    }
+  }
 }
 
 #[derive(Clone)]
@@ -381,6 +430,8 @@ impl ClientTrait for ReplClient {
   ) {
   }
 
+  async fn send_diagnostic_refresh_request(&self) {}
+
   async fn workspace_configuration(
     &self,
     scopes: Vec<Option<lsp::Url>>,
@@ -401,4 +452,11 @@ impl ClientTrait for ReplClient {
   ) -> Result<(), AnyError> {
     Ok(())
   }
+
+  async fn apply_edit(
+    &self,
+    _edit: lsp::WorkspaceEdit,
+  ) -> Result<(), AnyError> {
+    Ok(())
+  }
 }
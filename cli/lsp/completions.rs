@@ -108,7 +108,7 @@ async fn check_auto_config_registry(
 
 /// Ranges from the graph for specifiers include the leading and maybe trailing quote,
 /// which we want to ignore when replacing text.
-fn to_narrow_lsp_range(
+pub fn to_narrow_lsp_range(
   text_info: &SourceTextInfo,
   range: &deno_graph::Range,
 ) -> lsp::Range {
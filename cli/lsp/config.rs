@@ -59,6 +59,7 @@ use crate::cache::FastInsecureHasher;
 use crate::file_fetcher::FileFetcher;
 use crate::lsp::logging::lsp_warn;
 use crate::resolver::SloppyImportsResolver;
+use crate::tools::lint::resolve_lint_plugins_in_background;
 use crate::tools::lint::CliLinter;
 use crate::tools::lint::CliLinterOptions;
 use crate::tools::lint::LintRuleProvider;
@@ -1033,6 +1034,18 @@ impl Config {
     .unwrap_or(false)
   }
 
+  /// Diagnostics are only pulled by clients that can also be asked to pull
+  /// them again, since they are generated in the background.
+  pub fn diagnostic_pull_capable(&self) -> bool {
+    (|| {
+      let text_document = self.client_capabilities.text_document.as_ref()?;
+      text_document.diagnostic.as_ref()?;
+      let workspace = self.client_capabilities.workspace.as_ref()?;
+      workspace.diagnostic.as_ref()?.refresh_support
+    })()
+    .unwrap_or(false)
+  }
+
   pub fn code_action_disabled_capable(&self) -> bool {
     (|| {
       let text_document = self.client_capabilities.text_document.as_ref()?;
@@ -1554,6 +1567,12 @@ impl ConfigData {
       ),
       fix: false,
       deno_lint_config,
+      lint_plugins: resolve_lint_plugins_in_background(&member_dir)
+        .inspect_err(|err| {
+          lsp_warn!("  Failed to load lint plugins: {:#}", err)
+        })
+        .ok()
+        .flatten(),
     }));
 
     ConfigData {
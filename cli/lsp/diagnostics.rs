@@ -1,6 +1,7 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
 use super::analysis;
+use super::analysis::get_import_map_entry_for_bare_specifier;
 use super::client::Client;
 use super::config::Config;
 use super::documents;
@@ -107,15 +108,23 @@ type DiagnosticsBySource = HashMap<DiagnosticSource, VersionedDiagnostics>;
 struct DiagnosticsPublisher {
   client: Client,
   state: Arc<DiagnosticsState>,
+  /// Whether the client pulls the diagnostics instead of having them
+  /// published, in which case it's asked to pull them again when they change.
+  pull_diagnostics: bool,
   diagnostics_by_specifier:
     Mutex<HashMap<ModuleSpecifier, DiagnosticsBySource>>,
 }
 
 impl DiagnosticsPublisher {
-  pub fn new(client: Client, state: Arc<DiagnosticsState>) -> Self {
+  pub fn new(
+    client: Client,
+    state: Arc<DiagnosticsState>,
+    pull_diagnostics: bool,
+  ) -> Self {
     Self {
       client,
       state,
+      pull_diagnostics,
       diagnostics_by_specifier: Default::default(),
     }
   }
@@ -132,6 +141,7 @@ impl DiagnosticsPublisher {
       self.diagnostics_by_specifier.lock().await;
     let mut seen_specifiers = HashSet::with_capacity(diagnostics.len());
     let mut messages_sent = 0;
+    let mut messages_changed = 0;
 
     for record in diagnostics {
       if token.is_cancelled() {
@@ -159,6 +169,10 @@ impl DiagnosticsPublisher {
       self
         .state
         .update(&record.specifier, version, &all_specifier_diagnostics);
+      if self.pull_diagnostics {
+        messages_changed += 1;
+        continue;
+      }
       let file_referrer = documents.get_file_referrer(&record.specifier);
       self
         .client
@@ -190,6 +204,10 @@ impl DiagnosticsPublisher {
         if let Some(removed_value) = maybe_removed_value {
           // clear out any diagnostics for this specifier
           self.state.update(specifier, removed_value.version, &[]);
+          if self.pull_diagnostics {
+            messages_changed += 1;
+            continue;
+          }
           let file_referrer = documents.get_file_referrer(specifier);
           self
             .client
@@ -211,9 +229,34 @@ impl DiagnosticsPublisher {
       diagnostics_by_specifier.remove(&specifier);
     }
 
+    if messages_changed > 0 {
+      self.client.send_diagnostic_refresh_request();
+    }
+
     messages_sent
   }
 
+  /// Gets the latest diagnostics of a document from all sources.
+  pub async fn get(&self, specifier: &ModuleSpecifier) -> Vec<lsp::Diagnostic> {
+    let diagnostics_by_specifier = self.diagnostics_by_specifier.lock().await;
+    let Some(diagnostics_by_source) = diagnostics_by_specifier.get(specifier)
+    else {
+      return Vec::new();
+    };
+    // keep the order of the sources stable so that the result id of
+    // unchanged diagnostics stays the same
+    [
+      DiagnosticSource::Deno,
+      DiagnosticSource::Lint,
+      DiagnosticSource::Ts,
+    ]
+    .iter()
+    .filter_map(|source| diagnostics_by_source.get(source))
+    .flat_map(|versioned| &versioned.diagnostics)
+    .cloned()
+    .collect()
+  }
+
   pub async fn clear(&self) {
     let mut all_diagnostics = self.diagnostics_by_specifier.lock().await;
     all_diagnostics.clear();
@@ -382,6 +425,7 @@ impl DiagnosticsState {
 #[derive(Debug)]
 pub struct DiagnosticsServer {
   channel: Option<mpsc::UnboundedSender<ChannelMessage>>,
+  publisher: Option<Arc<DiagnosticsPublisher>>,
   ts_diagnostics: TsDiagnosticsStore,
   client: Client,
   performance: Arc<Performance>,
@@ -399,6 +443,7 @@ impl DiagnosticsServer {
   ) -> Self {
     DiagnosticsServer {
       channel: Default::default(),
+      publisher: Default::default(),
       ts_diagnostics: Default::default(),
       client,
       performance,
@@ -427,12 +472,29 @@ impl DiagnosticsServer {
     }
   }
 
+  /// Gets the diagnostics of an open document for a client that pulls
+  /// diagnostics. They are generated in the background after each change,
+  /// after which the client is asked to pull them again.
+  pub async fn pull(
+    &self,
+    specifier: &ModuleSpecifier,
+  ) -> Vec<lsp::Diagnostic> {
+    match &self.publisher {
+      Some(publisher) => publisher.get(specifier).await,
+      None => Vec::new(),
+    }
+  }
+
   #[allow(unused_must_use)]
-  pub fn start(&mut self) {
+  pub fn start(&mut self, pull_diagnostics: bool) {
     let (tx, mut rx) = mpsc::unbounded_channel::<ChannelMessage>();
     self.channel = Some(tx);
-    let client = self.client.clone();
-    let state = self.state.clone();
+    let diagnostics_publisher = Arc::new(DiagnosticsPublisher::new(
+      self.client.clone(),
+      self.state.clone(),
+      pull_diagnostics,
+    ));
+    self.publisher = Some(diagnostics_publisher.clone());
     let performance = self.performance.clone();
     let ts_diagnostics_store = self.ts_diagnostics.clone();
     let ts_server = self.ts_server.clone();
@@ -445,8 +507,6 @@ impl DiagnosticsServer {
         let mut ts_handle: Option<JoinHandle<()>> = None;
         let mut lint_handle: Option<JoinHandle<()>> = None;
         let mut deps_handle: Option<JoinHandle<()>> = None;
-        let diagnostics_publisher =
-          Arc::new(DiagnosticsPublisher::new(client.clone(), state.clone()));
 
         loop {
           match rx.recv().await {
@@ -836,6 +896,7 @@ fn generate_lint_diagnostics(
               default_jsx_factory: None,
               default_jsx_fragment_factory: None,
             },
+            lint_plugins: None,
           })),
         )
       });
@@ -1221,7 +1282,9 @@ impl DenoDiagnostic {
         | "no-attribute-type"
         | "redirect"
         | "import-node-prefix-missing" => true,
-        "no-local" => diagnostic.data.is_some(),
+        "no-local" | "resolver-error" | "import-prefix-missing" => {
+          diagnostic.data.is_some()
+        }
         _ => false,
       }
     } else {
@@ -1286,6 +1349,11 @@ impl DenoDiagnostic {
         lsp::DiagnosticSeverity::ERROR,
         message,
         graph_util::get_resolution_error_bare_node_specifier(err)
+          .or_else(|| {
+            graph_util::get_resolution_error_bare_specifier(err).filter(|s| {
+              get_import_map_entry_for_bare_specifier(s).is_some()
+            })
+          })
           .map(|specifier| json!({ "specifier": specifier }))
       )},
       Self::InvalidNodeSpecifier(specifier) => (lsp::DiagnosticSeverity::ERROR, format!("Unknown Node built-in module: {}", specifier.path()), None),
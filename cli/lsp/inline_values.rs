@@ -0,0 +1,420 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use super::analysis::source_range_to_lsp_range;
+
+use deno_ast::swc::ast;
+use deno_ast::swc::common::SyntaxContext;
+use deno_ast::swc::utils::find_pat_ids;
+use deno_ast::swc::visit::Visit;
+use deno_ast::swc::visit::VisitWith;
+use deno_ast::ParsedSource;
+use deno_ast::SourceRange;
+use deno_ast::SourceRangedForSpanned;
+use deno_ast::SourceTextInfo;
+use std::collections::HashSet;
+use tower_lsp::lsp_types as lsp;
+
+/// Walks a module finding the scopes which contain the location where
+/// execution stopped. The debugger looks up inline values by name in the
+/// paused frame, so only variables of these scopes can be displayed.
+struct StoppedScopeCollector<'a> {
+  text_info: &'a SourceTextInfo,
+  stopped_location: lsp::Range,
+  /// The innermost function containing the stopped location, or `None` when
+  /// execution stopped at the top level of the module.
+  function_range: Option<lsp::Range>,
+  /// The syntax contexts of the bindings that are in scope at the stopped
+  /// location, as assigned by the scope analysis of the parsed source.
+  contexts: HashSet<SyntaxContext>,
+}
+
+impl<'a> StoppedScopeCollector<'a> {
+  fn new(
+    parsed_source: &'a ParsedSource,
+    stopped_location: lsp::Range,
+  ) -> Self {
+    Self {
+      text_info: parsed_source.text_info_lazy(),
+      stopped_location,
+      function_range: None,
+      contexts: HashSet::from([
+        parsed_source.top_level_context(),
+        parsed_source.unresolved_context(),
+      ]),
+    }
+  }
+
+  fn contains_stopped(&self, range: &SourceRange) -> bool {
+    let range = source_range_to_lsp_range(range, self.text_info);
+    range.start <= self.stopped_location.end
+      && self.stopped_location.start <= range.end
+  }
+
+  /// Adds the scope of bindings that aren't declared in a block, like the
+  /// parameters of arrow functions and the variables of loop heads.
+  fn add_bindings<T>(&mut self, node: &T)
+  where
+    T: VisitWith<deno_ast::swc::utils::DestructuringFinder<ast::Id>>,
+  {
+    self.contexts.extend(
+      find_pat_ids::<_, ast::Id>(node)
+        .into_iter()
+        .map(|(_, ctxt)| ctxt),
+    );
+  }
+}
+
+impl Visit for StoppedScopeCollector<'_> {
+  fn visit_function(&mut self, node: &ast::Function) {
+    if !self.contains_stopped(&node.range()) {
+      return;
+    }
+    self.function_range =
+      Some(source_range_to_lsp_range(&node.range(), self.text_info));
+    self.contexts.insert(node.ctxt);
+    node.visit_children_with(self);
+  }
+
+  fn visit_arrow_expr(&mut self, node: &ast::ArrowExpr) {
+    if !self.contains_stopped(&node.range()) {
+      return;
+    }
+    self.function_range =
+      Some(source_range_to_lsp_range(&node.range(), self.text_info));
+    self.add_bindings(&node.params);
+    node.visit_children_with(self);
+  }
+
+  fn visit_constructor(&mut self, node: &ast::Constructor) {
+    if !self.contains_stopped(&node.range()) {
+      return;
+    }
+    self.function_range =
+      Some(source_range_to_lsp_range(&node.range(), self.text_info));
+    self.add_bindings(&node.params);
+    node.visit_children_with(self);
+  }
+
+  fn visit_block_stmt(&mut self, node: &ast::BlockStmt) {
+    if !self.contains_stopped(&node.range()) {
+      return;
+    }
+    self.contexts.insert(node.ctxt);
+    node.visit_children_with(self);
+  }
+
+  fn visit_for_stmt(&mut self, node: &ast::ForStmt) {
+    if !self.contains_stopped(&node.range()) {
+      return;
+    }
+    self.add_bindings(&node.init);
+    node.visit_children_with(self);
+  }
+
+  fn visit_for_in_stmt(&mut self, node: &ast::ForInStmt) {
+    if !self.contains_stopped(&node.range()) {
+      return;
+    }
+    self.add_bindings(&node.left);
+    node.visit_children_with(self);
+  }
+
+  fn visit_for_of_stmt(&mut self, node: &ast::ForOfStmt) {
+    if !self.contains_stopped(&node.range()) {
+      return;
+    }
+    self.add_bindings(&node.left);
+    node.visit_children_with(self);
+  }
+}
+
+/// Walks a module collecting the identifiers which refer to variables in
+/// scope where execution stopped, so that a debugger can look up and display
+/// their values inline. Property names, labels, type references and
+/// declarations of functions and classes are skipped, as they don't hold a
+/// value worth displaying.
+struct InlineValueCollector<'a> {
+  text_info: &'a SourceTextInfo,
+  range: lsp::Range,
+  stopped_line: u32,
+  function_range: Option<lsp::Range>,
+  contexts: HashSet<SyntaxContext>,
+  seen: HashSet<(u32, String)>,
+  inline_values: Vec<lsp::InlineValue>,
+}
+
+impl<'a> InlineValueCollector<'a> {
+  fn new(
+    parsed_source: &'a ParsedSource,
+    range: lsp::Range,
+    stopped_location: lsp::Range,
+  ) -> Self {
+    let mut scope_collector =
+      StoppedScopeCollector::new(parsed_source, stopped_location);
+    parsed_source.module().visit_with(&mut scope_collector);
+    Self {
+      text_info: parsed_source.text_info_lazy(),
+      range,
+      stopped_line: stopped_location.end.line,
+      function_range: scope_collector.function_range,
+      contexts: scope_collector.contexts,
+      seen: HashSet::new(),
+      inline_values: Vec::new(),
+    }
+  }
+
+  fn take(self) -> Vec<lsp::InlineValue> {
+    self.inline_values
+  }
+}
+
+impl Visit for InlineValueCollector<'_> {
+  fn visit_ident(&mut self, node: &ast::Ident) {
+    let range = source_range_to_lsp_range(&node.range(), self.text_info);
+    // values are only displayed up to where execution has stopped, anything
+    // after that hasn't been evaluated yet
+    if range.start.line > self.stopped_line
+      || range.start < self.range.start
+      || range.end > self.range.end
+    {
+      return;
+    }
+    // a variable of the same name elsewhere would be looked up in the paused
+    // frame and show the wrong value
+    if !self.contexts.contains(&node.ctxt) {
+      return;
+    }
+    if let Some(function_range) = &self.function_range {
+      if range.start < function_range.start || range.end > function_range.end {
+        return;
+      }
+    }
+    let name = node.sym.to_string();
+    if !self.seen.insert((range.start.line, name.clone())) {
+      return;
+    }
+    self.inline_values.push(lsp::InlineValue::VariableLookup(
+      lsp::InlineValueVariableLookup {
+        range,
+        variable_name: Some(name),
+        case_sensitive_lookup: true,
+      },
+    ));
+  }
+
+  fn visit_member_prop(&mut self, node: &ast::MemberProp) {
+    if let ast::MemberProp::Computed(computed) = node {
+      computed.visit_with(self);
+    }
+  }
+
+  fn visit_super_prop(&mut self, node: &ast::SuperProp) {
+    if let ast::SuperProp::Computed(computed) = node {
+      computed.visit_with(self);
+    }
+  }
+
+  fn visit_prop_name(&mut self, node: &ast::PropName) {
+    if let ast::PropName::Computed(computed) = node {
+      computed.visit_with(self);
+    }
+  }
+
+  fn visit_fn_decl(&mut self, node: &ast::FnDecl) {
+    node.function.visit_with(self);
+  }
+
+  fn visit_class_decl(&mut self, node: &ast::ClassDecl) {
+    node.class.visit_with(self);
+  }
+
+  fn visit_import_decl(&mut self, _node: &ast::ImportDecl) {}
+
+  fn visit_module_export_name(&mut self, _node: &ast::ModuleExportName) {}
+
+  fn visit_labeled_stmt(&mut self, node: &ast::LabeledStmt) {
+    node.body.visit_with(self);
+  }
+
+  fn visit_break_stmt(&mut self, _node: &ast::BreakStmt) {}
+
+  fn visit_continue_stmt(&mut self, _node: &ast::ContinueStmt) {}
+
+  fn visit_jsx_element_name(&mut self, _node: &ast::JSXElementName) {}
+
+  fn visit_jsx_attr_name(&mut self, _node: &ast::JSXAttrName) {}
+
+  fn visit_ts_type(&mut self, _node: &ast::TsType) {}
+
+  fn visit_ts_type_param_decl(&mut self, _node: &ast::TsTypeParamDecl) {}
+
+  fn visit_ts_type_param_instantiation(
+    &mut self,
+    _node: &ast::TsTypeParamInstantiation,
+  ) {
+  }
+
+  fn visit_ts_interface_decl(&mut self, _node: &ast::TsInterfaceDecl) {}
+
+  fn visit_ts_type_alias_decl(&mut self, _node: &ast::TsTypeAliasDecl) {}
+
+  fn visit_ts_module_decl(&mut self, _node: &ast::TsModuleDecl) {}
+}
+
+/// Return the inline values of the variables referenced in `range` of a
+/// module which are in scope where execution stopped, up to the line where
+/// it stopped. The module must be parsed with scope analysis.
+pub fn collect_inline_values(
+  parsed_source: &ParsedSource,
+  range: lsp::Range,
+  stopped_location: lsp::Range,
+) -> Vec<lsp::InlineValue> {
+  let mut collector =
+    InlineValueCollector::new(parsed_source, range, stopped_location);
+  parsed_source.module().visit_with(&mut collector);
+  collector.take()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use deno_ast::MediaType;
+  use deno_ast::ModuleSpecifier;
+  use pretty_assertions::assert_eq;
+
+  fn variable_lookup(
+    start_line: u32,
+    start_character: u32,
+    end_character: u32,
+    name: &str,
+  ) -> lsp::InlineValue {
+    lsp::InlineValue::VariableLookup(lsp::InlineValueVariableLookup {
+      range: lsp::Range {
+        start: lsp::Position {
+          line: start_line,
+          character: start_character,
+        },
+        end: lsp::Position {
+          line: start_line,
+          character: end_character,
+        },
+      },
+      variable_name: Some(name.to_string()),
+      case_sensitive_lookup: true,
+    })
+  }
+
+  #[test]
+  fn test_collect_inline_values() {
+    let specifier = ModuleSpecifier::parse("file:///a.ts").unwrap();
+    let source = r#"import { b } from "./b.ts";
+interface Point { x: number }
+function add(a: number, c: Point): number {
+  const sum = a + c.x + b;
+  label: for (const i of [sum]) { break label; }
+  return sum;
+}
+const result = add(1, { x: 2 });
+"#;
+    let parsed_source = deno_ast::parse_module(deno_ast::ParseParams {
+      specifier,
+      text: source.into(),
+      media_type: MediaType::TypeScript,
+      capture_tokens: true,
+      scope_analysis: true,
+      maybe_syntax: None,
+    })
+    .unwrap();
+    let inline_values = collect_inline_values(
+      &parsed_source,
+      lsp::Range {
+        start: lsp::Position {
+          line: 0,
+          character: 0,
+        },
+        end: lsp::Position {
+          line: 8,
+          character: 0,
+        },
+      },
+      lsp::Range {
+        start: lsp::Position {
+          line: 4,
+          character: 2,
+        },
+        end: lsp::Position {
+          line: 4,
+          character: 48,
+        },
+      },
+    );
+    assert_eq!(
+      inline_values,
+      vec![
+        variable_lookup(2, 13, 14, "a"),
+        variable_lookup(2, 24, 25, "c"),
+        variable_lookup(3, 8, 11, "sum"),
+        variable_lookup(3, 14, 15, "a"),
+        variable_lookup(3, 18, 19, "c"),
+        variable_lookup(3, 24, 25, "b"),
+        variable_lookup(4, 20, 21, "i"),
+        variable_lookup(4, 26, 29, "sum"),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_collect_inline_values_in_stopped_scope() {
+    let specifier = ModuleSpecifier::parse("file:///a.ts").unwrap();
+    let source = r#"const base = 1;
+function first() {
+  const value = base + 1;
+  return value;
+}
+function second() {
+  const value = base + 2;
+  return value;
+}
+"#;
+    let parsed_source = deno_ast::parse_module(deno_ast::ParseParams {
+      specifier,
+      text: source.into(),
+      media_type: MediaType::TypeScript,
+      capture_tokens: true,
+      scope_analysis: true,
+      maybe_syntax: None,
+    })
+    .unwrap();
+    let inline_values = collect_inline_values(
+      &parsed_source,
+      lsp::Range {
+        start: lsp::Position {
+          line: 0,
+          character: 0,
+        },
+        end: lsp::Position {
+          line: 9,
+          character: 0,
+        },
+      },
+      lsp::Range {
+        start: lsp::Position {
+          line: 7,
+          character: 2,
+        },
+        end: lsp::Position {
+          line: 7,
+          character: 15,
+        },
+      },
+    );
+    assert_eq!(
+      inline_values,
+      vec![
+        variable_lookup(6, 8, 13, "value"),
+        variable_lookup(6, 16, 20, "base"),
+        variable_lookup(7, 9, 14, "value"),
+      ]
+    );
+  }
+}
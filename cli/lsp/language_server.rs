@@ -2,6 +2,7 @@
 
 use base64::Engine;
 use deno_ast::MediaType;
+use deno_config::deno_json::ConfigFile;
 use deno_config::workspace::WorkspaceDirectory;
 use deno_config::workspace::WorkspaceDiscoverOptions;
 use deno_core::anyhow::anyhow;
@@ -18,6 +19,7 @@ use deno_graph::Resolution;
 use deno_runtime::deno_tls::rustls::RootCertStore;
 use deno_runtime::deno_tls::RootCertStoreProvider;
 use deno_semver::jsr::JsrPackageReqReference;
+use deno_semver::npm::NpmPackageReqReference;
 use indexmap::Equivalent;
 use indexmap::IndexSet;
 use log::error;
@@ -41,10 +43,15 @@ use tower_lsp::lsp_types::request::*;
 use tower_lsp::lsp_types::*;
 
 use super::analysis::fix_ts_import_changes;
+use super::analysis::get_add_import_map_entry_edits;
+use super::analysis::get_import_map_entry_for_bare_specifier;
+use super::analysis::get_remove_object_entries_edits;
+use super::analysis::get_statements_byte_range;
 use super::analysis::ts_changes_to_edit;
 use super::analysis::CodeActionCollection;
 use super::analysis::CodeActionData;
 use super::analysis::TsResponseImportMapper;
+use super::analysis::REMOVE_UNUSED_DEPENDENCIES_KIND;
 use super::cache::LspCache;
 use super::capabilities;
 use super::client::Client;
@@ -65,6 +72,7 @@ use super::documents::Document;
 use super::documents::Documents;
 use super::documents::DocumentsFilter;
 use super::documents::LanguageId;
+use super::inline_values;
 use super::jsr::CliJsrSearchApi;
 use super::logging::lsp_log;
 use super::logging::lsp_warn;
@@ -88,11 +96,13 @@ use super::urls;
 use crate::args::create_default_npmrc;
 use crate::args::get_root_cert_store;
 use crate::args::has_flag_env_var;
+use crate::args::jsr_url;
 use crate::args::CaData;
 use crate::args::CacheSetting;
 use crate::args::CliOptions;
 use crate::args::Flags;
 use crate::args::UnstableFmtOptions;
+use crate::cache::FastInsecureHasher;
 use crate::factory::CliFactory;
 use crate::file_fetcher::FileFetcher;
 use crate::graph_util;
@@ -754,7 +764,9 @@ impl Inner {
       self.config.set_client_capabilities(params.capabilities);
     }
 
-    self.diagnostics_server.start();
+    self
+      .diagnostics_server
+      .start(self.config.diagnostic_pull_capable());
     if let Err(e) = self
       .ts_server
       .start(self.config.internal_inspect().to_address())
@@ -1312,17 +1324,206 @@ impl Inner {
     Ok(response)
   }
 
+  async fn diagnostic(
+    &self,
+    params: DocumentDiagnosticParams,
+  ) -> LspResult<DocumentDiagnosticReportResult> {
+    let specifier = self
+      .url_map
+      .normalize_url(&params.text_document.uri, LspUrlKind::File);
+    let mark = self.performance.mark_with_args("lsp.diagnostic", &params);
+    let diagnostics = self.diagnostics_server.pull(&specifier).await;
+    let result_id = diagnostics_result_id(&diagnostics);
+    let report = if params.previous_result_id.as_ref() == Some(&result_id) {
+      DocumentDiagnosticReport::Unchanged(
+        RelatedUnchangedDocumentDiagnosticReport {
+          related_documents: None,
+          unchanged_document_diagnostic_report:
+            UnchangedDocumentDiagnosticReport { result_id },
+        },
+      )
+    } else {
+      DocumentDiagnosticReport::Full(RelatedFullDocumentDiagnosticReport {
+        related_documents: None,
+        full_document_diagnostic_report: FullDocumentDiagnosticReport {
+          result_id: Some(result_id),
+          items: diagnostics,
+        },
+      })
+    };
+    self.performance.measure(mark);
+    Ok(DocumentDiagnosticReportResult::Report(report))
+  }
+
+  async fn document_link(
+    &self,
+    params: DocumentLinkParams,
+  ) -> LspResult<Option<Vec<DocumentLink>>> {
+    let specifier = self
+      .url_map
+      .normalize_url(&params.text_document.uri, LspUrlKind::File);
+    if !self.is_diagnosable(&specifier)
+      || !self.config.specifier_enabled(&specifier)
+    {
+      return Ok(None);
+    }
+    let Some(document) = self.documents.get(&specifier) else {
+      return Ok(None);
+    };
+    let mark = self
+      .performance
+      .mark_with_args("lsp.document_link", &params);
+    let file_referrer = document.file_referrer();
+    let mut links = Vec::new();
+    for dependency in document.dependencies().values() {
+      let resolution = if dependency.maybe_code.is_none() {
+        &dependency.maybe_type
+      } else {
+        &dependency.maybe_code
+      };
+      let Some(target) = resolution.maybe_specifier().and_then(|resolved| {
+        self.resolve_link_target(resolved, &specifier, file_referrer)
+      }) else {
+        continue;
+      };
+      for import in &dependency.imports {
+        links.push(DocumentLink {
+          range: completions::to_narrow_lsp_range(
+            document.text_info(),
+            &import.range,
+          ),
+          target: Some(target.clone()),
+          tooltip: None,
+          data: None,
+        });
+      }
+    }
+    self.performance.measure(mark);
+    Ok(Some(links))
+  }
+
+  /// Resolves where the link of an import should go: the cached source of
+  /// the module, or the registry page of a package that isn't cached.
+  fn resolve_link_target(
+    &self,
+    specifier: &ModuleSpecifier,
+    referrer: &ModuleSpecifier,
+    file_referrer: Option<&ModuleSpecifier>,
+  ) -> Option<Url> {
+    let module_target = |specifier: &ModuleSpecifier| {
+      if !self.documents.exists(specifier, file_referrer) {
+        return None;
+      }
+      self
+        .url_map
+        .normalize_specifier(specifier, file_referrer)
+        .ok()
+        .map(|url| url.into_url())
+    };
+    if let Ok(jsr_req_ref) = JsrPackageReqReference::from_specifier(specifier) {
+      return self
+        .resolver
+        .jsr_to_resource_url(&jsr_req_ref, file_referrer)
+        .and_then(|url| module_target(&url))
+        .or_else(|| jsr_url().join(&jsr_req_ref.req().name).ok());
+    }
+    if let Ok(npm_req_ref) = NpmPackageReqReference::from_specifier(specifier) {
+      return self
+        .resolver
+        .npm_to_file_url(&npm_req_ref, referrer, file_referrer)
+        .map(|(url, _)| url)
+        .or_else(|| {
+          Url::parse(&format!(
+            "https://www.npmjs.com/package/{}",
+            npm_req_ref.req().name
+          ))
+          .ok()
+        });
+    }
+    match specifier.scheme() {
+      "file" => Some(specifier.clone()),
+      "http" | "https" => {
+        module_target(specifier).or_else(|| Some(specifier.clone()))
+      }
+      _ => None,
+    }
+  }
+
   async fn formatting(
     &self,
     params: DocumentFormattingParams,
   ) -> LspResult<Option<Vec<TextEdit>>> {
-    let file_referrer = (self
-      .documents
-      .is_valid_file_referrer(&params.text_document.uri))
-    .then(|| params.text_document.uri.clone());
-    let mut specifier = self
-      .url_map
-      .normalize_url(&params.text_document.uri, LspUrlKind::File);
+    let mark = self.performance.mark_with_args("lsp.formatting", &params);
+    let text_edits = self
+      .format_document(&params.text_document.uri, &params.options, false)
+      .await?
+      .map(|(_, text_edits)| text_edits);
+    self.performance.measure(mark);
+    Ok(text_edits.filter(|text_edits| !text_edits.is_empty()))
+  }
+
+  async fn range_formatting(
+    &self,
+    params: DocumentRangeFormattingParams,
+  ) -> LspResult<Option<Vec<TextEdit>>> {
+    let mark = self
+      .performance
+      .mark_with_args("lsp.range_formatting", &params);
+    let text_edits = self
+      .format_document(&params.text_document.uri, &params.options, false)
+      .await?
+      .and_then(|(document, text_edits)| {
+        filter_edits_to_range(&document, text_edits, params.range)
+      });
+    self.performance.measure(mark);
+    Ok(text_edits.filter(|text_edits| !text_edits.is_empty()))
+  }
+
+  async fn on_type_formatting(
+    &self,
+    params: DocumentOnTypeFormattingParams,
+  ) -> LspResult<Option<Vec<TextEdit>>> {
+    let position = params.text_document_position.position;
+    if position.character == 0 {
+      return Ok(None);
+    }
+    let mark = self
+      .performance
+      .mark_with_args("lsp.on_type_formatting", &params);
+    // format the statement or block that the typed character completed
+    let range = Range {
+      start: Position {
+        line: position.line,
+        character: position.character - 1,
+      },
+      end: position,
+    };
+    let text_edits = self
+      .format_document(
+        &params.text_document_position.text_document.uri,
+        &params.options,
+        true,
+      )
+      .await?
+      .and_then(|(document, text_edits)| {
+        filter_edits_to_range(&document, text_edits, range)
+      });
+    self.performance.measure(mark);
+    Ok(text_edits.filter(|text_edits| !text_edits.is_empty()))
+  }
+
+  /// Formats a whole document with the same formatter as `deno fmt`, returning
+  /// the edits along with the document they apply to. When `parsed_only` is
+  /// set, only JS/TS documents that parse without errors are formatted.
+  async fn format_document(
+    &self,
+    uri: &Url,
+    options: &FormattingOptions,
+    parsed_only: bool,
+  ) -> LspResult<Option<(Arc<Document>, Vec<TextEdit>)>> {
+    let file_referrer =
+      (self.documents.is_valid_file_referrer(uri)).then(|| uri.clone());
+    let mut specifier = self.url_map.normalize_url(uri, LspUrlKind::File);
     // skip formatting any files ignored by the config file
     if !self
       .config
@@ -1338,20 +1539,19 @@ impl Inner {
     let Some(document) = document else {
       return Ok(None);
     };
+    if parsed_only && !matches!(document.maybe_parsed_source(), Some(Ok(_))) {
+      return Ok(None);
+    }
     // Detect vendored paths. Vendor file URLs will normalize to their remote
     // counterparts, but for formatting we want to favour the file URL.
     // TODO(nayeemrmn): Implement `Document::file_resource_path()` or similar.
-    if specifier.scheme() != "file"
-      && params.text_document.uri.scheme() == "file"
-    {
-      specifier = params.text_document.uri.clone();
+    if specifier.scheme() != "file" && uri.scheme() == "file" {
+      specifier = uri.clone();
     }
     let file_path = specifier_to_file_path(&specifier).map_err(|err| {
       error!("{:#}", err);
       LspError::invalid_request()
     })?;
-    let mark = self.performance.mark_with_args("lsp.formatting", &params);
-
     // spawn a blocking task to allow doing other work while this is occurring
     let text_edits = deno_core::unsync::spawn_blocking({
       let mut fmt_options = self
@@ -1360,23 +1560,35 @@ impl Inner {
         .fmt_config_for_specifier(&specifier)
         .options
         .clone();
-      fmt_options.use_tabs = Some(!params.options.insert_spaces);
-      fmt_options.indent_width = Some(params.options.tab_size as u8);
+      fmt_options.use_tabs = Some(!options.insert_spaces);
+      fmt_options.indent_width = Some(options.tab_size as u8);
       let maybe_workspace = self
         .config
         .tree
         .data_for_specifier(&specifier)
         .map(|d| &d.member_dir.workspace);
       let unstable_options = UnstableFmtOptions {
+        css: maybe_workspace
+          .map(|w| w.has_unstable("fmt-css"))
+          .unwrap_or(false),
+        html: maybe_workspace
+          .map(|w| w.has_unstable("fmt-html"))
+          .unwrap_or(false),
+        component: maybe_workspace
+          .map(|w| w.has_unstable("fmt-component"))
+          .unwrap_or(false),
         yaml: maybe_workspace
           .map(|w| w.has_unstable("fmt-yaml"))
           .unwrap_or(false),
+        template_tags: maybe_workspace
+          .map(|w| w.has_unstable("fmt-template-tags"))
+          .unwrap_or(false),
       };
       let document = document.clone();
       move || {
         let format_result = match document.maybe_parsed_source() {
           Some(Ok(parsed_source)) => {
-            format_parsed_source(parsed_source, &fmt_options)
+            format_parsed_source(parsed_source, &fmt_options, &unstable_options)
           }
           Some(Err(err)) => Err(anyhow!("{:#}", err)),
           None => {
@@ -1414,16 +1626,7 @@ impl Inner {
     .await
     .unwrap();
 
-    self.performance.measure(mark);
-    if let Some(text_edits) = text_edits {
-      if text_edits.is_empty() {
-        Ok(None)
-      } else {
-        Ok(Some(text_edits))
-      }
-    } else {
-      Ok(None)
-    }
+    Ok(text_edits.map(|text_edits| (document, text_edits)))
   }
 
   async fn hover(&self, params: HoverParams) -> LspResult<Option<Hover>> {
@@ -1654,6 +1857,20 @@ impl Inner {
             }
           }
           Some("deno") => {
+            if matches!(
+              diagnostic.code.as_ref(),
+              Some(NumberOrString::String(code))
+                if code == "resolver-error" || code == "import-prefix-missing"
+            ) {
+              if let Err(err) = self.add_missing_dependency_action(
+                &specifier,
+                diagnostic,
+                &mut code_actions,
+              ) {
+                lsp_warn!("Unable to add missing dependency action: {:#}", err);
+              }
+              continue;
+            }
             if diagnostic.code
               == Some(NumberOrString::String("no-cache".to_string()))
               || diagnostic.code
@@ -1715,6 +1932,10 @@ impl Inner {
       .as_ref()
       .and_then(|values| values.first().map(|v| v.as_str().to_owned()))
       .unwrap_or_default();
+    // scanning the dependencies of the workspace is relatively expensive, so
+    // this is only done when source actions are explicitly requested
+    let include_remove_unused_dependencies =
+      !only.is_empty() && REMOVE_UNUSED_DEPENDENCIES_KIND.starts_with(&only);
     let refactor_infos = self
       .ts_server
       .get_applicable_refactors(
@@ -1742,6 +1963,15 @@ impl Inner {
         .map(CodeActionOrCommand::CodeAction),
     );
 
+    // Source
+    if include_remove_unused_dependencies {
+      if let Some(action) =
+        self.get_remove_unused_dependencies_action(&specifier)
+      {
+        all_actions.push(CodeActionOrCommand::CodeAction(action));
+      }
+    }
+
     let code_action_disabled_capable =
       self.config.code_action_disabled_capable();
     let actions: Vec<CodeActionOrCommand> = all_actions.into_iter().filter(|ca| {
@@ -1758,6 +1988,207 @@ impl Inner {
     Ok(response)
   }
 
+  fn add_missing_dependency_action(
+    &self,
+    specifier: &ModuleSpecifier,
+    diagnostic: &Diagnostic,
+    code_actions: &mut CodeActionCollection,
+  ) -> Result<(), AnyError> {
+    let Some(bare_specifier) = diagnostic
+      .data
+      .as_ref()
+      .and_then(|data| data.get("specifier"))
+      .and_then(|specifier| specifier.as_str())
+    else {
+      return Ok(());
+    };
+    let Some((key, value)) =
+      get_import_map_entry_for_bare_specifier(bare_specifier)
+    else {
+      return Ok(());
+    };
+    let Some(config_file) = self.imports_config_file(specifier) else {
+      return Ok(());
+    };
+    let Some(config_text) = self.read_config_text(&config_file.specifier)
+    else {
+      return Ok(());
+    };
+    let edits = get_add_import_map_entry_edits(
+      &config_text,
+      &key,
+      &value,
+      &specifier_to_file_path(&config_file.specifier)?,
+      &self
+        .config
+        .tree
+        .fmt_config_for_specifier(&config_file.specifier)
+        .options,
+    )?;
+    code_actions.add_missing_dependency_action(
+      diagnostic,
+      &config_file.specifier,
+      &value,
+      edits,
+    );
+    Ok(())
+  }
+
+  fn get_remove_unused_dependencies_action(
+    &self,
+    specifier: &ModuleSpecifier,
+  ) -> Option<CodeAction> {
+    let config_data = self.config.tree.data_for_specifier(specifier)?;
+    let documents = self.documents.documents(DocumentsFilter::AllDiagnosable);
+    // the dependency specifiers of all the modules in a directory
+    let used_specifiers = |dir: &ModuleSpecifier| {
+      documents
+        .iter()
+        .filter(|d| {
+          d.specifier().as_str().starts_with(dir.as_str())
+            && !d.specifier().path().contains("/node_modules/")
+        })
+        .flat_map(|d| d.dependencies().keys())
+        .collect::<HashSet<_>>()
+    };
+    let mut changes = HashMap::new();
+
+    if let Some(config_file) = self.imports_config_file(specifier) {
+      let used = used_specifiers(&config_file.specifier.join("./").ok()?);
+      let unused = config_file
+        .json
+        .imports
+        .as_ref()
+        .and_then(|imports| imports.as_object())
+        .into_iter()
+        .flat_map(|imports| imports.keys())
+        .filter(|key| {
+          !used.iter().any(|s| {
+            s == key
+              || if key.ends_with('/') {
+                s.starts_with(key.as_str())
+              } else {
+                s.strip_prefix(key.as_str())
+                  .is_some_and(|rest| rest.starts_with('/'))
+              }
+          })
+        })
+        .cloned()
+        .collect::<HashSet<_>>();
+      if !unused.is_empty() {
+        let edits = self
+          .get_remove_unused_entries_edits(
+            &config_file.specifier,
+            "imports",
+            &unused,
+          )
+          .inspect_err(|err| {
+            lsp_warn!("Unable to remove unused dependencies: {:#}", err);
+          })
+          .ok()?;
+        changes.insert(config_file.specifier.clone(), edits);
+      }
+    }
+
+    if let Some(pkg_json) = config_data.maybe_pkg_json() {
+      let pkg_json_specifier = pkg_json.specifier();
+      let used = used_specifiers(&pkg_json_specifier.join("./").ok()?);
+      let unused = pkg_json
+        .dependencies
+        .iter()
+        .flat_map(|deps| deps.keys())
+        .filter(|name| {
+          !used.iter().any(|s| {
+            let s = s.strip_prefix("npm:").unwrap_or(s.as_str());
+            s.strip_prefix(name.as_str()).is_some_and(|rest| {
+              rest.is_empty() || rest.starts_with('/') || rest.starts_with('@')
+            })
+          })
+        })
+        .cloned()
+        .collect::<HashSet<_>>();
+      if !unused.is_empty() {
+        let edits = self
+          .get_remove_unused_entries_edits(
+            &pkg_json_specifier,
+            "dependencies",
+            &unused,
+          )
+          .inspect_err(|err| {
+            lsp_warn!("Unable to remove unused dependencies: {:#}", err);
+          })
+          .ok()?;
+        changes.insert(pkg_json_specifier, edits);
+      }
+    }
+
+    if changes.is_empty() {
+      return None;
+    }
+    Some(CodeAction {
+      title: "Remove unused dependencies".to_string(),
+      kind: Some(CodeActionKind::from(REMOVE_UNUSED_DEPENDENCIES_KIND)),
+      edit: Some(WorkspaceEdit {
+        changes: Some(changes),
+        ..Default::default()
+      }),
+      ..Default::default()
+    })
+  }
+
+  fn get_remove_unused_entries_edits(
+    &self,
+    config_specifier: &ModuleSpecifier,
+    property_name: &str,
+    keys: &HashSet<String>,
+  ) -> Result<Vec<TextEdit>, AnyError> {
+    let config_text = self
+      .read_config_text(config_specifier)
+      .ok_or_else(|| anyhow!("Unable to read {}", config_specifier))?;
+    get_remove_object_entries_edits(
+      &config_text,
+      property_name,
+      keys,
+      &specifier_to_file_path(config_specifier)?,
+      &self
+        .config
+        .tree
+        .fmt_config_for_specifier(config_specifier)
+        .options,
+    )
+  }
+
+  /// Returns the deno.json whose `"imports"` apply to the specifier. This is
+  /// `None` when the imports are read from a separate import map file, which
+  /// isn't modified by code actions.
+  fn imports_config_file(
+    &self,
+    specifier: &ModuleSpecifier,
+  ) -> Option<Arc<ConfigFile>> {
+    let member_dir =
+      &self.config.tree.data_for_specifier(specifier)?.member_dir;
+    let config_file = member_dir
+      .maybe_deno_json()
+      .filter(|c| c.json.imports.is_some())
+      .or_else(|| member_dir.workspace.root_deno_json())
+      .or_else(|| member_dir.maybe_deno_json())?;
+    if config_file.json.import_map.is_some() {
+      return None;
+    }
+    Some(config_file.clone())
+  }
+
+  /// Reads the text of a configuration file, preferring the client's copy of
+  /// the document when it's open.
+  fn read_config_text(&self, specifier: &ModuleSpecifier) -> Option<String> {
+    if let Some(document) = self.documents.get(specifier) {
+      if document.is_open() {
+        return Some(document.content().to_string());
+      }
+    }
+    std::fs::read_to_string(specifier_to_file_path(specifier).ok()?).ok()
+  }
+
   async fn code_action_resolve(
     &self,
     params: CodeAction,
@@ -3016,6 +3447,21 @@ impl tower_lsp::LanguageServer for LanguageServer {
         .await
     } else if params.command == "deno.reloadImportRegistries" {
       self.inner.write().await.reload_import_registries().await
+    } else if params.command == "deno.organizeImportsInWorkspace" {
+      let edit = self
+        .inner
+        .read()
+        .await
+        .organize_imports_in_workspace()
+        .await?;
+      if let Some(edit) = edit {
+        if let Err(err) =
+          self.client.when_outside_lsp_lock().apply_edit(edit).await
+        {
+          lsp_warn!("Unable to apply organized imports.\n{:#}", err);
+        }
+      }
+      Ok(None)
     } else {
       Ok(None)
     }
@@ -3178,6 +3624,26 @@ impl tower_lsp::LanguageServer for LanguageServer {
     self.inner.read().await.document_symbol(params).await
   }
 
+  async fn diagnostic(
+    &self,
+    params: DocumentDiagnosticParams,
+  ) -> LspResult<DocumentDiagnosticReportResult> {
+    if !self.init_flag.is_raised() {
+      self.init_flag.wait_raised().await;
+    }
+    self.inner.read().await.diagnostic(params).await
+  }
+
+  async fn document_link(
+    &self,
+    params: DocumentLinkParams,
+  ) -> LspResult<Option<Vec<DocumentLink>>> {
+    if !self.init_flag.is_raised() {
+      self.init_flag.wait_raised().await;
+    }
+    self.inner.read().await.document_link(params).await
+  }
+
   async fn formatting(
     &self,
     params: DocumentFormattingParams,
@@ -3188,6 +3654,26 @@ impl tower_lsp::LanguageServer for LanguageServer {
     self.inner.read().await.formatting(params).await
   }
 
+  async fn range_formatting(
+    &self,
+    params: DocumentRangeFormattingParams,
+  ) -> LspResult<Option<Vec<TextEdit>>> {
+    if !self.init_flag.is_raised() {
+      self.init_flag.wait_raised().await;
+    }
+    self.inner.read().await.range_formatting(params).await
+  }
+
+  async fn on_type_formatting(
+    &self,
+    params: DocumentOnTypeFormattingParams,
+  ) -> LspResult<Option<Vec<TextEdit>>> {
+    if !self.init_flag.is_raised() {
+      self.init_flag.wait_raised().await;
+    }
+    self.inner.read().await.on_type_formatting(params).await
+  }
+
   async fn hover(&self, params: HoverParams) -> LspResult<Option<Hover>> {
     if !self.init_flag.is_raised() {
       self.init_flag.wait_raised().await;
@@ -3205,6 +3691,26 @@ impl tower_lsp::LanguageServer for LanguageServer {
     self.inner.read().await.inlay_hint(params).await
   }
 
+  async fn inline_value(
+    &self,
+    params: InlineValueParams,
+  ) -> LspResult<Option<Vec<InlineValue>>> {
+    if !self.init_flag.is_raised() {
+      self.init_flag.wait_raised().await;
+    }
+    self.inner.read().await.inline_value(params)
+  }
+
+  async fn linked_editing_range(
+    &self,
+    params: LinkedEditingRangeParams,
+  ) -> LspResult<Option<LinkedEditingRanges>> {
+    if !self.init_flag.is_raised() {
+      self.init_flag.wait_raised().await;
+    }
+    self.inner.read().await.linked_editing_range(params).await
+  }
+
   async fn code_action(
     &self,
     params: CodeActionParams,
@@ -3700,7 +4206,10 @@ impl Inner {
     for config_file in self.config.tree.config_files() {
       if let Some(tasks) = json!(&config_file.json.tasks).as_object() {
         for (name, value) in tasks {
-          let Some(command) = value.as_str() else {
+          let Some(command) = value
+            .as_str()
+            .or_else(|| value.get("command").and_then(|c| c.as_str()))
+          else {
             continue;
           };
           result.push(TaskDefinition {
@@ -3773,6 +4282,113 @@ impl Inner {
     Ok(maybe_inlay_hints)
   }
 
+  fn inline_value(
+    &self,
+    params: InlineValueParams,
+  ) -> LspResult<Option<Vec<InlineValue>>> {
+    let specifier = self
+      .url_map
+      .normalize_url(&params.text_document.uri, LspUrlKind::File);
+    if !self.is_diagnosable(&specifier)
+      || !self.config.specifier_enabled(&specifier)
+    {
+      return Ok(None);
+    }
+
+    let mark = self.performance.mark_with_args("lsp.inline_value", &params);
+    let asset_or_doc = self.get_asset_or_document(&specifier)?;
+    let Some(Ok(parsed_source)) = asset_or_doc.maybe_parsed_source() else {
+      self.performance.measure(mark);
+      return Ok(None);
+    };
+    let inline_values = inline_values::collect_inline_values(
+      parsed_source,
+      params.range,
+      params.context.stopped_location,
+    );
+    self.performance.measure(mark);
+    Ok(Some(inline_values))
+  }
+
+  async fn linked_editing_range(
+    &self,
+    params: LinkedEditingRangeParams,
+  ) -> LspResult<Option<LinkedEditingRanges>> {
+    let specifier = self.url_map.normalize_url(
+      &params.text_document_position_params.text_document.uri,
+      LspUrlKind::File,
+    );
+    if !self.is_diagnosable(&specifier)
+      || !self.config.specifier_enabled(&specifier)
+    {
+      return Ok(None);
+    }
+
+    let asset_or_doc = self.get_asset_or_document(&specifier)?;
+    // only JSX tags have linked editing ranges
+    if !matches!(asset_or_doc.media_type(), MediaType::Jsx | MediaType::Tsx) {
+      return Ok(None);
+    }
+
+    let mark = self
+      .performance
+      .mark_with_args("lsp.linked_editing_range", &params);
+    let line_index = asset_or_doc.line_index();
+    let maybe_linked_editing_info = self
+      .ts_server
+      .get_linked_editing_range(
+        self.snapshot(),
+        specifier,
+        line_index.offset_tsc(params.text_document_position_params.position)?,
+        asset_or_doc.scope().cloned(),
+      )
+      .await?;
+    let maybe_linked_editing_ranges = maybe_linked_editing_info
+      .map(|info| info.to_linked_editing_ranges(line_index));
+    self.performance.measure(mark);
+    Ok(maybe_linked_editing_ranges)
+  }
+
+  async fn organize_imports_in_workspace(
+    &self,
+  ) -> LspResult<Option<WorkspaceEdit>> {
+    let mark = self.performance.mark("lsp.organize_imports_in_workspace");
+    let mut all_changes = Vec::new();
+    for document in self.documents.documents(DocumentsFilter::AllDiagnosable) {
+      let specifier = document.specifier();
+      if specifier.scheme() != "file"
+        || specifier.path().contains("/node_modules/")
+        || !self.config.specifier_enabled(specifier)
+      {
+        continue;
+      }
+      let changes = self
+        .ts_server
+        .organize_imports(
+          self.snapshot(),
+          specifier,
+          (&self.config.tree.fmt_config_for_specifier(specifier).options)
+            .into(),
+          tsc::UserPreferences::from_config_for_specifier(
+            &self.config,
+            specifier,
+          ),
+          document.scope().cloned(),
+        )
+        .await
+        .map_err(|err| {
+          error!("Unable to organize imports of {}: {:#}", specifier, err);
+          LspError::internal_error()
+        })?;
+      all_changes.extend(changes);
+    }
+    self.performance.measure(mark);
+    if all_changes.is_empty() {
+      return Ok(None);
+    }
+    file_text_changes_to_workspace_edit(&all_changes, self)
+  }
+
   async fn reload_import_registries(&mut self) -> LspResult<Option<Value>> {
     remove_dir_all_if_exists(&self.module_registry.location)
       .await
@@ -3888,6 +4504,45 @@ impl Inner {
   }
 }
 
+/// Identifies a set of diagnostics, so that a client pulling diagnostics can
+/// be told when they didn't change.
+fn diagnostics_result_id(diagnostics: &[Diagnostic]) -> String {
+  let mut hasher = FastInsecureHasher::new_without_deno_version();
+  hasher.write_str(&serde_json::to_string(diagnostics).unwrap());
+  format!("{:016x}", hasher.finish())
+}
+
+/// Keeps the edits of formatting a whole document that fall within a range.
+/// In JS/TS documents the range is expanded to the statements it overlaps, so
+/// that the formatted code is always a complete statement.
+fn filter_edits_to_range(
+  document: &Document,
+  text_edits: Vec<TextEdit>,
+  range: Range,
+) -> Option<Vec<TextEdit>> {
+  let line_index = document.line_index();
+  let range: std::ops::Range<usize> =
+    line_index.offset(range.start).ok()?.into()
+      ..line_index.offset(range.end).ok()?.into();
+  let range = match document.maybe_parsed_source() {
+    Some(Ok(parsed_source)) => get_statements_byte_range(parsed_source, range)?,
+    _ => range,
+  };
+  let text_edits = text_edits
+    .into_iter()
+    .filter(|text_edit| {
+      let (Ok(start), Ok(end)) = (
+        line_index.offset(text_edit.range.start),
+        line_index.offset(text_edit.range.end),
+      ) else {
+        return false;
+      };
+      range.start <= usize::from(start) && usize::from(end) <= range.end
+    })
+    .collect();
+  Some(text_edits)
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -21,6 +21,7 @@ mod completions;
 mod config;
 mod diagnostics;
 mod documents;
+mod inline_values;
 mod jsr;
 pub mod language_server;
 mod logging;
@@ -298,6 +298,67 @@ fn visit_call_expr(
   }
 }
 
+/// Check a call expression of a bench to determine the name of the bench.
+fn visit_bench_call_expr(
+  node: &ast::CallExpr,
+  fns: &HashMap<String, ast::Function>,
+  range: Range,
+  text_info: &SourceTextInfo,
+  test_module: &mut TestModule,
+) {
+  let Some(expr) = node.args.first().map(|es| es.expr.as_ref()) else {
+    return;
+  };
+  let maybe_name = match expr {
+    ast::Expr::Object(obj_lit) => obj_lit.props.iter().find_map(|prop| {
+      let ast::PropOrSpread::Prop(prop) = prop else {
+        return None;
+      };
+      let ast::Prop::KeyValue(key_value_prop) = prop.as_ref() else {
+        return None;
+      };
+      let ast::PropName::Ident(ast::IdentName { sym, .. }) =
+        &key_value_prop.key
+      else {
+        return None;
+      };
+      if sym != "name" {
+        return None;
+      }
+      match key_value_prop.value.as_ref() {
+        ast::Expr::Lit(ast::Lit::Str(lit_str)) => {
+          Some(lit_str.value.to_string())
+        }
+        ast::Expr::Tpl(tpl) if tpl.quasis.len() == 1 => {
+          Some(tpl.quasis[0].raw.to_string())
+        }
+        _ => None,
+      }
+    }),
+    ast::Expr::Fn(fn_expr) => {
+      fn_expr.ident.as_ref().map(|ident| ident.sym.to_string())
+    }
+    ast::Expr::Lit(ast::Lit::Str(lit_str)) => Some(lit_str.value.to_string()),
+    ast::Expr::Tpl(tpl) => {
+      (tpl.quasis.len() == 1).then(|| tpl.quasis[0].raw.to_string())
+    }
+    ast::Expr::Ident(ident) => {
+      let name = ident.sym.to_string();
+      fns.contains_key(&name).then_some(name)
+    }
+    _ => {
+      let indexes = text_info.line_and_column_display(node.range().start);
+      Some(format!(
+        "Bench {}:{}",
+        indexes.line_number, indexes.column_number
+      ))
+    }
+  };
+  if let Some(name) = maybe_name {
+    test_module.register_bench(name, Some(range), false);
+  }
+}
+
 /// A structure which can be used to walk a branch of AST determining if the
 /// branch contains any testing steps.
 struct TestStepCollector<'a> {
@@ -442,10 +503,12 @@ impl Visit for TestStepCollector<'_> {
   }
 }
 
-/// Walk an AST and determine if it contains any `Deno.test` tests.
+/// Walk an AST and determine if it contains any `Deno.test` tests or
+/// `Deno.bench` benchmarks.
 pub struct TestCollector {
   test_module: TestModule,
   vars: HashSet<String>,
+  bench_vars: HashSet<String>,
   fns: HashMap<String, ast::Function>,
   text_info: SourceTextInfo,
 }
@@ -455,6 +518,7 @@ impl TestCollector {
     Self {
       test_module: TestModule::new(specifier),
       vars: HashSet::new(),
+      bench_vars: HashSet::new(),
       fns: HashMap::new(),
       text_info,
     }
@@ -468,22 +532,22 @@ impl TestCollector {
 
 impl Visit for TestCollector {
   fn visit_call_expr(&mut self, node: &ast::CallExpr) {
-    fn visit_if_deno_test(
+    fn visit_if_deno_test_or_bench(
       collector: &mut TestCollector,
       node: &ast::CallExpr,
       range: &deno_ast::SourceRange,
       ns_prop_ident: &ast::IdentName,
       member_expr: &ast::MemberExpr,
     ) {
-      if ns_prop_ident.sym == "test" {
-        let ast::Expr::Ident(ident) = member_expr.obj.as_ref() else {
-          return;
-        };
+      let ast::Expr::Ident(ident) = member_expr.obj.as_ref() else {
+        return;
+      };
 
-        if ident.sym != "Deno" {
-          return;
-        }
+      if ident.sym != "Deno" {
+        return;
+      }
 
+      if ns_prop_ident.sym == "test" {
         visit_call_expr(
           node,
           Some(&collector.fns),
@@ -492,6 +556,14 @@ impl Visit for TestCollector {
           &collector.text_info,
           &mut collector.test_module,
         );
+      } else if ns_prop_ident.sym == "bench" {
+        visit_bench_call_expr(
+          node,
+          &collector.fns,
+          source_range_to_lsp_range(range, &collector.text_info),
+          &collector.text_info,
+          &mut collector.test_module,
+        );
       }
     }
 
@@ -510,6 +582,14 @@ impl Visit for TestCollector {
             &self.text_info,
             &mut self.test_module,
           );
+        } else if self.bench_vars.contains(&ident.sym.to_string()) {
+          visit_bench_call_expr(
+            node,
+            &self.fns,
+            source_range_to_lsp_range(&ident.range(), &self.text_info),
+            &self.text_info,
+            &mut self.test_module,
+          );
         }
       }
       ast::Expr::Member(member_expr) => {
@@ -519,7 +599,7 @@ impl Visit for TestCollector {
 
         let ns_prop_ident_name = ns_prop_ident.sym.to_string();
 
-        visit_if_deno_test(
+        visit_if_deno_test_or_bench(
           self,
           node,
           &ns_prop_ident.range(),
@@ -539,7 +619,7 @@ impl Visit for TestCollector {
             return;
           };
 
-          visit_if_deno_test(
+          visit_if_deno_test_or_bench(
             self,
             node,
             &ns_prop_ident.range(),
@@ -573,6 +653,8 @@ impl Visit for TestCollector {
                 let name = prop.key.sym.to_string();
                 if name == "test" {
                   self.vars.insert(name);
+                } else if name == "bench" {
+                  self.bench_vars.insert(name);
                 }
               }
               ast::ObjectPatProp::KeyValue(prop) => {
@@ -580,9 +662,11 @@ impl Visit for TestCollector {
                   continue;
                 };
 
-                if key_ident.sym == "test" {
-                  if let ast::Pat::Ident(value_ident) = &prop.value.as_ref() {
+                if let ast::Pat::Ident(value_ident) = &prop.value.as_ref() {
+                  if key_ident.sym == "test" {
                     self.vars.insert(value_ident.id.sym.to_string());
+                  } else if key_ident.sym == "bench" {
+                    self.bench_vars.insert(value_ident.id.sym.to_string());
                   }
                 }
               }
@@ -604,12 +688,14 @@ impl Visit for TestCollector {
             continue;
           };
 
-          if prop_ident.sym != "test" {
+          let ast::Pat::Ident(binding_ident) = &decl.name else {
             continue;
-          }
+          };
 
-          if let ast::Pat::Ident(binding_ident) = &decl.name {
+          if prop_ident.sym == "test" {
             self.vars.insert(binding_ident.id.sym.to_string());
+          } else if prop_ident.sym == "bench" {
+            self.bench_vars.insert(binding_ident.id.sym.to_string());
           }
         }
         _ => (),
@@ -676,6 +762,7 @@ pub mod tests {
             name: "test".to_string(),
             range: Some(new_range(1, 11, 1, 15)),
             is_dynamic: false,
+            is_bench: false,
             parent_id: None,
             step_ids: Default::default(),
           }
@@ -708,6 +795,7 @@ pub mod tests {
             name: "test".to_string(),
             range: Some(new_range(1, 11, 1, 15)),
             is_dynamic: false,
+            is_bench: false,
             parent_id: None,
             step_ids: Default::default(),
           }
@@ -748,6 +836,7 @@ pub mod tests {
               name: "test".to_string(),
               range: Some(new_range(1, 11, 1, 15)),
               is_dynamic: false,
+              is_bench: false,
               parent_id: None,
               step_ids: vec!["704d24083fd4a3e1bd204faa20827dc594334812245e5d45dda222b3edc60a0c".to_string()].into_iter().collect(),
             }
@@ -759,6 +848,7 @@ pub mod tests {
               name: "step".to_string(),
               range: Some(new_range(4, 18, 4, 22)),
               is_dynamic: false,
+              is_bench: false,
               parent_id: Some("4ebb361c93f76a0f1bac300638675609f1cf481e6f3b9006c3c98604b3a184e9".to_string()),
               step_ids: vec!["0d006a4ec0abaa9cc1d18256b1ccd2677a4c882ff5cb807123890f7528ab1e8d".to_string()].into_iter().collect(),
             }
@@ -770,6 +860,7 @@ pub mod tests {
               name: "sub step".to_string(),
               range: Some(new_range(5, 18, 5, 22)),
               is_dynamic: false,
+              is_bench: false,
               parent_id: Some("704d24083fd4a3e1bd204faa20827dc594334812245e5d45dda222b3edc60a0c".to_string()),
               step_ids: Default::default(),
             }
@@ -809,6 +900,7 @@ pub mod tests {
               name: "test".to_string(),
               range: Some(new_range(1, 11, 1, 15)),
               is_dynamic: false,
+              is_bench: false,
               parent_id: None,
               step_ids: vec!["704d24083fd4a3e1bd204faa20827dc594334812245e5d45dda222b3edc60a0c".to_string()].into_iter().collect(),
             }
@@ -820,6 +912,7 @@ pub mod tests {
               name: "step".to_string(),
               range: Some(new_range(4, 18, 4, 22)),
               is_dynamic: false,
+              is_bench: false,
               parent_id: Some("4ebb361c93f76a0f1bac300638675609f1cf481e6f3b9006c3c98604b3a184e9".to_string()),
               step_ids: vec!["0d006a4ec0abaa9cc1d18256b1ccd2677a4c882ff5cb807123890f7528ab1e8d".to_string()].into_iter().collect(),
             }
@@ -831,6 +924,7 @@ pub mod tests {
               name: "sub step".to_string(),
               range: Some(new_range(5, 18, 5, 22)),
               is_dynamic: false,
+              is_bench: false,
               parent_id: Some("704d24083fd4a3e1bd204faa20827dc594334812245e5d45dda222b3edc60a0c".to_string()),
               step_ids: Default::default(),
             }
@@ -863,6 +957,7 @@ pub mod tests {
             name: "test".to_string(),
             range: Some(new_range(2, 6, 2, 10)),
             is_dynamic: false,
+            is_bench: false,
             parent_id: None,
             step_ids: Default::default(),
           }
@@ -895,6 +990,7 @@ pub mod tests {
               name: "useFnName".to_string(),
               range: Some(new_range(1, 11, 1, 15)),
               is_dynamic: false,
+              is_bench: false,
               parent_id: None,
               step_ids: vec!["dac8a169b8f8c6babf11122557ea545de2733bfafed594d044b22bc6863a0856".to_string()].into_iter().collect(),
             }
@@ -906,6 +1002,7 @@ pub mod tests {
               name: "step".to_string(),
               range: Some(new_range(2, 14, 2, 15)),
               is_dynamic: false,
+              is_bench: false,
               parent_id: Some("86b4c821900e38fc89f24bceb0e45193608ab3f9d2a6019c7b6a5aceff5d7df2".to_string()),
               step_ids: Default::default(),
             }
@@ -938,6 +1035,7 @@ pub mod tests {
             name: "test".to_string(),
             range: Some(new_range(2, 6, 2, 7)),
             is_dynamic: false,
+            is_bench: false,
             parent_id: None,
             step_ids: Default::default(),
           }
@@ -971,6 +1069,7 @@ pub mod tests {
             name: "test".to_string(),
             range: Some(new_range(2, 11, 2, 15)),
             is_dynamic: false,
+            is_bench: false,
             parent_id: None,
             step_ids: Default::default(),
           }
@@ -1003,6 +1102,7 @@ pub mod tests {
               name: "foo".to_string(),
               range: Some(new_range(2, 16, 2, 22)),
               is_dynamic: false,
+              is_bench: false,
               parent_id: None,
               step_ids: Default::default(),
             },
@@ -1014,6 +1114,7 @@ pub mod tests {
               name: "someFunction".to_string(),
               range: Some(new_range(1, 11, 1, 15)),
               is_dynamic: false,
+              is_bench: false,
               parent_id: None,
               step_ids: Default::default(),
             }
@@ -1025,6 +1126,7 @@ pub mod tests {
               name: "bar".to_string(),
               range: Some(new_range(3, 16, 3, 20)),
                 is_dynamic: false,
+                is_bench: false,
                 parent_id: None,
                 step_ids: Default::default(),
             }
@@ -1059,6 +1161,7 @@ pub mod tests {
             name: "someFunction".to_string(),
             range: Some(new_range(2, 11, 2, 15)),
             is_dynamic: false,
+            is_bench: false,
             parent_id: None,
             step_ids: Default::default(),
           }
@@ -1092,6 +1195,7 @@ pub mod tests {
             name: "Test 3:7".to_string(),
             range: Some(new_range(2, 11, 2, 15)),
             is_dynamic: false,
+            is_bench: false,
             parent_id: None,
             step_ids: Default::default(),
           }
@@ -1130,6 +1234,7 @@ pub mod tests {
               name: "1".to_string(),
               range: Some(new_range(1, 11, 1, 15)),
               is_dynamic: false,
+              is_bench: false,
               parent_id: None,
               step_ids: vec![
                 "e714fc695c0895327bf7148a934c3303ad515af029a14906be46f80340c6d7e3".to_string(),
@@ -1144,6 +1249,7 @@ pub mod tests {
               name: "step 1".to_string(),
               range: Some(new_range(2, 16, 2, 20)),
               is_dynamic: false,
+              is_bench: false,
               parent_id: Some("3799fc549a32532145ffc8532b0cd943e025bbc19a02e2cde9be94f87bceb829".to_string()),
               step_ids: vec!["d874949e18dfc297e15c52ff13f13b4e6ae911ec1818b2c761e3313bc018a3ab".to_string()].into_iter().collect()
             }
@@ -1155,6 +1261,7 @@ pub mod tests {
               name: "nested step".to_string(),
               range: Some(new_range(3, 18, 3, 22)),
               is_dynamic: false,
+              is_bench: false,
               parent_id: Some("e714fc695c0895327bf7148a934c3303ad515af029a14906be46f80340c6d7e3".to_string()),
               step_ids: Default::default(),
             }
@@ -1166,6 +1273,7 @@ pub mod tests {
               name: "step 2".to_string(),
               range: Some(new_range(5, 16, 5, 20)),
               is_dynamic: false,
+              is_bench: false,
               parent_id: Some("3799fc549a32532145ffc8532b0cd943e025bbc19a02e2cde9be94f87bceb829".to_string()),
               step_ids: vec!["96729f1f1608e50160b0bf11946719384b4021fd1d26b14eff7765034b3d2684".to_string()].into_iter().collect()
             }
@@ -1177,6 +1285,7 @@ pub mod tests {
               name: "nested step".to_string(),
               range: Some(new_range(6, 18, 6, 22)),
               is_dynamic: false,
+              is_bench: false,
               parent_id: Some("ec6b03d3dd3dde78d2d11ed981d3386083aeca701510cc049189d74bd79f8587".to_string()),
               step_ids: Default::default(),
             }
@@ -1185,4 +1294,72 @@ pub mod tests {
       }
     );
   }
+
+  #[test]
+  fn test_test_collector_bench() {
+    let test_module = collect(
+      r#"
+      Deno.bench("bench", () => {});
+      Deno.test("bench", () => {});
+      const { bench } = Deno;
+      bench({ name: "object bench", fn() {} });
+    "#,
+    );
+
+    assert_eq!(
+      &test_module,
+      &TestModule {
+        specifier: test_module.specifier.clone(),
+        defs: vec![
+          (
+            "5c6a83cbef442663d026d48cbd5dd352d389330be4b2a982a20f45afe7a24cc6"
+              .to_string(),
+            TestDefinition {
+              id:
+                "5c6a83cbef442663d026d48cbd5dd352d389330be4b2a982a20f45afe7a24cc6"
+                  .to_string(),
+              name: "bench".to_string(),
+              range: Some(new_range(1, 11, 1, 16)),
+              is_dynamic: false,
+              is_bench: true,
+              parent_id: None,
+              step_ids: Default::default(),
+            }
+          ),
+          (
+            "a43e8e98adc41e49e7f9ece47b4350154303d38ed7ff868e3e0adfe5e5699dad"
+              .to_string(),
+            TestDefinition {
+              id:
+                "a43e8e98adc41e49e7f9ece47b4350154303d38ed7ff868e3e0adfe5e5699dad"
+                  .to_string(),
+              name: "bench".to_string(),
+              range: Some(new_range(2, 11, 2, 15)),
+              is_dynamic: false,
+              is_bench: false,
+              parent_id: None,
+              step_ids: Default::default(),
+            }
+          ),
+          (
+            "53f77f74028e92894717e2e2f46de27d23ca7d6408324d5eaee753eba6e890cd"
+              .to_string(),
+            TestDefinition {
+              id:
+                "53f77f74028e92894717e2e2f46de27d23ca7d6408324d5eaee753eba6e890cd"
+                  .to_string(),
+              name: "object bench".to_string(),
+              range: Some(new_range(4, 6, 4, 11)),
+              is_dynamic: false,
+              is_bench: true,
+              parent_id: None,
+              step_ids: Default::default(),
+            }
+          ),
+        ]
+        .into_iter()
+        .collect(),
+      }
+    );
+  }
 }
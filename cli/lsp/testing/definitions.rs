@@ -5,6 +5,7 @@ use super::lsp_custom::TestData;
 
 use crate::lsp::client::TestingNotification;
 use crate::lsp::logging::lsp_warn;
+use crate::tools::bench::BenchDescription;
 use crate::tools::test::TestDescription;
 use crate::tools::test::TestStepDescription;
 use crate::util::checksum;
@@ -21,6 +22,7 @@ pub struct TestDefinition {
   pub name: String,
   pub range: Option<Range>,
   pub is_dynamic: bool,
+  pub is_bench: bool,
   pub parent_id: Option<String>,
   pub step_ids: HashSet<String>,
 }
@@ -47,7 +49,28 @@ impl TestModule {
     is_dynamic: bool,
     parent_id: Option<String>,
   ) -> (String, bool) {
-    let mut id_components = Vec::with_capacity(7);
+    self.register_inner(name, range, is_dynamic, false, parent_id)
+  }
+
+  /// Returns `(id, is_newly_registered)`.
+  pub fn register_bench(
+    &mut self,
+    name: String,
+    range: Option<Range>,
+    is_dynamic: bool,
+  ) -> (String, bool) {
+    self.register_inner(name, range, is_dynamic, true, None)
+  }
+
+  fn register_inner(
+    &mut self,
+    name: String,
+    range: Option<Range>,
+    is_dynamic: bool,
+    is_bench: bool,
+    parent_id: Option<String>,
+  ) -> (String, bool) {
+    let mut id_components = Vec::with_capacity(8);
     id_components.push(name.as_bytes());
     let mut current_parent_id = &parent_id;
     while let Some(parent_id) = current_parent_id {
@@ -63,6 +86,10 @@ impl TestModule {
       current_parent_id = &parent.parent_id;
     }
     id_components.push(self.specifier.as_str().as_bytes());
+    // a bench and a test can share a name in the same module
+    if is_bench {
+      id_components.push("bench".as_bytes());
+    }
     id_components.reverse();
     let id = checksum::gen(&id_components);
     if self.defs.contains_key(&id) {
@@ -79,6 +106,7 @@ impl TestModule {
         name,
         range,
         is_dynamic,
+        is_bench,
         parent_id,
         step_ids: Default::default(),
       },
@@ -91,6 +119,14 @@ impl TestModule {
     self.register(desc.name.clone(), None, true, None)
   }
 
+  /// Returns `(id, was_newly_registered)`.
+  pub fn register_bench_dynamic(
+    &mut self,
+    desc: &BenchDescription,
+  ) -> (String, bool) {
+    self.register_bench(desc.name.clone(), None, true)
+  }
+
   /// Returns `(id, was_newly_registered)`.
   pub fn register_step_dynamic(
     &mut self,
@@ -121,6 +157,7 @@ impl TestModule {
           .map(|id| get_test_data_inner(tm, id))
           .collect(),
         range: def.range,
+        is_bench: def.is_bench,
       }
     }
     let def = self.defs.get(id).unwrap();
@@ -133,6 +170,7 @@ impl TestModule {
         label: parent.name.clone(),
         steps: vec![current_data],
         range: None,
+        is_bench: parent.is_bench,
       };
       current_parent_id = &parent.parent_id;
     }
@@ -12,9 +12,12 @@ use crate::lsp::client::Client;
 use crate::lsp::client::TestingNotification;
 use crate::lsp::config;
 use crate::lsp::logging::lsp_log;
+use crate::tools::bench;
+use crate::tools::coverage;
 use crate::tools::test;
 use crate::tools::test::create_test_event_channel;
 use crate::tools::test::FailFastTracker;
+use crate::worker::CliMainWorkerFactory;
 
 use deno_core::anyhow::anyhow;
 use deno_core::error::AnyError;
@@ -32,9 +35,11 @@ use indexmap::IndexMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::num::NonZeroUsize;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 use std::time::Instant;
+use tokio::sync::mpsc::unbounded_channel;
 use tokio_util::sync::CancellationToken;
 use tower_lsp::lsp_types as lsp;
 
@@ -139,6 +144,37 @@ impl LspTestFilter {
       .filter(|id| !self.exclude.contains_key(id))
       .collect()
   }
+
+  /// Returns `true` if any of the tests of the module to be run is a bench,
+  /// when `is_bench` is set, or a test otherwise.
+  fn has_ids(&self, test_module: &TestModule, is_bench: bool) -> bool {
+    self.as_ids(test_module).iter().any(|id| {
+      test_module
+        .defs
+        .get(id)
+        .map(|d| d.is_bench == is_bench)
+        .unwrap_or(false)
+    })
+  }
+
+  fn as_test_filter(&self, is_bench: bool) -> test::TestFilter {
+    test::TestFilter {
+      substring: None,
+      regex: None,
+      include: self.include.as_ref().map(|i| {
+        i.values()
+          .filter(|t| t.is_bench == is_bench)
+          .map(|t| t.name.clone())
+          .collect()
+      }),
+      exclude: self
+        .exclude
+        .values()
+        .filter(|t| t.is_bench == is_bench)
+        .map(|t| t.name.clone())
+        .collect(),
+    }
+  }
 }
 
 #[derive(Debug, Clone)]
@@ -210,7 +246,18 @@ impl TestRun {
     client: &Client,
     maybe_root_uri: Option<&ModuleSpecifier>,
   ) -> Result<(), AnyError> {
-    let args = self.get_args();
+    let coverage_dir = if self.kind == lsp_custom::TestRunKind::Coverage {
+      Some(tempfile::TempDir::new()?)
+    } else {
+      None
+    };
+    let coverage_arg = coverage_dir
+      .as_ref()
+      .map(|dir| format!("--coverage={}", dir.path().display()));
+    let mut args = self.get_args();
+    if let Some(coverage_arg) = &coverage_arg {
+      args.push(coverage_arg);
+    }
     lsp_log!("Executing test run with arguments: {}", args.join(" "));
     let flags =
       Arc::new(flags_from_vec(args.into_iter().map(From::from).collect())?);
@@ -256,34 +303,26 @@ impl TestRun {
     let (test_event_sender_factory, mut receiver) = create_test_event_channel();
     let fail_fast_tracker = FailFastTracker::new(fail_fast);
 
-    let mut queue = self.queue.iter().collect::<Vec<&ModuleSpecifier>>();
-    queue.sort();
+    let (test_queue, bench_queue) = self.as_test_and_bench_queues().await;
 
     let tests: Arc<RwLock<IndexMap<usize, test::TestDescription>>> =
       Arc::new(RwLock::new(IndexMap::new()));
     let mut test_steps = IndexMap::new();
     let worker_factory =
       Arc::new(factory.create_cli_main_worker_factory().await?);
+    let bench_worker_factory = worker_factory.clone();
+    let bench_permissions = permissions.clone();
 
-    let join_handles = queue.into_iter().map(move |specifier| {
-      let specifier = specifier.clone();
+    let join_handles = test_queue.into_iter().map(move |specifier| {
       let worker_factory = worker_factory.clone();
       let permissions = permissions.clone();
       let worker_sender = test_event_sender_factory.worker();
       let fail_fast_tracker = fail_fast_tracker.clone();
-      let lsp_filter = self.filters.get(&specifier);
-      let filter = test::TestFilter {
-        substring: None,
-        regex: None,
-        include: lsp_filter.and_then(|f| {
-          f.include
-            .as_ref()
-            .map(|i| i.values().map(|t| t.name.clone()).collect())
-        }),
-        exclude: lsp_filter
-          .map(|f| f.exclude.values().map(|t| t.name.clone()).collect())
-          .unwrap_or_default(),
-      };
+      let filter = self
+        .filters
+        .get(&specifier)
+        .map(|f| f.as_test_filter(false))
+        .unwrap_or_default();
       let token = self.token.clone();
 
       spawn_blocking(move || {
@@ -435,6 +474,161 @@ impl TestRun {
 
     let (join_results, result) = future::join(join_stream, handler).await;
 
+    // any errors are propagated once the benches have been run and the
+    // coverage has been reported
+    let test_result = join_results
+      .into_iter()
+      .try_for_each(|join_result| join_result?)
+      .and_then(|_| result?);
+
+    let bench_result = self
+      .exec_benches(
+        bench_queue,
+        bench_worker_factory,
+        bench_permissions,
+        client,
+        maybe_root_uri,
+      )
+      .await;
+
+    if let Some(coverage_dir) = &coverage_dir {
+      self
+        .report_coverage(&factory, coverage_dir.path(), client)
+        .await?;
+    }
+
+    test_result?;
+    bench_result
+  }
+
+  /// Split the queue into the modules which have tests to be run and the
+  /// modules which have benches to be run, a module can be in both.
+  async fn as_test_and_bench_queues(
+    &self,
+  ) -> (Vec<ModuleSpecifier>, Vec<ModuleSpecifier>) {
+    let tests = self.tests.lock().await;
+    let mut test_queue = Vec::new();
+    let mut bench_queue = Vec::new();
+    for specifier in &self.queue {
+      let Some((test_module, _)) = tests.get(specifier) else {
+        continue;
+      };
+      let filter = self.filters.get(specifier).cloned().unwrap_or_default();
+      if filter.has_ids(test_module, false) {
+        test_queue.push(specifier.clone());
+      }
+      if filter.has_ids(test_module, true) {
+        bench_queue.push(specifier.clone());
+      }
+    }
+    test_queue.sort();
+    bench_queue.sort();
+    (test_queue, bench_queue)
+  }
+
+  /// Execute the benches, dispatching progress notifications to the client.
+  async fn exec_benches(
+    &self,
+    queue: Vec<ModuleSpecifier>,
+    worker_factory: Arc<CliMainWorkerFactory>,
+    permissions: Permissions,
+    client: &Client,
+    maybe_root_uri: Option<&ModuleSpecifier>,
+  ) -> Result<(), AnyError> {
+    if queue.is_empty() {
+      return Ok(());
+    }
+
+    let (sender, mut receiver) = unbounded_channel::<bench::BenchEvent>();
+
+    let join_handles = queue.into_iter().map(move |specifier| {
+      let worker_factory = worker_factory.clone();
+      let permissions = permissions.clone();
+      let sender = sender.clone();
+      let filter = self
+        .filters
+        .get(&specifier)
+        .map(|f| f.as_test_filter(true))
+        .unwrap_or_default();
+      let token = self.token.clone();
+
+      spawn_blocking(move || {
+        if token.is_cancelled() {
+          Ok(())
+        } else {
+          // All JsErrors are handled by bench_specifier and piped into the
+          // bench channel.
+          create_and_run_current_thread(bench::bench_specifier(
+            worker_factory,
+            permissions,
+            specifier,
+            sender,
+            filter,
+          ))
+        }
+      })
+    });
+
+    // benches are never run concurrently, so they don't skew each other's
+    // measurements
+    let join_stream = stream::iter(join_handles)
+      .buffer_unordered(1)
+      .collect::<Vec<Result<Result<(), AnyError>, tokio::task::JoinError>>>();
+
+    let mut reporter = Box::new(LspTestReporter::new(
+      self,
+      client.clone(),
+      maybe_root_uri,
+      self.tests.clone(),
+    ));
+
+    let handler = {
+      spawn(async move {
+        let mut benches = IndexMap::new();
+        let mut failed = 0;
+
+        while let Some(event) = receiver.recv().await {
+          match event {
+            bench::BenchEvent::Plan(_) => {}
+            bench::BenchEvent::Register(desc) => {
+              if !desc.warmup {
+                reporter.report_bench_register(&desc).await;
+              }
+              benches.insert(desc.id, desc);
+            }
+            bench::BenchEvent::Wait(id) => {
+              if let Some(desc) = benches.get(&id).filter(|d| !d.warmup) {
+                reporter.report_bench_wait(desc);
+              }
+            }
+            bench::BenchEvent::Output(output) => {
+              reporter.report_output(output.as_bytes());
+            }
+            bench::BenchEvent::Result(id, result) => {
+              if let Some(desc) = benches.get(&id).filter(|d| !d.warmup) {
+                if matches!(result, bench::BenchResult::Failed(_)) {
+                  failed += 1;
+                }
+                reporter.report_bench_result(desc, &result);
+              }
+            }
+            bench::BenchEvent::UncaughtError(origin, error) => {
+              reporter.report_uncaught_error(&origin, &error);
+              failed += 1;
+            }
+          }
+        }
+
+        if failed > 0 {
+          return Err(anyhow!("Bench failed"));
+        }
+
+        Ok(())
+      })
+    };
+
+    let (join_results, result) = future::join(join_stream, handler).await;
+
     // propagate any errors
     for join_result in join_results {
       join_result??;
@@ -445,6 +639,34 @@ impl TestRun {
     Ok(())
   }
 
+  /// Send the line coverage collected in `dir` during the run to the client.
+  async fn report_coverage(
+    &self,
+    factory: &CliFactory,
+    dir: &Path,
+    client: &Client,
+  ) -> Result<(), AnyError> {
+    let line_coverage = coverage::collect_line_coverage(factory, dir).await?;
+    for (specifier, found_lines) in line_coverage {
+      client.send_test_notification(TestingNotification::Progress(
+        lsp_custom::TestRunProgressParams {
+          id: self.id,
+          message: lsp_custom::TestRunProgressMessage::Coverage {
+            text_document: lsp::TextDocumentIdentifier { uri: specifier },
+            lines: found_lines
+              .into_iter()
+              .map(|(line, hits)| lsp_custom::TestLineCoverage {
+                line: line as u32,
+                hits: hits.max(0) as u32,
+              })
+              .collect(),
+          },
+        },
+      ));
+    }
+    Ok(())
+  }
+
   fn get_args(&self) -> Vec<&str> {
     let mut args = vec!["deno", "test"];
     args.extend(
@@ -487,6 +709,8 @@ enum LspTestDescription {
   TestDescription(test::TestDescription, String),
   /// `(desc, static_id)`
   TestStepDescription(test::TestStepDescription, String),
+  /// `(desc, static_id)`
+  BenchDescription(bench::BenchDescription, String),
 }
 
 impl LspTestDescription {
@@ -494,20 +718,25 @@ impl LspTestDescription {
     match self {
       LspTestDescription::TestDescription(d, _) => d.origin.as_str(),
       LspTestDescription::TestStepDescription(d, _) => d.origin.as_str(),
+      LspTestDescription::BenchDescription(d, _) => d.origin.as_str(),
     }
   }
 
-  fn location(&self) -> &test::TestLocation {
-    match self {
-      LspTestDescription::TestDescription(d, _) => &d.location,
-      LspTestDescription::TestStepDescription(d, _) => &d.location,
-    }
+  fn uri(&self) -> ModuleSpecifier {
+    let file_name = match self {
+      LspTestDescription::TestDescription(d, _) => &d.location.file_name,
+      LspTestDescription::TestStepDescription(d, _) => &d.location.file_name,
+      // benches don't have a location, their origin is the module
+      LspTestDescription::BenchDescription(d, _) => &d.origin,
+    };
+    ModuleSpecifier::parse(file_name).unwrap()
   }
 
   fn parent_id(&self) -> Option<usize> {
     match self {
       LspTestDescription::TestDescription(_, _) => None,
       LspTestDescription::TestStepDescription(d, _) => Some(d.parent_id),
+      LspTestDescription::BenchDescription(_, _) => None,
     }
   }
 
@@ -515,6 +744,7 @@ impl LspTestDescription {
     match self {
       LspTestDescription::TestDescription(_, i) => i,
       LspTestDescription::TestStepDescription(_, i) => i,
+      LspTestDescription::BenchDescription(_, i) => i,
     }
   }
 
@@ -522,7 +752,7 @@ impl LspTestDescription {
     &self,
     tests: &IndexMap<usize, LspTestDescription>,
   ) -> lsp_custom::TestIdentifier {
-    let uri = ModuleSpecifier::parse(&self.location().file_name).unwrap();
+    let uri = self.uri();
     let static_id = self.static_id();
     let mut root_desc = self;
     while let Some(parent_id) = root_desc.parent_id() {
@@ -758,6 +988,80 @@ impl LspTestReporter {
     }
   }
 
+  async fn report_bench_register(&mut self, desc: &bench::BenchDescription) {
+    let mut files = self.files.lock().await;
+    let specifier = ModuleSpecifier::parse(&desc.origin).unwrap();
+    let (test_module, _) = files
+      .entry(specifier.clone())
+      .or_insert_with(|| (TestModule::new(specifier), "1".to_string()));
+    let (static_id, is_new) = test_module.register_bench_dynamic(desc);
+    self.tests.insert(
+      desc.id,
+      LspTestDescription::BenchDescription(desc.clone(), static_id.clone()),
+    );
+    if is_new {
+      self
+        .client
+        .send_test_notification(TestingNotification::Module(
+          lsp_custom::TestModuleNotificationParams {
+            text_document: lsp::TextDocumentIdentifier {
+              uri: test_module.specifier.clone(),
+            },
+            kind: lsp_custom::TestModuleNotificationKind::Insert,
+            label: test_module.label(self.maybe_root_uri.as_ref()),
+            tests: vec![test_module.get_test_data(&static_id)],
+          },
+        ));
+    }
+  }
+
+  fn report_bench_wait(&mut self, desc: &bench::BenchDescription) {
+    self.current_test = Some(desc.id);
+    let desc = self.tests.get(&desc.id).unwrap();
+    let test = desc.as_test_identifier(&self.tests);
+    self.progress(lsp_custom::TestRunProgressMessage::Started { test });
+  }
+
+  fn report_bench_result(
+    &mut self,
+    desc: &bench::BenchDescription,
+    result: &bench::BenchResult,
+  ) {
+    self.current_test = None;
+    let desc = self.tests.get(&desc.id).unwrap();
+    let test = desc.as_test_identifier(&self.tests);
+    match result {
+      bench::BenchResult::Ok(stats) => {
+        self.progress(lsp_custom::TestRunProgressMessage::Output {
+          value: format!(
+            "{}/iter (min {}, max {}, p75 {}, p99 {})\r\n",
+            bench::fmt_duration(stats.avg),
+            bench::fmt_duration(stats.min),
+            bench::fmt_duration(stats.max),
+            bench::fmt_duration(stats.p75),
+            bench::fmt_duration(stats.p99),
+          ),
+          test: Some(test.clone()),
+          location: None,
+        });
+        self.progress(lsp_custom::TestRunProgressMessage::Passed {
+          test,
+          duration: None,
+        })
+      }
+      bench::BenchResult::Failed(js_error) => {
+        self.progress(lsp_custom::TestRunProgressMessage::Failed {
+          test,
+          messages: as_test_messages(
+            test::fmt::format_test_error(js_error),
+            false,
+          ),
+          duration: None,
+        })
+      }
+    }
+  }
+
   fn report_completed(&mut self) {
     // there is nothing to do on report_completed
   }
@@ -820,6 +1124,7 @@ mod tests {
       name: "test a".to_string(),
       range: Some(new_range(1, 5, 1, 9)),
       is_dynamic: false,
+      is_bench: false,
       parent_id: None,
       step_ids: Default::default(),
     };
@@ -829,6 +1134,7 @@ mod tests {
       name: "test b".to_string(),
       range: Some(new_range(2, 5, 2, 9)),
       is_dynamic: false,
+      is_bench: false,
       parent_id: None,
       step_ids: Default::default(),
     };
@@ -27,6 +27,10 @@ pub struct TestData {
   /// The range where the test is located.
   #[serde(skip_serializing_if = "Option::is_none")]
   pub range: Option<lsp::Range>,
+  /// If the test is a benchmark registered with `Deno.bench()`.
+  #[serde(skip_serializing_if = "std::ops::Not::not")]
+  #[serde(default)]
+  pub is_bench: bool,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -83,8 +87,7 @@ pub enum TestRunKind {
   Run,
   // The tests should be run and debugged, currently not implemented
   Debug,
-  // The tests should be run, collecting and reporting coverage information,
-  // currently not implemented
+  // The tests should be run, collecting and reporting coverage information
   Coverage,
 }
 
@@ -164,9 +167,25 @@ pub enum TestRunProgressMessage {
     #[serde(skip_serializing_if = "Option::is_none")]
     location: Option<lsp::Location>,
   },
+  /// The line coverage of a module collected during a run of the `Coverage`
+  /// kind, sent before the run ends.
+  #[serde(rename_all = "camelCase")]
+  Coverage {
+    text_document: lsp::TextDocumentIdentifier,
+    lines: Vec<TestLineCoverage>,
+  },
   End,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestLineCoverage {
+  /// The zero based line in the document.
+  pub line: u32,
+  /// The number of times the line was executed.
+  pub hits: u32,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TestMessage {
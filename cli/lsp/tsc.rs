@@ -748,6 +748,31 @@ impl TsServer {
       })
   }
 
+  pub async fn organize_imports(
+    &self,
+    snapshot: Arc<StateSnapshot>,
+    specifier: &ModuleSpecifier,
+    format_code_settings: FormatCodeSettings,
+    preferences: UserPreferences,
+    scope: Option<ModuleSpecifier>,
+  ) -> Result<Vec<FileTextChanges>, AnyError> {
+    let req = TscRequest::OrganizeImports(Box::new((
+      CombinedCodeFixScope {
+        r#type: "file",
+        file_name: self.specifier_map.denormalize(specifier),
+      },
+      format_code_settings,
+      preferences,
+    )));
+    let mut changes = self
+      .request::<Vec<FileTextChanges>>(snapshot, req, scope)
+      .await?;
+    for changes in &mut changes {
+      changes.normalize(&self.specifier_map)?;
+    }
+    Ok(changes)
+  }
+
   pub async fn get_edits_for_file_rename(
     &self,
     snapshot: Arc<StateSnapshot>,
@@ -1158,6 +1183,23 @@ impl TsServer {
     })
   }
 
+  pub async fn get_linked_editing_range(
+    &self,
+    snapshot: Arc<StateSnapshot>,
+    specifier: ModuleSpecifier,
+    position: u32,
+    scope: Option<ModuleSpecifier>,
+  ) -> Result<Option<LinkedEditingInfo>, LspError> {
+    let req = TscRequest::GetLinkedEditingRangeAtPosition((
+      self.specifier_map.denormalize(&specifier),
+      position,
+    ));
+    self.request(snapshot, req, scope).await.map_err(|err| {
+      log::error!("Failed to request to tsserver {}", err);
+      LspError::invalid_request()
+    })
+  }
+
   pub async fn get_encoded_semantic_classifications(
     &self,
     snapshot: Arc<StateSnapshot>,
@@ -4138,6 +4180,29 @@ impl SelectionRange {
   }
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkedEditingInfo {
+  ranges: Vec<TextSpan>,
+  word_pattern: Option<String>,
+}
+
+impl LinkedEditingInfo {
+  pub fn to_linked_editing_ranges(
+    &self,
+    line_index: Arc<LineIndex>,
+  ) -> lsp::LinkedEditingRanges {
+    lsp::LinkedEditingRanges {
+      ranges: self
+        .ranges
+        .iter()
+        .map(|span| span.to_range(line_index.clone()))
+        .collect(),
+      word_pattern: self.word_pattern.clone(),
+    }
+  }
+}
+
 #[derive(Debug, Default)]
 pub struct TscSpecifierMap {
   normalized_specifiers: DashMap<String, ModuleSpecifier>,
@@ -5184,6 +5249,10 @@ pub enum TscRequest {
   GetEditsForFileRename(
     Box<(String, String, FormatCodeSettings, UserPreferences)>,
   ),
+  // https://github.com/denoland/deno/blob/v1.37.1/cli/tsc/dts/typescript.d.ts#L6282
+  OrganizeImports(
+    Box<(CombinedCodeFixScope, FormatCodeSettings, UserPreferences)>,
+  ),
   // https://github.com/denoland/deno/blob/v1.37.1/cli/tsc/dts/typescript.d.ts#L6231
   GetDocumentHighlights(Box<(String, u32, Vec<String>)>),
   // https://github.com/denoland/deno/blob/v1.37.1/cli/tsc/dts/typescript.d.ts#L6226
@@ -5226,6 +5295,8 @@ pub enum TscRequest {
   FindRenameLocations((String, u32, bool, bool, bool)),
   // https://github.com/denoland/deno/blob/v1.37.1/cli/tsc/dts/typescript.d.ts#L6224
   GetSmartSelectionRange((String, u32)),
+  // getLinkedEditingRangeAtPosition() in cli/tsc/dts/typescript.d.ts
+  GetLinkedEditingRangeAtPosition((String, u32)),
   // https://github.com/denoland/deno/blob/v1.37.1/cli/tsc/dts/typescript.d.ts#L6183
   GetEncodedSemanticClassifications((String, TextSpan, &'static str)),
   // https://github.com/denoland/deno/blob/v1.37.1/cli/tsc/dts/typescript.d.ts#L6217
@@ -5276,6 +5347,9 @@ impl TscRequest {
       TscRequest::GetEditsForFileRename(args) => {
         ("getEditsForFileRename", Some(serde_v8::to_v8(scope, args)?))
       }
+      TscRequest::OrganizeImports(args) => {
+        ("organizeImports", Some(serde_v8::to_v8(scope, args)?))
+      }
       TscRequest::GetDocumentHighlights(args) => {
         ("getDocumentHighlights", Some(serde_v8::to_v8(scope, args)?))
       }
@@ -5320,6 +5394,10 @@ impl TscRequest {
         "getSmartSelectionRange",
         Some(serde_v8::to_v8(scope, args)?),
       ),
+      TscRequest::GetLinkedEditingRangeAtPosition(args) => (
+        "getLinkedEditingRangeAtPosition",
+        Some(serde_v8::to_v8(scope, args)?),
+      ),
       TscRequest::GetEncodedSemanticClassifications(args) => (
         "getEncodedSemanticClassifications",
         Some(serde_v8::to_v8(scope, args)?),
@@ -5353,6 +5431,7 @@ impl TscRequest {
       TscRequest::GetCombinedCodeFix(_) => "getCombinedCodeFix",
       TscRequest::GetEditsForRefactor(_) => "getEditsForRefactor",
       TscRequest::GetEditsForFileRename(_) => "getEditsForFileRename",
+      TscRequest::OrganizeImports(_) => "organizeImports",
       TscRequest::GetDocumentHighlights(_) => "getDocumentHighlights",
       TscRequest::GetDefinitionAndBoundSpan(_) => "getDefinitionAndBoundSpan",
       TscRequest::GetTypeDefinitionAtPosition(_) => {
@@ -5373,6 +5452,9 @@ impl TscRequest {
       TscRequest::PrepareCallHierarchy(_) => "prepareCallHierarchy",
       TscRequest::FindRenameLocations(_) => "findRenameLocations",
       TscRequest::GetSmartSelectionRange(_) => "getSmartSelectionRange",
+      TscRequest::GetLinkedEditingRangeAtPosition(_) => {
+        "getLinkedEditingRangeAtPosition"
+      }
       TscRequest::GetEncodedSemanticClassifications(_) => {
         "getEncodedSemanticClassifications"
       }
@@ -32,6 +32,7 @@ use crate::args::flags_from_vec;
 use crate::args::DenoSubcommand;
 use crate::args::Flags;
 use crate::args::DENO_FUTURE;
+use crate::args::SERVE_WORKER_ID;
 use crate::graph_container::ModuleGraphContainer;
 use crate::util::display;
 use crate::util::v8::get_v8_flags_from_env;
@@ -126,9 +127,10 @@ async fn run_subcommand(flags: Arc<Flags>) -> Result<i32, AnyError> {
       let factory = CliFactory::from_flags(flags);
       let main_graph_container =
         factory.main_module_graph_container().await?;
-      main_graph_container
+      let result = main_graph_container
         .load_and_type_check_files(&check_flags.files)
-        .await
+        .await;
+      tools::check::report_check_result(check_flags.reporter, result)
     }),
     DenoSubcommand::Compile(compile_flags) => spawn_subcommand(async {
       tools::compile::compile(flags, compile_flags).await
@@ -141,13 +143,9 @@ async fn run_subcommand(flags: Arc<Flags>) -> Result<i32, AnyError> {
         async move { tools::fmt::format(flags, fmt_flags).await },
       )
     }
-    DenoSubcommand::Init(init_flags) => {
-      spawn_subcommand(async {
-        // make compiler happy since init_project is sync
-        tokio::task::yield_now().await;
-        tools::init::init_project(init_flags)
-      })
-    }
+    DenoSubcommand::Init(init_flags) => spawn_subcommand(async {
+      tools::init::init_project(flags, init_flags).await
+    }),
     DenoSubcommand::Info(info_flags) => {
       spawn_subcommand(async { tools::info::info(flags, info_flags).await })
     }
@@ -183,7 +181,12 @@ async fn run_subcommand(flags: Arc<Flags>) -> Result<i32, AnyError> {
       }
     }),
     DenoSubcommand::Serve(serve_flags) => spawn_subcommand(async move {
-      tools::run::run_script(WorkerExecutionMode::Serve, flags, serve_flags.watch).await
+      match serve_flags.parallel {
+        Some(workers) if SERVE_WORKER_ID.is_none() => {
+          tools::serve::serve_parallel(&serve_flags, workers).await
+        }
+        _ => tools::run::run_script(WorkerExecutionMode::Serve, flags, serve_flags.watch).await,
+      }
     }),
     DenoSubcommand::Task(task_flags) => spawn_subcommand(async {
       tools::task::execute_script(flags, task_flags).await
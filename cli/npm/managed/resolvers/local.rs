@@ -796,6 +796,8 @@ async fn sync_resolution_with_fs(
               init_cwd,
               argv: &[],
               root_node_modules_dir: Some(root_node_modules_dir_path),
+              stdio: None,
+              shell: crate::args::TaskShell::Deno,
             })
             .await?;
           if exit_code != 0 {
@@ -25,7 +25,6 @@ use deno_core::anyhow::bail;
 use deno_core::anyhow::Context;
 use deno_core::error::AnyError;
 use deno_core::futures::io::AllowStdIo;
-use deno_core::futures::AsyncReadExt;
 use deno_core::futures::AsyncSeekExt;
 use deno_core::serde_json;
 use deno_core::url::Url;
@@ -47,19 +46,25 @@ use crate::args::PackageJsonInstallDepsProvider;
 use crate::args::PermissionFlags;
 use crate::args::UnstableConfig;
 use crate::cache::DenoDir;
+use crate::cache::FastInsecureHasher;
 use crate::file_fetcher::FileFetcher;
 use crate::http_util::HttpClientProvider;
 use crate::npm::CliNpmResolver;
 use crate::npm::InnerCliNpmResolverRef;
 use crate::standalone::virtual_fs::VfsEntry;
+use crate::util::display::human_size;
 use crate::util::fs::canonicalize_path_maybe_not_exists;
 use crate::util::progress_bar::ProgressBar;
 use crate::util::progress_bar::ProgressBarStyle;
 
+use super::sbom::describe_sbom;
+use super::sbom::SbomBuilder;
+use super::virtual_fs::is_single_normal_component;
 use super::virtual_fs::FileBackedVfs;
 use super::virtual_fs::VfsBuilder;
 use super::virtual_fs::VfsRoot;
 use super::virtual_fs::VirtualDirectory;
+use super::virtual_fs::COMPRESSION_LEVEL;
 
 const MAGIC_TRAILER: &[u8; 8] = b"d3n0l4nd";
 
@@ -104,9 +109,17 @@ pub struct Metadata {
   pub node_modules: Option<NodeModules>,
   pub disable_deprecated_api_warning: bool,
   pub unstable_config: UnstableConfig,
+  /// Identifies the modules of the executable in the V8 code cache. This is
+  /// `None` when compiled with `--no-code-cache`.
+  pub code_cache_key: Option<u64>,
 }
 
-pub fn load_npm_vfs(root_dir_path: PathBuf) -> Result<FileBackedVfs, AnyError> {
+/// Loads the virtual file system embedded in the executable, rooted at
+/// `root_dir_path`. Returns `None` when the executable has no npm packages or
+/// included files.
+pub fn load_vfs(
+  root_dir_path: PathBuf,
+) -> Result<Option<FileBackedVfs>, AnyError> {
   let data = libsui::find_section("d3n0l4nd").unwrap();
 
   // We do the first part sync so it can complete quickly
@@ -115,11 +128,21 @@ pub fn load_npm_vfs(root_dir_path: PathBuf) -> Result<FileBackedVfs, AnyError> {
     None => panic!("Could not find trailer"),
     Some(trailer) => trailer,
   };
-  let data = &data[TRAILER_SIZE..];
+  vfs_from_section_data(&data[TRAILER_SIZE..], &trailer, root_dir_path)
+}
 
+fn vfs_from_section_data(
+  data: &[u8],
+  trailer: &Trailer,
+  root_dir_path: PathBuf,
+) -> Result<Option<FileBackedVfs>, AnyError> {
   let vfs_data =
-    &data[trailer.npm_vfs_pos as usize..trailer.npm_files_pos as usize];
-  let mut dir: VirtualDirectory = serde_json::from_slice(vfs_data)?;
+    &data[trailer.vfs_pos as usize..trailer.vfs_files_pos as usize];
+  let Some(mut dir) =
+    serde_json::from_slice::<Option<VirtualDirectory>>(vfs_data)?
+  else {
+    return Ok(None);
+  };
 
   // align the name of the directory with the root dir
   dir.name = root_dir_path
@@ -131,23 +154,43 @@ pub fn load_npm_vfs(root_dir_path: PathBuf) -> Result<FileBackedVfs, AnyError> {
   let fs_root = VfsRoot {
     dir,
     root_path: root_dir_path,
-    start_file_offset: trailer.npm_files_pos,
+    start_file_offset: trailer.vfs_files_pos,
   };
-  Ok(FileBackedVfs::new(data.to_vec(), fs_root))
+  Ok(Some(FileBackedVfs::new(data.to_vec(), fs_root)))
+}
+
+/// The virtual file system to write to the binary.
+struct EmbeddedVfs {
+  root_dir: VirtualDirectory,
+  files: Vec<Vec<u8>>,
+  /// The size of the files before compression.
+  uncompressed_len: u64,
+  /// The bytes saved by storing files with the same content once.
+  deduplicated_len: u64,
 }
 
 fn write_binary_bytes(
   mut file_writer: File,
   original_bin: Vec<u8>,
   metadata: &Metadata,
-  eszip: eszip::EszipV2,
-  npm_vfs: Option<&VirtualDirectory>,
-  npm_files: &Vec<Vec<u8>>,
+  eszip_archive: Vec<u8>,
+  sbom: Option<&str>,
+  vfs: Option<&EmbeddedVfs>,
   compile_flags: &CompileFlags,
 ) -> Result<(), AnyError> {
   let metadata = serde_json::to_string(metadata)?.as_bytes().to_vec();
-  let npm_vfs = serde_json::to_string(&npm_vfs)?.as_bytes().to_vec();
-  let eszip_archive = eszip.into_bytes();
+  let sbom = sbom.unwrap_or_default().as_bytes();
+  let vfs_json =
+    serde_json::to_string(&vfs.map(|vfs| &vfs.root_dir))?.into_bytes();
+  let eszip_len = eszip_archive.len();
+  let (eszip_archive, flags) = if compile_flags.compress {
+    (
+      zstd::stream::encode_all(eszip_archive.as_slice(), COMPRESSION_LEVEL)?,
+      TRAILER_FLAG_COMPRESSED_ESZIP,
+    )
+  } else {
+    (eszip_archive, 0)
+  };
 
   let mut writer = Vec::new();
 
@@ -155,24 +198,42 @@ fn write_binary_bytes(
   // of the data blocks in the file
   writer.write_all(&{
     let metadata_pos = eszip_archive.len() as u64;
-    let npm_vfs_pos = metadata_pos + (metadata.len() as u64);
-    let npm_files_pos = npm_vfs_pos + (npm_vfs.len() as u64);
+    let sbom_pos = metadata_pos + (metadata.len() as u64);
+    let vfs_pos = sbom_pos + (sbom.len() as u64);
+    let vfs_files_pos = vfs_pos + (vfs_json.len() as u64);
     Trailer {
       eszip_pos: 0,
       metadata_pos,
-      npm_vfs_pos,
-      npm_files_pos,
+      sbom_pos,
+      vfs_pos,
+      vfs_files_pos,
+      flags,
     }
     .as_bytes()
   })?;
 
   writer.write_all(&eszip_archive)?;
   writer.write_all(&metadata)?;
-  writer.write_all(&npm_vfs)?;
-  for file in npm_files {
-    writer.write_all(file)?;
+  writer.write_all(sbom)?;
+  writer.write_all(&vfs_json)?;
+  let mut vfs_files_len = 0;
+  if let Some(vfs) = vfs {
+    for file in &vfs.files {
+      writer.write_all(file)?;
+      vfs_files_len += file.len();
+    }
   }
 
+  log_size_breakdown(
+    &original_bin,
+    eszip_len,
+    eszip_archive.len(),
+    metadata.len() + vfs_json.len(),
+    sbom.len(),
+    vfs_files_len,
+    vfs,
+  );
+
   let target = compile_flags.resolve_target();
   if target.contains("linux") {
     libsui::Elf::new(&original_bin).append(&writer, &mut file_writer)?;
@@ -188,6 +249,69 @@ fn write_binary_bytes(
   Ok(())
 }
 
+/// Logs how much each section appended to the base binary adds to the size
+/// of the executable.
+fn log_size_breakdown(
+  original_bin: &[u8],
+  eszip_len: usize,
+  written_eszip_len: usize,
+  metadata_len: usize,
+  sbom_len: usize,
+  vfs_files_len: usize,
+  vfs: Option<&EmbeddedVfs>,
+) {
+  fn size(len: usize) -> String {
+    human_size(len as f64)
+  }
+
+  log::info!("{}", crate::colors::bold("Size:"));
+  log::info!("  Runtime:  {}", size(original_bin.len()));
+  if written_eszip_len != eszip_len {
+    log::info!(
+      "  Modules:  {} (compressed from {})",
+      size(written_eszip_len),
+      size(eszip_len)
+    );
+  } else {
+    log::info!("  Modules:  {}", size(written_eszip_len));
+  }
+  log::info!("  Metadata: {}", size(metadata_len));
+  if sbom_len > 0 {
+    log::info!("  SBOM:     {}", size(sbom_len));
+  }
+  if let Some(vfs) = vfs {
+    let mut notes = Vec::new();
+    if vfs.uncompressed_len as usize != vfs_files_len {
+      notes.push(format!(
+        "compressed from {}",
+        size(vfs.uncompressed_len as usize)
+      ));
+    }
+    if vfs.deduplicated_len > 0 {
+      notes.push(format!(
+        "{} saved by deduplication",
+        size(vfs.deduplicated_len as usize)
+      ));
+    }
+    if notes.is_empty() {
+      log::info!("  Files:    {}", size(vfs_files_len));
+    } else {
+      log::info!("  Files:    {} ({})", size(vfs_files_len), notes.join(", "));
+    }
+  }
+  log::info!(
+    "  Total:    {}",
+    size(
+      original_bin.len()
+        + written_eszip_len
+        + metadata_len
+        + sbom_len
+        + vfs_files_len
+        + TRAILER_SIZE
+    )
+  );
+}
+
 pub fn is_standalone_binary(exe_path: &Path) -> bool {
   let Ok(data) = std::fs::read(exe_path) else {
     return false;
@@ -222,24 +346,13 @@ pub fn extract_standalone(
   let cli_args = cli_args.into_owned();
   // If we have an eszip, read it out
   Ok(Some(async move {
-    let bufreader =
-      deno_core::futures::io::BufReader::new(&data[TRAILER_SIZE..]);
-
-    let (eszip, loader) = eszip::EszipV2::parse(bufreader)
-      .await
-      .context("Failed to parse eszip header")?;
-
-    let bufreader = loader.await.context("Failed to parse eszip archive")?;
-
-    let mut metadata = String::new();
+    let data = &data[TRAILER_SIZE..];
+    let eszip = parse_eszip(data, &trailer).await?;
 
-    bufreader
-      .take(trailer.metadata_len())
-      .read_to_string(&mut metadata)
-      .await
+    let metadata =
+      &data[trailer.metadata_pos as usize..trailer.sbom_pos as usize];
+    let mut metadata: Metadata = serde_json::from_slice(metadata)
       .context("Failed to read metadata from the current executable")?;
-
-    let mut metadata: Metadata = serde_json::from_str(&metadata).unwrap();
     metadata.argv.reserve(cli_args.len() - 1);
     for arg in cli_args.into_iter().skip(1) {
       metadata.argv.push(arg.into_string().unwrap());
@@ -249,13 +362,304 @@ pub fn extract_standalone(
   }))
 }
 
+async fn parse_eszip(
+  data: &[u8],
+  trailer: &Trailer,
+) -> Result<eszip::EszipV2, AnyError> {
+  let eszip_data =
+    &data[trailer.eszip_pos as usize..trailer.metadata_pos as usize];
+  let eszip_data = if trailer.has_compressed_eszip() {
+    Cow::Owned(
+      zstd::stream::decode_all(eszip_data)
+        .context("Failed to decompress eszip")?,
+    )
+  } else {
+    Cow::Borrowed(eszip_data)
+  };
+  let bufreader = deno_core::futures::io::BufReader::new(eszip_data.as_ref());
+
+  let (eszip, loader) = eszip::EszipV2::parse(bufreader)
+    .await
+    .context("Failed to parse eszip header")?;
+
+  loader.await.context("Failed to parse eszip archive")?;
+
+  Ok(eszip)
+}
+
+/// An executable produced by `deno compile` that was read from disk, which is
+/// used to audit its contents with `deno compile --inspect-binary`.
+pub struct StandaloneBinary {
+  pub metadata: Metadata,
+  eszip: eszip::EszipV2,
+  vfs: Option<FileBackedVfs>,
+  /// The software bill of materials embedded with `deno compile --sbom`.
+  pub sbom: Option<Vec<u8>>,
+}
+
+impl StandaloneBinary {
+  pub async fn read(exe_path: &Path) -> Result<Self, AnyError> {
+    let file_data = std::fs::read(exe_path).with_context(|| {
+      format!("Failed to read executable '{}'", exe_path.display())
+    })?;
+    let Some((trailer, start)) = find_section_in_binary(&file_data) else {
+      bail!(
+        "'{}' is not an executable produced by deno compile.",
+        exe_path.display()
+      );
+    };
+    let data = &file_data[start + TRAILER_SIZE..];
+    let eszip = parse_eszip(data, &trailer).await?;
+    let metadata: Metadata = serde_json::from_slice(
+      &data[trailer.metadata_pos as usize..trailer.sbom_pos as usize],
+    )
+    .context("Failed to read metadata from the executable")?;
+    let sbom = &data[trailer.sbom_pos as usize..trailer.vfs_pos as usize];
+    let sbom = (!sbom.is_empty()).then(|| sbom.to_vec());
+    // files are read relative to the vfs root, so the root path is arbitrary
+    let vfs = vfs_from_section_data(data, &trailer, PathBuf::from("vfs"))
+      .context("Failed to read the virtual file system of the executable")?;
+    Ok(Self {
+      metadata,
+      eszip,
+      vfs,
+      sbom,
+    })
+  }
+
+  /// Logs the options the executable was compiled with.
+  pub fn log_metadata(&self) {
+    let metadata = &self.metadata;
+    log::info!("{}", crate::colors::bold("Metadata:"));
+    log::info!("  Entrypoint: {}", metadata.entrypoint_key);
+    if !metadata.argv.is_empty() {
+      log::info!("  Arguments: {}", metadata.argv.join(" "));
+    }
+    let permission_args = crate::args::Flags {
+      permissions: metadata.permissions.clone(),
+      ..Default::default()
+    }
+    .to_permission_args();
+    if permission_args.is_empty() {
+      log::info!("  Permissions: none");
+    } else {
+      log::info!("  Permissions:");
+      for arg in permission_args {
+        log::info!("    {}", arg);
+      }
+    }
+    let unstable_config = &metadata.unstable_config;
+    let mut unstable = unstable_config
+      .features
+      .iter()
+      .map(|feature| format!("--unstable-{}", feature))
+      .collect::<Vec<_>>();
+    if unstable_config.legacy_flag_enabled {
+      unstable.push("--unstable".to_string());
+    }
+    if unstable_config.bare_node_builtins {
+      unstable.push("--unstable-bare-node-builtins".to_string());
+    }
+    if unstable_config.byonm {
+      unstable.push("--unstable-byonm".to_string());
+    }
+    if unstable_config.sloppy_imports {
+      unstable.push("--unstable-sloppy-imports".to_string());
+    }
+    if !unstable.is_empty() {
+      log::info!("  Unstable: {}", unstable.join(" "));
+    }
+    if !metadata.env_vars_from_env_file.is_empty() {
+      log::info!("  Environment variables:");
+      let env_vars = metadata
+        .env_vars_from_env_file
+        .iter()
+        .collect::<BTreeMap<_, _>>();
+      for (key, value) in env_vars {
+        log::info!("    {}={}", key, value);
+      }
+    }
+    if !metadata.v8_flags.is_empty() {
+      log::info!("  V8 flags: {}", metadata.v8_flags.join(" "));
+    }
+    if let Some(seed) = metadata.seed {
+      log::info!("  Seed: {}", seed);
+    }
+    if let Some(location) = &metadata.location {
+      log::info!("  Location: {}", location);
+    }
+    if let Some(log_level) = metadata.log_level {
+      log::info!("  Log level: {}", log_level);
+    }
+    if let Some(ca_stores) = &metadata.ca_stores {
+      log::info!("  CA stores: {}", ca_stores.join(", "));
+    }
+    if metadata.ca_data.is_some() {
+      log::info!("  CA certificate: embedded");
+    }
+    if let Some(hosts) = &metadata.unsafely_ignore_certificate_errors {
+      if hosts.is_empty() {
+        log::info!("  Ignored certificate errors: all hosts");
+      } else {
+        log::info!("  Ignored certificate errors: {}", hosts.join(", "));
+      }
+    }
+    if let Some(node_modules) = &metadata.node_modules {
+      log::info!(
+        "  npm packages: {}",
+        match node_modules {
+          NodeModules::Managed { .. } => "managed",
+          NodeModules::Byonm { .. } => "bring your own node_modules",
+        }
+      );
+    }
+    log::info!(
+      "  Code cache: {}",
+      if metadata.code_cache_key.is_some() {
+        "enabled"
+      } else {
+        "disabled"
+      }
+    );
+    if let Some(sbom) = &self.sbom {
+      match describe_sbom(sbom) {
+        Some((format, count)) => {
+          log::info!("  SBOM: {} ({} components)", format, count)
+        }
+        None => log::info!("  SBOM: embedded"),
+      }
+    }
+  }
+
+  /// Logs the modules and files embedded in the executable.
+  pub fn log_contents(&self) {
+    log_contents(&self.eszip, self.vfs.as_ref().map(|vfs| vfs.root_dir()));
+  }
+
+  /// Writes the embedded modules to `<output_dir>/modules`, the files of the
+  /// virtual file system to `<output_dir>/files` and the software bill of
+  /// materials to `<output_dir>/sbom.json`.
+  pub async fn extract(&self, output_dir: &Path) -> Result<(), AnyError> {
+    let modules_dir = output_dir.join("modules");
+    for specifier in self.eszip.specifiers() {
+      let Some(module) = self.eszip.get_module(&specifier) else {
+        continue;
+      };
+      if module.specifier != specifier {
+        // redirect to a module that's extracted separately
+        continue;
+      }
+      let Some(relative_path) = module_extract_path(&specifier) else {
+        log::warn!(
+          "{} Skipped extracting module '{}'.",
+          crate::colors::yellow("Warning"),
+          specifier
+        );
+        continue;
+      };
+      let Some(source) = module.source().await else {
+        continue;
+      };
+      let path = modules_dir.join(relative_path);
+      fs::create_dir_all(path.parent().unwrap())?;
+      fs::write(&path, source)
+        .with_context(|| format!("Failed to write '{}'", path.display()))?;
+    }
+    if let Some(vfs) = &self.vfs {
+      vfs.extract_to(&output_dir.join("files"))?;
+    }
+    if let Some(sbom) = &self.sbom {
+      fs::create_dir_all(output_dir)?;
+      fs::write(output_dir.join("sbom.json"), sbom)?;
+    }
+    Ok(())
+  }
+}
+
+/// Gets the path relative to the extraction directory for a module in the
+/// eszip, which is either a path relative to the root directory of the
+/// executable or a remote url.
+fn module_extract_path(specifier: &str) -> Option<PathBuf> {
+  let mut path = PathBuf::new();
+  let segments = match Url::parse(specifier) {
+    Ok(url) => {
+      let host = url.host_str()?;
+      if !is_single_normal_component(host) {
+        return None;
+      }
+      path.push(url.scheme());
+      path.push(match url.port() {
+        Some(port) => format!("{}_{}", host, port),
+        None => host.to_string(),
+      });
+      url.path().to_string()
+    }
+    Err(_) => specifier.to_string(),
+  };
+  for segment in segments.split('/') {
+    match segment {
+      "" | "." => {}
+      // also rejects segments that are a different path on windows
+      segment if !is_single_normal_component(segment) => return None,
+      segment => path.push(segment),
+    }
+  }
+  if path.file_name().is_none() || segments.ends_with('/') {
+    path.push("index");
+  }
+  Some(path)
+}
+
+/// Finds the data appended by `deno compile` in the bytes of an executable,
+/// returning the trailer and its position.
+///
+/// `libsui` can only find the section in the current executable, so this
+/// searches for the magic trailer string instead, which also appears in the
+/// code of the binary itself.
+fn find_section_in_binary(data: &[u8]) -> Option<(Trailer, usize)> {
+  let mut end = data.len();
+  while let Some(start) = data[..end]
+    .windows(MAGIC_TRAILER.len())
+    .rposition(|window| window == MAGIC_TRAILER)
+  {
+    end = start;
+    let Some(trailer_data) = data.get(start..start + TRAILER_SIZE) else {
+      continue;
+    };
+    let Ok(Some(trailer)) = Trailer::parse(trailer_data) else {
+      continue;
+    };
+    let section_data = &data[start + TRAILER_SIZE..];
+    let is_valid = trailer.eszip_pos <= trailer.metadata_pos
+      && trailer.metadata_pos <= trailer.sbom_pos
+      && trailer.sbom_pos <= trailer.vfs_pos
+      && trailer.vfs_pos <= trailer.vfs_files_pos
+      && trailer.vfs_files_pos <= section_data.len() as u64
+      && serde_json::from_slice::<serde_json::Value>(
+        &section_data[trailer.metadata_pos as usize..trailer.sbom_pos as usize],
+      )
+      .is_ok();
+    if is_valid {
+      return Some((trailer, start));
+    }
+  }
+  None
+}
+
 const TRAILER_SIZE: usize = std::mem::size_of::<Trailer>() + 8; // 8 bytes for the magic trailer string
 
+/// Set in the trailer flags when the eszip is compressed with zstd.
+const TRAILER_FLAG_COMPRESSED_ESZIP: u64 = 1;
+
 struct Trailer {
   eszip_pos: u64,
   metadata_pos: u64,
-  npm_vfs_pos: u64,
-  npm_files_pos: u64,
+  /// Start of the software bill of materials, which is empty when not
+  /// compiled with `--sbom`.
+  sbom_pos: u64,
+  vfs_pos: u64,
+  vfs_files_pos: u64,
+  flags: u64,
 }
 
 impl Trailer {
@@ -267,35 +671,43 @@ impl Trailer {
 
     let (eszip_archive_pos, rest) = rest.split_at(8);
     let (metadata_pos, rest) = rest.split_at(8);
-    let (npm_vfs_pos, npm_files_pos) = rest.split_at(8);
+    let (sbom_pos, rest) = rest.split_at(8);
+    let (vfs_pos, rest) = rest.split_at(8);
+    let (vfs_files_pos, flags) = rest.split_at(8);
     let eszip_archive_pos = u64_from_bytes(eszip_archive_pos)?;
     let metadata_pos = u64_from_bytes(metadata_pos)?;
-    let npm_vfs_pos = u64_from_bytes(npm_vfs_pos)?;
-    let npm_files_pos = u64_from_bytes(npm_files_pos)?;
+    let sbom_pos = u64_from_bytes(sbom_pos)?;
+    let vfs_pos = u64_from_bytes(vfs_pos)?;
+    let vfs_files_pos = u64_from_bytes(vfs_files_pos)?;
+    let flags = u64_from_bytes(flags)?;
     Ok(Some(Trailer {
       eszip_pos: eszip_archive_pos,
       metadata_pos,
-      npm_vfs_pos,
-      npm_files_pos,
+      sbom_pos,
+      vfs_pos,
+      vfs_files_pos,
+      flags,
     }))
   }
 
-  pub fn metadata_len(&self) -> u64 {
-    self.npm_vfs_pos - self.metadata_pos
+  pub fn has_compressed_eszip(&self) -> bool {
+    self.flags & TRAILER_FLAG_COMPRESSED_ESZIP != 0
   }
 
-  pub fn npm_vfs_len(&self) -> u64 {
-    self.npm_files_pos - self.npm_vfs_pos
+  pub fn vfs_len(&self) -> u64 {
+    self.vfs_files_pos - self.vfs_pos
   }
 
   pub fn as_bytes(&self) -> Vec<u8> {
     let mut trailer = MAGIC_TRAILER.to_vec();
     trailer.write_all(&self.eszip_pos.to_be_bytes()).unwrap();
     trailer.write_all(&self.metadata_pos.to_be_bytes()).unwrap();
-    trailer.write_all(&self.npm_vfs_pos.to_be_bytes()).unwrap();
+    trailer.write_all(&self.sbom_pos.to_be_bytes()).unwrap();
+    trailer.write_all(&self.vfs_pos.to_be_bytes()).unwrap();
     trailer
-      .write_all(&self.npm_files_pos.to_be_bytes())
+      .write_all(&self.vfs_files_pos.to_be_bytes())
       .unwrap();
+    trailer.write_all(&self.flags.to_be_bytes()).unwrap();
     trailer
   }
 }
@@ -402,15 +814,20 @@ impl<'a> DenoCompileBinaryWriter<'a> {
     }
   }
 
+  /// Writes the executable, returning the software bill of materials when
+  /// compiling with `--sbom` so it can also be written next to it.
+  #[allow(clippy::too_many_arguments)]
   pub async fn write_bin(
     &self,
     writer: File,
     eszip: eszip::EszipV2,
     root_dir_url: EszipRelativeFileBaseUrl<'_>,
     entrypoint: &ModuleSpecifier,
+    include_files: Vec<PathBuf>,
+    binary_name: &str,
     compile_flags: &CompileFlags,
     cli_options: &CliOptions,
-  ) -> Result<(), AnyError> {
+  ) -> Result<Option<String>, AnyError> {
     // Select base binary based on target
     let mut original_binary = self.get_base_binary(compile_flags).await?;
 
@@ -430,6 +847,8 @@ impl<'a> DenoCompileBinaryWriter<'a> {
       eszip,
       root_dir_url,
       entrypoint,
+      include_files,
+      binary_name,
       cli_options,
       compile_flags,
     )
@@ -522,9 +941,11 @@ impl<'a> DenoCompileBinaryWriter<'a> {
     mut eszip: eszip::EszipV2,
     root_dir_url: EszipRelativeFileBaseUrl<'_>,
     entrypoint: &ModuleSpecifier,
+    include_files: Vec<PathBuf>,
+    binary_name: &str,
     cli_options: &CliOptions,
     compile_flags: &CompileFlags,
-  ) -> Result<(), AnyError> {
+  ) -> Result<Option<String>, AnyError> {
     let ca_data = match cli_options.ca_data() {
       Some(CaData::File(ca_file)) => Some(
         std::fs::read(ca_file)
@@ -534,19 +955,25 @@ impl<'a> DenoCompileBinaryWriter<'a> {
       None => None,
     };
     let root_path = root_dir_url.inner().to_file_path().unwrap();
-    let (npm_vfs, npm_files, node_modules) = match self.npm_resolver.as_inner()
-    {
+    let mut sbom_builder = compile_flags.sbom.map(|_| {
+      SbomBuilder::new(
+        crate::args::jsr_url(),
+        cli_options.maybe_lockfile().map(|l| l.as_ref()),
+      )
+    });
+    let (include_npm, node_modules) = match self.npm_resolver.as_inner() {
       InnerCliNpmResolverRef::Managed(managed) => {
         let snapshot =
           managed.serialized_valid_snapshot_for_system(&self.npm_system_info);
         if !snapshot.as_serialized().packages.is_empty() {
-          let (root_dir, files) = self
-            .build_vfs(&root_path, cli_options)?
-            .into_dir_and_files();
+          if let Some(sbom_builder) = &mut sbom_builder {
+            for package in &snapshot.as_serialized().packages {
+              sbom_builder.add_npm_package(package);
+            }
+          }
           eszip.add_npm_snapshot(snapshot);
           (
-            Some(root_dir),
-            files,
+            true,
             Some(NodeModules::Managed {
               node_modules_dir: self.npm_resolver.root_node_modules_path().map(
                 |path| {
@@ -560,16 +987,18 @@ impl<'a> DenoCompileBinaryWriter<'a> {
             }),
           )
         } else {
-          (None, Vec::new(), None)
+          (false, None)
         }
       }
       InnerCliNpmResolverRef::Byonm(resolver) => {
-        let (root_dir, files) = self
-          .build_vfs(&root_path, cli_options)?
-          .into_dir_and_files();
+        if sbom_builder.is_some() {
+          log::warn!(
+            "{} npm packages in a node_modules directory that Deno doesn't manage are not listed in the software bill of materials.",
+            crate::colors::yellow("Warning"),
+          );
+        }
         (
-          Some(root_dir),
-          files,
+          true,
           Some(NodeModules::Byonm {
             root_node_modules_dir: resolver.root_node_modules_path().map(
               |node_modules_dir| {
@@ -585,6 +1014,55 @@ impl<'a> DenoCompileBinaryWriter<'a> {
         )
       }
     };
+    let vfs = if include_npm || !include_files.is_empty() {
+      let mut builder =
+        self.build_vfs(&root_path, cli_options, include_npm, &include_files)?;
+      let uncompressed_len = builder.files_len();
+      let deduplicated_len = builder.deduplicated_len();
+      if compile_flags.compress {
+        builder.compress_files()?;
+      }
+      let (root_dir, files) = builder.into_dir_and_files();
+      Some(EmbeddedVfs {
+        root_dir,
+        files,
+        uncompressed_len,
+        deduplicated_len,
+      })
+    } else {
+      None
+    };
+
+    if compile_flags.list_contents {
+      log_contents(&eszip, vfs.as_ref().map(|vfs| &vfs.root_dir));
+    }
+
+    let sbom = match (sbom_builder, compile_flags.sbom) {
+      (Some(mut sbom_builder), Some(format)) => {
+        for specifier in eszip.specifiers() {
+          // skip redirects
+          if eszip
+            .get_module(&specifier)
+            .is_some_and(|module| module.specifier == specifier)
+          {
+            sbom_builder.add_module(&specifier);
+          }
+        }
+        Some(sbom_builder.build(format, binary_name))
+      }
+      _ => None,
+    };
+
+    let eszip_archive = eszip.into_bytes();
+    let code_cache_key = if cli_options.code_cache_enabled() {
+      Some(
+        FastInsecureHasher::new_deno_versioned()
+          .write(&eszip_archive)
+          .finish(),
+      )
+    } else {
+      None
+    };
 
     let env_vars_from_env_file = match cli_options.env_file_name() {
       Some(env_filename) => {
@@ -644,24 +1122,43 @@ impl<'a> DenoCompileBinaryWriter<'a> {
         sloppy_imports: cli_options.unstable_sloppy_imports(),
         features: cli_options.unstable_features(),
       },
+      code_cache_key,
     };
 
     write_binary_bytes(
       writer,
       original_bin,
       &metadata,
-      eszip,
-      npm_vfs.as_ref(),
-      &npm_files,
+      eszip_archive,
+      sbom.as_deref(),
+      vfs.as_ref(),
       compile_flags,
-    )
+    )?;
+    Ok(sbom)
   }
 
   fn build_vfs(
     &self,
     root_path: &Path,
     cli_options: &CliOptions,
+    include_npm: bool,
+    include_files: &[PathBuf],
   ) -> Result<VfsBuilder, AnyError> {
+    let mut builder = VfsBuilder::new(root_path.to_path_buf())?;
+    if include_npm {
+      self.add_npm_to_vfs(&mut builder, cli_options)?;
+    }
+    for path in include_files {
+      builder.add_file_at_path(path)?;
+    }
+    Ok(builder)
+  }
+
+  fn add_npm_to_vfs(
+    &self,
+    builder: &mut VfsBuilder,
+    cli_options: &CliOptions,
+  ) -> Result<(), AnyError> {
     fn maybe_warn_different_system(system_info: &NpmSystemInfo) {
       if system_info != &NpmSystemInfo::default() {
         log::warn!("{} The node_modules directory may be incompatible with the target system.", crate::colors::yellow("Warning"));
@@ -672,27 +1169,25 @@ impl<'a> DenoCompileBinaryWriter<'a> {
       InnerCliNpmResolverRef::Managed(npm_resolver) => {
         if let Some(node_modules_path) = npm_resolver.root_node_modules_path() {
           maybe_warn_different_system(&self.npm_system_info);
-          let mut builder = VfsBuilder::new(root_path.to_path_buf())?;
           builder.add_dir_recursive(node_modules_path)?;
-          Ok(builder)
+          Ok(())
         } else {
           // DO NOT include the user's registry url as it may contain credentials,
           // but also don't make this dependent on the registry url
           let root_path = npm_resolver.global_cache_root_folder();
-          let mut builder = VfsBuilder::new(root_path)?;
+          let mut cache_builder = VfsBuilder::new(root_path)?;
           for package in npm_resolver.all_system_packages(&self.npm_system_info)
           {
             let folder =
               npm_resolver.resolve_pkg_folder_from_pkg_id(&package.id)?;
-            builder.add_dir_recursive(&folder)?;
+            cache_builder.add_dir_recursive(&folder)?;
           }
 
           // Flatten all the registries folders into a single "node_modules/localhost" folder
           // that will be used by denort when loading the npm cache. This avoids us exposing
           // the user's private registry information and means we don't have to bother
           // serializing all the different registry config into the binary.
-          builder.with_root_dir(|root_dir| {
-            root_dir.name = "node_modules".to_string();
+          cache_builder.with_root_dir(|root_dir| {
             let mut new_entries = Vec::with_capacity(root_dir.entries.len());
             let mut localhost_entries = IndexMap::new();
             for entry in std::mem::take(&mut root_dir.entries) {
@@ -726,13 +1221,15 @@ impl<'a> DenoCompileBinaryWriter<'a> {
             new_entries.sort_by(|a, b| a.name().cmp(b.name()));
             root_dir.entries = new_entries;
           });
+          // denort reads the npm cache from the node_modules folder in the
+          // root of the vfs
+          builder.add_builder_as_root_dir("node_modules", cache_builder);
 
-          Ok(builder)
+          Ok(())
         }
       }
       InnerCliNpmResolverRef::Byonm(_) => {
         maybe_warn_different_system(&self.npm_system_info);
-        let mut builder = VfsBuilder::new(root_path.to_path_buf())?;
         for pkg_json in cli_options.workspace().package_jsons() {
           builder.add_file_at_path(&pkg_json.path)?;
         }
@@ -758,12 +1255,27 @@ impl<'a> DenoCompileBinaryWriter<'a> {
             }
           }
         }
-        Ok(builder)
+        Ok(())
       }
     }
   }
 }
 
+/// Logs the modules and files embedded in the executable for
+/// `deno compile --list-contents`.
+fn log_contents(eszip: &eszip::EszipV2, vfs: Option<&VirtualDirectory>) {
+  log::info!("{}", crate::colors::bold("Modules:"));
+  for specifier in eszip.specifiers() {
+    log::info!("  {}", specifier);
+  }
+  if let Some(vfs) = vfs {
+    log::info!("{}", crate::colors::bold("Files:"));
+    for line in vfs.display_tree().lines() {
+      log::info!("  {}", line);
+    }
+  }
+}
+
 /// This function returns the environment variables specified
 /// in the passed environment file.
 fn get_file_env_vars(
@@ -817,3 +1329,35 @@ fn set_windows_binary_to_gui(bin: &mut [u8]) -> Result<(), AnyError> {
     .copy_from_slice(&subsystem.to_le_bytes());
   Ok(())
 }
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn module_extract_paths() {
+    assert_eq!(
+      module_extract_path("https://deno.land:8080/std/mod.ts"),
+      Some(PathBuf::from("https/deno.land_8080/std/mod.ts"))
+    );
+    assert_eq!(
+      module_extract_path("https://deno.land/"),
+      Some(PathBuf::from("https/deno.land/index"))
+    );
+    assert_eq!(
+      module_extract_path("main.ts"),
+      Some(PathBuf::from("main.ts"))
+    );
+    for specifier in [
+      "../main.ts",
+      "sub/../../main.ts",
+      "..\\..\\main.ts",
+      "sub/..\\..\\main.ts",
+      "C:\\main.ts",
+      "sub/C:main.ts",
+      "https://[::1]/main.ts",
+    ] {
+      assert_eq!(module_extract_path(specifier), None, "{specifier}");
+    }
+  }
+}
@@ -0,0 +1,286 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use deno_ast::ModuleSpecifier;
+use deno_core::anyhow::bail;
+use deno_core::error::AnyError;
+use deno_core::parking_lot::Mutex;
+use deno_runtime::code_cache;
+
+const FILE_MAGIC: &[u8; 4] = b"dccc";
+
+type EntryKey = (String, u8);
+
+struct Entry {
+  source_hash: u64,
+  data: Vec<u8>,
+}
+
+enum FileState {
+  /// The file on disk has entries for this executable, so new entries can be
+  /// appended to it.
+  Valid,
+  /// The file is missing or for a different executable and needs to be
+  /// recreated before writing to it.
+  Invalid,
+  Open(File),
+}
+
+/// V8 code cache for the modules embedded in a `deno compile` executable.
+///
+/// The cache is populated on the first run and stored in a single file that
+/// entries are appended to as V8 produces them. The file starts with a key
+/// derived from the executable's modules, so the cache is discarded once the
+/// executable is recompiled.
+pub struct DenoCompileCodeCache {
+  file_path: PathBuf,
+  cache_key: u64,
+  entries: Mutex<HashMap<EntryKey, Entry>>,
+  file_state: Mutex<FileState>,
+}
+
+impl DenoCompileCodeCache {
+  pub fn new(file_path: PathBuf, cache_key: u64) -> Self {
+    let (entries, file_state) = match read_cache_file(&file_path, cache_key) {
+      Ok(Some(entries)) => (entries, FileState::Valid),
+      Ok(None) => (HashMap::new(), FileState::Invalid),
+      Err(err) => {
+        log::debug!("Failed to load code cache: {:#}", err);
+        (HashMap::new(), FileState::Invalid)
+      }
+    };
+    Self {
+      file_path,
+      cache_key,
+      entries: Mutex::new(entries),
+      file_state: Mutex::new(file_state),
+    }
+  }
+
+  fn append_entry(
+    &self,
+    key: &EntryKey,
+    entry: &Entry,
+  ) -> Result<(), AnyError> {
+    let mut file_state = self.file_state.lock();
+    if !matches!(*file_state, FileState::Open(_)) {
+      let file = if matches!(*file_state, FileState::Valid) {
+        OpenOptions::new().append(true).open(&self.file_path)?
+      } else {
+        if let Some(parent) = self.file_path.parent() {
+          std::fs::create_dir_all(parent)?;
+        }
+        let mut file = File::create(&self.file_path)?;
+        file.write_all(FILE_MAGIC)?;
+        file.write_all(&self.cache_key.to_le_bytes())?;
+        file
+      };
+      *file_state = FileState::Open(file);
+    }
+    let FileState::Open(file) = &mut *file_state else {
+      unreachable!();
+    };
+    // write the entry at once so a partially written entry is only possible
+    // at the end of the file
+    let mut bytes = Vec::with_capacity(key.0.len() + entry.data.len() + 17);
+    bytes.extend_from_slice(&(key.0.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(key.0.as_bytes());
+    bytes.push(key.1);
+    bytes.extend_from_slice(&entry.source_hash.to_le_bytes());
+    bytes.extend_from_slice(&(entry.data.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&entry.data);
+    file.write_all(&bytes)?;
+    Ok(())
+  }
+}
+
+impl code_cache::CodeCache for DenoCompileCodeCache {
+  fn get_sync(
+    &self,
+    specifier: &ModuleSpecifier,
+    code_cache_type: code_cache::CodeCacheType,
+    source_hash: u64,
+  ) -> Option<Vec<u8>> {
+    let key = (
+      specifier.to_string(),
+      serialize_code_cache_type(code_cache_type),
+    );
+    let entries = self.entries.lock();
+    let entry = entries.get(&key)?;
+    if entry.source_hash != source_hash {
+      return None;
+    }
+    Some(entry.data.clone())
+  }
+
+  fn set_sync(
+    &self,
+    specifier: ModuleSpecifier,
+    code_cache_type: code_cache::CodeCacheType,
+    source_hash: u64,
+    data: &[u8],
+  ) {
+    let key = (
+      specifier.to_string(),
+      serialize_code_cache_type(code_cache_type),
+    );
+    let entry = Entry {
+      source_hash,
+      data: data.to_vec(),
+    };
+    if let Err(err) = self.append_entry(&key, &entry) {
+      log::debug!("Failed to write code cache: {:#}", err);
+    }
+    self.entries.lock().insert(key, entry);
+  }
+}
+
+fn serialize_code_cache_type(code_cache_type: code_cache::CodeCacheType) -> u8 {
+  match code_cache_type {
+    code_cache::CodeCacheType::Script => 0,
+    code_cache::CodeCacheType::EsModule => 1,
+  }
+}
+
+/// Reads the entries of the cache file, returning `None` when the file
+/// doesn't exist or belongs to a different executable.
+fn read_cache_file(
+  file_path: &Path,
+  cache_key: u64,
+) -> Result<Option<HashMap<EntryKey, Entry>>, AnyError> {
+  fn take<'a>(data: &mut &'a [u8], len: usize) -> Result<&'a [u8], AnyError> {
+    if data.len() < len {
+      bail!("Unexpected end of code cache file.");
+    }
+    let (taken, rest) = data.split_at(len);
+    *data = rest;
+    Ok(taken)
+  }
+
+  fn take_u32(data: &mut &[u8]) -> Result<u32, AnyError> {
+    Ok(u32::from_le_bytes(take(data, 4)?.try_into().unwrap()))
+  }
+
+  fn take_u64(data: &mut &[u8]) -> Result<u64, AnyError> {
+    Ok(u64::from_le_bytes(take(data, 8)?.try_into().unwrap()))
+  }
+
+  let file_data = match std::fs::read(file_path) {
+    Ok(data) => data,
+    Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+    Err(err) => return Err(err.into()),
+  };
+  let mut data = file_data.as_slice();
+  if take(&mut data, FILE_MAGIC.len())? != FILE_MAGIC
+    || take_u64(&mut data)? != cache_key
+  {
+    return Ok(None);
+  }
+  let mut entries = HashMap::new();
+  while !data.is_empty() {
+    let specifier_len = take_u32(&mut data)? as usize;
+    let specifier =
+      String::from_utf8(take(&mut data, specifier_len)?.to_vec())?;
+    let code_cache_type = take(&mut data, 1)?[0];
+    let source_hash = take_u64(&mut data)?;
+    let data_len = take_u32(&mut data)? as usize;
+    let entry_data = take(&mut data, data_len)?.to_vec();
+    entries.insert(
+      (specifier, code_cache_type),
+      Entry {
+        source_hash,
+        data: entry_data,
+      },
+    );
+  }
+  Ok(Some(entries))
+}
+
+#[cfg(test)]
+mod test {
+  use code_cache::CodeCache;
+  use code_cache::CodeCacheType;
+  use test_util::TempDir;
+
+  use super::*;
+
+  #[test]
+  fn persists_entries() {
+    let temp_dir = TempDir::new();
+    let file_path = temp_dir.path().join("main.cache").to_path_buf();
+    let specifier = ModuleSpecifier::parse("file:///main.js").unwrap();
+    let cache = DenoCompileCodeCache::new(file_path.clone(), 1);
+    assert_eq!(cache.get_sync(&specifier, CodeCacheType::EsModule, 5), None);
+    cache.set_sync(specifier.clone(), CodeCacheType::EsModule, 5, &[1, 2]);
+    cache.set_sync(specifier.clone(), CodeCacheType::Script, 6, &[3]);
+    assert_eq!(
+      cache.get_sync(&specifier, CodeCacheType::EsModule, 5),
+      Some(vec![1, 2])
+    );
+    drop(cache);
+
+    // entries are loaded from the file on the next run
+    let cache = DenoCompileCodeCache::new(file_path.clone(), 1);
+    assert_eq!(
+      cache.get_sync(&specifier, CodeCacheType::EsModule, 5),
+      Some(vec![1, 2])
+    );
+    assert_eq!(
+      cache.get_sync(&specifier, CodeCacheType::Script, 6),
+      Some(vec![3])
+    );
+    // different source
+    assert_eq!(cache.get_sync(&specifier, CodeCacheType::EsModule, 6), None);
+    let other_specifier = ModuleSpecifier::parse("file:///other.js").unwrap();
+    cache.set_sync(other_specifier.clone(), CodeCacheType::EsModule, 7, &[4]);
+    drop(cache);
+
+    // appended entries are kept
+    let cache = DenoCompileCodeCache::new(file_path.clone(), 1);
+    assert_eq!(
+      cache.get_sync(&specifier, CodeCacheType::EsModule, 5),
+      Some(vec![1, 2])
+    );
+    assert_eq!(
+      cache.get_sync(&other_specifier, CodeCacheType::EsModule, 7),
+      Some(vec![4])
+    );
+    drop(cache);
+
+    // the executable was recompiled
+    let cache = DenoCompileCodeCache::new(file_path.clone(), 2);
+    assert_eq!(cache.get_sync(&specifier, CodeCacheType::EsModule, 5), None);
+    cache.set_sync(specifier.clone(), CodeCacheType::EsModule, 8, &[5]);
+    drop(cache);
+    let cache = DenoCompileCodeCache::new(file_path, 2);
+    assert_eq!(
+      cache.get_sync(&specifier, CodeCacheType::EsModule, 8),
+      Some(vec![5])
+    );
+    assert_eq!(
+      cache.get_sync(&other_specifier, CodeCacheType::EsModule, 7),
+      None
+    );
+  }
+
+  #[test]
+  fn ignores_corrupt_file() {
+    let temp_dir = TempDir::new();
+    let file_path = temp_dir.path().join("main.cache");
+    let specifier = ModuleSpecifier::parse("file:///main.js").unwrap();
+    let cache = DenoCompileCodeCache::new(file_path.to_path_buf(), 1);
+    cache.set_sync(specifier.clone(), CodeCacheType::EsModule, 5, &[1, 2]);
+    drop(cache);
+    let data = file_path.read_to_bytes_if_exists().unwrap();
+    file_path.write(&data[..data.len() - 1]);
+
+    let cache = DenoCompileCodeCache::new(file_path.to_path_buf(), 1);
+    assert_eq!(cache.get_sync(&specifier, CodeCacheType::EsModule, 5), None);
+  }
+}
@@ -22,8 +22,11 @@ use deno_core::ModuleSpecifier;
 use deno_core::ModuleType;
 use deno_core::RequestedModuleType;
 use deno_core::ResolutionKind;
+use deno_core::SourceCodeCacheInfo;
 use deno_npm::npm_rc::ResolvedNpmRc;
 use deno_package_json::PackageJsonDepValue;
+use deno_runtime::code_cache::CodeCache;
+use deno_runtime::code_cache::CodeCacheType;
 use deno_runtime::deno_fs;
 use deno_runtime::deno_node::create_host_defined_options;
 use deno_runtime::deno_node::NodeResolver;
@@ -39,6 +42,8 @@ use import_map::parse_from_json;
 use node_resolver::analyze::NodeCodeTranslator;
 use node_resolver::NodeResolutionMode;
 use std::borrow::Cow;
+use std::future::Future;
+use std::pin::Pin;
 use std::rc::Rc;
 use std::sync::Arc;
 
@@ -51,6 +56,7 @@ use crate::args::PackageJsonInstallDepsProvider;
 use crate::args::StorageKeyResolver;
 use crate::cache::Caches;
 use crate::cache::DenoDirProvider;
+use crate::cache::FastInsecureHasher;
 use crate::cache::NodeAnalysisCache;
 use crate::http_util::HttpClientProvider;
 use crate::node::CliCjsCodeAnalyzer;
@@ -63,6 +69,7 @@ use crate::npm::NpmCacheDir;
 use crate::resolver::CjsResolutionStore;
 use crate::resolver::CliNodeResolver;
 use crate::resolver::NpmModuleLoader;
+use crate::util::fs::canonicalize_path_maybe_not_exists;
 use crate::util::progress_bar::ProgressBar;
 use crate::util::progress_bar::ProgressBarStyle;
 use crate::util::v8::construct_v8_flags;
@@ -72,15 +79,18 @@ use crate::worker::ModuleLoaderAndSourceMapGetter;
 use crate::worker::ModuleLoaderFactory;
 
 pub mod binary;
+mod code_cache;
 mod file_system;
+mod sbom;
 mod virtual_fs;
 
 pub use binary::extract_standalone;
 pub use binary::is_standalone_binary;
 pub use binary::DenoCompileBinaryWriter;
 
-use self::binary::load_npm_vfs;
+use self::binary::load_vfs;
 use self::binary::Metadata;
+use self::code_cache::DenoCompileCodeCache;
 use self::file_system::DenoCompileFileSystem;
 
 struct WorkspaceEszipModule {
@@ -122,6 +132,33 @@ struct SharedModuleLoaderState {
   workspace_resolver: WorkspaceResolver,
   node_resolver: Arc<CliNodeResolver>,
   npm_module_loader: Arc<NpmModuleLoader>,
+  code_cache: Option<Arc<DenoCompileCodeCache>>,
+}
+
+impl SharedModuleLoaderState {
+  fn get_code_cache(
+    &self,
+    specifier: &ModuleSpecifier,
+    code: impl std::hash::Hash,
+  ) -> Option<SourceCodeCacheInfo> {
+    let code_cache = self.code_cache.as_ref()?;
+    let source_hash = FastInsecureHasher::new_deno_versioned()
+      .write_hashable(code)
+      .finish();
+    let data = code_cache
+      .get_sync(specifier, CodeCacheType::EsModule, source_hash)
+      .map(Cow::from)
+      .inspect(|_| {
+        // This log line is also used by tests.
+        log::debug!(
+          "V8 code cache hit for ES module: {specifier}, [{source_hash:?}]"
+        );
+      });
+    Some(SourceCodeCacheInfo {
+      hash: source_hash,
+      data,
+    })
+  }
 }
 
 #[derive(Clone)]
@@ -268,6 +305,27 @@ impl ModuleLoader for EmbeddedModuleLoader {
     }
   }
 
+  fn code_cache_ready(
+    &self,
+    specifier: ModuleSpecifier,
+    source_hash: u64,
+    code_cache: &[u8],
+  ) -> Pin<Box<dyn Future<Output = ()>>> {
+    if let Some(cache) = self.shared.code_cache.as_ref() {
+      // This log line is also used by tests.
+      log::debug!(
+        "Updating V8 code cache for ES module: {specifier}, [{source_hash:?}]"
+      );
+      cache.set_sync(
+        specifier,
+        CodeCacheType::EsModule,
+        source_hash,
+        code_cache,
+      );
+    }
+    std::future::ready(()).boxed_local()
+  }
+
   fn get_host_defined_options<'s>(
     &self,
     scope: &mut deno_core::v8::HandleScope<'s>,
@@ -311,23 +369,28 @@ impl ModuleLoader for EmbeddedModuleLoader {
     }
 
     if self.shared.node_resolver.in_npm_package(original_specifier) {
-      let npm_module_loader = self.shared.npm_module_loader.clone();
+      let shared = self.shared.clone();
       let original_specifier = original_specifier.clone();
       let maybe_referrer = maybe_referrer.cloned();
       return deno_core::ModuleLoadResponse::Async(
         async move {
-          let code_source = npm_module_loader
+          let code_source = shared
+            .npm_module_loader
             .load(&original_specifier, maybe_referrer.as_ref())
             .await?;
+          let (module_type, code_cache) = match code_source.media_type {
+            MediaType::Json => (ModuleType::Json, None),
+            _ => (
+              ModuleType::JavaScript,
+              shared.get_code_cache(&code_source.found_url, &code_source.code),
+            ),
+          };
           Ok(deno_core::ModuleSource::new_with_redirect(
-            match code_source.media_type {
-              MediaType::Json => ModuleType::Json,
-              _ => ModuleType::JavaScript,
-            },
+            module_type,
             code_source.code,
             &original_specifier,
             &code_source.found_url,
-            None,
+            code_cache,
           ))
         }
         .boxed_local(),
@@ -341,6 +404,7 @@ impl ModuleLoader for EmbeddedModuleLoader {
       ))));
     };
     let original_specifier = original_specifier.clone();
+    let shared = self.shared.clone();
 
     deno_core::ModuleLoadResponse::Async(
       async move {
@@ -349,21 +413,25 @@ impl ModuleLoader for EmbeddedModuleLoader {
         })?;
         let code = arc_u8_to_arc_str(code)
           .map_err(|_| type_error("Module source is not utf-8"))?;
+        let (module_type, code_cache) = match module.inner.kind {
+          eszip::ModuleKind::JavaScript => (
+            ModuleType::JavaScript,
+            shared.get_code_cache(&module.specifier, &code),
+          ),
+          eszip::ModuleKind::Json => (ModuleType::Json, None),
+          eszip::ModuleKind::Jsonc => {
+            return Err(type_error("jsonc modules not supported"))
+          }
+          eszip::ModuleKind::OpaqueData => {
+            unreachable!();
+          }
+        };
         Ok(deno_core::ModuleSource::new_with_redirect(
-          match module.inner.kind {
-            eszip::ModuleKind::JavaScript => ModuleType::JavaScript,
-            eszip::ModuleKind::Json => ModuleType::Json,
-            eszip::ModuleKind::Jsonc => {
-              return Err(type_error("jsonc modules not supported"))
-            }
-            eszip::ModuleKind::OpaqueData => {
-              unreachable!();
-            }
-          },
+          module_type,
           ModuleSourceCode::String(code.into()),
           &original_specifier,
           &module.specifier,
-          None,
+          code_cache,
         ))
       }
       .boxed_local(),
@@ -454,11 +522,35 @@ pub async fn run(
   ));
   // use a dummy npm registry url
   let npm_registry_url = ModuleSpecifier::parse("https://localhost/").unwrap();
-  let root_path =
-    std::env::temp_dir().join(format!("deno-compile-{}", current_exe_name));
+  // canonicalize so the path matches the npm cache dir, which is canonicalized
+  let root_path = canonicalize_path_maybe_not_exists(
+    &std::env::temp_dir().join(format!("deno-compile-{}", current_exe_name)),
+  )?;
   let root_dir_url =
     Arc::new(ModuleSpecifier::from_directory_path(&root_path).unwrap());
   let main_module = root_dir_url.join(&metadata.entrypoint_key).unwrap();
+  // stored in the user's DENO_DIR, keyed by the path of the executable so
+  // that different executables of the same name don't share a cache
+  let code_cache = match metadata.code_cache_key {
+    Some(code_cache_key) => match deno_dir_provider.get_or_create() {
+      Ok(deno_dir) => {
+        let exe_path_hash = FastInsecureHasher::new_without_deno_version()
+          .write_str(&current_exe_path.to_string_lossy())
+          .finish();
+        Some(Arc::new(DenoCompileCodeCache::new(
+          deno_dir
+            .standalone_code_cache_folder_path()
+            .join(format!("{}_{:x}.cache", current_exe_name, exe_path_hash)),
+          code_cache_key,
+        )))
+      }
+      Err(err) => {
+        log::debug!("Failed to resolve the code cache location: {:#}", err);
+        None
+      }
+    },
+    None => None,
+  };
   let root_node_modules_path = root_path.join("node_modules");
   let npm_cache_dir = NpmCacheDir::new(
     root_node_modules_path.clone(),
@@ -466,99 +558,88 @@ pub async fn run(
   );
   let npm_global_cache_dir = npm_cache_dir.get_cache_location();
   let cache_setting = CacheSetting::Only;
-  let (fs, npm_resolver, maybe_vfs_root) = match metadata.node_modules {
+  // the vfs holds the npm packages and the files included with `--include`
+  let vfs = load_vfs(root_path.clone()).context("Failed to load vfs.")?;
+  let maybe_vfs_root = vfs.as_ref().map(|_| root_path.clone());
+  let fs = match vfs {
+    Some(vfs) => {
+      Arc::new(DenoCompileFileSystem::new(vfs)) as Arc<dyn deno_fs::FileSystem>
+    }
+    None => Arc::new(deno_fs::RealFs) as Arc<dyn deno_fs::FileSystem>,
+  };
+  let npm_resolver = match metadata.node_modules {
     Some(binary::NodeModules::Managed { node_modules_dir }) => {
       // this will always have a snapshot
       let snapshot = eszip.take_npm_snapshot().unwrap();
-      let vfs_root_dir_path = if node_modules_dir.is_some() {
-        root_path.clone()
-      } else {
-        npm_cache_dir.root_dir().to_owned()
-      };
-      let vfs = load_npm_vfs(vfs_root_dir_path.clone())
-        .context("Failed to load npm vfs.")?;
       let maybe_node_modules_path = node_modules_dir
-        .map(|node_modules_dir| vfs_root_dir_path.join(node_modules_dir));
-      let fs = Arc::new(DenoCompileFileSystem::new(vfs))
-        as Arc<dyn deno_fs::FileSystem>;
-      let npm_resolver =
-        create_cli_npm_resolver(CliNpmResolverCreateOptions::Managed(
-          CliNpmResolverManagedCreateOptions {
-            snapshot: CliNpmResolverManagedSnapshotOption::Specified(Some(
-              snapshot,
-            )),
-            maybe_lockfile: None,
-            fs: fs.clone(),
-            http_client_provider: http_client_provider.clone(),
-            npm_global_cache_dir,
-            cache_setting,
-            text_only_progress_bar: progress_bar,
-            maybe_node_modules_path,
-            npm_system_info: Default::default(),
-            package_json_deps_provider: Arc::new(
-              // this is only used for installing packages, which isn't necessary with deno compile
-              PackageJsonInstallDepsProvider::empty(),
-            ),
-            // create an npmrc that uses the fake npm_registry_url to resolve packages
-            npmrc: Arc::new(ResolvedNpmRc {
-              default_config: deno_npm::npm_rc::RegistryConfigWithUrl {
-                registry_url: npm_registry_url.clone(),
-                config: Default::default(),
-              },
-              scopes: Default::default(),
-              registry_configs: Default::default(),
-            }),
-            lifecycle_scripts: Default::default(),
-          },
-        ))
-        .await?;
-      (fs, npm_resolver, Some(vfs_root_dir_path))
+        .map(|node_modules_dir| root_path.join(node_modules_dir));
+      create_cli_npm_resolver(CliNpmResolverCreateOptions::Managed(
+        CliNpmResolverManagedCreateOptions {
+          snapshot: CliNpmResolverManagedSnapshotOption::Specified(Some(
+            snapshot,
+          )),
+          maybe_lockfile: None,
+          fs: fs.clone(),
+          http_client_provider: http_client_provider.clone(),
+          npm_global_cache_dir,
+          cache_setting,
+          text_only_progress_bar: progress_bar,
+          maybe_node_modules_path,
+          npm_system_info: Default::default(),
+          package_json_deps_provider: Arc::new(
+            // this is only used for installing packages, which isn't necessary with deno compile
+            PackageJsonInstallDepsProvider::empty(),
+          ),
+          // create an npmrc that uses the fake npm_registry_url to resolve packages
+          npmrc: Arc::new(ResolvedNpmRc {
+            default_config: deno_npm::npm_rc::RegistryConfigWithUrl {
+              registry_url: npm_registry_url.clone(),
+              config: Default::default(),
+            },
+            scopes: Default::default(),
+            registry_configs: Default::default(),
+          }),
+          lifecycle_scripts: Default::default(),
+        },
+      ))
+      .await?
     }
     Some(binary::NodeModules::Byonm {
       root_node_modules_dir,
     }) => {
-      let vfs_root_dir_path = root_path.clone();
-      let vfs = load_npm_vfs(vfs_root_dir_path.clone())
-        .context("Failed to load vfs.")?;
       let root_node_modules_dir =
-        root_node_modules_dir.map(|p| vfs.root().join(p));
-      let fs = Arc::new(DenoCompileFileSystem::new(vfs))
-        as Arc<dyn deno_fs::FileSystem>;
-      let npm_resolver = create_cli_npm_resolver(
-        CliNpmResolverCreateOptions::Byonm(CliNpmResolverByonmCreateOptions {
+        root_node_modules_dir.map(|p| root_path.join(p));
+      create_cli_npm_resolver(CliNpmResolverCreateOptions::Byonm(
+        CliNpmResolverByonmCreateOptions {
           fs: fs.clone(),
           root_node_modules_dir,
-        }),
-      )
-      .await?;
-      (fs, npm_resolver, Some(vfs_root_dir_path))
+        },
+      ))
+      .await?
     }
     None => {
-      let fs = Arc::new(deno_fs::RealFs) as Arc<dyn deno_fs::FileSystem>;
-      let npm_resolver =
-        create_cli_npm_resolver(CliNpmResolverCreateOptions::Managed(
-          CliNpmResolverManagedCreateOptions {
-            snapshot: CliNpmResolverManagedSnapshotOption::Specified(None),
-            maybe_lockfile: None,
-            fs: fs.clone(),
-            http_client_provider: http_client_provider.clone(),
-            npm_global_cache_dir,
-            cache_setting,
-            text_only_progress_bar: progress_bar,
-            maybe_node_modules_path: None,
-            npm_system_info: Default::default(),
-            package_json_deps_provider: Arc::new(
-              // this is only used for installing packages, which isn't necessary with deno compile
-              PackageJsonInstallDepsProvider::empty(),
-            ),
-            // Packages from different registries are already inlined in the ESZip,
-            // so no need to create actual `.npmrc` configuration.
-            npmrc: create_default_npmrc(),
-            lifecycle_scripts: Default::default(),
-          },
-        ))
-        .await?;
-      (fs, npm_resolver, None)
+      create_cli_npm_resolver(CliNpmResolverCreateOptions::Managed(
+        CliNpmResolverManagedCreateOptions {
+          snapshot: CliNpmResolverManagedSnapshotOption::Specified(None),
+          maybe_lockfile: None,
+          fs: fs.clone(),
+          http_client_provider: http_client_provider.clone(),
+          npm_global_cache_dir,
+          cache_setting,
+          text_only_progress_bar: progress_bar,
+          maybe_node_modules_path: None,
+          npm_system_info: Default::default(),
+          package_json_deps_provider: Arc::new(
+            // this is only used for installing packages, which isn't necessary with deno compile
+            PackageJsonInstallDepsProvider::empty(),
+          ),
+          // Packages from different registries are already inlined in the ESZip,
+          // so no need to create actual `.npmrc` configuration.
+          npmrc: create_default_npmrc(),
+          lifecycle_scripts: Default::default(),
+        },
+      ))
+      .await?
     }
   };
 
@@ -635,13 +716,14 @@ pub async fn run(
         fs.clone(),
         cli_node_resolver,
       )),
+      code_cache: code_cache.clone(),
     }),
   };
 
   let permissions = {
     let mut permissions =
       metadata.permissions.to_options(maybe_cwd.as_deref())?;
-    // if running with an npm vfs, grant read access to it
+    // if running with a vfs, grant read access to it
     if let Some(vfs_root) = maybe_vfs_root {
       match &mut permissions.allow_read {
         Some(vec) if vec.is_empty() => {
@@ -718,12 +800,12 @@ pub async fn run(
     None,
     None,
     false,
+    false,
     // TODO(bartlomieju): temporarily disabled
     // metadata.disable_deprecated_api_warning,
     true,
     false,
-    // Code cache is not supported for standalone binary yet.
-    None,
+    code_cache.map(|code_cache| code_cache as Arc<dyn CodeCache>),
   );
 
   // Initialize v8 once from the main thread.
@@ -0,0 +1,456 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::BTreeSet;
+
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use chrono::SecondsFormat;
+use deno_core::serde_json;
+use deno_core::serde_json::json;
+use deno_core::url::Url;
+use deno_npm::resolution::SerializedNpmResolutionSnapshotPackage;
+use uuid::Uuid;
+
+use crate::args::CliLockfile;
+use crate::args::SbomFormat;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum ComponentKind {
+  Jsr,
+  Npm,
+  Remote,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ComponentHash {
+  /// The name of the algorithm as used by CycloneDX.
+  algorithm: &'static str,
+  hex: String,
+}
+
+/// A package or remote module embedded in the executable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Component {
+  kind: ComponentKind,
+  name: String,
+  version: Option<String>,
+  hash: Option<ComponentHash>,
+}
+
+impl Component {
+  fn purl(&self) -> Option<String> {
+    let kind = match self.kind {
+      ComponentKind::Jsr => "jsr",
+      ComponentKind::Npm => "npm",
+      ComponentKind::Remote => return None,
+    };
+    let name = self.name.replace('@', "%40");
+    Some(match &self.version {
+      Some(version) => format!("pkg:{kind}/{name}@{version}"),
+      None => format!("pkg:{kind}/{name}"),
+    })
+  }
+
+  fn bom_ref(&self) -> String {
+    self.purl().unwrap_or_else(|| self.name.clone())
+  }
+}
+
+/// Collects the jsr, npm and remote modules embedded in an executable, which
+/// are listed in its software bill of materials.
+pub struct SbomBuilder<'a> {
+  jsr_url: &'a Url,
+  lockfile: Option<&'a CliLockfile>,
+  jsr_packages: BTreeSet<(String, String)>,
+  components: Vec<Component>,
+}
+
+impl<'a> SbomBuilder<'a> {
+  pub fn new(jsr_url: &'a Url, lockfile: Option<&'a CliLockfile>) -> Self {
+    Self {
+      jsr_url,
+      lockfile,
+      jsr_packages: Default::default(),
+      components: Default::default(),
+    }
+  }
+
+  /// Adds a module of the eszip. Modules of jsr packages are added as their
+  /// package and local modules are ignored.
+  pub fn add_module(&mut self, specifier: &str) {
+    let Ok(url) = Url::parse(specifier) else {
+      // a module relative to the root directory of the executable
+      return;
+    };
+    if !matches!(url.scheme(), "http" | "https") {
+      return;
+    }
+    if let Some(path) = specifier.strip_prefix(self.jsr_url.as_str()) {
+      let mut parts = path.splitn(4, '/');
+      if let (Some(scope), Some(name), Some(version), Some(_)) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+      {
+        if scope.starts_with('@') {
+          self
+            .jsr_packages
+            .insert((format!("{scope}/{name}"), version.to_string()));
+          return;
+        }
+      }
+    }
+    let hash = self.lockfile.and_then(|lockfile| {
+      let checksum = lockfile.lock().content.remote.get(specifier).cloned()?;
+      Some(ComponentHash {
+        algorithm: "SHA-256",
+        hex: checksum,
+      })
+    });
+    self.components.push(Component {
+      kind: ComponentKind::Remote,
+      name: specifier.to_string(),
+      version: None,
+      hash,
+    });
+  }
+
+  pub fn add_npm_package(
+    &mut self,
+    package: &SerializedNpmResolutionSnapshotPackage,
+  ) {
+    self.components.push(Component {
+      kind: ComponentKind::Npm,
+      name: package.id.nv.name.to_string(),
+      version: Some(package.id.nv.version.to_string()),
+      hash: npm_integrity_to_hash(&package.dist.integrity().for_lockfile()),
+    });
+  }
+
+  /// Serializes the bill of materials of the executable with the provided
+  /// name.
+  pub fn build(mut self, format: SbomFormat, name: &str) -> String {
+    let jsr_packages = std::mem::take(&mut self.jsr_packages);
+    for (name, version) in jsr_packages {
+      let hash = self.lockfile.and_then(|lockfile| {
+        let lockfile = lockfile.lock();
+        let info = lockfile
+          .content
+          .packages
+          .jsr
+          .get(&format!("{name}@{version}"))?;
+        Some(ComponentHash {
+          algorithm: "SHA-256",
+          hex: info.integrity.clone(),
+        })
+      });
+      self.components.push(Component {
+        kind: ComponentKind::Jsr,
+        name,
+        version: Some(version),
+        hash,
+      });
+    }
+    let mut components = self.components;
+    components.sort_by(|a, b| {
+      (a.kind, &a.name, &a.version).cmp(&(b.kind, &b.name, &b.version))
+    });
+    components.dedup();
+
+    let timestamp =
+      chrono::Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+    let serial_number = Uuid::new_v4();
+    let document = match format {
+      SbomFormat::CycloneDx => {
+        cyclonedx_document(name, &components, &timestamp, &serial_number)
+      }
+      SbomFormat::Spdx => {
+        spdx_document(name, &components, &timestamp, &serial_number)
+      }
+    };
+    serde_json::to_string_pretty(&document).unwrap()
+  }
+}
+
+/// Converts the integrity of an npm package (ex. `sha512-<base64>`) to a
+/// hex encoded hash.
+fn npm_integrity_to_hash(integrity: &str) -> Option<ComponentHash> {
+  let Some((algorithm, base64_hash)) = integrity.split_once('-') else {
+    // legacy packages only have a hex encoded sha1 hash
+    if integrity.len() == 40 {
+      return Some(ComponentHash {
+        algorithm: "SHA-1",
+        hex: integrity.to_string(),
+      });
+    }
+    return None;
+  };
+  let algorithm = match algorithm {
+    "sha1" => "SHA-1",
+    "sha256" => "SHA-256",
+    "sha384" => "SHA-384",
+    "sha512" => "SHA-512",
+    _ => return None,
+  };
+  let hash = BASE64_STANDARD.decode(base64_hash).ok()?;
+  Some(ComponentHash {
+    algorithm,
+    hex: faster_hex::hex_string(&hash),
+  })
+}
+
+fn cyclonedx_document(
+  name: &str,
+  components: &[Component],
+  timestamp: &str,
+  serial_number: &Uuid,
+) -> serde_json::Value {
+  let components = components
+    .iter()
+    .map(|component| {
+      let mut value = json!({
+        "type": match component.kind {
+          ComponentKind::Jsr | ComponentKind::Npm => "library",
+          ComponentKind::Remote => "file",
+        },
+        "bom-ref": component.bom_ref(),
+        "name": component.name,
+      });
+      if let Some(version) = &component.version {
+        value["version"] = json!(version);
+      }
+      if let Some(purl) = component.purl() {
+        value["purl"] = json!(purl);
+      }
+      if let Some(hash) = &component.hash {
+        value["hashes"] = json!([{
+          "alg": hash.algorithm,
+          "content": hash.hex,
+        }]);
+      }
+      if component.kind == ComponentKind::Remote {
+        value["externalReferences"] = json!([{
+          "type": "distribution",
+          "url": component.name,
+        }]);
+      }
+      value
+    })
+    .collect::<Vec<_>>();
+  json!({
+    "bomFormat": "CycloneDX",
+    "specVersion": "1.5",
+    "serialNumber": format!("urn:uuid:{serial_number}"),
+    "version": 1,
+    "metadata": {
+      "timestamp": timestamp,
+      "tools": {
+        "components": [{
+          "type": "application",
+          "name": "deno",
+          "version": crate::version::deno(),
+        }],
+      },
+      "component": {
+        "type": "application",
+        "bom-ref": name,
+        "name": name,
+      },
+    },
+    "components": components,
+  })
+}
+
+fn spdx_document(
+  name: &str,
+  components: &[Component],
+  timestamp: &str,
+  serial_number: &Uuid,
+) -> serde_json::Value {
+  let mut packages = vec![json!({
+    "name": name,
+    "SPDXID": "SPDXRef-Application",
+    "downloadLocation": "NOASSERTION",
+    "filesAnalyzed": false,
+  })];
+  let mut relationships = vec![json!({
+    "spdxElementId": "SPDXRef-DOCUMENT",
+    "relationshipType": "DESCRIBES",
+    "relatedSpdxElement": "SPDXRef-Application",
+  })];
+  for (index, component) in components.iter().enumerate() {
+    let spdx_id = format!("SPDXRef-Package-{}", index + 1);
+    let mut value = json!({
+      "name": component.name,
+      "SPDXID": spdx_id,
+      "downloadLocation": match component.kind {
+        ComponentKind::Remote => component.name.as_str(),
+        ComponentKind::Jsr | ComponentKind::Npm => "NOASSERTION",
+      },
+      "filesAnalyzed": false,
+    });
+    if let Some(version) = &component.version {
+      value["versionInfo"] = json!(version);
+    }
+    if let Some(hash) = &component.hash {
+      value["checksums"] = json!([{
+        // SPDX names the algorithms without a hyphen
+        "algorithm": hash.algorithm.replace('-', ""),
+        "checksumValue": hash.hex,
+      }]);
+    }
+    if let Some(purl) = component.purl() {
+      value["externalRefs"] = json!([{
+        "referenceCategory": "PACKAGE-MANAGER",
+        "referenceType": "purl",
+        "referenceLocator": purl,
+      }]);
+    }
+    packages.push(value);
+    relationships.push(json!({
+      "spdxElementId": "SPDXRef-Application",
+      "relationshipType": "CONTAINS",
+      "relatedSpdxElement": spdx_id,
+    }));
+  }
+  json!({
+    "spdxVersion": "SPDX-2.3",
+    "dataLicense": "CC0-1.0",
+    "SPDXID": "SPDXRef-DOCUMENT",
+    "name": name,
+    "documentNamespace": format!(
+      "https://deno.land/spdx/{}-{}",
+      name.replace(|c: char| !c.is_ascii_alphanumeric() && c != '-', "-"),
+      serial_number
+    ),
+    "creationInfo": {
+      "created": timestamp,
+      "creators": [format!("Tool: deno-{}", crate::version::deno())],
+    },
+    "packages": packages,
+    "relationships": relationships,
+  })
+}
+
+/// Summarizes an embedded bill of materials for `deno compile --inspect-binary`
+/// as the name of its format and the number of components.
+pub fn describe_sbom(data: &[u8]) -> Option<(&'static str, usize)> {
+  let document: serde_json::Value = serde_json::from_slice(data).ok()?;
+  if let Some(components) = document.get("components") {
+    let count = components.as_array().map(|c| c.len()).unwrap_or(0);
+    return Some(("CycloneDX", count));
+  }
+  let packages = document.get("packages")?.as_array()?;
+  // the first package is the executable itself
+  Some(("SPDX", packages.len().saturating_sub(1)))
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn components() -> Vec<Component> {
+    vec![
+      Component {
+        kind: ComponentKind::Jsr,
+        name: "@std/path".to_string(),
+        version: Some("1.0.0".to_string()),
+        hash: Some(ComponentHash {
+          algorithm: "SHA-256",
+          hex: "abcd".to_string(),
+        }),
+      },
+      Component {
+        kind: ComponentKind::Remote,
+        name: "https://example.com/mod.ts".to_string(),
+        version: None,
+        hash: None,
+      },
+    ]
+  }
+
+  #[test]
+  fn npm_integrity() {
+    assert_eq!(
+      npm_integrity_to_hash("sha512-AAEC"),
+      Some(ComponentHash {
+        algorithm: "SHA-512",
+        hex: "000102".to_string(),
+      })
+    );
+    assert_eq!(
+      npm_integrity_to_hash("0123456789abcdef0123456789abcdef01234567"),
+      Some(ComponentHash {
+        algorithm: "SHA-1",
+        hex: "0123456789abcdef0123456789abcdef01234567".to_string(),
+      })
+    );
+    assert_eq!(npm_integrity_to_hash("md5-AAEC"), None);
+    assert_eq!(npm_integrity_to_hash(""), None);
+  }
+
+  #[test]
+  fn cyclonedx() {
+    let document = cyclonedx_document(
+      "app",
+      &components(),
+      "2024-01-01T00:00:00Z",
+      &Uuid::nil(),
+    );
+    assert_eq!(
+      document["components"],
+      json!([{
+        "type": "library",
+        "bom-ref": "pkg:jsr/%40std/path@1.0.0",
+        "name": "@std/path",
+        "version": "1.0.0",
+        "purl": "pkg:jsr/%40std/path@1.0.0",
+        "hashes": [{ "alg": "SHA-256", "content": "abcd" }],
+      }, {
+        "type": "file",
+        "bom-ref": "https://example.com/mod.ts",
+        "name": "https://example.com/mod.ts",
+        "externalReferences": [{
+          "type": "distribution",
+          "url": "https://example.com/mod.ts",
+        }],
+      }])
+    );
+    assert_eq!(
+      describe_sbom(document.to_string().as_bytes()),
+      Some(("CycloneDX", 2))
+    );
+  }
+
+  #[test]
+  fn spdx() {
+    let document =
+      spdx_document("app", &components(), "2024-01-01T00:00:00Z", &Uuid::nil());
+    assert_eq!(
+      document["packages"][1],
+      json!({
+        "name": "@std/path",
+        "SPDXID": "SPDXRef-Package-1",
+        "downloadLocation": "NOASSERTION",
+        "filesAnalyzed": false,
+        "versionInfo": "1.0.0",
+        "checksums": [{ "algorithm": "SHA256", "checksumValue": "abcd" }],
+        "externalRefs": [{
+          "referenceCategory": "PACKAGE-MANAGER",
+          "referenceType": "purl",
+          "referenceLocator": "pkg:jsr/%40std/path@1.0.0",
+        }],
+      })
+    );
+    assert_eq!(
+      document["relationships"][2],
+      json!({
+        "spdxElementId": "SPDXRef-Application",
+        "relationshipType": "CONTAINS",
+        "relatedSpdxElement": "SPDXRef-Package-2",
+      })
+    );
+    assert_eq!(
+      describe_sbom(document.to_string().as_bytes()),
+      Some(("SPDX", 2))
+    );
+  }
+}
@@ -7,12 +7,14 @@ use std::fs::File;
 use std::io::Read;
 use std::io::Seek;
 use std::io::SeekFrom;
+use std::path::Component;
 use std::path::Path;
 use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::Arc;
 
 use deno_core::anyhow::anyhow;
+use deno_core::anyhow::bail;
 use deno_core::anyhow::Context;
 use deno_core::error::AnyError;
 use deno_core::parking_lot::Mutex;
@@ -40,12 +42,18 @@ pub struct StripRootError {
   target: PathBuf,
 }
 
+/// The zstd level used when compressing files with `deno compile --compress`.
+/// This favours size over speed as compression only happens once at compile
+/// time, while decompression speed is barely affected by the level.
+pub const COMPRESSION_LEVEL: i32 = 19;
+
 pub struct VfsBuilder {
   root_path: PathBuf,
   root_dir: VirtualDirectory,
   files: Vec<Vec<u8>>,
   current_offset: u64,
   file_offsets: HashMap<String, u64>,
+  deduplicated_len: u64,
 }
 
 impl VfsBuilder {
@@ -64,6 +72,7 @@ impl VfsBuilder {
       files: Vec::new(),
       current_offset: 0,
       file_offsets: Default::default(),
+      deduplicated_len: 0,
     })
   }
 
@@ -222,8 +231,12 @@ impl VfsBuilder {
             name: name.to_string(),
             offset,
             len: data.len() as u64,
+            compressed_len: None,
           }),
         );
+        if self.current_offset != offset {
+          self.deduplicated_len += data_len as u64;
+        }
       }
     }
 
@@ -271,6 +284,136 @@ impl VfsBuilder {
     Ok(())
   }
 
+  /// Moves the contents of another builder into a directory named `name` in
+  /// the root of this builder. Files with the same content as a file already
+  /// in this builder are stored once.
+  pub fn add_builder_as_root_dir(&mut self, name: &str, other: VfsBuilder) {
+    fn relocate_entries(
+      dir: &mut VirtualDirectory,
+      name: &str,
+      offsets: &HashMap<u64, u64>,
+    ) {
+      for entry in &mut dir.entries {
+        match entry {
+          VfsEntry::Dir(dir) => relocate_entries(dir, name, offsets),
+          VfsEntry::File(file) => {
+            if let Some(offset) = offsets.get(&file.offset) {
+              file.offset = *offset;
+            }
+          }
+          VfsEntry::Symlink(symlink) => {
+            symlink.dest_parts.insert(0, name.to_string());
+          }
+        }
+      }
+    }
+
+    self.deduplicated_len += other.deduplicated_len;
+    let (mut dir, files) = other.into_dir_and_files();
+    dir.name = name.to_string();
+    // maps the offsets in the other builder to the offsets in this one
+    let mut offsets = HashMap::with_capacity(files.len());
+    let mut other_offset = 0;
+    for data in files {
+      let data_len = data.len() as u64;
+      let checksum = util::checksum::gen(&[&data]);
+      let offset = match self.file_offsets.get(&checksum) {
+        Some(offset) => {
+          self.deduplicated_len += data_len;
+          *offset
+        }
+        None => {
+          let offset = self.current_offset;
+          self.file_offsets.insert(checksum, offset);
+          self.files.push(data);
+          self.current_offset += data_len;
+          offset
+        }
+      };
+      offsets.insert(other_offset, offset);
+      other_offset += data_len;
+    }
+    relocate_entries(&mut dir, name, &offsets);
+    match self
+      .root_dir
+      .entries
+      .binary_search_by(|e| e.name().cmp(name))
+    {
+      Ok(index) => {
+        self.root_dir.entries[index] = VfsEntry::Dir(dir);
+      }
+      Err(insert_index) => {
+        self
+          .root_dir
+          .entries
+          .insert(insert_index, VfsEntry::Dir(dir));
+      }
+    }
+  }
+
+  /// Compresses the content of each file with zstd so it can be decompressed
+  /// on demand. Files which don't get smaller are stored as is.
+  pub fn compress_files(&mut self) -> Result<(), AnyError> {
+    fn update_entries(
+      dir: &mut VirtualDirectory,
+      offsets: &HashMap<u64, (u64, Option<u64>)>,
+    ) {
+      for entry in &mut dir.entries {
+        match entry {
+          VfsEntry::Dir(dir) => update_entries(dir, offsets),
+          VfsEntry::File(file) => {
+            if file.len == 0 {
+              // may share its offset with the next file, but has nothing
+              // to read anyway
+              file.offset = 0;
+              continue;
+            }
+            let (offset, compressed_len) = offsets[&file.offset];
+            file.offset = offset;
+            file.compressed_len = compressed_len;
+          }
+          VfsEntry::Symlink(_) => {}
+        }
+      }
+    }
+
+    let mut offsets = HashMap::with_capacity(self.files.len());
+    let mut old_offset = 0;
+    let mut new_offset = 0;
+    for data in &mut self.files {
+      let data_len = data.len() as u64;
+      let compressed = zstd::bulk::compress(data, COMPRESSION_LEVEL)?;
+      let compressed_len = if compressed.len() < data.len() {
+        *data = compressed;
+        Some(data.len() as u64)
+      } else {
+        None
+      };
+      offsets.insert(old_offset, (new_offset, compressed_len));
+      old_offset += data_len;
+      new_offset += data.len() as u64;
+    }
+    for offset in self.file_offsets.values_mut() {
+      if let Some((new_offset, _)) = offsets.get(offset) {
+        *offset = *new_offset;
+      }
+    }
+    self.current_offset = new_offset;
+    update_entries(&mut self.root_dir, &offsets);
+    Ok(())
+  }
+
+  /// The total size of the stored file contents.
+  pub fn files_len(&self) -> u64 {
+    self.current_offset
+  }
+
+  /// The number of bytes that were saved by storing files with the same
+  /// content only once.
+  pub fn deduplicated_len(&self) -> u64 {
+    self.deduplicated_len
+  }
+
   pub fn into_dir_and_files(self) -> (VirtualDirectory, Vec<Vec<u8>>) {
     (self.root_dir, self.files)
   }
@@ -399,11 +542,62 @@ pub struct VirtualDirectory {
   pub entries: Vec<VfsEntry>,
 }
 
+impl VirtualDirectory {
+  /// Formats the entries of the directory as a tree for displaying it to the
+  /// user, for example with `deno compile --list-contents`.
+  pub fn display_tree(&self) -> String {
+    fn write_entries(
+      output: &mut String,
+      dir: &VirtualDirectory,
+      prefix: &str,
+    ) {
+      for (i, entry) in dir.entries.iter().enumerate() {
+        let is_last = i == dir.entries.len() - 1;
+        let (branch, child_prefix) = if is_last {
+          ("└── ", "    ")
+        } else {
+          ("├── ", "│   ")
+        };
+        output.push_str(prefix);
+        output.push_str(branch);
+        match entry {
+          VfsEntry::Dir(dir) => {
+            output.push_str(&format!("{}/\n", dir.name));
+            write_entries(output, dir, &format!("{prefix}{child_prefix}"));
+          }
+          VfsEntry::File(file) => {
+            output.push_str(&format!(
+              "{} ({})\n",
+              file.name,
+              util::display::human_size(file.len as f64)
+            ));
+          }
+          VfsEntry::Symlink(symlink) => {
+            output.push_str(&format!(
+              "{} --> {}\n",
+              symlink.name,
+              symlink.dest_parts.join("/")
+            ));
+          }
+        }
+      }
+    }
+
+    let mut output = format!("{}/\n", self.name);
+    write_entries(&mut output, self, "");
+    output
+  }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VirtualFile {
   pub name: String,
   pub offset: u64,
+  /// The uncompressed length of the file.
   pub len: u64,
+  /// The length of the stored data when it is compressed with zstd.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub compressed_len: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -750,6 +944,8 @@ impl deno_io::fs::File for FileBackedVfsFile {
 pub struct FileBackedVfs {
   file: Mutex<Vec<u8>>,
   fs_root: VfsRoot,
+  /// Compressed files that have been read, keyed by offset.
+  decompressed_files: Mutex<HashMap<u64, Arc<Vec<u8>>>>,
 }
 
 impl FileBackedVfs {
@@ -757,13 +953,10 @@ impl FileBackedVfs {
     Self {
       file: Mutex::new(file),
       fs_root,
+      decompressed_files: Default::default(),
     }
   }
 
-  pub fn root(&self) -> &Path {
-    &self.fs_root.root_path
-  }
-
   pub fn is_path_within(&self, path: &Path) -> bool {
     path.starts_with(&self.fs_root.root_path)
   }
@@ -836,6 +1029,20 @@ impl FileBackedVfs {
     pos: u64,
     buf: &mut [u8],
   ) -> std::io::Result<usize> {
+    if file.compressed_len.is_some() {
+      let data = self.decompressed_file(file)?;
+      let start = pos;
+      let end = start + buf.len() as u64;
+      if end > data.len() as u64 {
+        return Err(std::io::Error::new(
+          std::io::ErrorKind::UnexpectedEof,
+          "unexpected EOF",
+        ));
+      }
+      buf.copy_from_slice(&data[start as usize..end as usize]);
+      return Ok(buf.len());
+    }
+
     let data = self.file.lock();
     let start = self.fs_root.start_file_offset + file.offset + pos;
     let end = start + buf.len() as u64;
@@ -850,6 +1057,37 @@ impl FileBackedVfs {
     Ok(buf.len())
   }
 
+  fn decompressed_file(
+    &self,
+    file: &VirtualFile,
+  ) -> std::io::Result<Arc<Vec<u8>>> {
+    if let Some(data) = self.decompressed_files.lock().get(&file.offset) {
+      return Ok(data.clone());
+    }
+    let compressed_len = file.compressed_len.unwrap_or(file.len);
+    let data = {
+      let data = self.file.lock();
+      let start = self.fs_root.start_file_offset + file.offset;
+      let end = start + compressed_len;
+      if end > data.len() as u64 {
+        return Err(std::io::Error::new(
+          std::io::ErrorKind::UnexpectedEof,
+          "unexpected EOF",
+        ));
+      }
+      zstd::bulk::decompress(
+        &data[start as usize..end as usize],
+        file.len as usize,
+      )?
+    };
+    let data = Arc::new(data);
+    self
+      .decompressed_files
+      .lock()
+      .insert(file.offset, data.clone());
+    Ok(data)
+  }
+
   pub fn dir_entry(&self, path: &Path) -> std::io::Result<&VirtualDirectory> {
     let (_, entry) = self.fs_root.find_entry(path)?;
     match entry {
@@ -873,6 +1111,95 @@ impl FileBackedVfs {
       VfsEntryRef::File(file) => Ok(file),
     }
   }
+
+  pub fn root_dir(&self) -> &VirtualDirectory {
+    &self.fs_root.dir
+  }
+
+  /// Writes the entries of the virtual file system to `output_dir`, for
+  /// example for `deno compile --inspect-binary`.
+  pub fn extract_to(&self, output_dir: &Path) -> Result<(), AnyError> {
+    fn extract_dir<'a>(
+      vfs: &FileBackedVfs,
+      dir: &'a VirtualDirectory,
+      dir_path: &Path,
+      depth: usize,
+      symlinks: &mut Vec<(PathBuf, usize, &'a VirtualSymlink)>,
+    ) -> Result<(), AnyError> {
+      std::fs::create_dir_all(dir_path).with_context(|| {
+        format!("Failed to create directory '{}'", dir_path.display())
+      })?;
+      for entry in &dir.entries {
+        let path = dir_path.join(validate_entry_name(entry.name())?);
+        match entry {
+          VfsEntry::Dir(dir) => {
+            extract_dir(vfs, dir, &path, depth + 1, symlinks)?;
+          }
+          VfsEntry::File(file) => {
+            let data = vfs.read_file_all(file)?;
+            std::fs::write(&path, data).with_context(|| {
+              format!("Failed to write '{}'", path.display())
+            })?;
+          }
+          VfsEntry::Symlink(symlink) => {
+            symlinks.push((path, depth, symlink));
+          }
+        }
+      }
+      Ok(())
+    }
+
+    let mut symlinks = Vec::new();
+    extract_dir(self, &self.fs_root.dir, output_dir, 0, &mut symlinks)?;
+    // create the symlinks last so it's known whether their destination is a
+    // directory, which is necessary on Windows
+    for (path, depth, symlink) in symlinks {
+      // make the symlink relative so the output directory can be moved
+      let mut dest = PathBuf::new();
+      for _ in 0..depth {
+        dest.push("..");
+      }
+      // the destination is relative to the root, so this keeps it within the
+      // output directory
+      for part in &symlink.dest_parts {
+        dest.push(validate_entry_name(part).with_context(|| {
+          format!("Invalid destination of symlink '{}'", path.display())
+        })?);
+      }
+      #[cfg(unix)]
+      let result = std::os::unix::fs::symlink(&dest, &path);
+      #[cfg(windows)]
+      let result = if symlink.resolve_dest_from_root(output_dir).is_dir() {
+        std::os::windows::fs::symlink_dir(&dest, &path)
+      } else {
+        std::os::windows::fs::symlink_file(&dest, &path)
+      };
+      result.with_context(|| {
+        format!("Failed to create symlink '{}'", path.display())
+      })?;
+    }
+    Ok(())
+  }
+}
+
+/// The virtual file system comes from the executable being inspected, so an
+/// entry name must not be able to point anywhere but into its directory.
+fn validate_entry_name(name: &str) -> Result<&str, AnyError> {
+  if is_single_normal_component(name) {
+    Ok(name)
+  } else {
+    bail!("Invalid entry name '{}' in the virtual file system", name)
+  }
+}
+
+/// Gets if a name is a single normal path component on every platform, so
+/// that joining it to a directory can't result in a path outside of it.
+pub fn is_single_normal_component(name: &str) -> bool {
+  let mut components = Path::new(name).components();
+  matches!(
+    (components.next(), components.next()),
+    (Some(Component::Normal(_)), None)
+  ) && !name.contains(['/', '\\', ':'])
 }
 
 #[cfg(test)]
@@ -1038,6 +1365,175 @@ mod test {
     )
   }
 
+  #[test]
+  fn builder_as_root_dir() {
+    let temp_dir = TempDir::new();
+    let src_path = temp_dir.path().canonicalize().join("src");
+    src_path.create_dir_all();
+    let cache_path = temp_dir.path().canonicalize().join("cache");
+    cache_path.create_dir_all();
+    src_path.join("a.txt").write("data");
+    cache_path.join("pkg").create_dir_all();
+    cache_path.join("pkg").join("index.js").write("module");
+    cache_path.join("pkg").join("data.txt").write("data");
+    let mut builder = VfsBuilder::new(src_path.to_path_buf()).unwrap();
+    builder
+      .add_file_at_path(&src_path.join("a.txt").to_path_buf())
+      .unwrap();
+    let mut cache_builder = VfsBuilder::new(cache_path.to_path_buf()).unwrap();
+    cache_builder
+      .add_dir_recursive(&cache_path.join("pkg").to_path_buf())
+      .unwrap();
+    builder.add_builder_as_root_dir("node_modules", cache_builder);
+    // the data.txt file has the same content as a.txt
+    assert_eq!(builder.deduplicated_len(), 4);
+    assert_eq!(builder.files.len(), 2);
+    let (dest_path, virtual_fs) = into_virtual_fs(builder, &temp_dir);
+    assert_eq!(read_file(&virtual_fs, &dest_path.join("a.txt")), "data");
+    assert_eq!(
+      read_file(
+        &virtual_fs,
+        &dest_path.join("node_modules").join("pkg").join("data.txt")
+      ),
+      "data"
+    );
+    assert_eq!(
+      read_file(
+        &virtual_fs,
+        &dest_path.join("node_modules").join("pkg").join("index.js")
+      ),
+      "module"
+    );
+    assert_eq!(
+      virtual_fs.fs_root.dir.display_tree(),
+      concat!(
+        "src/\n",
+        "├── a.txt (4B)\n",
+        "└── node_modules/\n",
+        "    └── pkg/\n",
+        "        ├── data.txt (4B)\n",
+        "        └── index.js (6B)\n",
+      )
+    );
+  }
+
+  #[test]
+  fn compressed_files() {
+    let temp_dir = TempDir::new();
+    let src_path = temp_dir.path().canonicalize().join("src");
+    src_path.create_dir_all();
+    let src_path = src_path.to_path_buf();
+    let mut builder = VfsBuilder::new(src_path.clone()).unwrap();
+    let text = "compressible ".repeat(100);
+    builder
+      .add_file(&src_path.join("a.txt"), text.clone().into())
+      .unwrap();
+    builder
+      .add_file(&src_path.join("b.txt"), text.clone().into())
+      .unwrap();
+    builder
+      .add_file(&src_path.join("c.txt"), Vec::new())
+      .unwrap();
+    builder
+      .add_file(&src_path.join("d.txt"), "d".into())
+      .unwrap();
+    assert_eq!(builder.deduplicated_len(), text.len() as u64);
+    builder.compress_files().unwrap();
+    let (dest_path, virtual_fs) = into_virtual_fs(builder, &temp_dir);
+    let a_file = virtual_fs.file_entry(&dest_path.join("a.txt")).unwrap();
+    assert!(a_file.compressed_len.unwrap() < a_file.len);
+    assert_eq!(read_file(&virtual_fs, &dest_path.join("a.txt")), text);
+    assert_eq!(read_file(&virtual_fs, &dest_path.join("b.txt")), text);
+    assert_eq!(read_file(&virtual_fs, &dest_path.join("c.txt")), "");
+    // too small to benefit from compression
+    let d_file = virtual_fs.file_entry(&dest_path.join("d.txt")).unwrap();
+    assert_eq!(d_file.compressed_len, None);
+    assert_eq!(read_file(&virtual_fs, &dest_path.join("d.txt")), "d");
+    let mut buf = vec![0; 5];
+    virtual_fs.read_file(a_file, 13, &mut buf).unwrap();
+    assert_eq!(buf, b"compr");
+  }
+
+  #[test]
+  #[cfg(unix)]
+  fn extracts_files() {
+    let temp_dir = TempDir::new();
+    let src_path = temp_dir.path().canonicalize().join("src");
+    src_path.create_dir_all();
+    let src_path = src_path.to_path_buf();
+    let mut builder = VfsBuilder::new(src_path.clone()).unwrap();
+    builder
+      .add_file(&src_path.join("a.txt"), "a".into())
+      .unwrap();
+    builder
+      .add_file(&src_path.join("sub").join("b.txt"), "b".into())
+      .unwrap();
+    builder
+      .add_symlink(
+        &src_path.join("sub").join("link.txt"),
+        &src_path.join("a.txt"),
+      )
+      .unwrap();
+    builder.compress_files().unwrap();
+    let (_, virtual_fs) = into_virtual_fs(builder, &temp_dir);
+    let output_dir = temp_dir.path().join("output");
+    virtual_fs.extract_to(output_dir.as_path()).unwrap();
+    assert_eq!(output_dir.join("a.txt").read_to_string(), "a");
+    assert_eq!(output_dir.join("sub").join("b.txt").read_to_string(), "b");
+    assert_eq!(
+      std::fs::read_link(output_dir.join("sub").join("link.txt")).unwrap(),
+      PathBuf::from("..").join("a.txt")
+    );
+    assert_eq!(
+      output_dir.join("sub").join("link.txt").read_to_string(),
+      "a"
+    );
+  }
+
+  #[test]
+  fn extract_rejects_entries_outside_output_dir() {
+    let temp_dir = TempDir::new();
+    let output_dir = temp_dir.path().join("output");
+    let extract = |entry: VfsEntry| {
+      let virtual_fs = FileBackedVfs::new(
+        Vec::new(),
+        VfsRoot {
+          dir: VirtualDirectory {
+            name: "root".to_string(),
+            entries: vec![entry],
+          },
+          root_path: temp_dir.path().join("root").to_path_buf(),
+          start_file_offset: 0,
+        },
+      );
+      virtual_fs.extract_to(output_dir.as_path())
+    };
+    let file = |name: &str| {
+      VfsEntry::File(VirtualFile {
+        name: name.to_string(),
+        offset: 0,
+        len: 0,
+        compressed_len: None,
+      })
+    };
+
+    for name in ["../escaped.txt", "sub/../../escaped.txt", "..", "", "."] {
+      assert!(extract(file(name)).is_err(), "{name}");
+    }
+    let absolute_path = temp_dir.path().join("escaped.txt");
+    let absolute_name = absolute_path.to_string_lossy().to_string();
+    assert!(extract(file(absolute_name.as_str())).is_err());
+    assert!(!absolute_path.exists());
+
+    let err = extract(VfsEntry::Symlink(VirtualSymlink {
+      name: "link".to_string(),
+      dest_parts: vec!["..".to_string(), "escaped.txt".to_string()],
+    }))
+    .unwrap_err();
+    assert!(format!("{err:#}").contains("Invalid destination of symlink"));
+    assert!(std::fs::symlink_metadata(output_dir.join("link")).is_err());
+  }
+
   #[test]
   fn circular_symlink() {
     let temp_dir = TempDir::new();
@@ -16,14 +16,20 @@ use deno_task_shell::ExecutableCommand;
 use deno_task_shell::ExecuteResult;
 use deno_task_shell::ShellCommand;
 use deno_task_shell::ShellCommandContext;
+use deno_task_shell::ShellPipeReader;
+use deno_task_shell::ShellPipeWriter;
+use deno_task_shell::ShellState;
 use lazy_regex::Lazy;
 use regex::Regex;
 use tokio::task::LocalSet;
 
+use crate::args::TaskShell;
 use crate::npm::CliNpmResolver;
 use crate::npm::InnerCliNpmResolverRef;
 use crate::npm::ManagedCliNpmResolver;
 
+mod builtins;
+
 pub fn get_script_with_args(script: &str, argv: &[String]) -> String {
   let additional_args = argv
     .iter()
@@ -45,26 +51,57 @@ pub struct RunTaskOptions<'a> {
   pub argv: &'a [String],
   pub custom_commands: HashMap<String, Rc<dyn ShellCommand>>,
   pub root_node_modules_dir: Option<&'a Path>,
+  /// Where to write the output of the task instead of the stdout and stderr
+  /// of the current process.
+  pub stdio: Option<TaskIo>,
+  pub shell: TaskShell,
+}
+
+/// Pipes the output of a task is written to, for example to prefix each line
+/// of output when running multiple tasks in parallel.
+pub struct TaskIo {
+  pub stdout: ShellPipeWriter,
+  pub stderr: ShellPipeWriter,
 }
 
 pub type TaskCustomCommands = HashMap<String, Rc<dyn ShellCommand>>;
 
 pub async fn run_task(opts: RunTaskOptions<'_>) -> Result<i32, AnyError> {
   let script = get_script_with_args(opts.script, opts.argv);
+  let script = match opts.shell {
+    TaskShell::Deno => script,
+    // let the shell spawn bash so the output still goes through its pipes
+    TaskShell::Bash => format!("bash -c {}", quote_single(&script)),
+  };
   let seq_list = deno_task_shell::parser::parse(&script)
     .with_context(|| format!("Error parsing script '{}'.", opts.task_name))?;
   let env_vars =
     prepare_env_vars(opts.env_vars, opts.init_cwd, opts.root_node_modules_dir);
   let local = LocalSet::new();
-  let future = deno_task_shell::execute(
+  let mut commands = builtins::builtin_commands();
+  commands.extend(opts.custom_commands);
+  let state = ShellState::new(env_vars, opts.cwd, commands);
+  let (stdout, stderr) = match opts.stdio {
+    Some(stdio) => (stdio.stdout, stdio.stderr),
+    None => (ShellPipeWriter::stdout(), ShellPipeWriter::stderr()),
+  };
+  let future = deno_task_shell::execute_with_pipes(
     seq_list,
-    env_vars,
-    opts.cwd,
-    opts.custom_commands,
+    state,
+    ShellPipeReader::stdin(),
+    stdout,
+    stderr,
   );
   Ok(local.run_until(future).await)
 }
 
+/// Surrounds the text in single quotes, which don't support any escapes, so
+/// single quotes within the text end the quoted string, add an escaped quote
+/// and start a new quoted string.
+fn quote_single(text: &str) -> String {
+  format!("'{}'", text.replace('\'', r#"'"'"'"#))
+}
+
 fn prepare_env_vars(
   mut env_vars: HashMap<String, String>,
   initial_cwd: &Path,
@@ -473,6 +510,12 @@ mod test {
     );
   }
 
+  #[test]
+  fn test_quote_single() {
+    assert_eq!(quote_single("echo hi"), "'echo hi'");
+    assert_eq!(quote_single("echo 'hi'"), r#"'echo '"'"'hi'"'"''"#);
+  }
+
   #[test]
   fn test_resolve_execution_path_from_npx_shim() {
     // example shim on unix
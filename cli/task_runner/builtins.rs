@@ -0,0 +1,330 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Commands that deno_task_shell doesn't provide out of the box, but that
+//! scripts written for bash commonly use. Implementing them here means tasks
+//! work the same on every platform without coreutils being installed.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::rc::Rc;
+use std::time::SystemTime;
+
+use deno_core::futures;
+use deno_core::futures::future::LocalBoxFuture;
+use deno_task_shell::ExecutableCommand;
+use deno_task_shell::ExecuteResult;
+use deno_task_shell::ShellCommand;
+use deno_task_shell::ShellCommandContext;
+
+pub fn builtin_commands() -> HashMap<String, Rc<dyn ShellCommand>> {
+  HashMap::from([
+    (
+      "touch".to_string(),
+      Rc::new(TouchCommand) as Rc<dyn ShellCommand>,
+    ),
+    (
+      "which".to_string(),
+      Rc::new(WhichCommand) as Rc<dyn ShellCommand>,
+    ),
+    (
+      "env".to_string(),
+      Rc::new(EnvCommand) as Rc<dyn ShellCommand>,
+    ),
+    (
+      "test".to_string(),
+      Rc::new(TestCommand { is_bracket: false }) as Rc<dyn ShellCommand>,
+    ),
+    (
+      "[".to_string(),
+      Rc::new(TestCommand { is_bracket: true }) as Rc<dyn ShellCommand>,
+    ),
+  ])
+}
+
+fn exit_code_result(code: i32) -> LocalBoxFuture<'static, ExecuteResult> {
+  Box::pin(futures::future::ready(ExecuteResult::from_exit_code(code)))
+}
+
+/// `touch [-c] <file>...`
+pub struct TouchCommand;
+
+impl ShellCommand for TouchCommand {
+  fn execute(
+    &self,
+    mut context: ShellCommandContext,
+  ) -> LocalBoxFuture<'static, ExecuteResult> {
+    let mut no_create = false;
+    let mut paths = Vec::new();
+    for arg in &context.args {
+      match arg.as_str() {
+        "-c" | "--no-create" => no_create = true,
+        _ if arg.starts_with('-') && arg.len() > 1 => {
+          let _ = context
+            .stderr
+            .write_line(&format!("touch: unsupported flag: {arg}"));
+          return exit_code_result(1);
+        }
+        _ => paths.push(context.state.cwd().join(arg)),
+      }
+    }
+    if paths.is_empty() {
+      let _ = context.stderr.write_line("touch: missing file operand");
+      return exit_code_result(1);
+    }
+    let mut exit_code = 0;
+    for path in paths {
+      if no_create && !path.exists() {
+        continue;
+      }
+      if let Err(err) = touch(&path) {
+        let _ = context
+          .stderr
+          .write_line(&format!("touch: {}: {err}", path.display()));
+        exit_code = 1;
+      }
+    }
+    exit_code_result(exit_code)
+  }
+}
+
+fn touch(path: &Path) -> std::io::Result<()> {
+  let file = std::fs::OpenOptions::new()
+    .create(true)
+    .append(true)
+    .open(path)?;
+  file.set_modified(SystemTime::now())
+}
+
+/// `which <command>...`
+pub struct WhichCommand;
+
+impl ShellCommand for WhichCommand {
+  fn execute(
+    &self,
+    mut context: ShellCommandContext,
+  ) -> LocalBoxFuture<'static, ExecuteResult> {
+    if context.args.is_empty() {
+      let _ = context.stderr.write_line("which: missing command name");
+      return exit_code_result(1);
+    }
+    let mut exit_code = 0;
+    for name in &context.args {
+      if context.state.resolve_custom_command(name).is_some() {
+        let _ = context
+          .stdout
+          .write_line(&format!("{name}: shell built-in command"));
+        continue;
+      }
+      match context.state.resolve_command_path(name) {
+        Ok(path) => {
+          let _ = context.stdout.write_line(&path.display().to_string());
+        }
+        Err(_) => {
+          let _ = context
+            .stderr
+            .write_line(&format!("which: no {name} in PATH"));
+          exit_code = 1;
+        }
+      }
+    }
+    exit_code_result(exit_code)
+  }
+}
+
+/// `env [NAME=VALUE]... [<command> [<arg>...]]`
+pub struct EnvCommand;
+
+impl ShellCommand for EnvCommand {
+  fn execute(
+    &self,
+    mut context: ShellCommandContext,
+  ) -> LocalBoxFuture<'static, ExecuteResult> {
+    let mut args = std::mem::take(&mut context.args).into_iter().peekable();
+    while let Some((name, value)) = args
+      .peek()
+      .and_then(|arg| arg.split_once('='))
+      .filter(|(name, _)| !name.is_empty())
+    {
+      context.state.apply_env_var(name, value);
+      args.next();
+    }
+
+    let Some(command_name) = args.next() else {
+      let mut env_vars = context
+        .state
+        .env_vars()
+        .iter()
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect::<Vec<_>>();
+      env_vars.sort();
+      for env_var in env_vars {
+        let _ = context.stdout.write_line(&env_var);
+      }
+      return exit_code_result(0);
+    };
+    let args = args.collect::<Vec<_>>();
+    if let Some(command) = context.state.resolve_custom_command(&command_name) {
+      return command.execute(ShellCommandContext { args, ..context });
+    }
+    match context.state.resolve_command_path(&command_name) {
+      Ok(path) => ExecutableCommand::new(command_name, path)
+        .execute(ShellCommandContext { args, ..context }),
+      Err(err) => {
+        let _ = context.stderr.write_line(&format!("{}", err));
+        exit_code_result(err.exit_code())
+      }
+    }
+  }
+}
+
+/// `test <expression>` and `[ <expression> ]`
+pub struct TestCommand {
+  /// Whether the command was invoked as `[`, which requires a closing `]`.
+  is_bracket: bool,
+}
+
+impl ShellCommand for TestCommand {
+  fn execute(
+    &self,
+    mut context: ShellCommandContext,
+  ) -> LocalBoxFuture<'static, ExecuteResult> {
+    let command_name = if self.is_bracket { "[" } else { "test" };
+    let mut args = context.args.as_slice();
+    if self.is_bracket {
+      match args.split_last() {
+        Some((last, rest)) if last == "]" => args = rest,
+        _ => {
+          let _ = context.stderr.write_line("[: missing ']'");
+          return exit_code_result(2);
+        }
+      }
+    }
+    match evaluate_test_expression(args, context.state.cwd()) {
+      Ok(true) => exit_code_result(0),
+      Ok(false) => exit_code_result(1),
+      Err(err) => {
+        let _ = context.stderr.write_line(&format!("{command_name}: {err}"));
+        exit_code_result(2)
+      }
+    }
+  }
+}
+
+/// Evaluates the expression of `test`, supporting `!`, `-a` and `-o` along
+/// with the common file, string and integer operators.
+fn evaluate_test_expression(
+  args: &[String],
+  cwd: &Path,
+) -> Result<bool, String> {
+  // -o has a lower precedence than -a
+  if let Some(index) = args.iter().rposition(|arg| arg == "-o") {
+    if index > 0 && index < args.len() - 1 {
+      return Ok(
+        evaluate_test_expression(&args[..index], cwd)?
+          || evaluate_test_expression(&args[index + 1..], cwd)?,
+      );
+    }
+  }
+  if let Some(index) = args.iter().rposition(|arg| arg == "-a") {
+    if index > 0 && index < args.len() - 1 {
+      return Ok(
+        evaluate_test_expression(&args[..index], cwd)?
+          && evaluate_test_expression(&args[index + 1..], cwd)?,
+      );
+    }
+  }
+  match args {
+    [] => Ok(false),
+    [value] => Ok(!value.is_empty()),
+    [not, rest @ ..] if not == "!" => Ok(!evaluate_test_expression(rest, cwd)?),
+    [op, value] => evaluate_unary(op, value, cwd),
+    [left, op, right] => evaluate_binary(left, op, right),
+    _ => Err("too many arguments".to_string()),
+  }
+}
+
+fn evaluate_unary(op: &str, value: &str, cwd: &Path) -> Result<bool, String> {
+  let path = || cwd.join(value);
+  Ok(match op {
+    "-n" => !value.is_empty(),
+    "-z" => value.is_empty(),
+    "-e" => path().exists(),
+    "-f" => path().is_file(),
+    "-d" => path().is_dir(),
+    "-L" | "-h" => path().is_symlink(),
+    "-s" => std::fs::metadata(path())
+      .map(|metadata| metadata.len() > 0)
+      .unwrap_or(false),
+    _ => return Err(format!("unknown unary operator: {op}")),
+  })
+}
+
+fn evaluate_binary(left: &str, op: &str, right: &str) -> Result<bool, String> {
+  let parse_int = |value: &str| {
+    value
+      .trim()
+      .parse::<i64>()
+      .map_err(|_| format!("integer expression expected: {value}"))
+  };
+  Ok(match op {
+    "=" | "==" => left == right,
+    "!=" => left != right,
+    "-eq" => parse_int(left)? == parse_int(right)?,
+    "-ne" => parse_int(left)? != parse_int(right)?,
+    "-lt" => parse_int(left)? < parse_int(right)?,
+    "-le" => parse_int(left)? <= parse_int(right)?,
+    "-gt" => parse_int(left)? > parse_int(right)?,
+    "-ge" => parse_int(left)? >= parse_int(right)?,
+    _ => return Err(format!("unknown binary operator: {op}")),
+  })
+}
+
+#[cfg(test)]
+mod test {
+  use test_util::TempDir;
+
+  use super::*;
+
+  fn evaluate(expression: &str, cwd: &Path) -> Result<bool, String> {
+    let args = expression
+      .split(' ')
+      .map(|arg| arg.to_string())
+      .collect::<Vec<_>>();
+    evaluate_test_expression(&args, cwd)
+  }
+
+  #[test]
+  fn test_expression() {
+    let temp_dir = TempDir::new();
+    temp_dir.write("file.txt", "data");
+    temp_dir.write("empty.txt", "");
+    temp_dir.create_dir_all("dir");
+    let cwd = temp_dir.path().as_path();
+
+    assert_eq!(evaluate("-f file.txt", cwd), Ok(true));
+    assert_eq!(evaluate("-f dir", cwd), Ok(false));
+    assert_eq!(evaluate("-d dir", cwd), Ok(true));
+    assert_eq!(evaluate("-e missing.txt", cwd), Ok(false));
+    assert_eq!(evaluate("! -e missing.txt", cwd), Ok(true));
+    assert_eq!(evaluate("-s file.txt", cwd), Ok(true));
+    assert_eq!(evaluate("-s empty.txt", cwd), Ok(false));
+    assert_eq!(evaluate("-z value", cwd), Ok(false));
+    assert_eq!(evaluate("-n value", cwd), Ok(true));
+    assert_eq!(evaluate("value", cwd), Ok(true));
+    assert_eq!(evaluate("a = a", cwd), Ok(true));
+    assert_eq!(evaluate("a != a", cwd), Ok(false));
+    assert_eq!(evaluate("2 -lt 10", cwd), Ok(true));
+    assert_eq!(evaluate("2 -ge 10", cwd), Ok(false));
+    assert_eq!(evaluate("-d dir -a -f file.txt", cwd), Ok(true));
+    assert_eq!(evaluate("-d file.txt -a -f file.txt", cwd), Ok(false));
+    assert_eq!(evaluate("-d file.txt -o -f file.txt", cwd), Ok(true));
+    assert_eq!(
+      evaluate("a -lt 10", cwd),
+      Err("integer expression expected: a".to_string())
+    );
+    assert_eq!(
+      evaluate("a -foo b", cwd),
+      Err("unknown binary operator: -foo".to_string())
+    );
+  }
+}
@@ -48,6 +48,7 @@ use tokio::sync::mpsc::UnboundedSender;
 mod mitata;
 mod reporters;
 
+pub use mitata::fmt_duration;
 use reporters::BenchReporter;
 use reporters::ConsoleReporter;
 use reporters::JsonReporter;
@@ -143,7 +144,7 @@ fn create_reporter(
 }
 
 /// Run a single specifier as an executable bench module.
-async fn bench_specifier(
+pub async fn bench_specifier(
   worker_factory: Arc<CliMainWorkerFactory>,
   permissions: Permissions,
   specifier: ModuleSpecifier,
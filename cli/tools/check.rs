@@ -14,6 +14,7 @@ use deno_terminal::colors;
 use once_cell::sync::Lazy;
 use regex::Regex;
 
+use crate::args::CheckReporterKind;
 use crate::args::CliOptions;
 use crate::args::TsConfig;
 use crate::args::TsConfigType;
@@ -28,7 +29,14 @@ use crate::graph_util::ModuleGraphBuilder;
 use crate::npm::CliNpmResolver;
 use crate::tsc;
 use crate::tsc::Diagnostics;
+use crate::util::checkstyle::CheckstyleError;
+use crate::util::checkstyle::CheckstyleReport;
+use crate::util::checkstyle::CheckstyleSeverity;
 use crate::util::path::to_percent_decoded_str;
+use crate::util::sarif::SarifLevel;
+use crate::util::sarif::SarifLog;
+use crate::util::sarif::SarifRegion;
+use crate::util::sarif::SarifResult;
 
 /// Options for performing a check of a module graph. Note that the decision to
 /// emit or not is determined by the `ts_config` settings.
@@ -244,6 +252,123 @@ impl TypeChecker {
   }
 }
 
+/// Writes the outcome of a `deno check` run in a machine readable format.
+///
+/// Type errors are written to stdout and exit the process with a non-zero
+/// exit code. Any other error is returned so it gets reported as usual.
+pub fn report_check_result(
+  kind: CheckReporterKind,
+  result: Result<(), AnyError>,
+) -> Result<(), AnyError> {
+  let diagnostics = match result {
+    Ok(()) => Diagnostics::default(),
+    Err(err) => match err.downcast::<Diagnostics>() {
+      Ok(diagnostics) => diagnostics,
+      Err(err) => return Err(err),
+    },
+  };
+  let output = match kind {
+    CheckReporterKind::Pretty => {
+      return if diagnostics.is_empty() {
+        Ok(())
+      } else {
+        Err(diagnostics.into())
+      };
+    }
+    CheckReporterKind::Sarif => diagnostics_to_sarif(&diagnostics),
+    CheckReporterKind::Checkstyle => diagnostics_to_checkstyle(&diagnostics),
+  };
+  #[allow(clippy::print_stdout)]
+  {
+    println!("{}", output);
+  }
+  if diagnostics.is_empty() {
+    Ok(())
+  } else {
+    std::process::exit(1);
+  }
+}
+
+fn diagnostic_message(diagnostic: &tsc::Diagnostic) -> String {
+  match &diagnostic.message_chain {
+    Some(chain) => chain.format_message(0),
+    None => diagnostic.message_text.clone().unwrap_or_default(),
+  }
+}
+
+/// The 0-indexed start and end of a diagnostic in the original source. For
+/// fast checked modules only the start can be mapped back.
+fn diagnostic_positions(
+  diagnostic: &tsc::Diagnostic,
+) -> Option<(&tsc::Position, &tsc::Position)> {
+  match (&diagnostic.original_source_start, &diagnostic.start) {
+    (Some(start), _) => Some((start, start)),
+    (None, Some(start)) => {
+      Some((start, diagnostic.end.as_ref().unwrap_or(start)))
+    }
+    (None, None) => None,
+  }
+}
+
+fn diagnostics_to_sarif(diagnostics: &Diagnostics) -> String {
+  let mut log = SarifLog::new("deno check");
+  for diagnostic in diagnostics.iter() {
+    log.add_result(SarifResult {
+      rule_id: format!("TS{}", diagnostic.code),
+      level: match diagnostic.category {
+        tsc::DiagnosticCategory::Error => SarifLevel::Error,
+        tsc::DiagnosticCategory::Warning => SarifLevel::Warning,
+        tsc::DiagnosticCategory::Suggestion
+        | tsc::DiagnosticCategory::Message => SarifLevel::Note,
+      },
+      message: diagnostic_message(diagnostic),
+      uri: diagnostic.file_name.clone(),
+      region: diagnostic_positions(diagnostic).map(|(start, end)| {
+        SarifRegion {
+          start_line: start.line as usize + 1,
+          start_column: start.character as usize + 1,
+          end_line: end.line as usize + 1,
+          end_column: end.character as usize + 1,
+        }
+      }),
+    });
+  }
+  log.to_json()
+}
+
+fn diagnostics_to_checkstyle(diagnostics: &Diagnostics) -> String {
+  let mut report = CheckstyleReport::default();
+  for diagnostic in diagnostics.iter() {
+    let file_name = match &diagnostic.file_name {
+      Some(file_name) => match ModuleSpecifier::parse(file_name)
+        .ok()
+        .and_then(|specifier| specifier.to_file_path().ok())
+      {
+        Some(path) => path.to_string_lossy().to_string(),
+        None => file_name.clone(),
+      },
+      None => "<unknown>".to_string(),
+    };
+    let start = diagnostic_positions(diagnostic).map(|(start, _)| start);
+    report.add_error(
+      file_name,
+      CheckstyleError {
+        line: start.map(|start| start.line as usize + 1),
+        column: start.map(|start| start.character as usize + 1),
+        severity: match diagnostic.category {
+          tsc::DiagnosticCategory::Error => CheckstyleSeverity::Error,
+          tsc::DiagnosticCategory::Warning => CheckstyleSeverity::Warning,
+          tsc::DiagnosticCategory::Suggestion
+          | tsc::DiagnosticCategory::Message => CheckstyleSeverity::Info,
+        },
+        message: diagnostic_message(diagnostic),
+        source: format!("TS{}", diagnostic.code),
+      },
+    );
+  }
+  report.to_xml()
+}
+
 enum CheckHashResult {
   Hash(CacheDBHash),
   NoFiles,
@@ -4,8 +4,15 @@ use crate::args::CompileFlags;
 use crate::args::Flags;
 use crate::factory::CliFactory;
 use crate::http_util::HttpClientProvider;
+use crate::standalone::binary::StandaloneBinary;
 use crate::standalone::is_standalone_binary;
+use crate::util::fs::specifier_from_file_path;
+use crate::util::path::is_script_ext;
 use deno_ast::ModuleSpecifier;
+use deno_config::glob::FileCollector;
+use deno_config::glob::FilePatterns;
+use deno_config::glob::PathOrPattern;
+use deno_config::glob::PathOrPatternSet;
 use deno_core::anyhow::bail;
 use deno_core::anyhow::Context;
 use deno_core::error::generic_error;
@@ -15,6 +22,7 @@ use deno_graph::GraphKind;
 use deno_terminal::colors;
 use eszip::EszipRelativeFileBaseUrl;
 use rand::Rng;
+use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -25,6 +33,10 @@ pub async fn compile(
   flags: Arc<Flags>,
   compile_flags: CompileFlags,
 ) -> Result<(), AnyError> {
+  if compile_flags.inspect_binary {
+    return inspect_binary(compile_flags).await;
+  }
+
   let factory = CliFactory::from_flags(flags);
   let cli_options = factory.cli_options()?;
   let module_graph_creator = factory.module_graph_creator().await?;
@@ -32,12 +44,12 @@ pub async fn compile(
   let binary_writer = factory.create_compile_binary_writer().await?;
   let http_client = factory.http_client_provider();
   let module_specifier = cli_options.resolve_main_module()?;
+  let (include_modules, include_files) =
+    resolve_includes(&compile_flags.include, cli_options.initial_cwd())?;
   let module_roots = {
-    let mut vec = Vec::with_capacity(compile_flags.include.len() + 1);
+    let mut vec = Vec::with_capacity(include_modules.len() + 1);
     vec.push(module_specifier.clone());
-    for side_module in &compile_flags.include {
-      vec.push(resolve_url_or_path(side_module, cli_options.initial_cwd())?);
-    }
+    vec.extend(include_modules);
     vec
   };
 
@@ -59,6 +71,16 @@ pub async fn compile(
     cli_options.initial_cwd(),
   )
   .await?;
+  // never embed the executable being written, which may be the target of a
+  // directory or glob include
+  let include_files = include_files
+    .into_iter()
+    .filter(|path| path != &output_path)
+    .collect::<Vec<_>>();
+  let include_file_specifiers = include_files
+    .iter()
+    .map(|path| specifier_from_file_path(path))
+    .collect::<Result<Vec<_>, _>>()?;
 
   let graph = Arc::try_unwrap(
     module_graph_creator
@@ -90,7 +112,8 @@ pub async fn compile(
       cli_options
         .node_modules_dir_path()
         .and_then(|p| ModuleSpecifier::from_directory_path(p).ok())
-        .iter(),
+        .iter()
+        .chain(include_file_specifiers.iter()),
     ),
   );
   log::debug!("Binary root dir: {}", root_dir_url);
@@ -123,6 +146,16 @@ pub async fn compile(
     .unwrap()
   ));
   let temp_path = output_path.with_file_name(temp_filename);
+  // don't repeat the extension of windows executables in the sbom file name
+  let binary_name =
+    if output_path.extension().is_some_and(|ext| ext == "exe") {
+      output_path.file_stem()
+    } else {
+      output_path.file_name()
+    }
+    .unwrap()
+    .to_string_lossy()
+    .to_string();
 
   let file = std::fs::File::create(&temp_path).with_context(|| {
     format!("Opening temporary file '{}'", temp_path.display())
@@ -134,6 +167,8 @@ pub async fn compile(
       eszip,
       root_dir_url,
       &module_specifier,
+      include_files,
+      &binary_name,
       &compile_flags,
       cli_options,
     )
@@ -144,7 +179,7 @@ pub async fn compile(
 
   // set it as executable
   #[cfg(unix)]
-  let write_result = write_result.and_then(|_| {
+  let write_result = write_result.and_then(|sbom| {
     use std::os::unix::fs::PermissionsExt;
     let perms = std::fs::Permissions::from_mode(0o755);
     std::fs::set_permissions(&temp_path, perms).with_context(|| {
@@ -152,28 +187,135 @@ pub async fn compile(
         "Setting permissions on temporary file '{}'",
         temp_path.display()
       )
-    })
+    })?;
+    Ok(sbom)
   });
 
-  let write_result = write_result.and_then(|_| {
+  let write_result = write_result.and_then(|sbom| {
     std::fs::rename(&temp_path, &output_path).with_context(|| {
       format!(
         "Renaming temporary file '{}' to '{}'",
         temp_path.display(),
         output_path.display()
       )
-    })
+    })?;
+    Ok(sbom)
   });
 
-  if let Err(err) = write_result {
-    // errored, so attempt to remove the temporary file
-    let _ = std::fs::remove_file(temp_path);
-    return Err(err);
+  let sbom = match write_result {
+    Ok(sbom) => sbom,
+    Err(err) => {
+      // errored, so attempt to remove the temporary file
+      let _ = std::fs::remove_file(temp_path);
+      return Err(err);
+    }
+  };
+
+  if let (Some(sbom), Some(format)) = (sbom, compile_flags.sbom) {
+    let sbom_path = output_path.with_file_name(format!(
+      "{}.{}",
+      binary_name,
+      format.file_extension()
+    ));
+    std::fs::write(&sbom_path, sbom).with_context(|| {
+      format!(
+        "Writing software bill of materials '{}'",
+        sbom_path.display()
+      )
+    })?;
+    log::info!(
+      "{} software bill of materials to {}",
+      colors::green("Wrote"),
+      sbom_path.display(),
+    );
   }
 
   Ok(())
 }
 
+/// Shows the contents of an existing executable for
+/// `deno compile --inspect-binary` and extracts them when `--output` is
+/// provided.
+async fn inspect_binary(compile_flags: CompileFlags) -> Result<(), AnyError> {
+  let cwd = std::env::current_dir().context("Unable to get CWD")?;
+  let exe_path = cwd.join(&compile_flags.source_file);
+  let binary = StandaloneBinary::read(&exe_path).await?;
+  if compile_flags.sbom.is_some() {
+    let Some(sbom) = &binary.sbom else {
+      bail!(
+        "'{}' has no software bill of materials. Compile it with `--sbom` to embed one.",
+        exe_path.display()
+      );
+    };
+    std::io::stdout().write_all(sbom)?;
+    return Ok(());
+  }
+  binary.log_metadata();
+  binary.log_contents();
+
+  if let Some(output) = &compile_flags.output {
+    let output_dir = cwd.join(output);
+    if output_dir.exists() && std::fs::read_dir(&output_dir)?.next().is_some() {
+      bail!(
+        "Could not extract to '{}' because the directory is not empty.",
+        output_dir.display()
+      );
+    }
+    binary.extract(&output_dir).await?;
+    log::info!(
+      "{} {} to {}",
+      colors::green("Extracted"),
+      exe_path.display(),
+      output_dir.display(),
+    );
+  }
+  Ok(())
+}
+
+/// Splits the values of `--include` into additional module graph roots and
+/// files to embed in the virtual file system. Remote modules and local script
+/// files become modules, while other files, directories and globs are
+/// expanded to the files that will be readable at runtime.
+fn resolve_includes(
+  include: &[String],
+  initial_cwd: &Path,
+) -> Result<(Vec<ModuleSpecifier>, Vec<PathBuf>), AnyError> {
+  let mut modules = Vec::new();
+  let mut file_patterns = Vec::new();
+  let path_or_patterns =
+    PathOrPatternSet::from_include_relative_path_or_patterns(
+      initial_cwd,
+      include,
+    )?
+    .into_path_or_patterns();
+  for path_or_pattern in path_or_patterns {
+    match path_or_pattern {
+      PathOrPattern::RemoteUrl(url) => modules.push(url),
+      PathOrPattern::Path(path) if !path.is_dir() && is_script_ext(&path) => {
+        modules.push(specifier_from_file_path(&path)?);
+      }
+      PathOrPattern::Path(path) if !path.exists() => {
+        bail!("Included path '{}' does not exist.", path.display());
+      }
+      path_or_pattern => file_patterns.push(path_or_pattern),
+    }
+  }
+  if file_patterns.is_empty() {
+    return Ok((modules, Vec::new()));
+  }
+  let files = FileCollector::new(|_| true)
+    .ignore_git_folder()
+    .collect_file_patterns(
+      &deno_config::fs::RealDenoConfigFs,
+      FilePatterns {
+        base: initial_cwd.to_path_buf(),
+        include: Some(PathOrPatternSet::new(file_patterns)),
+        exclude: Default::default(),
+      },
+    )?;
+  Ok((modules, files))
+}
+
 /// This function writes out a final binary to specified path. If output path
 /// is not already standalone binary it will return error instead.
 fn validate_output_path(output_path: &Path) -> Result<(), AnyError> {
@@ -362,6 +504,10 @@ mod test {
         target: Some("x86_64-unknown-linux-gnu".to_string()),
         no_terminal: false,
         include: vec![],
+        list_contents: false,
+        compress: false,
+        inspect_binary: false,
+        sbom: None,
       },
       &std::env::current_dir().unwrap(),
     )
@@ -386,6 +532,10 @@ mod test {
         target: Some("x86_64-pc-windows-msvc".to_string()),
         include: vec![],
         no_terminal: false,
+        list_contents: false,
+        compress: false,
+        inspect_binary: false,
+        sbom: None,
       },
       &std::env::current_dir().unwrap(),
     )
@@ -5,7 +5,9 @@ use crate::args::CoverageFlags;
 use crate::args::FileFlags;
 use crate::args::Flags;
 use crate::cdp;
+use crate::emit::Emitter;
 use crate::factory::CliFactory;
+use crate::file_fetcher::FileFetcher;
 use crate::npm::CliNpmResolver;
 use crate::tools::fmt::format_json;
 use crate::tools::test::is_supported_test_path;
@@ -473,6 +475,82 @@ fn filter_coverages(
     .collect::<Vec<cdp::ScriptCoverage>>()
 }
 
+/// Loads the source the runtime executed for a covered module and generates
+/// its coverage report. Returns the report along with the original source.
+fn generate_script_coverage_report(
+  script_coverage: &cdp::ScriptCoverage,
+  cli_options: &CliOptions,
+  file_fetcher: &FileFetcher,
+  emitter: &Emitter,
+  out_mode: &Option<PathBuf>,
+) -> Result<(CoverageReport, Arc<str>), AnyError> {
+  let module_specifier = deno_core::resolve_url_or_path(
+    &script_coverage.url,
+    cli_options.initial_cwd(),
+  )?;
+
+  let maybe_file = if module_specifier.scheme() == "file" {
+    file_fetcher.get_source(&module_specifier)
+  } else {
+    file_fetcher
+      .fetch_cached(&module_specifier, 10)
+      .with_context(|| {
+        format!("Failed to fetch \"{module_specifier}\" from cache.")
+      })?
+  };
+  let file = maybe_file.ok_or_else(|| {
+    anyhow!("Failed to fetch \"{}\" from cache.
+          Before generating coverage report, run `deno test --coverage` to ensure consistent state.",
+        module_specifier
+      )
+  })?.into_text_decoded()?;
+
+  let original_source = file.source.clone();
+  // Check if file was transpiled
+  let transpiled_code = match file.media_type {
+    MediaType::JavaScript
+    | MediaType::Unknown
+    | MediaType::Cjs
+    | MediaType::Mjs
+    | MediaType::Json => None,
+    MediaType::Dts | MediaType::Dmts | MediaType::Dcts => Some(Vec::new()),
+    MediaType::TypeScript
+    | MediaType::Jsx
+    | MediaType::Mts
+    | MediaType::Cts
+    | MediaType::Tsx => {
+      Some(match emitter.maybe_cached_emit(&file.specifier, &file.source) {
+        Some(code) => code,
+        None => {
+          return Err(anyhow!(
+            "Missing transpiled source code for: \"{}\".
+              Before generating coverage report, run `deno test --coverage` to ensure consistent state.",
+            file.specifier,
+          ))
+        }
+      })
+    }
+    MediaType::Wasm | MediaType::TsBuildInfo | MediaType::SourceMap => {
+      unreachable!()
+    }
+  };
+  let runtime_code: String = match transpiled_code {
+    Some(code) => String::from_utf8(code)
+      .with_context(|| format!("Failed decoding {}", file.specifier))?,
+    None => original_source.to_string(),
+  };
+
+  let source_map = source_map_from_code(runtime_code.as_bytes());
+  let coverage_report = generate_coverage_report(
+    script_coverage,
+    runtime_code.as_str().to_owned(),
+    &source_map,
+    out_mode,
+  );
+
+  Ok((coverage_report, original_source))
+}
+
 pub async fn cover_files(
   flags: Arc<Flags>,
   coverage_flags: CoverageFlags,
@@ -536,69 +614,13 @@ pub async fn cover_files(
   };
 
   for script_coverage in script_coverages {
-    let module_specifier = deno_core::resolve_url_or_path(
-      &script_coverage.url,
-      cli_options.initial_cwd(),
-    )?;
-
-    let maybe_file = if module_specifier.scheme() == "file" {
-      file_fetcher.get_source(&module_specifier)
-    } else {
-      file_fetcher
-        .fetch_cached(&module_specifier, 10)
-        .with_context(|| {
-          format!("Failed to fetch \"{module_specifier}\" from cache.")
-        })?
-    };
-    let file = maybe_file.ok_or_else(|| {
-      anyhow!("Failed to fetch \"{}\" from cache.
-          Before generating coverage report, run `deno test --coverage` to ensure consistent state.",
-          module_specifier
-        )
-    })?.into_text_decoded()?;
-
-    let original_source = file.source.clone();
-    // Check if file was transpiled
-    let transpiled_code = match file.media_type {
-      MediaType::JavaScript
-      | MediaType::Unknown
-      | MediaType::Cjs
-      | MediaType::Mjs
-      | MediaType::Json => None,
-      MediaType::Dts | MediaType::Dmts | MediaType::Dcts => Some(Vec::new()),
-      MediaType::TypeScript
-      | MediaType::Jsx
-      | MediaType::Mts
-      | MediaType::Cts
-      | MediaType::Tsx => {
-        Some(match emitter.maybe_cached_emit(&file.specifier, &file.source) {
-          Some(code) => code,
-          None => {
-            return Err(anyhow!(
-              "Missing transpiled source code for: \"{}\".
-              Before generating coverage report, run `deno test --coverage` to ensure consistent state.",
-              file.specifier,
-            ))
-          }
-        })
-      }
-      MediaType::Wasm | MediaType::TsBuildInfo | MediaType::SourceMap => {
-        unreachable!()
-      }
-    };
-    let runtime_code: String = match transpiled_code {
-      Some(code) => String::from_utf8(code)
-        .with_context(|| format!("Failed decoding {}", file.specifier))?,
-      None => original_source.to_string(),
-    };
-
-    let source_map = source_map_from_code(runtime_code.as_bytes());
-    let coverage_report = generate_coverage_report(
+    let (coverage_report, original_source) = generate_script_coverage_report(
       &script_coverage,
-      runtime_code.as_str().to_owned(),
-      &source_map,
+      cli_options,
+      file_fetcher,
+      emitter,
       &out_mode,
-    );
+    )?;
 
     if !coverage_report.found_lines.is_empty() {
       reporter.report(&coverage_report, &original_source)?;
@@ -609,3 +631,57 @@ pub async fn cover_files(
 
   Ok(())
 }
+
+/// Collects the per-line hit counts of the coverage profiles written to `dir`,
+/// keyed by the covered module. This is used by the language server to report
+/// the coverage of a test run back to the client.
+pub async fn collect_line_coverage(
+  factory: &CliFactory,
+  dir: &Path,
+) -> Result<Vec<(ModuleSpecifier, Vec<(usize, i64)>)>, AnyError> {
+  let cli_options = factory.cli_options()?;
+  let npm_resolver = factory.npm_resolver().await?;
+  let file_fetcher = factory.file_fetcher()?;
+  let emitter = factory.emitter()?;
+
+  let script_coverages = collect_coverages(
+    cli_options,
+    FileFlags {
+      include: vec![dir.to_string_lossy().to_string()],
+      ignore: vec![],
+    },
+    cli_options.initial_cwd(),
+  )?;
+  let script_coverages = filter_coverages(
+    script_coverages,
+    vec![],
+    vec![],
+    npm_resolver.as_ref(),
+  );
+  let proc_coverages: Vec<_> = script_coverages
+    .into_iter()
+    .map(|cov| ProcessCoverage { result: vec![cov] })
+    .collect();
+  let script_coverages = if let Some(c) = merge::merge_processes(proc_coverages)
+  {
+    c.result
+  } else {
+    vec![]
+  };
+
+  let mut line_coverage = Vec::with_capacity(script_coverages.len());
+  for script_coverage in script_coverages {
+    let (coverage_report, _) = generate_script_coverage_report(
+      &script_coverage,
+      cli_options,
+      file_fetcher,
+      emitter,
+      &None,
+    )?;
+    if !coverage_report.found_lines.is_empty() {
+      line_coverage.push((coverage_report.url, coverage_report.found_lines));
+    }
+  }
+
+  Ok(line_coverage)
+}
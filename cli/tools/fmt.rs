@@ -22,7 +22,14 @@ use crate::util::file_watcher;
 use crate::util::fs::canonicalize_path;
 use crate::util::path::get_extension;
 use async_trait::async_trait;
+use deno_ast::swc::ast;
+use deno_ast::swc::visit::Visit;
+use deno_ast::swc::visit::VisitWith;
+use deno_ast::MediaType;
+use deno_ast::ModuleSpecifier;
 use deno_ast::ParsedSource;
+use deno_ast::SourceRangedForSpanned;
+use deno_ast::SourceTextInfo;
 use deno_config::glob::FileCollector;
 use deno_config::glob::FilePatterns;
 use deno_core::anyhow::anyhow;
@@ -36,6 +43,7 @@ use deno_core::unsync::spawn_blocking;
 use log::debug;
 use log::info;
 use log::warn;
+use std::borrow::Cow;
 use std::fs;
 use std::io::stdin;
 use std::io::stdout;
@@ -305,7 +313,152 @@ pub fn format_json(
   dprint_plugin_json::format_text(file_path, file_text, &config)
 }
 
-/// Formats a single TS, TSX, JS, JSX, JSONC, JSON, MD, or IPYNB file.
+/// Formats CSS, SCSS, Sass and Less using <https://github.com/g-plane/malva>.
+pub fn format_css(
+  file_path: &Path,
+  file_text: &str,
+  fmt_options: &FmtOptionsConfig,
+) -> Result<Option<String>, AnyError> {
+  let formatted = malva::format_text(
+    file_text,
+    malva::detect_syntax(file_path).unwrap_or(malva::Syntax::Css),
+    &get_resolved_malva_config(fmt_options),
+  )
+  .map_err(AnyError::from)?;
+  Ok(if formatted == file_text {
+    None
+  } else {
+    Some(formatted)
+  })
+}
+
+/// Formats HTML and component files (Vue, Svelte and Astro) using
+/// <https://github.com/g-plane/markup_fmt>. Embedded `<script>` and `<style>`
+/// blocks are formatted with the TS, JSON and CSS formatters.
+pub fn format_html(
+  file_path: &Path,
+  file_text: &str,
+  fmt_options: &FmtOptionsConfig,
+) -> Result<Option<String>, AnyError> {
+  let formatted = markup_fmt::format_text(
+    file_text,
+    markup_fmt::detect_language(file_path)
+      .unwrap_or(markup_fmt::Language::Html),
+    &get_resolved_markup_fmt_config(fmt_options),
+    |text, hints| {
+      // It's important to tell the formatters the proper file extension, as
+      // it decides the syntax that is used for parsing.
+      let mut file_name =
+        file_path.file_name().unwrap_or_default().to_os_string();
+      file_name.push(".");
+      file_name.push(hints.ext);
+      let path = file_path.with_file_name(file_name);
+      match hints.ext {
+        "css" | "scss" | "sass" | "less" => {
+          let mut malva_config = get_resolved_malva_config(fmt_options);
+          malva_config.layout.print_width = hints.print_width;
+          if hints.attr {
+            // the `style` attribute value is already quoted
+            malva_config.language.quotes =
+              if let Some(true) = fmt_options.single_quote {
+                malva::config::Quotes::AlwaysDouble
+              } else {
+                malva::config::Quotes::AlwaysSingle
+              };
+          }
+          malva::format_text(
+            text,
+            malva::detect_syntax(&path).unwrap_or(malva::Syntax::Css),
+            &malva_config,
+          )
+          .map(Cow::from)
+          .map_err(AnyError::from)
+        }
+        "json" | "jsonc" => {
+          let mut json_config = get_resolved_json_config(fmt_options);
+          json_config.line_width = hints.print_width as u32;
+          dprint_plugin_json::format_text(&path, text, &json_config).map(
+            |formatted| match formatted {
+              Some(formatted) => Cow::from(formatted),
+              None => Cow::from(text),
+            },
+          )
+        }
+        _ => {
+          let mut typescript_config =
+            get_resolved_typescript_config(fmt_options);
+          typescript_config.line_width = hints.print_width as u32;
+          dprint_plugin_typescript::format_text(
+            &path,
+            text.to_string(),
+            &typescript_config,
+          )
+          .map(|formatted| match formatted {
+            Some(formatted) => Cow::from(formatted),
+            None => Cow::from(text),
+          })
+        }
+      }
+    },
+  )
+  .map_err(|error| match error {
+    markup_fmt::FormatError::Syntax(error) => AnyError::from(error),
+    markup_fmt::FormatError::External(errors) => AnyError::msg(
+      errors
+        .into_iter()
+        .map(|error| error.to_string())
+        .collect::<Vec<_>>()
+        .join("\n\n"),
+    ),
+  })?;
+  Ok(if formatted == file_text {
+    None
+  } else {
+    Some(formatted)
+  })
+}
+
+/// Formats SQL using <https://github.com/shssoichiro/sqlformat-rs>.
+fn format_sql(
+  file_text: &str,
+  fmt_options: &FmtOptionsConfig,
+) -> Result<Option<String>, AnyError> {
+  let options = sqlformat::FormatOptions {
+    indent: if fmt_options.use_tabs.unwrap_or(false) {
+      sqlformat::Indent::Tabs
+    } else {
+      sqlformat::Indent::Spaces(fmt_options.indent_width.unwrap_or(2))
+    },
+    ..Default::default()
+  };
+  let formatted =
+    sqlformat::format(file_text, &sqlformat::QueryParams::None, options);
+  Ok(if formatted == file_text {
+    None
+  } else {
+    Some(formatted)
+  })
+}
+
+/// Formats GraphQL using <https://github.com/g-plane/pretty_graphql>.
+fn format_graphql(
+  file_text: &str,
+  fmt_options: &FmtOptionsConfig,
+) -> Result<Option<String>, AnyError> {
+  let formatted = pretty_graphql::format_text(
+    file_text,
+    &get_resolved_graphql_config(fmt_options),
+  )
+  .map_err(AnyError::from)?;
+  Ok(if formatted == file_text {
+    None
+  } else {
+    Some(formatted)
+  })
+}
+
+/// Formats a single TS, TSX, JS, JSX, JSONC, JSON, MD, IPYNB, YAML, CSS, HTML
+/// or component file.
 pub fn format_file(
   file_path: &Path,
   file_text: &str,
@@ -319,6 +472,27 @@ pub fn format_file(
       format_markdown(file_text, fmt_options, unstable_options)
     }
     "json" | "jsonc" => format_json(file_path, file_text, fmt_options),
+    "css" | "scss" | "sass" | "less" => {
+      if unstable_options.css {
+        format_css(file_path, file_text, fmt_options)
+      } else {
+        Ok(None)
+      }
+    }
+    "html" => {
+      if unstable_options.html {
+        format_html(file_path, file_text, fmt_options)
+      } else {
+        Ok(None)
+      }
+    }
+    "svelte" | "vue" | "astro" => {
+      if unstable_options.component {
+        format_html(file_path, file_text, fmt_options)
+      } else {
+        Ok(None)
+      }
+    }
     "yml" | "yaml" => {
       if unstable_options.yaml {
         pretty_yaml::format_text(
@@ -339,11 +513,28 @@ pub fn format_file(
     ),
     _ => {
       let config = get_resolved_typescript_config(fmt_options);
-      dprint_plugin_typescript::format_text(
+      let formatted = dprint_plugin_typescript::format_text(
         file_path,
         file_text.to_string(),
         &config,
-      )
+      )?;
+      if !unstable_options.template_tags {
+        return Ok(formatted);
+      }
+      let specifier = ModuleSpecifier::from_file_path(file_path)
+        .unwrap_or_else(|_| {
+          ModuleSpecifier::parse("file:///deno_fmt_stdin.ts").unwrap()
+        });
+      let parsed_source = deno_ast::parse_program(deno_ast::ParseParams {
+        specifier,
+        text: formatted.as_deref().unwrap_or(file_text).into(),
+        media_type: MediaType::from_path(file_path),
+        capture_tokens: false,
+        scope_analysis: false,
+        maybe_syntax: None,
+      })?;
+      let embedded = format_embedded_templates(&parsed_source, fmt_options)?;
+      Ok(embedded.or(formatted))
     }
   }
 }
@@ -351,11 +542,244 @@ pub fn format_file(
 pub fn format_parsed_source(
   parsed_source: &ParsedSource,
   fmt_options: &FmtOptionsConfig,
+  unstable_options: &UnstableFmtOptions,
 ) -> Result<Option<String>, AnyError> {
-  dprint_plugin_typescript::format_parsed_source(
+  let formatted = dprint_plugin_typescript::format_parsed_source(
     parsed_source,
     &get_resolved_typescript_config(fmt_options),
-  )
+  )?;
+  if !unstable_options.template_tags {
+    return Ok(formatted);
+  }
+  let embedded = match &formatted {
+    Some(formatted) => {
+      let parsed_source = deno_ast::parse_program(deno_ast::ParseParams {
+        specifier: parsed_source.specifier().clone(),
+        text: formatted.as_str().into(),
+        media_type: parsed_source.media_type(),
+        capture_tokens: false,
+        scope_analysis: false,
+        maybe_syntax: None,
+      })?;
+      format_embedded_templates(&parsed_source, fmt_options)?
+    }
+    None => format_embedded_templates(parsed_source, fmt_options)?,
+  };
+  Ok(embedded.or(formatted))
+}
+
+/// Formats the contents of tagged template literals whose tag names their
+/// language, for example `` html`<p>${text}</p>` ``.
+///
+/// Interpolations are swapped for placeholders while formatting. Templates
+/// that can't be formatted safely, like ones with escapes or multi-line
+/// interpolations, are left untouched.
+fn format_embedded_templates(
+  parsed_source: &ParsedSource,
+  fmt_options: &FmtOptionsConfig,
+) -> Result<Option<String>, AnyError> {
+  let text_info = parsed_source.text_info_lazy();
+  let mut collector = EmbeddedTemplateCollector {
+    text_info,
+    templates: Vec::new(),
+  };
+  parsed_source.program().visit_with(&mut collector);
+  if collector.templates.is_empty() {
+    return Ok(None);
+  }
+
+  let text = text_info.text_str();
+  let indent_unit = if fmt_options.use_tabs.unwrap_or(false) {
+    "\t".to_string()
+  } else {
+    " ".repeat(fmt_options.indent_width.unwrap_or(2) as usize)
+  };
+  let mut result = String::with_capacity(text.len());
+  let mut last_end = 0;
+  for template in collector.templates {
+    let line_start = text[..template.start].rfind('\n').map_or(0, |i| i + 1);
+    let line = &text[line_start..];
+    let base_indent = &line[..line.len() - line.trim_start().len()];
+    let Some(formatted) = format_embedded_template(
+      &template,
+      &text[template.start..template.end],
+      fmt_options,
+      base_indent,
+      &indent_unit,
+    )?
+    else {
+      continue;
+    };
+    result.push_str(&text[last_end..template.start]);
+    result.push_str(&formatted);
+    last_end = template.end;
+  }
+  result.push_str(&text[last_end..]);
+
+  Ok(if result == text { None } else { Some(result) })
+}
+
+fn format_embedded_template(
+  template: &EmbeddedTemplate,
+  current_text: &str,
+  fmt_options: &FmtOptionsConfig,
+  base_indent: &str,
+  indent_unit: &str,
+) -> Result<Option<String>, AnyError> {
+  let placeholder_prefix = "deno_fmt_placeholder_";
+  if current_text.contains(placeholder_prefix) {
+    return Ok(None);
+  }
+  let placeholder = |index: usize| format!("{placeholder_prefix}{index}_");
+  let mut contents = String::new();
+  for (index, quasi) in template.quasis.iter().enumerate() {
+    contents.push_str(quasi);
+    if index < template.exprs.len() {
+      contents.push_str(&placeholder(index));
+    }
+  }
+
+  let line_width = fmt_options.line_width.unwrap_or(80) as usize;
+  let indent_width = base_indent.len() + indent_unit.len();
+  let mut embedded_options = fmt_options.clone();
+  embedded_options.line_width =
+    Some(line_width.saturating_sub(indent_width).max(40) as u32);
+  let result = match template.language {
+    EmbeddedFmtLanguage::Html => format_html(
+      Path::new("deno_fmt_embedded.html"),
+      &contents,
+      &embedded_options,
+    ),
+    EmbeddedFmtLanguage::Css => format_css(
+      Path::new("deno_fmt_embedded.css"),
+      &contents,
+      &embedded_options,
+    ),
+    EmbeddedFmtLanguage::Sql => format_sql(&contents, &embedded_options),
+    EmbeddedFmtLanguage::Graphql => {
+      format_graphql(&contents, &embedded_options)
+    }
+  };
+  let formatted = match result {
+    Ok(formatted) => formatted.unwrap_or(contents),
+    // the contents may not be valid on their own, for example a CSS
+    // declaration list or HTML with interpolated tag names
+    Err(err) => {
+      debug!("Failed formatting embedded template: {:#}", err);
+      return Ok(None);
+    }
+  };
+  // bail if the formatter introduced something that ends the template or
+  // changes its value, or lost track of an interpolation
+  if formatted.contains('`')
+    || formatted.contains("${")
+    || formatted.contains('\\')
+  {
+    return Ok(None);
+  }
+  let mut formatted = formatted.trim().to_string();
+  for (index, expr) in template.exprs.iter().enumerate() {
+    let placeholder = placeholder(index);
+    if formatted.matches(&placeholder).count() != 1 {
+      return Ok(None);
+    }
+    formatted = formatted.replace(&placeholder, &format!("${{{expr}}}"));
+  }
+
+  let was_multi_line = current_text.contains('\n');
+  Ok(Some(if !was_multi_line && !formatted.contains('\n') {
+    formatted
+  } else {
+    let mut text = String::from("\n");
+    for line in formatted.lines() {
+      if !line.trim().is_empty() {
+        text.push_str(base_indent);
+        text.push_str(indent_unit);
+        text.push_str(line);
+      }
+      text.push('\n');
+    }
+    text.push_str(base_indent);
+    text
+  }))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum EmbeddedFmtLanguage {
+  Html,
+  Css,
+  Sql,
+  Graphql,
+}
+
+impl EmbeddedFmtLanguage {
+  fn from_tag(tag: &str) -> Option<Self> {
+    match tag {
+      "html" => Some(Self::Html),
+      "css" => Some(Self::Css),
+      "sql" => Some(Self::Sql),
+      "gql" | "graphql" => Some(Self::Graphql),
+      _ => None,
+    }
+  }
+}
+
+struct EmbeddedTemplate {
+  language: EmbeddedFmtLanguage,
+  /// Byte range of the text between the backticks.
+  start: usize,
+  end: usize,
+  quasis: Vec<String>,
+  exprs: Vec<String>,
+}
+
+struct EmbeddedTemplateCollector<'a> {
+  text_info: &'a SourceTextInfo,
+  templates: Vec<EmbeddedTemplate>,
+}
+
+impl EmbeddedTemplateCollector<'_> {
+  fn collect(&self, node: &ast::TaggedTpl) -> Option<EmbeddedTemplate> {
+    let tag = self.text_info.range_text(&node.tag.range());
+    let language = EmbeddedFmtLanguage::from_tag(tag)?;
+    if node.type_params.is_some() {
+      return None;
+    }
+    let mut quasis = Vec::with_capacity(node.tpl.quasis.len());
+    for quasi in &node.tpl.quasis {
+      if quasi.raw.contains('\\') {
+        return None;
+      }
+      quasis.push(quasi.raw.to_string());
+    }
+    let mut exprs = Vec::with_capacity(node.tpl.exprs.len());
+    for expr in &node.tpl.exprs {
+      let expr = self.text_info.range_text(&expr.range());
+      if expr.contains('\n') {
+        return None;
+      }
+      exprs.push(expr.to_string());
+    }
+    let start_pos = self.text_info.range().start;
+    let range = node.tpl.range();
+    Some(EmbeddedTemplate {
+      language,
+      start: range.start.as_byte_index(start_pos) + 1,
+      end: range.end.as_byte_index(start_pos) - 1,
+      quasis,
+      exprs,
+    })
+  }
+}
+
+impl Visit for EmbeddedTemplateCollector<'_> {
+  fn visit_tagged_tpl(&mut self, node: &ast::TaggedTpl) {
+    match self.collect(node) {
+      Some(template) => self.templates.push(template),
+      // there might be templates to format in the interpolations
+      None => node.visit_children_with(self),
+    }
+  }
 }
 
 #[async_trait]
@@ -772,6 +1196,87 @@ fn get_resolved_yaml_config(
   }
 }
 
+fn get_resolved_malva_config(
+  options: &FmtOptionsConfig,
+) -> malva::config::FormatOptions {
+  use malva::config::*;
+
+  let layout_options = LayoutOptions {
+    print_width: options.line_width.unwrap_or(80) as usize,
+    use_tabs: options.use_tabs.unwrap_or_default(),
+    indent_width: options.indent_width.unwrap_or(2) as usize,
+    line_break: LineBreak::Lf,
+  };
+
+  let language_options = LanguageOptions {
+    hex_case: HexCase::Lower,
+    quotes: if let Some(true) = options.single_quote {
+      Quotes::PreferSingle
+    } else {
+      Quotes::PreferDouble
+    },
+    format_comments: false,
+    ignore_comment_directive: "deno-fmt-ignore".into(),
+    ..Default::default()
+  };
+
+  FormatOptions {
+    layout: layout_options,
+    language: language_options,
+  }
+}
+
+fn get_resolved_markup_fmt_config(
+  options: &FmtOptionsConfig,
+) -> markup_fmt::config::FormatOptions {
+  use markup_fmt::config::*;
+
+  let layout_options = LayoutOptions {
+    print_width: options.line_width.unwrap_or(80) as usize,
+    use_tabs: options.use_tabs.unwrap_or_default(),
+    indent_width: options.indent_width.unwrap_or(2) as usize,
+    line_break: LineBreak::Lf,
+  };
+
+  let language_options = LanguageOptions {
+    quotes: Quotes::Double,
+    format_comments: false,
+    script_indent: true,
+    style_indent: true,
+    whitespace_sensitivity: WhitespaceSensitivity::Css,
+    ignore_comment_directive: "deno-fmt-ignore".into(),
+    ..Default::default()
+  };
+
+  FormatOptions {
+    layout: layout_options,
+    language: language_options,
+  }
+}
+
+fn get_resolved_graphql_config(
+  options: &FmtOptionsConfig,
+) -> pretty_graphql::config::FormatOptions {
+  use pretty_graphql::config::*;
+
+  let layout_options = LayoutOptions {
+    print_width: options.line_width.unwrap_or(80) as usize,
+    use_tabs: options.use_tabs.unwrap_or_default(),
+    indent_width: options.indent_width.unwrap_or(2) as usize,
+    line_break: LineBreak::Lf,
+  };
+
+  let language_options = LanguageOptions {
+    ignore_comment_directive: "deno-fmt-ignore".into(),
+    ..Default::default()
+  };
+
+  FormatOptions {
+    layout: layout_options,
+    language: language_options,
+  }
+}
+
 struct FileContents {
   text: String,
   had_bom: bool,
@@ -870,6 +1375,14 @@ fn is_supported_ext_fmt(path: &Path) -> bool {
         | "mdwn"
         | "mdown"
         | "markdown"
+        | "css"
+        | "scss"
+        | "sass"
+        | "less"
+        | "html"
+        | "svelte"
+        | "vue"
+        | "astro"
         | "yml"
         | "yaml"
         | "ipynb"
@@ -911,6 +1424,14 @@ mod test {
     assert!(is_supported_ext_fmt(Path::new("foo.yaml")));
     assert!(is_supported_ext_fmt(Path::new("foo.YaML")));
     assert!(is_supported_ext_fmt(Path::new("foo.ipynb")));
+    assert!(is_supported_ext_fmt(Path::new("foo.css")));
+    assert!(is_supported_ext_fmt(Path::new("foo.SCSS")));
+    assert!(is_supported_ext_fmt(Path::new("foo.sass")));
+    assert!(is_supported_ext_fmt(Path::new("foo.less")));
+    assert!(is_supported_ext_fmt(Path::new("foo.html")));
+    assert!(is_supported_ext_fmt(Path::new("foo.svelte")));
+    assert!(is_supported_ext_fmt(Path::new("foo.vue")));
+    assert!(is_supported_ext_fmt(Path::new("foo.astro")));
   }
 
   #[test]
@@ -981,4 +1502,117 @@ mod test {
       "console.log(\"there's\");\nconsole.log('hi');\nconsole.log('bye');\n",
     );
   }
+
+  #[test]
+  fn test_format_embedded_templates() {
+    let unstable_options = UnstableFmtOptions {
+      template_tags: true,
+      ..Default::default()
+    };
+    let file_text = format_file(
+      &PathBuf::from("test.ts"),
+      concat!(
+        "function f() {\n",
+        "  return css`a{color:${color}}`;\n",
+        "}\n",
+        "const b = css`a{color:\\\"red\\\"}`;\n",
+      ),
+      &Default::default(),
+      &unstable_options,
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(
+      file_text,
+      concat!(
+        "function f() {\n",
+        "  return css`\n",
+        "    a {\n",
+        "      color: ${color};\n",
+        "    }\n",
+        "  `;\n",
+        "}\n",
+        // escapes are left alone
+        "const b = css`a{color:\\\"red\\\"}`;\n",
+      ),
+    );
+  }
+
+  fn format_templates(file_text: &str) -> String {
+    let unstable_options = UnstableFmtOptions {
+      template_tags: true,
+      ..Default::default()
+    };
+    format_file(
+      &PathBuf::from("test.ts"),
+      file_text,
+      &Default::default(),
+      &unstable_options,
+    )
+    .unwrap()
+    .unwrap()
+  }
+
+  #[test]
+  fn test_format_embedded_html_template() {
+    let file_text = format_templates(concat!(
+      "const card = html`<div   class=\"card\"><h1>${title}</h1>\n",
+      "<p>${body}</p></div>`;\n",
+    ));
+    assert_eq!(
+      file_text,
+      concat!(
+        "const card = html`\n",
+        "  <div class=\"card\">\n",
+        "    <h1>${title}</h1>\n",
+        "    <p>${body}</p>\n",
+        "  </div>\n",
+        "`;\n",
+      ),
+    );
+  }
+
+  #[test]
+  fn test_format_embedded_sql_template() {
+    let file_text = format_templates(
+      "const query = sql`select id, name from users where id = ${id}`;\n",
+    );
+    assert_eq!(
+      file_text,
+      concat!(
+        "const query = sql`\n",
+        "  select\n",
+        "    id,\n",
+        "    name\n",
+        "  from\n",
+        "    users\n",
+        "  where\n",
+        "    id = ${id}\n",
+        "`;\n",
+      ),
+    );
+  }
+
+  #[test]
+  fn test_format_embedded_graphql_template() {
+    let file_text = format_templates(concat!(
+      "function f() {\n",
+      "  return gql`query { user(id: ${id}) { name } }`;\n",
+      "}\n",
+    ));
+    assert_eq!(
+      file_text,
+      concat!(
+        "function f() {\n",
+        "  return gql`\n",
+        "    query {\n",
+        "      user(id: ${id}) {\n",
+        "        name\n",
+        "      }\n",
+        "    }\n",
+        "  `;\n",
+        "}\n",
+      ),
+    );
+  }
 }
@@ -0,0 +1,204 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! The templates that ship with `deno init --template`.
+
+pub struct BuiltinTemplate {
+  pub files: &'static [(&'static str, &'static str)],
+  /// Pairs of a comment and a command shown once the project is initialized.
+  pub next_steps: &'static [(&'static str, &'static str)],
+}
+
+pub const BUILTIN_TEMPLATE_NAMES: &[&str] = &["serve", "workspace", "cli"];
+
+pub fn builtin_template(name: &str) -> Option<&'static BuiltinTemplate> {
+  match name {
+    "serve" => Some(&SERVE),
+    "workspace" => Some(&WORKSPACE),
+    "cli" => Some(&CLI),
+    _ => None,
+  }
+}
+
+static SERVE: BuiltinTemplate = BuiltinTemplate {
+  files: &[
+    (
+      "main.ts",
+      r#"export function handler(req: Request): Response {
+  const url = new URL(req.url);
+  if (url.pathname === "/") {
+    return new Response("Hello from {{name}}!");
+  }
+  return new Response("Not Found", { status: 404 });
+}
+
+export default {
+  fetch: handler,
+} satisfies Deno.ServeDefaultExport;
+"#,
+    ),
+    (
+      "main_test.ts",
+      r#"import { assertEquals } from "@std/assert";
+import { handler } from "./main.ts";
+
+Deno.test(async function homeTest() {
+  const res = handler(new Request("http://localhost/"));
+  assertEquals(res.status, 200);
+  assertEquals(await res.text(), "Hello from {{name}}!");
+});
+
+Deno.test(async function notFoundTest() {
+  const res = handler(new Request("http://localhost/missing"));
+  assertEquals(res.status, 404);
+  await res.body?.cancel();
+});
+"#,
+    ),
+    (
+      "deno.json",
+      r#"{
+  "tasks": {
+    "dev": "deno serve --watch main.ts",
+    "start": "deno serve main.ts"
+  },
+  "imports": {
+    "@std/assert": "jsr:@std/assert@1"
+  }
+}
+"#,
+    ),
+  ],
+  next_steps: &[
+    (
+      "Start the server and watch for file changes",
+      "deno task dev",
+    ),
+    ("Run the tests", "deno test"),
+  ],
+};
+
+static WORKSPACE: BuiltinTemplate = BuiltinTemplate {
+  files: &[
+    (
+      "deno.json",
+      r#"{
+  "workspace": ["./packages/core", "./packages/app"],
+  "imports": {
+    "@std/assert": "jsr:@std/assert@1"
+  }
+}
+"#,
+    ),
+    (
+      "packages/core/deno.json",
+      r#"{
+  "name": "@{{name}}/core",
+  "version": "0.1.0",
+  "exports": "./mod.ts"
+}
+"#,
+    ),
+    (
+      "packages/core/mod.ts",
+      r#"export function add(a: number, b: number): number {
+  return a + b;
+}
+"#,
+    ),
+    (
+      "packages/core/mod_test.ts",
+      r#"import { assertEquals } from "@std/assert";
+import { add } from "./mod.ts";
+
+Deno.test(function addTest() {
+  assertEquals(add(2, 3), 5);
+});
+"#,
+    ),
+    (
+      "packages/app/deno.json",
+      r#"{
+  "name": "@{{name}}/app",
+  "version": "0.1.0",
+  "exports": "./main.ts",
+  "tasks": {
+    "dev": "deno run --watch main.ts"
+  }
+}
+"#,
+    ),
+    (
+      "packages/app/main.ts",
+      r#"import { add } from "@{{name}}/core";
+
+if (import.meta.main) {
+  console.log("Add 2 + 3 =", add(2, 3));
+}
+"#,
+    ),
+  ],
+  next_steps: &[
+    ("Run the app", "deno run packages/app/main.ts"),
+    ("Run the tests of every member", "deno test"),
+  ],
+};
+
+static CLI: BuiltinTemplate = BuiltinTemplate {
+  files: &[
+    (
+      "main.ts",
+      r#"import { parseArgs } from "@std/cli/parse-args";
+
+export function greet(name: string): string {
+  return `Hello, ${name}!`;
+}
+
+if (import.meta.main) {
+  const args = parseArgs(Deno.args, {
+    boolean: ["help"],
+    string: ["name"],
+    default: { name: "world" },
+    alias: { h: "help", n: "name" },
+  });
+  if (args.help) {
+    console.log("Usage: {{name}} [--name <name>]");
+    Deno.exit(0);
+  }
+  console.log(greet(args.name));
+}
+"#,
+    ),
+    (
+      "main_test.ts",
+      r#"import { assertEquals } from "@std/assert";
+import { greet } from "./main.ts";
+
+Deno.test(function greetTest() {
+  assertEquals(greet("Deno"), "Hello, Deno!");
+});
+"#,
+    ),
+    (
+      "deno.json",
+      r#"{
+  "tasks": {
+    "dev": "deno run --watch main.ts",
+    "compile": "deno compile --output {{name}} main.ts"
+  },
+  "imports": {
+    "@std/assert": "jsr:@std/assert@1",
+    "@std/cli": "jsr:@std/cli@1"
+  }
+}
+"#,
+    ),
+  ],
+  next_steps: &[
+    ("Run the program", "deno run main.ts --name Deno"),
+    ("Run the tests", "deno test"),
+    (
+      "Compile the program into an executable",
+      "deno task compile",
+    ),
+  ],
+};
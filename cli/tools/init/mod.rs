@@ -1,5 +1,6 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use crate::args::Flags;
 use crate::args::InitFlags;
 use crate::colors;
 use deno_core::anyhow::Context;
@@ -8,8 +9,18 @@ use deno_core::serde_json::json;
 use log::info;
 use std::io::Write;
 use std::path::Path;
+use std::sync::Arc;
 
-pub fn init_project(init_flags: InitFlags) -> Result<(), AnyError> {
+mod builtin;
+mod template;
+
+use template::load_template;
+use template::TemplateVars;
+
+pub async fn init_project(
+  flags: Arc<Flags>,
+  init_flags: InitFlags,
+) -> Result<(), AnyError> {
   let cwd =
     std::env::current_dir().context("Can't read current working directory.")?;
   let dir = if let Some(dir) = &init_flags.dir {
@@ -17,17 +28,28 @@ pub fn init_project(init_flags: InitFlags) -> Result<(), AnyError> {
     std::fs::create_dir_all(&dir)?;
     dir
   } else {
-    cwd
+    cwd.clone()
   };
 
-  if init_flags.lib {
-    // Extract the directory name to use as the project name
-    let project_name = dir
-      .file_name()
-      .unwrap_or_else(|| dir.as_os_str())
-      .to_str()
-      .unwrap();
+  // Extract the directory name to use as the project name
+  let project_name = dir
+    .file_name()
+    .unwrap_or_else(|| dir.as_os_str())
+    .to_string_lossy();
 
+  let mut template_next_steps = None;
+  if let Some(template) = &init_flags.template {
+    let template = load_template(flags, template, &cwd).await?;
+    let vars = TemplateVars::new(project_name.to_string());
+    for file in template.files {
+      let file = vars.apply(file);
+      if let Some(parent) = file.path.parent() {
+        std::fs::create_dir_all(dir.join(parent))?;
+      }
+      create_file(&dir, &file.path.to_string_lossy(), &file.contents)?;
+    }
+    template_next_steps = Some(template.next_steps);
+  } else if init_flags.lib {
     create_file(
       &dir,
       "mod.ts",
@@ -111,7 +133,15 @@ Deno.test(function addTest() {
     info!("  cd {}", dir);
     info!("");
   }
-  if init_flags.lib {
+  if let Some(next_steps) = template_next_steps {
+    for (i, (comment, command)) in next_steps.iter().enumerate() {
+      if i > 0 {
+        info!("");
+      }
+      info!("  {}", colors::gray(format!("# {comment}")));
+      info!("  {command}");
+    }
+  } else if init_flags.lib {
     info!("  {}", colors::gray("# Run the tests"));
     info!("  deno test");
     info!("");
@@ -152,7 +182,7 @@ fn create_json_file(
 fn create_file(
   dir: &Path,
   filename: &str,
-  content: &str,
+  content: impl AsRef<[u8]>,
 ) -> Result<(), AnyError> {
   let path = dir.join(filename);
   if path.exists() {
@@ -167,7 +197,7 @@ fn create_file(
       .create_new(true)
       .open(path)
       .with_context(|| format!("Failed to create {filename} file"))?;
-    file.write_all(content.as_bytes())?;
+    file.write_all(content.as_ref())?;
     Ok(())
   }
 }
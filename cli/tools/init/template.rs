@@ -0,0 +1,347 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::serde_json;
+use deno_core::url::Url;
+use deno_runtime::deno_permissions::PermissionsContainer;
+use deno_semver::jsr::JsrPackageReqReference;
+use flate2::read::GzDecoder;
+use serde::Deserialize;
+use tar::Archive;
+use tar::EntryType;
+
+use super::builtin::builtin_template;
+use super::builtin::BUILTIN_TEMPLATE_NAMES;
+use crate::args::jsr_url;
+use crate::args::Flags;
+use crate::factory::CliFactory;
+use crate::jsr::JsrFetchResolver;
+
+/// A file of a template with a path relative to the project directory.
+pub struct TemplateFile {
+  pub path: PathBuf,
+  pub contents: Vec<u8>,
+}
+
+pub struct Template {
+  pub files: Vec<TemplateFile>,
+  /// Pairs of a comment and a command shown once the project is initialized.
+  pub next_steps: &'static [(&'static str, &'static str)],
+}
+
+/// The values of the `{{name}}` and `{{author}}` placeholders of a template.
+pub struct TemplateVars {
+  pub name: String,
+  pub author: String,
+}
+
+impl TemplateVars {
+  pub fn new(name: String) -> Self {
+    let author = ["GIT_AUTHOR_NAME", "USER", "USERNAME"]
+      .into_iter()
+      .find_map(|var| std::env::var(var).ok().filter(|value| !value.is_empty()))
+      .unwrap_or_default();
+    Self { name, author }
+  }
+
+  pub fn substitute(&self, text: &str) -> String {
+    text
+      .replace("{{name}}", &self.name)
+      .replace("{{author}}", &self.author)
+  }
+
+  /// Substitutes the placeholders in the path and, unless it's a binary
+  /// file, the contents of a template file.
+  pub fn apply(&self, file: TemplateFile) -> TemplateFile {
+    let path = PathBuf::from(self.substitute(&file.path.to_string_lossy()));
+    let contents = match String::from_utf8(file.contents) {
+      Ok(text) => self.substitute(&text).into_bytes(),
+      Err(err) => err.into_bytes(),
+    };
+    TemplateFile { path, contents }
+  }
+}
+
+/// Loads a built-in template, a template published to JSR, a template
+/// directory or a gzipped tarball of a template.
+pub async fn load_template(
+  flags: Arc<Flags>,
+  template: &str,
+  cwd: &Path,
+) -> Result<Template, AnyError> {
+  if let Some(builtin) = builtin_template(template) {
+    return Ok(Template {
+      files: builtin
+        .files
+        .iter()
+        .map(|(path, contents)| TemplateFile {
+          path: PathBuf::from(path),
+          contents: contents.as_bytes().to_vec(),
+        })
+        .collect(),
+      next_steps: builtin.next_steps,
+    });
+  }
+
+  let files = if template.starts_with("jsr:") {
+    load_jsr_template(flags, template).await?
+  } else if template.starts_with("https://") || template.starts_with("http://")
+  {
+    load_tarball_template(flags, template).await?
+  } else {
+    let dir = cwd.join(template);
+    if !dir.is_dir() {
+      bail!(
+        "Unknown template '{}'. Use one of the built-in templates ({}), a jsr: package, a directory or a URL to a .tar.gz archive.",
+        template,
+        BUILTIN_TEMPLATE_NAMES.join(", ")
+      );
+    }
+    load_dir_template(&dir)?
+  };
+  if files.is_empty() {
+    bail!("Template '{}' does not contain any files.", template);
+  }
+  Ok(Template {
+    files,
+    next_steps: &[("List the tasks of the project", "deno task")],
+  })
+}
+
+#[derive(Deserialize)]
+struct JsrVersionManifest {
+  #[serde(default)]
+  manifest: BTreeMap<String, serde_json::Value>,
+}
+
+/// Fetches the files of a JSR package. When the specifier has a sub path, only
+/// the files of that directory are used, which allows a single package to
+/// hold several templates.
+async fn load_jsr_template(
+  flags: Arc<Flags>,
+  template: &str,
+) -> Result<Vec<TemplateFile>, AnyError> {
+  let req_ref = JsrPackageReqReference::from_str(template)?.into_inner();
+  let factory = CliFactory::from_flags(flags);
+  let file_fetcher = factory.file_fetcher()?.clone();
+  let jsr_resolver = JsrFetchResolver::new(file_fetcher.clone());
+  let Some(nv) = jsr_resolver.req_to_nv(&req_ref.req).await else {
+    bail!("Could not find a version of '{}' on JSR.", req_ref.req);
+  };
+
+  let meta_url =
+    jsr_url().join(&format!("{}/{}_meta.json", nv.name, nv.version))?;
+  let meta_file = file_fetcher
+    .fetch(&meta_url, &PermissionsContainer::allow_all())
+    .await?;
+  let meta: JsrVersionManifest = serde_json::from_slice(&meta_file.source)
+    .with_context(|| format!("Failed parsing {}", meta_url))?;
+
+  let package_url = jsr_url().join(&format!("{}/{}/", nv.name, nv.version))?;
+  let prefix = match &req_ref.sub_path {
+    Some(sub_path) => format!("/{}/", sub_path.trim_matches('/')),
+    None => "/".to_string(),
+  };
+  let mut files = Vec::new();
+  for path in meta.manifest.keys() {
+    let Some(relative_path) = path.strip_prefix(&prefix) else {
+      continue;
+    };
+    let url = package_url.join(path.trim_start_matches('/'))?;
+    let file = file_fetcher
+      .fetch(&url, &PermissionsContainer::allow_all())
+      .await?;
+    files.push(TemplateFile {
+      path: to_template_path(relative_path)?,
+      contents: file.source.to_vec(),
+    });
+  }
+  Ok(files)
+}
+
+async fn load_tarball_template(
+  flags: Arc<Flags>,
+  template: &str,
+) -> Result<Vec<TemplateFile>, AnyError> {
+  let url = Url::parse(template)?;
+  let factory = CliFactory::from_flags(flags);
+  let client = factory.http_client_provider().get_or_create()?;
+  let data = client
+    .download(url)
+    .await
+    .with_context(|| format!("Failed downloading template {}", template))?;
+  extract_tarball_template(&data)
+    .with_context(|| format!("Failed extracting template {}", template))
+}
+
+fn extract_tarball_template(
+  data: &[u8],
+) -> Result<Vec<TemplateFile>, AnyError> {
+  let mut archive = Archive::new(GzDecoder::new(data));
+  let mut files = Vec::new();
+  for entry in archive.entries()? {
+    let mut entry = entry?;
+    if entry.header().entry_type() != EntryType::Regular {
+      continue;
+    }
+    let path = to_template_path(&entry.path()?.to_string_lossy())?;
+    let mut contents = Vec::new();
+    entry.read_to_end(&mut contents)?;
+    files.push(TemplateFile { path, contents });
+  }
+  Ok(strip_common_root_dir(files))
+}
+
+/// Archives of repositories usually have all their files in a single
+/// directory (ex. `my-template-main/`), which shouldn't end up in the project.
+fn strip_common_root_dir(mut files: Vec<TemplateFile>) -> Vec<TemplateFile> {
+  let Some(Component::Normal(root)) =
+    files.first().and_then(|file| file.path.components().next())
+  else {
+    return files;
+  };
+  let root = PathBuf::from(root);
+  let is_common_root = files.iter().all(|file| {
+    file.path.starts_with(&root) && file.path.components().count() > 1
+  });
+  if is_common_root {
+    for file in &mut files {
+      file.path = file.path.strip_prefix(&root).unwrap().to_path_buf();
+    }
+  }
+  files
+}
+
+fn load_dir_template(dir: &Path) -> Result<Vec<TemplateFile>, AnyError> {
+  let mut files = Vec::new();
+  let mut pending_dirs = vec![PathBuf::new()];
+  while let Some(relative_dir) = pending_dirs.pop() {
+    let read_dir = std::fs::read_dir(dir.join(&relative_dir))
+      .with_context(|| format!("Failed reading {}", dir.display()))?;
+    for entry in read_dir {
+      let entry = entry?;
+      let file_type = entry.file_type()?;
+      let relative_path = relative_dir.join(entry.file_name());
+      if file_type.is_dir() {
+        if entry.file_name() != ".git" {
+          pending_dirs.push(relative_path);
+        }
+      } else if file_type.is_file() {
+        let contents = std::fs::read(entry.path()).with_context(|| {
+          format!("Failed reading {}", entry.path().display())
+        })?;
+        files.push(TemplateFile {
+          path: relative_path,
+          contents,
+        });
+      }
+    }
+  }
+  files.sort_by(|a, b| a.path.cmp(&b.path));
+  Ok(files)
+}
+
+/// Ensures a path from a remote template stays within the project directory.
+fn to_template_path(path: &str) -> Result<PathBuf, AnyError> {
+  let mut template_path = PathBuf::new();
+  for component in Path::new(path).components() {
+    match component {
+      Component::Normal(name) => template_path.push(name),
+      Component::CurDir => {}
+      _ => bail!("Template file '{}' is outside the template.", path),
+    }
+  }
+  Ok(template_path)
+}
+
+#[cfg(test)]
+mod test {
+  use flate2::write::GzEncoder;
+  use flate2::Compression;
+  use test_util::TempDir;
+
+  use super::*;
+
+  fn paths(files: &[TemplateFile]) -> Vec<String> {
+    files
+      .iter()
+      .map(|file| file.path.to_string_lossy().replace('\\', "/"))
+      .collect()
+  }
+
+  #[test]
+  fn substitutes_vars() {
+    let vars = TemplateVars {
+      name: "my_app".to_string(),
+      author: "Jane".to_string(),
+    };
+    let file = vars.apply(TemplateFile {
+      path: PathBuf::from("{{name}}.ts"),
+      contents: b"// {{name}} by {{author}}".to_vec(),
+    });
+    assert_eq!(file.path, PathBuf::from("my_app.ts"));
+    assert_eq!(file.contents, b"// my_app by Jane");
+
+    let binary = vec![0xff, 0xfe, b'{', b'{'];
+    let file = vars.apply(TemplateFile {
+      path: PathBuf::from("image.png"),
+      contents: binary.clone(),
+    });
+    assert_eq!(file.contents, binary);
+  }
+
+  #[test]
+  fn extracts_tarball() {
+    let mut builder =
+      tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+    for (path, contents) in [
+      ("template-main/deno.json", "{}"),
+      ("template-main/src/main.ts", "console.log('{{name}}');"),
+    ] {
+      let mut header = tar::Header::new_gnu();
+      header.set_size(contents.len() as u64);
+      header.set_mode(0o644);
+      header.set_cksum();
+      builder
+        .append_data(&mut header, path, contents.as_bytes())
+        .unwrap();
+    }
+    let data = builder.into_inner().unwrap().finish().unwrap();
+
+    let files = extract_tarball_template(&data).unwrap();
+    assert_eq!(paths(&files), vec!["deno.json", "src/main.ts"]);
+    assert_eq!(files[1].contents, b"console.log('{{name}}');");
+  }
+
+  #[test]
+  fn loads_dir() {
+    let temp_dir = TempDir::new();
+    temp_dir.write("deno.json", "{}");
+    temp_dir.create_dir_all("src");
+    temp_dir.write("src/main.ts", "");
+    temp_dir.create_dir_all(".git");
+    temp_dir.write(".git/HEAD", "");
+
+    let files = load_dir_template(temp_dir.path().as_path()).unwrap();
+    assert_eq!(paths(&files), vec!["deno.json", "src/main.ts"]);
+  }
+
+  #[test]
+  fn rejects_paths_outside_template() {
+    assert_eq!(
+      to_template_path("./src/main.ts").unwrap(),
+      PathBuf::from("src").join("main.ts")
+    );
+    assert!(to_template_path("../main.ts").is_err());
+    assert!(to_template_path("/etc/passwd").is_err());
+  }
+}
@@ -1,30 +1,42 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use crate::args::flags_from_vec;
 use crate::args::resolve_no_prompt;
 use crate::args::AddFlags;
 use crate::args::CaData;
 use crate::args::ConfigFlag;
+use crate::args::DenoSubcommand;
 use crate::args::Flags;
 use crate::args::InstallFlags;
 use crate::args::InstallFlagsGlobal;
+use crate::args::InstallFlagsGlobalList;
+use crate::args::InstallFlagsGlobalUpgrade;
 use crate::args::InstallKind;
 use crate::args::TypeCheckMode;
 use crate::args::UninstallFlags;
 use crate::args::UninstallKind;
 use crate::factory::CliFactory;
+use crate::graph_container::ModuleGraphContainer;
 use crate::http_util::HttpClientProvider;
 use crate::util::fs::canonicalize_path_maybe_not_exists;
 
+use deno_core::anyhow::bail;
 use deno_core::anyhow::Context;
 use deno_core::error::generic_error;
 use deno_core::error::AnyError;
 use deno_core::resolve_url_or_path;
+use deno_core::serde_json;
 use deno_core::url::Url;
+use deno_runtime::colors;
+use deno_semver::jsr::JsrPackageReqReference;
 use deno_semver::npm::NpmPackageReqReference;
 use log::Level;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use regex::RegexBuilder;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::BTreeMap;
 use std::env;
 use std::fs;
 use std::fs::File;
@@ -133,6 +145,73 @@ fn get_installer_root() -> Result<PathBuf, io::Error> {
   Ok(home_path)
 }
 
+fn resolve_installer_root(root: Option<&str>) -> Result<PathBuf, AnyError> {
+  match root {
+    Some(root) => {
+      let cwd = std::env::current_dir().context("Unable to get CWD")?;
+      Ok(canonicalize_path_maybe_not_exists(&cwd.join(root))?)
+    }
+    None => Ok(get_installer_root()?),
+  }
+}
+
+/// An executable installed with `deno install --global`, as recorded in the
+/// installation root's manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InstalledCommand {
+  /// The specifier the executable runs.
+  specifier: String,
+  /// The version a jsr or npm specifier resolved to when installed.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  version: Option<String>,
+  #[serde(default)]
+  permissions: Vec<String>,
+  /// Flags other than the permissions that are passed to `deno run`.
+  #[serde(default)]
+  flags: Vec<String>,
+  /// Arguments passed to the script.
+  #[serde(default)]
+  args: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct InstallManifest {
+  #[serde(default)]
+  installs: BTreeMap<String, InstalledCommand>,
+}
+
+impl InstallManifest {
+  fn path(root: &Path) -> PathBuf {
+    root.join("installs.json")
+  }
+
+  fn load(root: &Path) -> Result<Self, AnyError> {
+    let path = Self::path(root);
+    match fs::read_to_string(&path) {
+      Ok(text) => serde_json::from_str(&text)
+        .with_context(|| format!("Failed parsing {}", path.display())),
+      Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  fn save(&self, root: &Path) -> Result<(), AnyError> {
+    let path = Self::path(root);
+    if self.installs.is_empty() {
+      if path.exists() {
+        fs::remove_file(&path)?;
+      }
+      return Ok(());
+    }
+    let mut text = serde_json::to_string_pretty(self)?;
+    text.push('\n');
+    fs::write(&path, text)
+      .with_context(|| format!("Failed writing {}", path.display()))?;
+    Ok(())
+  }
+}
+
 pub async fn infer_name_from_url(
   http_client_provider: &HttpClientProvider,
   url: &Url,
@@ -205,12 +284,7 @@ pub fn uninstall(uninstall_flags: UninstallFlags) -> Result<(), AnyError> {
     UninstallKind::Local => unreachable!(),
   };
 
-  let cwd = std::env::current_dir().context("Unable to get CWD")?;
-  let root = if let Some(root) = uninstall_flags.root {
-    canonicalize_path_maybe_not_exists(&cwd.join(root))?
-  } else {
-    get_installer_root()?
-  };
+  let root = resolve_installer_root(uninstall_flags.root.as_deref())?;
   let installation_dir = root.join("bin");
 
   // ensure directory exists
@@ -257,6 +331,11 @@ pub fn uninstall(uninstall_flags: UninstallFlags) -> Result<(), AnyError> {
     }
   }
 
+  let mut manifest = InstallManifest::load(&root)?;
+  if manifest.installs.remove(&uninstall_flags.name).is_some() {
+    manifest.save(&root)?;
+  }
+
   log::info!("✅ Successfully uninstalled {}", uninstall_flags.name);
   Ok(())
 }
@@ -291,37 +370,225 @@ pub async fn install_command(
 
       install_global(flags, global_flags).await
     }
+    InstallKind::GlobalList(list_flags) => install_list(list_flags),
+    InstallKind::GlobalUpgrade(upgrade_flags) => {
+      install_upgrade(upgrade_flags).await
+    }
     InstallKind::Local(maybe_add_flags) => {
       install_local(flags, maybe_add_flags).await
     }
   }
 }
 
+#[allow(clippy::print_stdout)]
+fn install_list(list_flags: InstallFlagsGlobalList) -> Result<(), AnyError> {
+  let root = resolve_installer_root(list_flags.root.as_deref())?;
+  let manifest = InstallManifest::load(&root)?;
+  if manifest.installs.is_empty() {
+    log::info!("No executables installed in {}", root.display());
+    return Ok(());
+  }
+  for (name, installed) in &manifest.installs {
+    match &installed.version {
+      Some(version) => println!(
+        "{} {} {}",
+        colors::green(name),
+        installed.specifier,
+        colors::gray(format!("({version})"))
+      ),
+      None => println!("{} {}", colors::green(name), installed.specifier),
+    }
+    for (label, values) in [
+      ("permissions", &installed.permissions),
+      ("flags", &installed.flags),
+      ("args", &installed.args),
+    ] {
+      if !values.is_empty() {
+        println!("  {}: {}", colors::gray(label), values.join(" "));
+      }
+    }
+  }
+  Ok(())
+}
+
+async fn install_upgrade(
+  upgrade_flags: InstallFlagsGlobalUpgrade,
+) -> Result<(), AnyError> {
+  let root = resolve_installer_root(upgrade_flags.root.as_deref())?;
+  let manifest = InstallManifest::load(&root)?;
+  let installs = match &upgrade_flags.name {
+    Some(name) => match manifest.installs.get(name) {
+      Some(installed) => vec![(name.clone(), installed.clone())],
+      None => bail!("No installation found for {}", name),
+    },
+    None => manifest.installs.into_iter().collect(),
+  };
+  if installs.is_empty() {
+    log::info!("No executables installed in {}", root.display());
+    return Ok(());
+  }
+
+  let mut failed = 0;
+  for (name, installed) in installs {
+    log::info!("{} {}", colors::green("Upgrading"), name);
+    let result = upgrade_installed_command(&root, &name, &installed).await;
+    match result {
+      Ok(Some(new_version))
+        if installed.version.as_ref() != Some(&new_version) =>
+      {
+        log::info!(
+          "Upgraded {} from {} to {}",
+          name,
+          installed.version.as_deref().unwrap_or("unknown version"),
+          new_version
+        );
+      }
+      Ok(_) => log::info!("{} is up to date", name),
+      Err(err) => {
+        log::error!("Failed to upgrade {}: {:#}", name, err);
+        failed += 1;
+      }
+    }
+  }
+  if failed > 0 {
+    bail!("Failed to upgrade {} executable(s)", failed);
+  }
+  Ok(())
+}
+
+/// Reinstalls a previously installed executable with the same flags,
+/// allowing a jsr or npm specifier to resolve to the newest version that
+/// matches its version requirement. Returns the newly installed version.
+async fn upgrade_installed_command(
+  root: &Path,
+  name: &str,
+  installed: &InstalledCommand,
+) -> Result<Option<String>, AnyError> {
+  let specifier = upgrade_specifier(installed);
+  let mut args = vec![
+    "deno".to_string(),
+    "install".to_string(),
+    "--global".to_string(),
+    "--force".to_string(),
+    // ensure registry information and remote modules are fetched again
+    "--reload".to_string(),
+    "--name".to_string(),
+    name.to_string(),
+    "--root".to_string(),
+    root.to_string_lossy().to_string(),
+  ];
+  args.extend(installed.permissions.iter().cloned());
+  let mut run_flags = installed.flags.iter();
+  while let Some(flag) = run_flags.next() {
+    // drop the lockfile of the previous installation so that the newer
+    // versions aren't rejected by it
+    if flag == "--lock" {
+      run_flags.next();
+      continue;
+    }
+    args.push(flag.clone());
+  }
+  args.push(specifier);
+  args.extend(installed.args.iter().cloned());
+
+  let flags = flags_from_vec(args.into_iter().map(Into::into).collect())?;
+  let DenoSubcommand::Install(InstallFlags {
+    kind: InstallKind::Global(install_flags_global),
+    ..
+  }) = flags.subcommand.clone()
+  else {
+    unreachable!();
+  };
+  install_global(Arc::new(flags), install_flags_global).await?;
+
+  let manifest = InstallManifest::load(root)?;
+  Ok(manifest.installs.get(name).and_then(|i| i.version.clone()))
+}
+
+/// Keeps the version requirement of a jsr or npm specifier so that it's only
+/// resolved again. A specifier without a version is widened to anything semver
+/// compatible with the installed version.
+fn upgrade_specifier(installed: &InstalledCommand) -> String {
+  let Some(version) = &installed.version else {
+    return installed.specifier.clone();
+  };
+  let (prefix, req, sub_path) = if let Ok(req_ref) =
+    JsrPackageReqReference::from_str(&installed.specifier)
+  {
+    let req_ref = req_ref.into_inner();
+    ("jsr", req_ref.req, req_ref.sub_path)
+  } else if let Ok(req_ref) =
+    NpmPackageReqReference::from_str(&installed.specifier)
+  {
+    let req_ref = req_ref.into_inner();
+    ("npm", req_ref.req, req_ref.sub_path)
+  } else {
+    return installed.specifier.clone();
+  };
+  if req.version_req.version_text() != "*" {
+    return installed.specifier.clone();
+  }
+  let mut specifier = format!("{}:{}@^{}", prefix, req.name, version);
+  if let Some(sub_path) = sub_path {
+    specifier.push('/');
+    specifier.push_str(&sub_path);
+  }
+  specifier
+}
+
 async fn install_global(
   flags: Arc<Flags>,
   install_flags_global: InstallFlagsGlobal,
 ) -> Result<(), AnyError> {
   // ensure the module is cached
   let factory = CliFactory::from_flags(flags.clone());
-  factory
-    .main_module_graph_container()
-    .await?
+  let graph_container = factory.main_module_graph_container().await?;
+  graph_container
     .load_and_type_check_files(&[install_flags_global.module_url.clone()])
     .await?;
   let http_client = factory.http_client_provider();
 
+  let module_url = resolve_url_or_path(
+    &install_flags_global.module_url,
+    factory.cli_options()?.initial_cwd(),
+  )?;
+  let version =
+    if let Ok(jsr_ref) = JsrPackageReqReference::from_specifier(&module_url) {
+      let graph = graph_container.graph();
+      graph
+        .packages
+        .mappings()
+        .get(jsr_ref.req())
+        .map(|nv| nv.version.to_string())
+    } else if let Ok(npm_ref) =
+      NpmPackageReqReference::from_specifier(&module_url)
+    {
+      factory
+        .npm_resolver()
+        .await?
+        .as_managed()
+        .and_then(|npm_resolver| {
+          npm_resolver.resolve_pkg_id_from_pkg_req(npm_ref.req()).ok()
+        })
+        .map(|pkg_id| pkg_id.nv.version.to_string())
+    } else {
+      None
+    };
+
   // create the install shim
-  create_install_shim(http_client, &flags, install_flags_global).await
+  create_install_shim(http_client, &flags, install_flags_global, version).await
 }
 
 async fn create_install_shim(
   http_client_provider: &HttpClientProvider,
   flags: &Flags,
   install_flags_global: InstallFlagsGlobal,
+  version: Option<String>,
 ) -> Result<(), AnyError> {
-  let shim_data =
+  let mut shim_data =
     resolve_shim_data(http_client_provider, flags, &install_flags_global)
       .await?;
+  shim_data.installed.version = version;
 
   // ensure directory exists
   if let Ok(metadata) = fs::metadata(&shim_data.installation_dir) {
@@ -343,6 +610,12 @@ async fn create_install_shim(
     fs::write(path, contents)?;
   }
 
+  let mut manifest = InstallManifest::load(&shim_data.root)?;
+  manifest
+    .installs
+    .insert(shim_data.name.clone(), shim_data.installed);
+  manifest.save(&shim_data.root)?;
+
   log::info!("✅ Successfully installed {}", shim_data.name);
   log::info!("{}", shim_data.file_path.display());
   if cfg!(windows) {
@@ -365,10 +638,12 @@ async fn create_install_shim(
 
 struct ShimData {
   name: String,
+  root: PathBuf,
   installation_dir: PathBuf,
   file_path: PathBuf,
   args: Vec<String>,
   extra_files: Vec<(PathBuf, String)>,
+  installed: InstalledCommand,
 }
 
 async fn resolve_shim_data(
@@ -377,11 +652,7 @@ async fn resolve_shim_data(
   install_flags_global: &InstallFlagsGlobal,
 ) -> Result<ShimData, AnyError> {
   let cwd = std::env::current_dir().context("Unable to get CWD")?;
-  let root = if let Some(root) = &install_flags_global.root {
-    canonicalize_path_maybe_not_exists(&cwd.join(root))?
-  } else {
-    get_installer_root()?
-  };
+  let root = resolve_installer_root(install_flags_global.root.as_deref())?;
   let installation_dir = root.join("bin");
 
   // Check if module_url is remote
@@ -410,7 +681,8 @@ async fn resolve_shim_data(
   let mut extra_files: Vec<(PathBuf, String)> = vec![];
 
   let mut executable_args = vec!["run".to_string()];
-  executable_args.extend_from_slice(&flags.to_permission_args());
+  let permissions = flags.to_permission_args();
+  executable_args.extend_from_slice(&permissions);
   if let Some(url) = flags.location.as_ref() {
     executable_args.push("--location".to_string());
     executable_args.push(url.to_string());
@@ -534,15 +806,25 @@ async fn resolve_shim_data(
     }
   }
 
+  let installed = InstalledCommand {
+    specifier: module_url.to_string(),
+    version: None,
+    flags: executable_args[1 + permissions.len()..].to_vec(),
+    permissions,
+    args: install_flags_global.args.clone(),
+  };
+
   executable_args.push(module_url.to_string());
   executable_args.extend_from_slice(&install_flags_global.args);
 
   Ok(ShimData {
     name,
+    root,
     installation_dir,
     file_path,
     args: executable_args,
     extra_files,
+    installed,
   })
 }
 
@@ -793,6 +1075,7 @@ mod tests {
         root: Some(temp_dir.path().to_string()),
         force: false,
       },
+      None,
     )
     .await
     .unwrap();
@@ -1182,6 +1465,7 @@ mod tests {
         root: Some(temp_dir.path().to_string()),
         force: false,
       },
+      None,
     )
     .await
     .unwrap();
@@ -1212,6 +1496,7 @@ mod tests {
         root: Some(temp_dir.path().to_string()),
         force: false,
       },
+      None,
     )
     .await
     .unwrap();
@@ -1233,6 +1518,7 @@ mod tests {
         root: Some(temp_dir.path().to_string()),
         force: false,
       },
+      None,
     )
     .await;
     assert!(no_force_result.is_err());
@@ -1255,6 +1541,7 @@ mod tests {
         root: Some(temp_dir.path().to_string()),
         force: true,
       },
+      None,
     )
     .await;
     assert!(force_result.is_ok());
@@ -1286,6 +1573,7 @@ mod tests {
         root: Some(temp_dir.path().to_string()),
         force: true,
       },
+      None,
     )
     .await;
     assert!(result.is_ok());
@@ -1316,6 +1604,7 @@ mod tests {
         root: Some(temp_dir.path().to_string()),
         force: false,
       },
+      None,
     )
     .await
     .unwrap();
@@ -1357,6 +1646,7 @@ mod tests {
         root: Some(temp_dir.path().to_string()),
         force: false,
       },
+      None,
     )
     .await
     .unwrap();
@@ -1402,6 +1692,7 @@ mod tests {
         root: Some(temp_dir.path().to_string()),
         force: true,
       },
+      None,
     )
     .await;
     assert!(result.is_ok());
@@ -1445,6 +1736,7 @@ mod tests {
         root: Some(temp_dir.path().to_string()),
         force: true,
       },
+      None,
     )
     .await;
     assert!(result.is_ok());
@@ -1513,4 +1805,95 @@ mod tests {
       assert!(!file_path.exists());
     }
   }
+
+  #[tokio::test]
+  async fn install_manifest() {
+    let temp_dir = TempDir::new();
+    let root = temp_dir.path().to_path_buf();
+
+    create_install_shim(
+      &HttpClientProvider::new(None, None),
+      &Flags {
+        permissions: PermissionFlags {
+          allow_net: Some(vec![]),
+          ..Default::default()
+        },
+        ..Flags::default()
+      },
+      InstallFlagsGlobal {
+        module_url: "jsr:@std/http/file-server".to_string(),
+        args: vec!["--port".to_string(), "8080".to_string()],
+        name: Some("serve".to_string()),
+        root: Some(temp_dir.path().to_string()),
+        force: false,
+      },
+      Some("1.0.0".to_string()),
+    )
+    .await
+    .unwrap();
+
+    let manifest = InstallManifest::load(root.as_path()).unwrap();
+    assert_eq!(
+      manifest.installs.get("serve"),
+      Some(&InstalledCommand {
+        specifier: "jsr:@std/http/file-server".to_string(),
+        version: Some("1.0.0".to_string()),
+        permissions: vec!["--allow-net".to_string()],
+        flags: vec!["--no-config".to_string()],
+        args: vec!["--port".to_string(), "8080".to_string()],
+      })
+    );
+
+    uninstall(UninstallFlags {
+      kind: UninstallKind::Global(UninstallFlagsGlobal {
+        name: "serve".to_string(),
+        root: Some(temp_dir.path().to_string()),
+      }),
+      global: true,
+    })
+    .unwrap();
+
+    assert!(!InstallManifest::path(root.as_path()).exists());
+  }
+
+  #[test]
+  fn install_upgrade_specifier() {
+    let installed = |specifier: &str, version: Option<&str>| InstalledCommand {
+      specifier: specifier.to_string(),
+      version: version.map(|v| v.to_string()),
+      permissions: vec![],
+      flags: vec![],
+      args: vec![],
+    };
+    assert_eq!(
+      upgrade_specifier(&installed(
+        "jsr:@std/http@1.0.0/file-server",
+        Some("1.0.0")
+      )),
+      "jsr:@std/http@1.0.0/file-server"
+    );
+    assert_eq!(
+      upgrade_specifier(&installed("jsr:@std/http/file-server", Some("1.0.0"))),
+      "jsr:@std/http@^1.0.0/file-server"
+    );
+    assert_eq!(
+      upgrade_specifier(&installed("npm:cowsay", Some("1.5.0"))),
+      "npm:cowsay@^1.5.0"
+    );
+    assert_eq!(
+      upgrade_specifier(&installed("npm:@scope/pkg@~2.1/bin", Some("2.1.3"))),
+      "npm:@scope/pkg@~2.1/bin"
+    );
+    assert_eq!(
+      upgrade_specifier(&installed("jsr:@std/http/file-server", None)),
+      "jsr:@std/http/file-server"
+    );
+    assert_eq!(
+      upgrade_specifier(&installed(
+        "https://example.com/cli.ts",
+        Some("1.0.0")
+      )),
+      "https://example.com/cli.ts"
+    );
+  }
 }
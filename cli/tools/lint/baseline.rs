@@ -0,0 +1,322 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Baseline of known lint diagnostics. This allows enabling a rule in an
+//! existing codebase and only getting diagnostics for new code.
+
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+
+use deno_ast::ModuleSpecifier;
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::parking_lot::Mutex;
+use deno_core::serde_json;
+use deno_lint::diagnostic::LintDiagnostic;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::args::LintBaselineOptions;
+use crate::cache::FastInsecureHasher;
+use crate::colors;
+use crate::util::fs::atomic_write_file_with_retries;
+
+const BASELINE_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct BaselineFile {
+  version: u32,
+  /// Entries keyed by the path of the file relative to the baseline file.
+  files: BTreeMap<String, Vec<BaselineEntry>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BaselineEntry {
+  code: String,
+  fingerprint: String,
+  count: usize,
+}
+
+/// The rule code and fingerprint of a diagnostic.
+type EntryKey = (String, String);
+
+type EntryCounts = BTreeMap<String, BTreeMap<EntryKey, usize>>;
+
+#[derive(Default)]
+struct LintBaselineState {
+  /// Entries of the baseline file that didn't match a diagnostic yet.
+  unmatched: EntryCounts,
+  /// Files that were linted in this run.
+  linted_files: HashSet<String>,
+  /// Diagnostics recorded when writing the baseline.
+  recorded: EntryCounts,
+}
+
+impl LintBaselineState {
+  /// Matches a diagnostic against the baseline, returning `true` when it's a
+  /// known diagnostic.
+  fn take(&mut self, file: &str, key: &EntryKey) -> bool {
+    let Some(count) = self
+      .unmatched
+      .get_mut(file)
+      .and_then(|entries| entries.get_mut(key))
+    else {
+      return false;
+    };
+    if *count == 0 {
+      return false;
+    }
+    *count -= 1;
+    true
+  }
+
+  fn record(&mut self, file: String, key: EntryKey) {
+    *self
+      .recorded
+      .entry(file)
+      .or_default()
+      .entry(key)
+      .or_default() += 1;
+  }
+
+  /// Unmatched entries of the files that were linted, which means the code
+  /// they were recorded for was fixed.
+  fn stale_entries(&self) -> Vec<(&str, Vec<&str>)> {
+    let mut stale = Vec::new();
+    for (file, entries) in &self.unmatched {
+      if !self.linted_files.contains(file) {
+        continue;
+      }
+      let codes = entries
+        .iter()
+        .filter(|(_, count)| **count > 0)
+        .map(|((code, _), _)| code.as_str())
+        .collect::<Vec<_>>();
+      if !codes.is_empty() {
+        stale.push((file.as_str(), codes));
+      }
+    }
+    stale
+  }
+
+  /// The entries of the new baseline file. Entries of files that weren't
+  /// linted in this run are kept as is.
+  fn into_baseline_entries(self) -> EntryCounts {
+    let mut entries = self.unmatched;
+    entries.retain(|file, _| !self.linted_files.contains(file));
+    entries.extend(self.recorded);
+    entries
+  }
+}
+
+pub struct LintBaseline {
+  path: PathBuf,
+  base_dir: PathBuf,
+  write: bool,
+  state: Mutex<LintBaselineState>,
+}
+
+impl LintBaseline {
+  pub fn load(options: &LintBaselineOptions) -> Result<Self, AnyError> {
+    let base_dir = options
+      .path
+      .parent()
+      .map(ToOwned::to_owned)
+      .unwrap_or_default();
+    let mut state = LintBaselineState::default();
+    // when writing, the entries of files that aren't linted are kept
+    if !options.write || options.path.is_file() {
+      let text = std::fs::read_to_string(&options.path).with_context(|| {
+        format!("Failed reading lint baseline '{}'", options.path.display())
+      })?;
+      let file: BaselineFile =
+        serde_json::from_str(&text).with_context(|| {
+          format!("Failed parsing lint baseline '{}'", options.path.display())
+        })?;
+      if file.version != BASELINE_VERSION {
+        bail!(
+          "Unsupported lint baseline version {} in '{}'. Recreate it with --baseline-write.",
+          file.version,
+          options.path.display()
+        );
+      }
+      for (file_name, entries) in file.files {
+        let counts = state.unmatched.entry(file_name).or_default();
+        for entry in entries {
+          *counts.entry((entry.code, entry.fingerprint)).or_default() +=
+            entry.count;
+        }
+      }
+    }
+    Ok(Self {
+      path: options.path.clone(),
+      base_dir,
+      write: options.write,
+      state: Mutex::new(state),
+    })
+  }
+
+  /// Marks a file as linted, which includes files that were skipped because
+  /// the incremental cache knows they don't have any diagnostics.
+  pub fn mark_linted(&self, file_path: &Path) {
+    let file = self.file_key(file_path);
+    self.state.lock().linted_files.insert(file);
+  }
+
+  /// Removes the diagnostics that are in the baseline. When writing the
+  /// baseline all diagnostics are recorded and none are returned.
+  pub fn filter_diagnostics(
+    &self,
+    diagnostics: Vec<LintDiagnostic>,
+  ) -> Vec<LintDiagnostic> {
+    let mut state = self.state.lock();
+    diagnostics
+      .into_iter()
+      .filter(|diagnostic| {
+        let file = self.specifier_key(&diagnostic.specifier);
+        let key = (diagnostic.code().to_string(), fingerprint(diagnostic));
+        if self.write {
+          state.record(file, key);
+          false
+        } else {
+          !state.take(&file, &key)
+        }
+      })
+      .collect()
+  }
+
+  /// Writes the baseline file when recording, or otherwise warns about
+  /// entries that no longer match any diagnostic.
+  pub fn finish(&self) -> Result<(), AnyError> {
+    let mut state = self.state.lock();
+    if self.write {
+      let entries = std::mem::take(&mut *state).into_baseline_entries();
+      let mut entry_count = 0;
+      let file = BaselineFile {
+        version: BASELINE_VERSION,
+        files: entries
+          .into_iter()
+          .map(|(file_name, counts)| {
+            let entries = counts
+              .into_iter()
+              .map(|((code, fingerprint), count)| {
+                entry_count += count;
+                BaselineEntry {
+                  code,
+                  fingerprint,
+                  count,
+                }
+              })
+              .collect();
+            (file_name, entries)
+          })
+          .collect(),
+      };
+      let mut text = serde_json::to_string_pretty(&file)?;
+      text.push('\n');
+      atomic_write_file_with_retries(&self.path, text, 0o644).with_context(
+        || format!("Failed writing lint baseline '{}'", self.path.display()),
+      )?;
+      log::info!(
+        "Wrote {} {} to {}",
+        entry_count,
+        if entry_count == 1 {
+          "diagnostic"
+        } else {
+          "diagnostics"
+        },
+        self.path.display()
+      );
+    } else {
+      let stale = state.stale_entries();
+      if !stale.is_empty() {
+        log::warn!(
+          "{} The lint baseline has entries for diagnostics that were fixed:",
+          colors::yellow("Warning"),
+        );
+        for (file, codes) in stale {
+          log::warn!("  {} ({})", file, codes.join(", "));
+        }
+        log::warn!("Run `deno lint --baseline-write` to remove them.");
+      }
+    }
+    Ok(())
+  }
+
+  fn specifier_key(&self, specifier: &ModuleSpecifier) -> String {
+    match specifier.to_file_path() {
+      Ok(file_path) => self.file_key(&file_path),
+      Err(()) => specifier.to_string(),
+    }
+  }
+
+  fn file_key(&self, file_path: &Path) -> String {
+    let path = file_path.strip_prefix(&self.base_dir).unwrap_or(file_path);
+    path.to_string_lossy().replace('\\', "/")
+  }
+}
+
+/// Hashes the code a diagnostic points at rather than its position, so that
+/// entries keep matching when unrelated code above them changes. Whitespace
+/// is normalized to survive reformatting.
+fn fingerprint(diagnostic: &LintDiagnostic) -> String {
+  let text = match &diagnostic.range {
+    Some(range) => range.text_info.range_text(&range.range),
+    None => diagnostic.message(),
+  };
+  let mut hasher = FastInsecureHasher::new_without_deno_version();
+  for part in text.split_whitespace() {
+    hasher.write_str(part).write_u8(b' ');
+  }
+  format!("{:016x}", hasher.finish())
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn key(code: &str, fingerprint: &str) -> EntryKey {
+    (code.to_string(), fingerprint.to_string())
+  }
+
+  #[test]
+  fn matches_and_reports_stale_entries() {
+    let mut state = LintBaselineState::default();
+    state.unmatched.insert(
+      "a.ts".to_string(),
+      BTreeMap::from([(key("no-var", "1"), 2), (key("no-empty", "2"), 1)]),
+    );
+    state.unmatched.insert(
+      "b.ts".to_string(),
+      BTreeMap::from([(key("no-var", "3"), 1)]),
+    );
+    state.linted_files.insert("a.ts".to_string());
+
+    assert!(state.take("a.ts", &key("no-var", "1")));
+    assert!(state.take("a.ts", &key("no-var", "1")));
+    // a third occurrence is new
+    assert!(!state.take("a.ts", &key("no-var", "1")));
+    assert!(!state.take("a.ts", &key("no-var", "3")));
+
+    // b.ts wasn't linted, so its entry isn't stale
+    assert_eq!(state.stale_entries(), vec![("a.ts", vec!["no-empty"])]);
+
+    state.record("a.ts".to_string(), key("no-var", "1"));
+    let entries = state.into_baseline_entries();
+    assert_eq!(
+      entries,
+      BTreeMap::from([
+        (
+          "a.ts".to_string(),
+          BTreeMap::from([(key("no-var", "1"), 1)])
+        ),
+        (
+          "b.ts".to_string(),
+          BTreeMap::from([(key("no-var", "3"), 1)])
+        ),
+      ])
+    );
+  }
+}
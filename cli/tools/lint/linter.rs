@@ -1,6 +1,7 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
 use std::path::Path;
+use std::sync::Arc;
 
 use deno_ast::MediaType;
 use deno_ast::ModuleSpecifier;
@@ -18,6 +19,8 @@ use deno_lint::linter::LinterOptions;
 use crate::util::fs::atomic_write_file_with_retries;
 use crate::util::fs::specifier_from_file_path;
 
+use super::plugins::LintPluginRunner;
+use super::plugins::LintPluginsRule;
 use super::rules::FileOrPackageLintRule;
 use super::rules::PackageLintRule;
 use super::ConfiguredRules;
@@ -26,6 +29,7 @@ pub struct CliLinterOptions {
   pub configured_rules: ConfiguredRules,
   pub fix: bool,
   pub deno_lint_config: DenoLintConfig,
+  pub lint_plugins: Option<Arc<LintPluginRunner>>,
 }
 
 #[derive(Debug)]
@@ -51,12 +55,17 @@ impl CliLinter {
         }
       }
     }
+    let mut all_rule_codes = options.configured_rules.all_rule_codes;
+    if let Some(lint_plugins) = options.lint_plugins {
+      all_rule_codes.extend(lint_plugins.rule_codes());
+      deno_lint_rules.push(Box::new(LintPluginsRule::new(lint_plugins)));
+    }
     Self {
       fix: options.fix,
       package_rules,
       linter: DenoLintLinter::new(LinterOptions {
         rules: deno_lint_rules,
-        all_rule_codes: options.configured_rules.all_rule_codes,
+        all_rule_codes,
         custom_ignore_file_directive: None,
         custom_ignore_diagnostic_directive: None,
       }),
@@ -50,12 +50,19 @@ use crate::util::fs::canonicalize_path;
 use crate::util::path::is_script_ext;
 use crate::util::sync::AtomicFlag;
 
+use self::baseline::LintBaseline;
+
+mod baseline;
 mod linter;
+mod plugins;
 mod reporters;
 mod rules;
 
 pub use linter::CliLinter;
 pub use linter::CliLinterOptions;
+pub use plugins::resolve_lint_plugins;
+pub use plugins::resolve_lint_plugins_in_background;
+pub use plugins::LintPluginRunner;
 pub use rules::collect_no_slow_type_diagnostics;
 pub use rules::ConfiguredRules;
 pub use rules::LintRuleProvider;
@@ -110,7 +117,7 @@ pub async fn lint(
             factory.module_graph_creator().await?.clone(),
             cli_options.start_dir.clone(),
             &cli_options.resolve_workspace_lint_options(&lint_flags)?,
-          );
+          )?;
           for paths_with_options in paths_with_options_batches {
             linter
               .lint_files(
@@ -122,7 +129,7 @@ pub async fn lint(
               .await?;
           }
 
-          linter.finish();
+          linter.finish()?;
 
           Ok(())
         })
@@ -151,8 +158,11 @@ pub async fn lint(
           lint_options.rules,
           start_dir.maybe_deno_json().map(|c| c.as_ref()),
         )?;
+      let lint_plugins = resolve_lint_plugins(start_dir)?;
+      reporter_lock.lock().visit_rules(&lint_rules.rules);
       let file_path = cli_options.initial_cwd().join(STDIN_FILE_NAME);
-      let r = lint_stdin(&file_path, lint_rules, deno_lint_config);
+      let r =
+        lint_stdin(&file_path, lint_rules, deno_lint_config, lint_plugins);
       let success = handle_lint_result(
         &file_path.to_string_lossy(),
         r,
@@ -167,7 +177,7 @@ pub async fn lint(
         factory.module_graph_creator().await?.clone(),
         cli_options.start_dir.clone(),
         &workspace_lint_options,
-      );
+      )?;
       let paths_with_options_batches =
         resolve_paths_with_options_batches(cli_options, &lint_flags)?;
       for paths_with_options in paths_with_options_batches {
@@ -180,7 +190,7 @@ pub async fn lint(
           )
           .await?;
       }
-      linter.finish()
+      linter.finish()?
     };
     if !success {
       std::process::exit(1);
@@ -229,6 +239,7 @@ struct WorkspaceLinter {
   module_graph_creator: Arc<ModuleGraphCreator>,
   workspace_dir: Arc<WorkspaceDirectory>,
   reporter_lock: Arc<Mutex<Box<dyn LintReporter + Send>>>,
+  baseline: Option<Arc<LintBaseline>>,
   workspace_module_graph: Option<WorkspaceModuleGraphFuture>,
   has_error: Arc<AtomicFlag>,
   file_count: usize,
@@ -241,19 +252,24 @@ impl WorkspaceLinter {
     module_graph_creator: Arc<ModuleGraphCreator>,
     workspace_dir: Arc<WorkspaceDirectory>,
     workspace_options: &WorkspaceLintOptions,
-  ) -> Self {
+  ) -> Result<Self, AnyError> {
     let reporter_lock =
       Arc::new(Mutex::new(create_reporter(workspace_options.reporter_kind)));
-    Self {
+    let baseline = match &workspace_options.baseline {
+      Some(options) => Some(Arc::new(LintBaseline::load(options)?)),
+      None => None,
+    };
+    Ok(Self {
       caches,
       lint_rule_provider,
       module_graph_creator,
       workspace_dir,
       reporter_lock,
+      baseline,
       workspace_module_graph: None,
       has_error: Default::default(),
       file_count: 0,
-    }
+    })
   }
 
   pub async fn lint_files(
@@ -269,19 +285,25 @@ impl WorkspaceLinter {
       lint_options.rules,
       member_dir.maybe_deno_json().map(|c| c.as_ref()),
     )?;
-    let maybe_incremental_cache =
-      lint_rules.incremental_cache_state().map(|state| {
+    let lint_plugins = resolve_lint_plugins(&member_dir)?;
+    // the incremental cache can't tell when a plugin changed
+    let maybe_incremental_cache = lint_rules
+      .incremental_cache_state()
+      .filter(|_| lint_plugins.is_none())
+      .map(|state| {
         Arc::new(IncrementalCache::new(
           self.caches.lint_incremental_cache_db(),
           &state,
           &paths,
         ))
       });
+    self.reporter_lock.lock().visit_rules(&lint_rules.rules);
 
     let linter = Arc::new(CliLinter::new(CliLinterOptions {
       configured_rules: lint_rules,
       fix: lint_options.fix,
       deno_lint_config: lint_config,
+      lint_plugins,
     }));
 
     let mut futures = Vec::with_capacity(2);
@@ -307,6 +329,7 @@ impl WorkspaceLinter {
       if let Some(publish_config) = publish_config {
         let has_error = self.has_error.clone();
         let reporter_lock = self.reporter_lock.clone();
+        let baseline = self.baseline.clone();
         let linter = linter.clone();
         let path_urls = paths
           .iter()
@@ -322,7 +345,10 @@ impl WorkspaceLinter {
             if !export_urls.iter().any(|url| path_urls.contains(url)) {
               return Ok(()); // entrypoint is not specified, so skip
             }
-            let diagnostics = linter.lint_package(&graph, &export_urls);
+            let mut diagnostics = linter.lint_package(&graph, &export_urls);
+            if let Some(baseline) = &baseline {
+              diagnostics = baseline.filter_diagnostics(diagnostics);
+            }
             if !diagnostics.is_empty() {
               has_error.raise();
               let mut reporter = reporter_lock.lock();
@@ -341,6 +367,7 @@ impl WorkspaceLinter {
       let has_error = self.has_error.clone();
       let reporter_lock = self.reporter_lock.clone();
       let maybe_incremental_cache = maybe_incremental_cache.clone();
+      let baseline = self.baseline.clone();
       let linter = linter.clone();
       async move {
         run_parallelized(paths, {
@@ -351,12 +378,16 @@ impl WorkspaceLinter {
             // don't bother rechecking this file if it didn't have any diagnostics before
             if let Some(incremental_cache) = &maybe_incremental_cache {
               if incremental_cache.is_file_same(&file_path, &file_text) {
+                // any baseline entries for this file are stale
+                if let Some(baseline) = &baseline {
+                  baseline.mark_linted(&file_path);
+                }
                 return Ok(());
               }
             }
 
-            let r = linter.lint_file(&file_path, file_text);
-            if let Ok((file_source, file_diagnostics)) = &r {
+            let mut r = linter.lint_file(&file_path, file_text);
+            if let Ok((file_source, file_diagnostics)) = &mut r {
               if let Some(incremental_cache) = &maybe_incremental_cache {
                 if file_diagnostics.is_empty() {
                   // update the incremental cache if there were no diagnostics
@@ -367,6 +398,13 @@ impl WorkspaceLinter {
                   )
                 }
               }
+              // only filter after updating the incremental cache, so that
+              // files with known diagnostics keep being linted
+              if let Some(baseline) = &baseline {
+                baseline.mark_linted(&file_path);
+                *file_diagnostics =
+                  baseline.filter_diagnostics(std::mem::take(file_diagnostics));
+              }
             }
 
             let success = handle_lint_result(
@@ -404,10 +442,13 @@ impl WorkspaceLinter {
     Ok(())
   }
 
-  pub fn finish(self) -> bool {
+  pub fn finish(self) -> Result<bool, AnyError> {
     debug!("Found {} files", self.file_count);
     self.reporter_lock.lock().close(self.file_count);
-    !self.has_error.is_raised() // success
+    if let Some(baseline) = &self.baseline {
+      baseline.finish()?;
+    }
+    Ok(!self.has_error.is_raised()) // success
   }
 }
 
@@ -476,6 +517,7 @@ fn lint_stdin(
   file_path: &Path,
   configured_rules: ConfiguredRules,
   deno_lint_config: LintConfig,
+  lint_plugins: Option<Arc<LintPluginRunner>>,
 ) -> Result<(ParsedSource, Vec<LintDiagnostic>), AnyError> {
   let mut source_code = String::new();
   if stdin().read_to_string(&mut source_code).is_err() {
@@ -486,6 +528,7 @@ fn lint_stdin(
     fix: false,
     configured_rules,
     deno_lint_config,
+    lint_plugins,
   });
 
   linter
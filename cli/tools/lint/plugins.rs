@@ -0,0 +1,654 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Lint rules written in JavaScript or TypeScript.
+//!
+//! Plugins are local modules listed under `"lintPlugins"` in a config file.
+//! They are loaded into a bare isolate that lives on its own thread, get the
+//! AST of every linted file and report diagnostics back. All plugin rules run
+//! as a single deno_lint rule, so ignore directives, `--fix` and the reporters
+//! treat them the same as the built-in rules.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::rc::Rc;
+use std::sync::mpsc;
+use std::sync::mpsc::TryRecvError;
+use std::sync::Arc;
+
+use deno_ast::MediaType;
+use deno_ast::ModuleSpecifier;
+use deno_ast::ParseParams;
+use deno_ast::ParsedSource;
+use deno_ast::SourceRange;
+use deno_ast::SourceTextInfo;
+use deno_config::deno_json::ConfigFile;
+use deno_config::workspace::WorkspaceDirectory;
+use deno_core::anyhow::anyhow;
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::op2;
+use deno_core::parking_lot::Mutex;
+use deno_core::resolve_import;
+use deno_core::serde_json;
+use deno_core::serde_v8;
+use deno_core::v8;
+use deno_core::JsRuntime;
+use deno_core::ModuleLoadResponse;
+use deno_core::ModuleLoader;
+use deno_core::ModuleSource;
+use deno_core::ModuleSourceCode;
+use deno_core::ModuleType;
+use deno_core::OpState;
+use deno_core::RequestedModuleType;
+use deno_core::ResolutionKind;
+use deno_core::RuntimeOptions;
+use deno_lint::diagnostic::LintDiagnosticDetails;
+use deno_lint::diagnostic::LintDiagnosticRange;
+use deno_lint::diagnostic::LintFix;
+use deno_lint::diagnostic::LintFixChange;
+use deno_lint::rules::LintRule;
+use deno_runtime::tokio_util::create_basic_runtime;
+use once_cell::sync::Lazy;
+use once_cell::sync::OnceCell;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Top level config file key that lists the plugin modules.
+const CONFIG_KEY: &str = "lintPlugins";
+/// Code of the rule that runs the plugins. Used for reporting plugin failures.
+const CODE: &str = "lint-plugins";
+
+const MAIN_SPECIFIER: &str = "lint-plugin:main.js";
+const HOST_SPECIFIER: &str = "lint-plugin:host.js";
+
+/// Runners by the plugins they run. The LSP resolves the plugins again every
+/// time the config changes, which shouldn't load them again.
+static RUNNERS: Lazy<
+  Mutex<HashMap<Vec<ModuleSpecifier>, Arc<LintPluginRunner>>>,
+> = Lazy::new(Default::default);
+
+/// deno_lint only deals in static rule codes, so every distinct code of a
+/// plugin rule is leaked once.
+static RULE_CODES: Lazy<Mutex<HashSet<&'static str>>> =
+  Lazy::new(Default::default);
+
+fn intern_rule_code(code: String) -> &'static str {
+  let mut rule_codes = RULE_CODES.lock();
+  if let Some(code) = rule_codes.get(code.as_str()) {
+    return code;
+  }
+  let code: &'static str = Box::leak(code.into_boxed_str());
+  rule_codes.insert(code);
+  code
+}
+
+/// Loads the lint plugins configured for a workspace member, falling back to
+/// the ones of the workspace root.
+pub fn resolve_lint_plugins(
+  member_dir: &WorkspaceDirectory,
+) -> Result<Option<Arc<LintPluginRunner>>, AnyError> {
+  let Some((config_file, runner)) = start_lint_plugins(member_dir)? else {
+    return Ok(None);
+  };
+  runner.wait_until_loaded().with_context(|| {
+    format!("Failed loading lint plugins of {}", config_file.specifier)
+  })?;
+  Ok(Some(runner))
+}
+
+/// Like `resolve_lint_plugins`, but doesn't wait for the plugins to be
+/// loaded. Files linted in the meantime are queued until they are, and the
+/// codes of their rules are only known from then on.
+pub fn resolve_lint_plugins_in_background(
+  member_dir: &WorkspaceDirectory,
+) -> Result<Option<Arc<LintPluginRunner>>, AnyError> {
+  Ok(start_lint_plugins(member_dir)?.map(|(_, runner)| runner))
+}
+
+fn start_lint_plugins(
+  member_dir: &WorkspaceDirectory,
+) -> Result<Option<(&Arc<ConfigFile>, Arc<LintPluginRunner>)>, AnyError> {
+  let config_files = [
+    member_dir.maybe_deno_json(),
+    member_dir.workspace.root_deno_json(),
+  ];
+  for config_file in config_files.into_iter().flatten() {
+    let Some(specifiers) = plugin_specifiers(config_file)? else {
+      continue;
+    };
+    if specifiers.is_empty() {
+      return Ok(None);
+    }
+    let mut runners = RUNNERS.lock();
+    if let Some(runner) = runners.get(&specifiers) {
+      // retry plugins that failed to load, they might have been fixed since
+      if !runner.failed_loading() {
+        return Ok(Some((config_file, runner.clone())));
+      }
+    }
+    let runner = Arc::new(LintPluginRunner::start(specifiers.clone())?);
+    runners.insert(specifiers, runner.clone());
+    return Ok(Some((config_file, runner)));
+  }
+  Ok(None)
+}
+
+/// deno_config doesn't know about lint plugins, so the key is read from the
+/// config file directly. Returns `None` when the key isn't set.
+fn plugin_specifiers(
+  config_file: &ConfigFile,
+) -> Result<Option<Vec<ModuleSpecifier>>, AnyError> {
+  let Ok(path) = config_file.specifier.to_file_path() else {
+    return Ok(None);
+  };
+  let text = std::fs::read_to_string(&path)
+    .with_context(|| format!("Failed reading {}", path.display()))?;
+  parse_plugin_specifiers(&config_file.specifier, &text)
+}
+
+fn parse_plugin_specifiers(
+  config_specifier: &ModuleSpecifier,
+  text: &str,
+) -> Result<Option<Vec<ModuleSpecifier>>, AnyError> {
+  let value = jsonc_parser::parse_to_serde_value(text, &Default::default())?;
+  let Some(plugins) = value.as_ref().and_then(|value| value.get(CONFIG_KEY))
+  else {
+    return Ok(None);
+  };
+  let plugins = serde_json::from_value::<Vec<String>>(plugins.clone())
+    .map_err(|_| anyhow!("\"{CONFIG_KEY}\" must be an array of strings"))?;
+  plugins
+    .iter()
+    .map(|plugin| {
+      let specifier = resolve_import(plugin, config_specifier.as_str())?;
+      if specifier.scheme() != "file" {
+        bail!("Lint plugin \"{plugin}\" must be a local module");
+      }
+      Ok(specifier)
+    })
+    .collect::<Result<Vec<_>, _>>()
+    .map(Some)
+}
+
+/// What the plugins get to see of a file.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PluginFile {
+  specifier: String,
+  source: String,
+  /// The swc AST, serialized as JSON.
+  ast: String,
+  /// Byte position of the start of the file. Spans in the AST count from it.
+  start: u32,
+}
+
+/// A diagnostic reported by a plugin rule. Ranges are byte offsets into the
+/// file.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PluginDiagnostic {
+  code: String,
+  message: String,
+  hint: Option<String>,
+  docs_url: String,
+  start: usize,
+  end: usize,
+  fix: Option<PluginFix>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PluginFix {
+  description: String,
+  changes: Vec<PluginFixChange>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PluginFixChange {
+  start: usize,
+  end: usize,
+  text: String,
+}
+
+struct LintRequest {
+  file: PluginFile,
+  reply: mpsc::Sender<Result<Vec<PluginDiagnostic>, AnyError>>,
+}
+
+type LoadResult = Result<Vec<&'static str>, AnyError>;
+
+/// Handle to the thread running the plugins. Linting is done from many
+/// threads at once, while an isolate is bound to one, so files are sent over
+/// and linted one at a time.
+#[derive(Debug)]
+pub struct LintPluginRunner {
+  sender: mpsc::Sender<LintRequest>,
+  loaded_receiver: Mutex<mpsc::Receiver<LoadResult>>,
+  /// The rule codes of the plugins, or why they failed to load.
+  loaded: OnceCell<Result<Vec<&'static str>, String>>,
+}
+
+impl LintPluginRunner {
+  fn start(specifiers: Vec<ModuleSpecifier>) -> Result<Self, AnyError> {
+    let (sender, receiver) = mpsc::channel::<LintRequest>();
+    let (loaded_sender, loaded_receiver) = mpsc::channel();
+    std::thread::Builder::new()
+      .name("lint-plugins".to_string())
+      .spawn(move || {
+        let tokio_runtime = create_basic_runtime();
+        let mut runtime = JsRuntime::new(RuntimeOptions {
+          module_loader: Some(Rc::new(PluginModuleLoader)),
+          extensions: vec![deno_lint_plugins::init_ops()],
+          ..Default::default()
+        });
+        let result: LoadResult = tokio_runtime
+          .block_on(load_plugins(&mut runtime, &specifiers))
+          .map(|codes| codes.into_iter().map(intern_rule_code).collect());
+        let load_error = result.as_ref().err().map(|err| format!("{err:#}"));
+        let _ = loaded_sender.send(result);
+        for request in receiver {
+          let result = match &load_error {
+            Some(err) => Err(anyhow!("{err}")),
+            None => {
+              runtime.op_state().borrow_mut().put(request.file);
+              call_host(
+                &mut runtime,
+                "globalThis[Symbol.for(\"Deno.lint.runPlugins\")]()",
+              )
+            }
+          };
+          let _ = request.reply.send(result);
+        }
+      })?;
+    Ok(Self {
+      sender,
+      loaded_receiver: Mutex::new(loaded_receiver),
+      loaded: OnceCell::new(),
+    })
+  }
+
+  /// Returns `None` if the plugins are still loading and `wait` is false.
+  fn load_result(
+    &self,
+    wait: bool,
+  ) -> Option<&Result<Vec<&'static str>, String>> {
+    if let Some(result) = self.loaded.get() {
+      return Some(result);
+    }
+    let receiver = self.loaded_receiver.lock();
+    if let Some(result) = self.loaded.get() {
+      return Some(result);
+    }
+    let received = if wait {
+      receiver.recv().map_err(|_| TryRecvError::Disconnected)
+    } else {
+      receiver.try_recv()
+    };
+    let result = match received {
+      Ok(result) => result.map_err(|err| format!("{err:#}")),
+      Err(TryRecvError::Empty) => return None,
+      Err(TryRecvError::Disconnected) => {
+        Err("The lint plugin thread exited unexpectedly.".to_string())
+      }
+    };
+    Some(self.loaded.get_or_init(|| result))
+  }
+
+  fn wait_until_loaded(&self) -> Result<(), AnyError> {
+    match self.load_result(true) {
+      Some(Err(err)) => bail!("{err}"),
+      _ => Ok(()),
+    }
+  }
+
+  fn failed_loading(&self) -> bool {
+    matches!(self.load_result(false), Some(Err(_)))
+  }
+
+  /// The codes of the plugin rules that are known so far.
+  pub fn rule_codes(&self) -> impl Iterator<Item = &'static str> + '_ {
+    let rule_codes = match self.load_result(false) {
+      Some(Ok(rule_codes)) => rule_codes.as_slice(),
+      _ => &[],
+    };
+    rule_codes.iter().copied().chain([CODE])
+  }
+
+  fn lint(
+    &self,
+    parsed_source: &ParsedSource,
+  ) -> Result<Vec<PluginDiagnostic>, AnyError> {
+    let file = PluginFile {
+      specifier: parsed_source.specifier().to_string(),
+      source: parsed_source.text().to_string(),
+      ast: serde_json::to_string(parsed_source.program_ref())?,
+      start: parsed_source
+        .text_info_lazy()
+        .range()
+        .start
+        .as_source_pos()
+        .as_byte_pos()
+        .0,
+    };
+    let (reply, receiver) = mpsc::channel();
+    self
+      .sender
+      .send(LintRequest { file, reply })
+      .map_err(|_| anyhow!("The lint plugin thread has exited."))?;
+    receiver
+      .recv()
+      .map_err(|_| anyhow!("The lint plugin thread exited unexpectedly."))?
+  }
+}
+
+async fn load_plugins(
+  runtime: &mut JsRuntime,
+  specifiers: &[ModuleSpecifier],
+) -> Result<Vec<String>, AnyError> {
+  // Import every plugin statically from a generated module, so that they are
+  // all loaded and evaluated in one go.
+  let mut code =
+    format!("import {{ installPlugins }} from \"{HOST_SPECIFIER}\";\n");
+  for (i, specifier) in specifiers.iter().enumerate() {
+    code.push_str(&format!(
+      "import * as plugin{i} from {};\n",
+      serde_json::to_string(specifier.as_str())?
+    ));
+  }
+  code.push_str("installPlugins([");
+  for (i, specifier) in specifiers.iter().enumerate() {
+    code.push_str(&format!(
+      "[{}, plugin{i}],",
+      serde_json::to_string(specifier.as_str())?
+    ));
+  }
+  code.push_str("]);\n");
+
+  let main_specifier = ModuleSpecifier::parse(MAIN_SPECIFIER).unwrap();
+  let module_id = runtime
+    .load_main_es_module_from_code(&main_specifier, code)
+    .await?;
+  let evaluation = runtime.mod_evaluate(module_id);
+  runtime.run_event_loop(Default::default()).await?;
+  evaluation.await?;
+  call_host(runtime, "globalThis[Symbol.for(\"Deno.lint.ruleCodes\")]()")
+}
+
+fn call_host<T: DeserializeOwned>(
+  runtime: &mut JsRuntime,
+  script: &'static str,
+) -> Result<T, AnyError> {
+  let value = runtime.execute_script("ext:cli/lint_plugins.js", script)?;
+  let scope = &mut runtime.handle_scope();
+  let value = v8::Local::new(scope, value);
+  Ok(serde_v8::from_v8(scope, value)?)
+}
+
+#[op2]
+#[serde]
+fn op_lint_plugin_file(state: &mut OpState) -> PluginFile {
+  state.take::<PluginFile>()
+}
+
+deno_core::extension!(deno_lint_plugins, ops = [op_lint_plugin_file]);
+
+/// Loads plugins and the local modules they import. Anything remote is
+/// rejected, so that linting never hits the network.
+struct PluginModuleLoader;
+
+impl ModuleLoader for PluginModuleLoader {
+  fn resolve(
+    &self,
+    specifier: &str,
+    referrer: &str,
+    _kind: ResolutionKind,
+  ) -> Result<ModuleSpecifier, AnyError> {
+    Ok(resolve_import(specifier, referrer)?)
+  }
+
+  fn load(
+    &self,
+    module_specifier: &ModuleSpecifier,
+    _maybe_referrer: Option<&ModuleSpecifier>,
+    _is_dyn_import: bool,
+    _requested_module_type: RequestedModuleType,
+  ) -> ModuleLoadResponse {
+    ModuleLoadResponse::Sync(load_plugin_module(module_specifier))
+  }
+}
+
+fn load_plugin_module(
+  specifier: &ModuleSpecifier,
+) -> Result<ModuleSource, AnyError> {
+  if specifier.as_str() == HOST_SPECIFIER {
+    return Ok(ModuleSource::new(
+      ModuleType::JavaScript,
+      ModuleSourceCode::String(
+        deno_core::ascii_str_include!("../../js/40_lint_plugins.js").into(),
+      ),
+      specifier,
+      None,
+    ));
+  }
+  if specifier.scheme() != "file" {
+    bail!("Lint plugins can only import local modules, not \"{specifier}\"");
+  }
+  let path = specifier
+    .to_file_path()
+    .map_err(|_| anyhow!("Invalid file path \"{specifier}\""))?;
+  let text = std::fs::read_to_string(&path)
+    .with_context(|| format!("Failed reading {}", path.display()))?;
+  let media_type = MediaType::from_specifier(specifier);
+  let (module_type, code) = match media_type {
+    MediaType::JavaScript | MediaType::Mjs => (ModuleType::JavaScript, text),
+    MediaType::Json => (ModuleType::Json, text),
+    MediaType::TypeScript
+    | MediaType::Mts
+    | MediaType::Jsx
+    | MediaType::Tsx => (
+      ModuleType::JavaScript,
+      transpile(specifier, media_type, text)?,
+    ),
+    _ => bail!(
+      "Unsupported media type {media_type:?} of lint plugin module \"{specifier}\""
+    ),
+  };
+  Ok(ModuleSource::new(
+    module_type,
+    ModuleSourceCode::String(code.into()),
+    specifier,
+    None,
+  ))
+}
+
+fn transpile(
+  specifier: &ModuleSpecifier,
+  media_type: MediaType,
+  text: String,
+) -> Result<String, AnyError> {
+  let parsed = deno_ast::parse_module(ParseParams {
+    specifier: specifier.clone(),
+    text: text.into(),
+    media_type,
+    capture_tokens: false,
+    scope_analysis: false,
+    maybe_syntax: None,
+  })?;
+  let transpiled_source = parsed
+    .transpile(
+      &deno_ast::TranspileOptions {
+        imports_not_used_as_values: deno_ast::ImportsNotUsedAsValues::Remove,
+        ..Default::default()
+      },
+      &Default::default(),
+    )?
+    .into_source();
+  Ok(String::from_utf8(transpiled_source.source)?)
+}
+
+/// Runs all plugin rules as part of a deno_lint run.
+#[derive(Debug)]
+pub struct LintPluginsRule {
+  runner: Arc<LintPluginRunner>,
+}
+
+impl LintPluginsRule {
+  pub fn new(runner: Arc<LintPluginRunner>) -> Self {
+    Self { runner }
+  }
+}
+
+impl LintRule for LintPluginsRule {
+  fn lint_program_with_ast_view<'view>(
+    &self,
+    context: &mut deno_lint::context::Context<'view>,
+    _program: deno_lint::Program<'view>,
+  ) {
+    let text_info = context.text_info().clone();
+    let result =
+      self
+        .runner
+        .lint(context.parsed_source())
+        .and_then(|diagnostics| {
+          diagnostics
+            .into_iter()
+            .map(|diagnostic| into_diagnostic_parts(&text_info, diagnostic))
+            .collect::<Result<Vec<_>, _>>()
+        });
+    match result {
+      Ok(diagnostics) => {
+        for (range, details) in diagnostics {
+          context.add_diagnostic_details(Some(range), details);
+        }
+      }
+      Err(err) => {
+        context.add_diagnostic_details(
+          None,
+          LintDiagnosticDetails {
+            message: format!("Lint plugin failed: {err:#}"),
+            code: CODE.to_string(),
+            hint: None,
+            fixes: vec![],
+            custom_docs_url: None,
+            info: vec![],
+          },
+        );
+      }
+    }
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn docs(&self) -> &'static str {
+    "Runs the lint rules of the plugins listed under \"lintPlugins\" in the config file."
+  }
+
+  fn tags(&self) -> &'static [&'static str] {
+    &[]
+  }
+}
+
+fn into_diagnostic_parts(
+  text_info: &SourceTextInfo,
+  diagnostic: PluginDiagnostic,
+) -> Result<(LintDiagnosticRange, LintDiagnosticDetails), AnyError> {
+  let to_range = |start: usize, end: usize| {
+    let text = text_info.text_str();
+    if start > end
+      || end > text.len()
+      || !text.is_char_boundary(start)
+      || !text.is_char_boundary(end)
+    {
+      bail!(
+        "Lint rule \"{}\" reported an invalid range {start}..{end}",
+        diagnostic.code
+      );
+    }
+    let file_start = text_info.range().start;
+    Ok(SourceRange::new(file_start + start, file_start + end))
+  };
+  let range = to_range(diagnostic.start, diagnostic.end)?;
+  let fixes = match &diagnostic.fix {
+    Some(fix) => vec![LintFix {
+      description: Cow::Owned(fix.description.clone()),
+      changes: fix
+        .changes
+        .iter()
+        .map(|change| {
+          Ok(LintFixChange {
+            new_text: Cow::Owned(change.text.clone()),
+            range: to_range(change.start, change.end)?,
+          })
+        })
+        .collect::<Result<Vec<_>, AnyError>>()?,
+    }],
+    None => vec![],
+  };
+  Ok((
+    LintDiagnosticRange {
+      range,
+      description: None,
+      text_info: text_info.clone(),
+    },
+    LintDiagnosticDetails {
+      message: diagnostic.message,
+      code: diagnostic.code,
+      hint: diagnostic.hint,
+      fixes,
+      custom_docs_url: Some(diagnostic.docs_url),
+      info: vec![],
+    },
+  ))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_plugin_specifiers() {
+    let config_specifier =
+      ModuleSpecifier::parse("file:///project/deno.json").unwrap();
+    let specifiers = parse_plugin_specifiers(
+      &config_specifier,
+      r#"{
+        // comments are allowed
+        "lintPlugins": ["./rules/mod.ts", "/shared/plugin.js"]
+      }"#,
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(
+      specifiers.iter().map(|s| s.as_str()).collect::<Vec<_>>(),
+      vec!["file:///project/rules/mod.ts", "file:///shared/plugin.js"]
+    );
+
+    assert!(parse_plugin_specifiers(&config_specifier, "{}")
+      .unwrap()
+      .is_none());
+    assert!(parse_plugin_specifiers(
+      &config_specifier,
+      r#"{ "lintPlugins": ["https://example.com/plugin.ts"] }"#,
+    )
+    .is_err());
+    assert!(parse_plugin_specifiers(
+      &config_specifier,
+      r#"{ "lintPlugins": "./plugin.ts" }"#,
+    )
+    .is_err());
+  }
+
+  #[test]
+  fn interns_rule_codes() {
+    let code = intern_rule_code("my-plugin/no-foo".to_string());
+    assert_eq!(code, "my-plugin/no-foo");
+    assert!(std::ptr::eq(
+      code,
+      intern_rule_code("my-plugin/no-foo".to_string())
+    ));
+  }
+}
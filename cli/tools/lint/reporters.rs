@@ -9,7 +9,16 @@ use log::info;
 use serde::Serialize;
 
 use crate::args::LintReporterKind;
+use crate::util::checkstyle::CheckstyleError;
+use crate::util::checkstyle::CheckstyleReport;
+use crate::util::checkstyle::CheckstyleSeverity;
+use crate::util::sarif::SarifLevel;
+use crate::util::sarif::SarifLog;
+use crate::util::sarif::SarifRegion;
+use crate::util::sarif::SarifResult;
+use crate::util::sarif::SarifRule;
 
+use super::rules::CliLintRule;
 use super::LintError;
 
 pub fn create_reporter(kind: LintReporterKind) -> Box<dyn LintReporter + Send> {
@@ -17,10 +26,15 @@ pub fn create_reporter(kind: LintReporterKind) -> Box<dyn LintReporter + Send> {
     LintReporterKind::Pretty => Box::new(PrettyLintReporter::new()),
     LintReporterKind::Json => Box::new(JsonLintReporter::new()),
     LintReporterKind::Compact => Box::new(CompactLintReporter::new()),
+    LintReporterKind::Sarif => Box::new(SarifLintReporter::new()),
+    LintReporterKind::Checkstyle => Box::new(CheckstyleLintReporter::new()),
   }
 }
 
 pub trait LintReporter {
+  /// Called with the rules that are about to be run, before any of the
+  /// diagnostics they produce are visited.
+  fn visit_rules(&mut self, _rules: &[CliLintRule]) {}
   fn visit_diagnostic(&mut self, d: &LintDiagnostic);
   fn visit_error(&mut self, file_path: &str, err: &AnyError);
   fn close(&mut self, check_count: usize);
@@ -224,6 +238,142 @@ impl LintReporter for JsonLintReporter {
   }
 }
 
+struct SarifLintReporter {
+  log: SarifLog,
+}
+
+impl SarifLintReporter {
+  fn new() -> SarifLintReporter {
+    SarifLintReporter {
+      log: SarifLog::new("deno lint"),
+    }
+  }
+}
+
+impl LintReporter for SarifLintReporter {
+  fn visit_rules(&mut self, rules: &[CliLintRule]) {
+    for rule in rules {
+      self.log.add_rule(SarifRule {
+        id: rule.code().to_string(),
+        short_description: rule
+          .docs()
+          .lines()
+          .map(|line| line.trim())
+          .find(|line| !line.is_empty())
+          .map(|line| line.to_string()),
+        help_uri: Some(rule.help_docs_url().into_owned()),
+        tags: rule.tags().iter().map(|tag| tag.to_string()).collect(),
+      });
+    }
+  }
+
+  fn visit_diagnostic(&mut self, d: &LintDiagnostic) {
+    // rules that aren't built in (ex. plugins) only carry their docs url on
+    // the diagnostic
+    if let Some(docs_url) = &d.details.custom_docs_url {
+      self.log.add_rule(SarifRule {
+        id: d.code().to_string(),
+        help_uri: Some(docs_url.clone()),
+        ..Default::default()
+      });
+    }
+    self.log.add_result(SarifResult {
+      rule_id: d.code().to_string(),
+      level: SarifLevel::Error,
+      message: match d.hint() {
+        Some(hint) => format!("{}\n\nhint: {}", d.message(), hint),
+        None => d.message().to_string(),
+      },
+      uri: Some(d.specifier.to_string()),
+      region: d.range.as_ref().map(|range| {
+        let text_info = &range.text_info;
+        let start = text_info.line_and_column_index(range.range.start);
+        let end = text_info.line_and_column_index(range.range.end);
+        SarifRegion {
+          start_line: start.line_index + 1,
+          start_column: start.column_index + 1,
+          end_line: end.line_index + 1,
+          end_column: end.column_index + 1,
+        }
+      }),
+    });
+  }
+
+  fn visit_error(&mut self, file_path: &str, err: &AnyError) {
+    self.log.add_notification(
+      format!("Error linting: {file_path}\n{err}"),
+      deno_core::url::Url::from_file_path(file_path)
+        .ok()
+        .map(|url| url.to_string()),
+    );
+  }
+
+  fn close(&mut self, _check_count: usize) {
+    #[allow(clippy::print_stdout)]
+    {
+      println!("{}", self.log.to_json());
+    }
+  }
+}
+
+struct CheckstyleLintReporter {
+  report: CheckstyleReport,
+}
+
+impl CheckstyleLintReporter {
+  fn new() -> CheckstyleLintReporter {
+    CheckstyleLintReporter {
+      report: CheckstyleReport::default(),
+    }
+  }
+}
+
+impl LintReporter for CheckstyleLintReporter {
+  fn visit_diagnostic(&mut self, d: &LintDiagnostic) {
+    let line_and_column = d
+      .range
+      .as_ref()
+      .map(|range| range.text_info.line_and_column_index(range.range.start));
+    let file_name = match d.specifier.to_file_path() {
+      Ok(path) => path.to_string_lossy().to_string(),
+      Err(()) => d.specifier.to_string(),
+    };
+    self.report.add_error(
+      file_name,
+      CheckstyleError {
+        line: line_and_column.map(|l| l.line_index + 1),
+        column: line_and_column.map(|l| l.column_index + 1),
+        severity: CheckstyleSeverity::Error,
+        message: match d.hint() {
+          Some(hint) => format!("{} ({})", d.message(), hint),
+          None => d.message().to_string(),
+        },
+        source: d.code().to_string(),
+      },
+    );
+  }
+
+  fn visit_error(&mut self, file_path: &str, err: &AnyError) {
+    self.report.add_error(
+      file_path.to_string(),
+      CheckstyleError {
+        line: None,
+        column: None,
+        severity: CheckstyleSeverity::Error,
+        message: format!("Error linting: {err}"),
+        source: "deno-lint".to_string(),
+      },
+    );
+  }
+
+  fn close(&mut self, _check_count: usize) {
+    #[allow(clippy::print_stdout)]
+    {
+      println!("{}", self.report.to_xml());
+    }
+  }
+}
+
 fn sort_diagnostics(diagnostics: &mut [JsonLintDiagnostic]) {
   // Sort so that we guarantee a deterministic output which is useful for tests
   diagnostics.sort_by(|a, b| {
@@ -0,0 +1,272 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! `deno serve --parallel`: supervises several server processes that share the
+//! listening port through `SO_REUSEPORT`, letting the kernel balance incoming
+//! connections between them.
+
+use std::ffi::OsString;
+use std::io::Write;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::pin::pin;
+use std::process::Stdio;
+use std::time::Duration;
+use std::time::Instant;
+
+use deno_core::anyhow::bail;
+use deno_core::error::AnyError;
+use deno_core::futures::stream::FuturesUnordered;
+use deno_core::futures::StreamExt;
+use deno_core::unsync::spawn;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncRead;
+use tokio::io::BufReader;
+use tokio::process::Child;
+use tokio::process::Command;
+use tokio::sync::watch;
+
+use crate::args::ServeFlags;
+use crate::args::SERVE_WORKER_ID_ENV_VAR_NAME;
+use crate::colors;
+
+/// How long workers get to finish their in-flight requests once asked to shut
+/// down, before they are killed.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+/// A worker that exits sooner than this after starting counts as crash
+/// looping rather than as a one-off failure.
+const MIN_HEALTHY_UPTIME: Duration = Duration::from_secs(5);
+/// Stop restarting a worker after this many crashes in a row.
+const MAX_CONSECUTIVE_CRASHES: u32 = 5;
+
+pub async fn serve_parallel(
+  serve_flags: &ServeFlags,
+  workers: NonZeroUsize,
+) -> Result<i32, AnyError> {
+  if !cfg!(target_os = "linux") {
+    bail!("--parallel is only supported on Linux");
+  }
+  if serve_flags.port == 0 {
+    bail!("--parallel requires a fixed --port, so that every worker listens on the same one");
+  }
+
+  // Workers re-run this exact command line. The worker id in the environment
+  // makes them serve instead of supervising.
+  let exe = std::env::current_exe()?;
+  let args = std::env::args_os().skip(1).collect::<Vec<_>>();
+
+  log::info!(
+    "{} {} workers on {}:{}",
+    colors::green("Starting"),
+    workers,
+    serve_flags.host,
+    serve_flags.port
+  );
+
+  let (shutdown_tx, shutdown_rx) = watch::channel(false);
+  let mut supervisors = (0..workers.get())
+    .map(|id| supervise_worker(id, &exe, &args, shutdown_rx.clone()))
+    .collect::<FuturesUnordered<_>>();
+  let mut shutdown_signal = pin!(shutdown_signal());
+  let mut shutting_down = false;
+  let mut exit_code = 0;
+
+  loop {
+    tokio::select! {
+      result = supervisors.next() => {
+        let Some(result) = result else {
+          break;
+        };
+        let code = result?;
+        if code != 0 && !shutting_down {
+          // A worker gave up, so the server is in a bad state. Take the rest
+          // down with it rather than running at reduced capacity.
+          exit_code = code;
+          shutting_down = true;
+          shutdown_tx.send_replace(true);
+        }
+      }
+      result = &mut shutdown_signal, if !shutting_down => {
+        result?;
+        log::info!("{} workers", colors::green("Shutting down"));
+        shutting_down = true;
+        shutdown_tx.send_replace(true);
+      }
+    }
+  }
+
+  Ok(exit_code)
+}
+
+/// Runs one worker, restarting it whenever it crashes. Resolves with the exit
+/// code once the worker has stopped for good.
+async fn supervise_worker(
+  id: usize,
+  exe: &Path,
+  args: &[OsString],
+  mut shutdown: watch::Receiver<bool>,
+) -> Result<i32, AnyError> {
+  let mut crashes = 0;
+  loop {
+    let started = Instant::now();
+    let mut child = spawn_worker(id, exe, args)?;
+    let status = tokio::select! {
+      status = child.wait() => status?,
+      _ = shutdown.changed() => {
+        stop_worker(id, child).await?;
+        return Ok(0);
+      }
+    };
+
+    if status.success() {
+      log::info!("{} exited", worker_name(id));
+      return Ok(0);
+    }
+
+    crashes = if started.elapsed() < MIN_HEALTHY_UPTIME {
+      crashes + 1
+    } else {
+      1
+    };
+    if crashes >= MAX_CONSECUTIVE_CRASHES {
+      log::error!(
+        "{}: {} crashed {} times in a row ({}), giving up",
+        colors::red_bold("error"),
+        worker_name(id),
+        crashes,
+        status
+      );
+      return Ok(status.code().unwrap_or(1));
+    }
+
+    // Back off exponentially while the worker keeps crashing on startup.
+    let backoff = Duration::from_millis(250 << (crashes - 1));
+    log::warn!(
+      "{} {} exited ({}), restarting in {}ms",
+      colors::yellow("Warning"),
+      worker_name(id),
+      status,
+      backoff.as_millis()
+    );
+    tokio::select! {
+      _ = tokio::time::sleep(backoff) => {}
+      _ = shutdown.changed() => return Ok(0),
+    }
+  }
+}
+
+fn spawn_worker(
+  id: usize,
+  exe: &Path,
+  args: &[OsString],
+) -> Result<Child, AnyError> {
+  let mut command = Command::new(exe);
+  command
+    .args(args)
+    .env(SERVE_WORKER_ID_ENV_VAR_NAME, id.to_string())
+    .stdin(Stdio::null())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .kill_on_drop(true);
+  // Keep workers out of the terminal's process group, so that Ctrl+C only
+  // reaches the supervisor, which then shuts them down gracefully.
+  #[cfg(unix)]
+  command.process_group(0);
+
+  let mut child = command.spawn()?;
+  if let Some(stdout) = child.stdout.take() {
+    forward_output(id, stdout, std::io::stdout);
+  }
+  if let Some(stderr) = child.stderr.take() {
+    forward_output(id, stderr, std::io::stderr);
+  }
+  Ok(child)
+}
+
+/// Copies a worker's output line by line, prefixing each line with the
+/// worker's name so the interleaved output of all workers stays readable.
+fn forward_output<W: Write>(
+  id: usize,
+  reader: impl AsyncRead + Unpin + 'static,
+  writer: impl Fn() -> W + 'static,
+) {
+  spawn(async move {
+    let prefix = format!("{} ", colors::gray(format!("[{}]", worker_name(id))));
+    let mut reader = BufReader::new(reader);
+    let mut line = Vec::new();
+    loop {
+      line.clear();
+      match reader.read_until(b'\n', &mut line).await {
+        Ok(0) | Err(_) => break,
+        Ok(_) => {}
+      }
+      if !line.ends_with(b"\n") {
+        line.push(b'\n');
+      }
+      let mut writer = writer();
+      let _ = writer
+        .write_all(prefix.as_bytes())
+        .and_then(|_| writer.write_all(&line));
+    }
+  });
+}
+
+async fn stop_worker(id: usize, mut child: Child) -> Result<(), AnyError> {
+  request_shutdown(&mut child);
+  match tokio::time::timeout(SHUTDOWN_TIMEOUT, child.wait()).await {
+    Ok(status) => {
+      status?;
+    }
+    Err(_) => {
+      log::warn!(
+        "{} {} did not shut down within {}s, killing it",
+        colors::yellow("Warning"),
+        worker_name(id),
+        SHUTDOWN_TIMEOUT.as_secs()
+      );
+      child.kill().await?;
+    }
+  }
+  Ok(())
+}
+
+/// Asks a worker to stop accepting connections and exit once its in-flight
+/// requests are done.
+#[cfg(unix)]
+fn request_shutdown(child: &mut Child) {
+  use nix::sys::signal::kill;
+  use nix::sys::signal::Signal;
+  use nix::unistd::Pid;
+
+  if let Some(pid) = child.id() {
+    let _ = kill(Pid::from_raw(pid as i32), Signal::SIGTERM);
+  }
+}
+
+#[cfg(not(unix))]
+fn request_shutdown(child: &mut Child) {
+  let _ = child.start_kill();
+}
+
+#[cfg(unix)]
+async fn shutdown_signal() -> Result<(), AnyError> {
+  use tokio::signal::unix::signal;
+  use tokio::signal::unix::SignalKind;
+
+  let mut interrupt = signal(SignalKind::interrupt())?;
+  let mut terminate = signal(SignalKind::terminate())?;
+  tokio::select! {
+    _ = interrupt.recv() => {}
+    _ = terminate.recv() => {}
+  }
+  Ok(())
+}
+
+#[cfg(not(unix))]
+async fn shutdown_signal() -> Result<(), AnyError> {
+  tokio::signal::ctrl_c().await?;
+  Ok(())
+}
+
+fn worker_name(id: usize) -> String {
+  format!("worker {id}")
+}
@@ -3,28 +3,126 @@
 use crate::args::CliOptions;
 use crate::args::Flags;
 use crate::args::TaskFlags;
+use crate::args::TaskShell;
+use crate::cache::CacheDBHash;
+use crate::cache::FastInsecureHasher;
+use crate::cache::TaskCache;
 use crate::colors;
 use crate::factory::CliFactory;
 use crate::npm::CliNpmResolver;
 use crate::task_runner;
 use crate::util::fs::canonicalize_path;
+use deno_config::deno_json::ConfigFile;
 use deno_config::deno_json::Task;
+use deno_config::glob::FileCollector;
+use deno_config::glob::FilePatterns;
+use deno_config::glob::PathOrPatternSet;
 use deno_config::workspace::TaskOrScript;
 use deno_config::workspace::WorkspaceDirectory;
+use deno_config::workspace::WorkspaceMemberTasksConfig;
+use deno_config::workspace::WorkspaceMemberTasksConfigFile;
 use deno_config::workspace::WorkspaceTasksConfig;
 use deno_core::anyhow::bail;
 use deno_core::anyhow::Context;
 use deno_core::error::AnyError;
+use deno_core::futures::stream::FuturesUnordered;
+use deno_core::futures::FutureExt;
+use deno_core::futures::StreamExt;
 use deno_core::normalize_path;
+use deno_core::serde_json;
+use deno_core::url::Url;
+use deno_package_json::PackageJson;
+use deno_package_json::PackageJsonDepValue;
 use deno_task_shell::ShellCommand;
+use deno_task_shell::ShellPipeReader;
+use indexmap::IndexMap;
+use regex::Regex;
+use serde::Deserialize;
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::io::Write;
+use std::num::NonZeroUsize;
 use std::path::Path;
 use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::Arc;
 
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct TaskOptions {
+  /// Shown when listing the available tasks.
+  description: Option<String>,
+  /// Names of the tasks that need to succeed before this task runs.
+  dependencies: Vec<String>,
+  /// Files, directories or globs relative to the config file that the task
+  /// reads. When set, the task is skipped if none of them changed since the
+  /// task last succeeded.
+  inputs: Vec<String>,
+}
+
+/// A value of "tasks" in a deno.json. deno_config only supports the string
+/// form, so the values are parsed here to also support the object form.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum TaskValue {
+  Definition(String),
+  /// Added by deno_config when task comments are included.
+  Commented {
+    definition: Box<TaskValue>,
+    comments: Vec<String>,
+  },
+  Object(TaskObject),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+struct TaskObject {
+  command: String,
+  #[serde(default)]
+  description: Option<String>,
+  #[serde(default)]
+  dependencies: Vec<String>,
+  #[serde(default)]
+  inputs: Vec<String>,
+}
+
+impl TaskValue {
+  fn into_task(self) -> (Task, Option<TaskOptions>) {
+    match self {
+      TaskValue::Definition(definition) => (Task::Definition(definition), None),
+      TaskValue::Commented {
+        definition,
+        comments,
+      } => {
+        let (task, options) = definition.into_task();
+        let definition = match task {
+          Task::Definition(definition) => definition,
+          Task::Commented { definition, .. } => definition,
+        };
+        (
+          Task::Commented {
+            definition,
+            comments,
+          },
+          options,
+        )
+      }
+      TaskValue::Object(object) => (
+        Task::Definition(object.command),
+        Some(TaskOptions {
+          description: object.description,
+          dependencies: object.dependencies,
+          inputs: object.inputs,
+        }),
+      ),
+    }
+  }
+}
+
+/// The task options of the config files of a workspace directory, keyed by
+/// the url of the directory the config file is in.
+type TaskOptionsByFolder = HashMap<Url, HashMap<String, TaskOptions>>;
+
 pub async fn execute_script(
   flags: Arc<Flags>,
   task_flags: TaskFlags,
@@ -45,11 +143,11 @@ pub async fn execute_script(
         v == "1"
       })
       .unwrap_or(false);
-  let tasks_config = start_dir.to_tasks_config()?;
-  let tasks_config = if force_use_pkg_json {
-    tasks_config.with_only_pkg_json()
+  let (tasks_config, task_options) = resolve_tasks_config(start_dir)?;
+  let (tasks_config, task_options) = if force_use_pkg_json {
+    (tasks_config.with_only_pkg_json(), Default::default())
   } else {
-    tasks_config
+    (tasks_config, task_options)
   };
 
   let task_name = match &task_flags.task {
@@ -59,6 +157,7 @@ pub async fn execute_script(
         &mut std::io::stdout(),
         &cli_options.start_dir,
         &tasks_config,
+        &task_options,
       )?;
       return Ok(1);
     }
@@ -68,28 +167,121 @@ pub async fn execute_script(
   let node_resolver = factory.node_resolver().await?;
   let env_vars = task_runner::real_env_vars();
 
+  if task_flags.recursive || task_flags.filter.is_some() {
+    let members = resolve_member_tasks(
+      cli_options,
+      task_name,
+      task_flags.filter.as_deref(),
+      force_use_pkg_json,
+    )?;
+    if members.is_empty() {
+      match &task_flags.filter {
+        Some(filter) => log::error!(
+          "No workspace members matching '{filter}' have task: {task_name}"
+        ),
+        None => log::error!("No workspace members have task: {task_name}"),
+      }
+      return Ok(1);
+    }
+    let custom_commands = task_runner::resolve_custom_commands(
+      npm_resolver.as_ref(),
+      node_resolver,
+    )?;
+    let mut ensured_npm_install = false;
+    let mut results = Vec::with_capacity(members.len());
+    for member in &members {
+      if let Some(dependency) = member
+        .dependencies
+        .iter()
+        .find(|dependency| results[**dependency] != MemberTaskResult::Success)
+      {
+        results.push(MemberTaskResult::Skipped {
+          dependency: members[*dependency].name.clone(),
+        });
+        continue;
+      }
+      log::info!(
+        "{} {} {}",
+        colors::green("Running"),
+        colors::cyan(task_name),
+        colors::gray(format!("in {}", member.name)),
+      );
+      let plan =
+        plan_tasks(task_name, &member.tasks_config, &member.task_options)?;
+      if !ensured_npm_install
+        && plan.iter().any(|task| task.is_script)
+        && cli_options.has_node_modules_dir()
+      {
+        if let Some(npm_resolver) = npm_resolver.as_managed() {
+          npm_resolver.ensure_top_level_package_json_install().await?;
+        }
+        ensured_npm_install = true;
+      }
+      let cwd = normalize_path(member.dir_url.to_file_path().unwrap());
+      let exit_code = PlannedTaskRunner {
+        plan: &plan,
+        cwd: &cwd,
+        env_vars: env_vars.clone(),
+        custom_commands: custom_commands.clone(),
+        npm_resolver: npm_resolver.as_ref(),
+        cli_options,
+        task_cache: TaskCache::new(factory.caches()?.task_cache_db()),
+        shell: task_flags.shell,
+      }
+      .run()
+      .await?;
+      results.push(if exit_code == 0 {
+        MemberTaskResult::Success
+      } else {
+        MemberTaskResult::Failed { exit_code }
+      });
+    }
+    print_member_task_summary(&members, &results);
+    let exit_code = results
+      .iter()
+      .find_map(|result| match result {
+        MemberTaskResult::Failed { exit_code } => Some(*exit_code),
+        _ => None,
+      })
+      .unwrap_or(0);
+    return Ok(exit_code);
+  }
+
   match tasks_config.task(task_name) {
     Some((dir_url, task_or_script)) => match task_or_script {
-      TaskOrScript::Task(_tasks, script) => {
+      TaskOrScript::Task(_tasks, _script) => {
         let cwd = match task_flags.cwd {
           Some(path) => canonicalize_path(&PathBuf::from(path))
             .context("failed canonicalizing --cwd")?,
           None => normalize_path(dir_url.to_file_path().unwrap()),
         };
 
+        let plan = plan_tasks(task_name, &tasks_config, &task_options)?;
+        // dependencies might be package.json scripts
+        if plan.iter().any(|task| task.is_script) {
+          if cli_options.has_node_modules_dir() {
+            if let Some(npm_resolver) = npm_resolver.as_managed() {
+              npm_resolver.ensure_top_level_package_json_install().await?;
+            }
+          }
+        }
+
         let custom_commands = task_runner::resolve_custom_commands(
           npm_resolver.as_ref(),
           node_resolver,
         )?;
-        run_task(RunTaskOptions {
-          task_name,
-          script,
+        let task_cache = TaskCache::new(factory.caches()?.task_cache_db());
+        PlannedTaskRunner {
+          plan: &plan,
           cwd: &cwd,
           env_vars,
           custom_commands,
           npm_resolver: npm_resolver.as_ref(),
           cli_options,
-        })
+          task_cache,
+          shell: task_flags.shell,
+        }
+        .run()
         .await
       }
       TaskOrScript::Script(scripts, _script) => {
@@ -124,10 +316,13 @@ pub async fn execute_script(
               task_name,
               script,
               cwd: &cwd,
+              argv: cli_options.argv(),
               env_vars: env_vars.clone(),
               custom_commands: custom_commands.clone(),
               npm_resolver: npm_resolver.as_ref(),
               cli_options,
+              stdio: None,
+              shell: task_flags.shell,
             })
             .await?;
             if exit_code > 0 {
@@ -146,6 +341,7 @@ pub async fn execute_script(
           &mut std::io::stderr(),
           &cli_options.start_dir,
           &tasks_config,
+          &task_options,
         )?;
       }
       Ok(1)
@@ -153,14 +349,678 @@ pub async fn execute_script(
   }
 }
 
+/// Resolves the tasks of the workspace directory and the workspace root like
+/// `WorkspaceDirectory::to_tasks_config`, along with the options of the tasks
+/// defined in the object form.
+fn resolve_tasks_config(
+  workspace_dir: &WorkspaceDirectory,
+) -> Result<(WorkspaceTasksConfig, TaskOptionsByFolder), AnyError> {
+  fn to_member_tasks_config(
+    maybe_deno_json: Option<&ConfigFile>,
+    maybe_pkg_json: Option<&PackageJson>,
+    task_options: &mut TaskOptionsByFolder,
+  ) -> Result<Option<WorkspaceMemberTasksConfig>, AnyError> {
+    let deno_json = match maybe_deno_json {
+      Some(deno_json) => {
+        let folder_url =
+          Url::from_directory_path(deno_json.dir_path()).unwrap();
+        let mut options = HashMap::new();
+        let tasks = read_deno_json_tasks(deno_json, &mut options)
+          .with_context(|| {
+            format!("Failed parsing '{}'.", deno_json.specifier)
+          })?;
+        if !options.is_empty() {
+          task_options.insert(folder_url.clone(), options);
+        }
+        tasks.map(|tasks| WorkspaceMemberTasksConfigFile { folder_url, tasks })
+      }
+      None => None,
+    };
+    let package_json = maybe_pkg_json.and_then(|pkg_json| {
+      pkg_json
+        .scripts
+        .clone()
+        .map(|scripts| WorkspaceMemberTasksConfigFile {
+          folder_url: Url::from_directory_path(pkg_json.dir_path()).unwrap(),
+          tasks: scripts,
+        })
+    });
+    if deno_json.is_none() && package_json.is_none() {
+      return Ok(None);
+    }
+    Ok(Some(WorkspaceMemberTasksConfig {
+      deno_json,
+      package_json,
+    }))
+  }
+
+  let member_deno_json = workspace_dir.maybe_deno_json();
+  let member_pkg_json = workspace_dir.maybe_pkg_json();
+  // the root config is only separate when it's not the member config
+  let root_deno_json =
+    workspace_dir.workspace.root_deno_json().filter(|root| {
+      Some(&root.specifier) != member_deno_json.map(|c| &c.specifier)
+    });
+  let root_pkg_json = workspace_dir
+    .workspace
+    .root_pkg_json()
+    .filter(|root| Some(&root.path) != member_pkg_json.map(|c| &c.path));
+  let mut task_options = TaskOptionsByFolder::new();
+  let tasks_config = WorkspaceTasksConfig {
+    root: to_member_tasks_config(
+      root_deno_json.map(|c| c.as_ref()),
+      root_pkg_json.map(|c| c.as_ref()),
+      &mut task_options,
+    )?,
+    member: to_member_tasks_config(
+      member_deno_json.map(|c| c.as_ref()),
+      member_pkg_json.map(|c| c.as_ref()),
+      &mut task_options,
+    )?,
+  };
+  Ok((tasks_config, task_options))
+}
+
+/// Reads the "tasks" of a config file, collecting the options of the tasks
+/// defined in the object form.
+fn read_deno_json_tasks(
+  config_file: &ConfigFile,
+  task_options: &mut HashMap<String, TaskOptions>,
+) -> Result<Option<IndexMap<String, Task>>, AnyError> {
+  let Some(value) = config_file.json.tasks.clone() else {
+    return Ok(None);
+  };
+  let values: IndexMap<String, TaskValue> = serde_json::from_value(value)
+    .context("Failed to parse \"tasks\" configuration")?;
+  let mut tasks = IndexMap::with_capacity(values.len());
+  for (name, value) in values {
+    let (task, options) = value.into_task();
+    if let Some(options) = options {
+      task_options.insert(name.clone(), options);
+    }
+    tasks.insert(name, task);
+  }
+  Ok(Some(tasks))
+}
+
+/// A workspace member to run a task in for `deno task --recursive` and
+/// `deno task --filter`.
+struct MemberTask {
+  /// The package name of the member or its path relative to the workspace
+  /// root when it has no name.
+  name: String,
+  dir_url: Arc<Url>,
+  tasks_config: WorkspaceTasksConfig,
+  task_options: TaskOptionsByFolder,
+  /// Indexes of the members that this member imports.
+  dependencies: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum MemberTaskResult {
+  Success,
+  Failed { exit_code: i32 },
+  Skipped { dependency: String },
+}
+
+/// Resolves the workspace members that define the task and match the filter,
+/// ordered so that each member comes after the members it imports.
+fn resolve_member_tasks(
+  cli_options: &CliOptions,
+  task_name: &str,
+  filter: Option<&str>,
+  force_use_pkg_json: bool,
+) -> Result<Vec<MemberTask>, AnyError> {
+  let filter = filter.map(filter_to_regex).transpose()?;
+  let workspace = cli_options.workspace();
+  let root_url = workspace.root_dir();
+  let config_folders = workspace.config_folders();
+  let mut members = Vec::new();
+  let mut member_imports = Vec::new();
+  for (folder_url, folder) in config_folders {
+    // the workspace root is not a member when it has members
+    if folder_url == root_url && config_folders.len() > 1 {
+      continue;
+    }
+    let package_name = folder
+      .deno_json
+      .as_ref()
+      .and_then(|config| config.json.name.clone())
+      .or_else(|| {
+        folder
+          .pkg_json
+          .as_ref()
+          .and_then(|pkg_json| pkg_json.name.clone())
+      });
+    if let Some(filter) = &filter {
+      match &package_name {
+        Some(name) if filter.is_match(name) => {}
+        _ => continue,
+      }
+    }
+    let member_dir = workspace.resolve_member_dir(folder_url);
+    let (tasks_config, task_options) = resolve_tasks_config(&member_dir)?;
+    let (tasks_config, task_options) = if force_use_pkg_json {
+      (tasks_config.with_only_pkg_json(), Default::default())
+    } else {
+      (tasks_config, task_options)
+    };
+    // skip members that only inherit the task from the workspace root
+    match tasks_config.task(task_name) {
+      Some((dir_url, _)) if dir_url == folder_url.as_ref() => {}
+      _ => continue,
+    }
+    let name = package_name.unwrap_or_else(|| {
+      let mut relative_path = root_url
+        .make_relative(folder_url)
+        .unwrap_or_else(|| folder_url.to_string());
+      if relative_path.ends_with('/') {
+        relative_path.pop();
+      }
+      relative_path
+    });
+    let mut imports = HashSet::new();
+    if let Some(deno_json) = &folder.deno_json {
+      if let Some(import_map) =
+        deno_json.json.imports.as_ref().and_then(|v| v.as_object())
+      {
+        imports.extend(import_map.keys().cloned());
+      }
+      imports.extend(
+        crate::args::deno_json::deno_json_deps(deno_json)
+          .into_iter()
+          .map(|dep| dep.req.name),
+      );
+    }
+    if let Some(pkg_json) = &folder.pkg_json {
+      for (alias, dep) in pkg_json.resolve_local_package_json_deps() {
+        match dep {
+          Ok(PackageJsonDepValue::Req(req)) => imports.insert(req.name.clone()),
+          _ => imports.insert(alias.to_string()),
+        };
+      }
+    }
+    members.push(MemberTask {
+      name,
+      dir_url: folder_url.clone(),
+      tasks_config,
+      task_options,
+      dependencies: Vec::new(),
+    });
+    member_imports.push(imports);
+  }
+
+  let dependencies = (0..members.len())
+    .map(|index| {
+      (0..members.len())
+        .filter(|other| {
+          *other != index
+            && member_imports[index].contains(&members[*other].name)
+        })
+        .collect::<Vec<_>>()
+    })
+    .collect::<Vec<_>>();
+  sort_members_by_dependencies(members, dependencies)
+}
+
+/// Sorts the members so that each member comes after its dependencies,
+/// otherwise keeping the order of the workspace.
+fn sort_members_by_dependencies(
+  members: Vec<MemberTask>,
+  dependencies: Vec<Vec<usize>>,
+) -> Result<Vec<MemberTask>, AnyError> {
+  fn visit(
+    index: usize,
+    members: &[MemberTask],
+    dependencies: &[Vec<usize>],
+    path: &mut Vec<usize>,
+    order: &mut Vec<usize>,
+  ) -> Result<(), AnyError> {
+    if order.contains(&index) {
+      return Ok(());
+    }
+    if path.contains(&index) {
+      let cycle = path
+        .iter()
+        .skip_while(|i| **i != index)
+        .chain(std::iter::once(&index))
+        .map(|i| members[*i].name.as_str())
+        .collect::<Vec<_>>();
+      bail!(
+        "Workspace member dependency cycle detected: {}",
+        cycle.join(" -> ")
+      );
+    }
+    path.push(index);
+    for dependency in &dependencies[index] {
+      visit(*dependency, members, dependencies, path, order)?;
+    }
+    path.pop();
+    order.push(index);
+    Ok(())
+  }
+
+  let mut order = Vec::with_capacity(members.len());
+  for index in 0..members.len() {
+    visit(index, &members, &dependencies, &mut Vec::new(), &mut order)?;
+  }
+  let mut new_indexes = vec![0; members.len()];
+  for (new_index, old_index) in order.iter().enumerate() {
+    new_indexes[*old_index] = new_index;
+  }
+  let mut members = members.into_iter().map(Some).collect::<Vec<_>>();
+  Ok(
+    order
+      .into_iter()
+      .map(|old_index| {
+        let mut member = members[old_index].take().unwrap();
+        member.dependencies = dependencies[old_index]
+          .iter()
+          .map(|dependency| new_indexes[*dependency])
+          .collect();
+        member
+      })
+      .collect(),
+  )
+}
+
+/// Converts a `--filter` pattern where `*` matches any characters to a regex.
+fn filter_to_regex(filter: &str) -> Result<Regex, AnyError> {
+  let pattern = filter
+    .split('*')
+    .map(regex::escape)
+    .collect::<Vec<_>>()
+    .join(".*");
+  Ok(Regex::new(&format!("^{pattern}$"))?)
+}
+
+fn print_member_task_summary(
+  members: &[MemberTask],
+  results: &[MemberTaskResult],
+) {
+  let name_width = members.iter().map(|m| m.name.len()).max().unwrap_or(0);
+  log::info!("{}", colors::green("Summary:"));
+  for (member, result) in members.iter().zip(results) {
+    let name = format!("{:width$}", member.name, width = name_width);
+    match result {
+      MemberTaskResult::Success => {
+        log::info!("  {} {}", colors::cyan(name), colors::green("ok"));
+      }
+      MemberTaskResult::Failed { exit_code } => {
+        log::info!(
+          "  {} {}",
+          colors::cyan(name),
+          colors::red(format!("failed (exit code {exit_code})"))
+        );
+      }
+      MemberTaskResult::Skipped { dependency } => {
+        log::info!(
+          "  {} {}",
+          colors::cyan(name),
+          colors::gray(format!("skipped ({dependency} failed)"))
+        );
+      }
+    }
+  }
+}
+
+/// A task to run for `deno task`, which is either the requested task or one
+/// of its dependencies.
+struct PlannedTask<'a> {
+  name: &'a str,
+  dir_url: &'a Url,
+  script: &'a str,
+  /// Whether this is a package.json script.
+  is_script: bool,
+  options: Option<&'a TaskOptions>,
+  /// Indexes of the tasks in the plan that need to succeed first.
+  dependencies: Vec<usize>,
+}
+
+/// Resolves the tasks to run for a task name, ordered so that each task comes
+/// after its dependencies. The requested task is the last one.
+fn plan_tasks<'a>(
+  task_name: &'a str,
+  tasks_config: &'a WorkspaceTasksConfig,
+  task_options: &'a TaskOptionsByFolder,
+) -> Result<Vec<PlannedTask<'a>>, AnyError> {
+  fn visit<'a>(
+    task_name: &'a str,
+    tasks_config: &'a WorkspaceTasksConfig,
+    task_options: &'a TaskOptionsByFolder,
+    path: &mut Vec<&'a str>,
+    indexes: &mut HashMap<&'a str, usize>,
+    plan: &mut Vec<PlannedTask<'a>>,
+  ) -> Result<usize, AnyError> {
+    if let Some(index) = indexes.get(task_name) {
+      return Ok(*index);
+    }
+    if path.contains(&task_name) {
+      bail!(
+        "Task dependency cycle detected: {} -> {}",
+        path.join(" -> "),
+        task_name
+      );
+    }
+    let Some((dir_url, task_or_script)) = tasks_config.task(task_name) else {
+      bail!(
+        "Task not found: {} (dependency of task {})",
+        task_name,
+        path.last().unwrap()
+      );
+    };
+    let (script, is_script) = match task_or_script {
+      TaskOrScript::Task(_tasks, script) => (script, false),
+      TaskOrScript::Script(_scripts, script) => (script, true),
+    };
+    let options = if is_script {
+      None
+    } else {
+      task_options
+        .get(dir_url)
+        .and_then(|options| options.get(task_name))
+    };
+    let dependency_names = options
+      .map(|options| options.dependencies.as_slice())
+      .unwrap_or_default();
+    path.push(task_name);
+    let mut dependencies = Vec::new();
+    for dependency in dependency_names {
+      dependencies.push(visit(
+        dependency,
+        tasks_config,
+        task_options,
+        path,
+        indexes,
+        plan,
+      )?);
+    }
+    path.pop();
+    plan.push(PlannedTask {
+      name: task_name,
+      dir_url,
+      script,
+      is_script,
+      options,
+      dependencies,
+    });
+    indexes.insert(task_name, plan.len() - 1);
+    Ok(plan.len() - 1)
+  }
+
+  let mut plan = Vec::new();
+  visit(
+    task_name,
+    tasks_config,
+    task_options,
+    &mut Vec::new(),
+    &mut HashMap::new(),
+    &mut plan,
+  )?;
+  Ok(plan)
+}
+
+/// Runs a task and its dependencies, starting each task as soon as its
+/// dependencies succeeded. When more than one task runs, the output of each
+/// task is prefixed with its name.
+struct PlannedTaskRunner<'a> {
+  plan: &'a [PlannedTask<'a>],
+  /// The directory to run the requested task in.
+  cwd: &'a Path,
+  env_vars: HashMap<String, String>,
+  custom_commands: HashMap<String, Rc<dyn ShellCommand>>,
+  npm_resolver: &'a dyn CliNpmResolver,
+  cli_options: &'a CliOptions,
+  task_cache: TaskCache,
+  shell: TaskShell,
+}
+
+impl<'a> PlannedTaskRunner<'a> {
+  async fn run(&self) -> Result<i32, AnyError> {
+    let concurrency = std::env::var("DENO_JOBS")
+      .ok()
+      .and_then(|value| value.parse::<NonZeroUsize>().ok())
+      .or_else(|| std::thread::available_parallelism().ok())
+      .map(|value| value.get())
+      .unwrap_or(1);
+    let mut started = vec![false; self.plan.len()];
+    let mut succeeded = vec![false; self.plan.len()];
+    let mut exit_code = 0;
+    let mut running = FuturesUnordered::new();
+    loop {
+      // don't start any more tasks once one failed
+      if exit_code == 0 {
+        for (index, task) in self.plan.iter().enumerate() {
+          if running.len() >= concurrency {
+            break;
+          }
+          if !started[index]
+            && task
+              .dependencies
+              .iter()
+              .all(|dependency| succeeded[*dependency])
+          {
+            started[index] = true;
+            running.push(
+              self
+                .run_planned_task(index)
+                .map(move |result| (index, result))
+                .boxed_local(),
+            );
+          }
+        }
+      }
+      let Some((index, result)) = running.next().await else {
+        break;
+      };
+      let task_exit_code = result?;
+      if task_exit_code == 0 {
+        succeeded[index] = true;
+      } else if exit_code == 0 {
+        exit_code = task_exit_code;
+      }
+    }
+    Ok(exit_code)
+  }
+
+  async fn run_planned_task(&self, index: usize) -> Result<i32, AnyError> {
+    let task = &self.plan[index];
+    let is_requested_task = index == self.plan.len() - 1;
+    // only the requested task gets the additional arguments and uses --cwd
+    let (cwd, argv) = if is_requested_task {
+      (self.cwd.to_path_buf(), self.cli_options.argv())
+    } else {
+      (
+        normalize_path(task.dir_url.to_file_path().unwrap()),
+        &[][..],
+      )
+    };
+
+    let task_key = format!("{}#{}", task.dir_url, task.name);
+    let inputs_hash = match task.options.filter(|o| !o.inputs.is_empty()) {
+      Some(options) => Some(hash_task_inputs(task, options, &cwd, argv)?),
+      None => None,
+    };
+    if let Some(inputs_hash) = inputs_hash {
+      if self.task_cache.get_inputs_hash(&task_key) == Some(inputs_hash) {
+        log::info!(
+          "{} {} {}",
+          colors::green("Task"),
+          colors::cyan(task.name),
+          colors::gray("(skipped, inputs unchanged)"),
+        );
+        return Ok(0);
+      }
+    }
+
+    let exit_code = if self.plan.len() > 1 {
+      let prefix_width =
+        self.plan.iter().map(|task| task.name.len()).max().unwrap();
+      let prefix = format!(
+        "{} ",
+        colors::cyan(format!("[{:width$}]", task.name, width = prefix_width))
+      );
+      let (stdout_reader, stdout_writer) = deno_task_shell::pipe();
+      let (stderr_reader, stderr_writer) = deno_task_shell::pipe();
+      let stdout_handle =
+        spawn_prefixed_output(stdout_reader, prefix.clone(), false);
+      let stderr_handle = spawn_prefixed_output(stderr_reader, prefix, true);
+      let exit_code = run_task(RunTaskOptions {
+        task_name: task.name,
+        script: task.script,
+        cwd: &cwd,
+        argv,
+        env_vars: self.env_vars.clone(),
+        custom_commands: self.custom_commands.clone(),
+        npm_resolver: self.npm_resolver,
+        cli_options: self.cli_options,
+        shell: self.shell,
+        stdio: Some(task_runner::TaskIo {
+          stdout: stdout_writer,
+          stderr: stderr_writer,
+        }),
+      })
+      .await?;
+      stdout_handle.await??;
+      stderr_handle.await??;
+      exit_code
+    } else {
+      run_task(RunTaskOptions {
+        task_name: task.name,
+        script: task.script,
+        cwd: &cwd,
+        argv,
+        env_vars: self.env_vars.clone(),
+        custom_commands: self.custom_commands.clone(),
+        npm_resolver: self.npm_resolver,
+        cli_options: self.cli_options,
+        shell: self.shell,
+        stdio: None,
+      })
+      .await?
+    };
+
+    if exit_code == 0 {
+      if let Some(inputs_hash) = inputs_hash {
+        self.task_cache.set_inputs_hash(&task_key, inputs_hash);
+      }
+    }
+    Ok(exit_code)
+  }
+}
+
+/// Hashes the command of a task along with the paths and contents of the
+/// input files it declares.
+fn hash_task_inputs(
+  task: &PlannedTask,
+  options: &TaskOptions,
+  cwd: &Path,
+  argv: &[String],
+) -> Result<CacheDBHash, AnyError> {
+  let base = normalize_path(task.dir_url.to_file_path().unwrap());
+  let include = PathOrPatternSet::from_include_relative_path_or_patterns(
+    &base,
+    &options.inputs,
+  )?;
+  let mut files = FileCollector::new(|_| true)
+    .ignore_git_folder()
+    .collect_file_patterns(
+      &deno_config::fs::RealDenoConfigFs,
+      FilePatterns {
+        base: base.clone(),
+        include: Some(include),
+        exclude: Default::default(),
+      },
+    )?;
+  files.sort();
+  let mut hasher = FastInsecureHasher::new_deno_versioned();
+  hasher.write_hashable(task.script);
+  hasher.write_hashable(argv);
+  hasher.write_hashable(cwd);
+  for file in files {
+    let data = std::fs::read(&file)
+      .with_context(|| format!("Failed reading {}", file.display()))?;
+    hasher.write_hashable(&file);
+    hasher.write(&data);
+  }
+  Ok(CacheDBHash::new(hasher.finish()))
+}
+
+/// Writes the output of a task to the stdout or stderr of the current process,
+/// prefixing each line.
+fn spawn_prefixed_output(
+  reader: ShellPipeReader,
+  prefix: String,
+  is_stderr: bool,
+) -> tokio::task::JoinHandle<Result<(), AnyError>> {
+  tokio::task::spawn_blocking(move || {
+    let mut writer = PrefixedLineWriter {
+      prefix,
+      is_stderr,
+      line: Vec::new(),
+    };
+    reader.pipe_to(&mut writer)?;
+    writer.finish()?;
+    Ok(())
+  })
+}
+
+struct PrefixedLineWriter {
+  prefix: String,
+  is_stderr: bool,
+  /// The incomplete line that was written so far.
+  line: Vec<u8>,
+}
+
+impl PrefixedLineWriter {
+  fn write_line(&mut self) -> std::io::Result<()> {
+    let mut output = Vec::with_capacity(self.prefix.len() + self.line.len());
+    output.extend_from_slice(self.prefix.as_bytes());
+    output.append(&mut self.line);
+    // write the whole line at once so lines of tasks don't get mixed up
+    if self.is_stderr {
+      std::io::stderr().lock().write_all(&output)
+    } else {
+      std::io::stdout().lock().write_all(&output)
+    }
+  }
+
+  fn finish(&mut self) -> std::io::Result<()> {
+    if !self.line.is_empty() {
+      self.line.push(b'\n');
+      self.write_line()?;
+    }
+    Ok(())
+  }
+}
+
+impl Write for PrefixedLineWriter {
+  fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+    for byte in buf {
+      self.line.push(*byte);
+      if *byte == b'\n' {
+        self.write_line()?;
+      }
+    }
+    Ok(buf.len())
+  }
+
+  fn flush(&mut self) -> std::io::Result<()> {
+    Ok(())
+  }
+}
+
 struct RunTaskOptions<'a> {
   task_name: &'a str,
   script: &'a str,
   cwd: &'a Path,
+  argv: &'a [String],
   env_vars: HashMap<String, String>,
   custom_commands: HashMap<String, Rc<dyn ShellCommand>>,
   npm_resolver: &'a dyn CliNpmResolver,
   cli_options: &'a CliOptions,
+  stdio: Option<task_runner::TaskIo>,
+  shell: TaskShell,
 }
 
 async fn run_task(opts: RunTaskOptions<'_>) -> Result<i32, AnyError> {
@@ -168,15 +1028,18 @@ async fn run_task(opts: RunTaskOptions<'_>) -> Result<i32, AnyError> {
     task_name,
     script,
     cwd,
+    argv,
     env_vars,
     custom_commands,
     npm_resolver,
     cli_options,
+    stdio,
+    shell,
   } = opts;
 
   output_task(
     opts.task_name,
-    &task_runner::get_script_with_args(script, cli_options.argv()),
+    &task_runner::get_script_with_args(script, argv),
   );
 
   task_runner::run_task(task_runner::RunTaskOptions {
@@ -186,10 +1049,12 @@ async fn run_task(opts: RunTaskOptions<'_>) -> Result<i32, AnyError> {
     env_vars,
     custom_commands,
     init_cwd: opts.cli_options.initial_cwd(),
-    argv: cli_options.argv(),
+    argv,
     root_node_modules_dir: npm_resolver
       .root_node_modules_path()
       .map(|p| p.as_path()),
+    stdio,
+    shell,
   })
   .await
 }
@@ -207,6 +1072,7 @@ fn print_available_tasks(
   writer: &mut dyn std::io::Write,
   workspace_dir: &Arc<WorkspaceDirectory>,
   tasks_config: &WorkspaceTasksConfig,
+  task_options: &TaskOptionsByFolder,
 ) -> Result<(), std::io::Error> {
   writeln!(writer, "{}", colors::green("Available tasks:"))?;
   let is_cwd_root_dir = tasks_config.root.is_none();
@@ -224,17 +1090,22 @@ fn print_available_tasks(
       let Some(config) = maybe_config else {
         continue;
       };
-      for (is_root, is_deno, (key, task)) in config
+      for (is_root, is_deno, options, (key, task)) in config
         .deno_json
         .as_ref()
         .map(|config| {
           let is_root = !is_cwd_root_dir
             && config.folder_url
               == *workspace_dir.workspace.root_dir().as_ref();
-          config
-            .tasks
-            .iter()
-            .map(move |(k, t)| (is_root, true, (k, Cow::Borrowed(t))))
+          let options = task_options.get(&config.folder_url);
+          config.tasks.iter().map(move |(k, t)| {
+            (
+              is_root,
+              true,
+              options.and_then(|options| options.get(k)),
+              (k, Cow::Borrowed(t)),
+            )
+          })
         })
         .into_iter()
         .flatten()
@@ -247,7 +1118,12 @@ fn print_available_tasks(
                 && config.folder_url
                   == *workspace_dir.workspace.root_dir().as_ref();
               config.tasks.iter().map(move |(k, v)| {
-                (is_root, false, (k, Cow::Owned(Task::Definition(v.clone()))))
+                (
+                  is_root,
+                  false,
+                  None,
+                  (k, Cow::Owned(Task::Definition(v.clone()))),
+                )
               })
             })
             .into_iter()
@@ -277,8 +1153,19 @@ fn print_available_tasks(
           Task::Definition(definition) => definition,
           Task::Commented { definition, .. } => definition,
         };
+        let slash_slash = colors::italic_gray("//");
+        if let Some(description) =
+          options.and_then(|options| options.description.as_ref())
+        {
+          for line in description.lines() {
+            writeln!(
+              writer,
+              "    {slash_slash} {}",
+              colors::italic_gray(line)
+            )?;
+          }
+        }
         if let Task::Commented { comments, .. } = task.as_ref() {
-          let slash_slash = colors::italic_gray("//");
           for comment in comments {
             writeln!(
               writer,
@@ -288,6 +1175,18 @@ fn print_available_tasks(
           }
         }
         writeln!(writer, "    {definition}")?;
+        if let Some(options) =
+          options.filter(|options| !options.dependencies.is_empty())
+        {
+          writeln!(
+            writer,
+            "    {}",
+            colors::gray(format!(
+              "depends on: {}",
+              options.dependencies.join(", ")
+            ))
+          )?;
+        }
       }
     }
   }
@@ -5758,6 +5758,31 @@ declare namespace Deno {
      * The unit is seconds, with a default of 30.
      * Set to `0` to disable timeouts. */
     idleTimeout?: number;
+    /** Accept the client's offer of `permessage-deflate` compression, if it
+     * makes one. Pass an object to limit the parameters that are agreed to.
+     *
+     * @default {false} */
+    compression?: boolean | WebSocketCompressionOptions;
+  }
+
+  /** Parameters for the `permessage-deflate` WebSocket extension
+   * (RFC 7692), which compresses each message.
+   *
+   * @category Web Sockets */
+  export interface WebSocketCompressionOptions {
+    /** Limit the size of the LZ77 window the server compresses with, as a
+     * base 2 logarithm between 9 and 15. Smaller windows use less memory
+     * at the cost of compression ratio. */
+    serverMaxWindowBits?: number;
+    /** Limit the size of the LZ77 window the client compresses with, as a
+     * base 2 logarithm between 9 and 15. */
+    clientMaxWindowBits?: number;
+    /** Have the server start each message with a fresh compression context,
+     * so no memory is kept between messages. */
+    serverNoContextTakeover?: boolean;
+    /** Have the client start each message with a fresh compression context,
+     * so no memory is kept between messages. */
+    clientNoContextTakeover?: boolean;
   }
 
   /**
@@ -6285,11 +6310,36 @@ declare namespace Deno {
     ) => Response | Promise<Response>;
   }
 
+  /** Resource limits which can be set when calling {@linkcode Deno.serve}.
+   *
+   * @category HTTP Server
+   */
+  export interface ServeLimits {
+    /** The maximum number of connections that are open at the same time.
+     * Further clients wait until one of the open connections closes. */
+    maxConnections?: number;
+
+    /** The number of requests served on a single connection before the
+     * server asks the client to reconnect. HTTP/1.1 responses are sent with
+     * `Connection: close` and HTTP/2 connections receive a GOAWAY frame. */
+    maxRequestsPerConnection?: number;
+
+    /** The maximum size of a request's headers, in bytes. Must be at least
+     * 8192. */
+    maxHeaderSize?: number;
+
+    /** The maximum size of a request body, in bytes. Requests that declare a
+     * larger `Content-Length` are answered with `413 Payload Too Large`
+     * without calling the handler; streamed bodies fail once they grow past
+     * the limit. */
+    maxBodySize?: number;
+  }
+
   /** Options which can be set when calling {@linkcode Deno.serve}.
    *
    * @category HTTP Server
    */
-  export interface ServeOptions {
+  export interface ServeOptions extends ServeLimits {
     /** The port to listen on.
      *
      * Set to `0` to listen on any available port.
@@ -6361,7 +6411,7 @@ declare namespace Deno {
   }
 
   /** @category HTTP Server */
-  export interface ServeUnixOptions {
+  export interface ServeUnixOptions extends ServeLimits {
     /** The unix domain socket path to listen on. */
     path: string;
 
@@ -6434,6 +6484,94 @@ declare namespace Deno {
      * while pending requests will be allowed to finish.
      */
     shutdown(): Promise<void>;
+
+    /** Drain the server for a rolling deploy. No more new connections are
+     * accepted, idle keep-alive connections are closed and HTTP/2 clients are
+     * sent a GOAWAY frame. Pending requests are allowed to finish until the
+     * optional `timeout` (in milliseconds) passes, after which the remaining
+     * connections are closed and reported.
+     *
+     * ```ts
+     * const server = Deno.serve((_req) => new Response("Hello, world"));
+     * Deno.addSignalListener("SIGTERM", async () => {
+     *   const report = await server.drain({ timeout: 10_000 });
+     *   if (report.timedOut) {
+     *     console.warn(`Cut off ${report.abortedRequests} requests`);
+     *   }
+     * });
+     * ```
+     */
+    drain(options?: { timeout?: number }): Promise<ServeDrainReport>;
+
+    /** Returns a snapshot of the connection and request counters of this
+     * server. After the server has finished, the final values are returned.
+     *
+     * ```ts
+     * const server = Deno.serve((_req) => new Response("Hello, world"));
+     * setInterval(() => {
+     *   const { inFlightRequests, activeConnections } = server.metrics();
+     *   console.log({ inFlightRequests, activeConnections });
+     * }, 10_000);
+     * ```
+     */
+    metrics(): ServeMetrics;
+  }
+
+  /** The outcome of {@linkcode Deno.HttpServer.drain}.
+   *
+   * @category HTTP Server
+   */
+  export interface ServeDrainReport {
+    /** Whether the timeout passed before all pending requests finished. */
+    timedOut: boolean;
+    /** Number of requests that were still in flight when the timeout
+     * passed. */
+    abortedRequests: number;
+    /** Number of connections that were still open when the timeout
+     * passed. */
+    abortedConnections: number;
+  }
+
+  /** Connection and request counters of a {@linkcode Deno.HttpServer}, as
+   * returned by {@linkcode Deno.HttpServer.metrics}.
+   *
+   * @category HTTP Server
+   */
+  export interface ServeMetrics {
+    /** Number of currently open connections. */
+    activeConnections: number;
+    /** Number of connections accepted since the server started. */
+    totalConnections: number;
+    /** Number of currently open connections speaking HTTP/1.x. */
+    http1Connections: number;
+    /** Number of currently open connections speaking HTTP/2. */
+    http2Connections: number;
+    /** Number of requests that have been received but whose response has not
+     * been fully sent yet. */
+    inFlightRequests: number;
+    /** Number of requests received since the server started. */
+    totalRequests: number;
+    /** Number of HTTP/1.x requests received since the server started. */
+    http1Requests: number;
+    /** Number of HTTP/2 requests received since the server started. */
+    http2Requests: number;
+    /** Bytes read from client connections. For TLS connections this counts
+     * the decrypted HTTP traffic. */
+    bytesReceived: number;
+    /** Bytes written to client connections. For TLS connections this counts
+     * the HTTP traffic before encryption. */
+    bytesSent: number;
+    /** Histogram of the time from receiving a request until its response was
+     * fully sent. */
+    requestDuration: {
+      /** Pairs of upper bound in seconds and the cumulative number of
+       * requests that completed within it. */
+      buckets: [number, number][];
+      /** Number of completed requests. */
+      count: number;
+      /** Total duration of all completed requests, in seconds. */
+      sum: number;
+    };
   }
 
   /**
@@ -6717,4 +6855,146 @@ declare namespace Deno {
         | (ServeTlsOptions & TlsCertifiedKeyOptions)
       ),
   ): HttpServer<Deno.NetAddr>;
+
+  /** Types for writing lint plugins. A lint plugin is a local module listed
+   * under `"lintPlugins"` in the config file, whose default export is a
+   * {@linkcode Deno.lint.Plugin}.
+   *
+   * ```json
+   * {
+   *   "lintPlugins": ["./lint/my_plugin.ts"]
+   * }
+   * ```
+   *
+   * Plugins run in an isolate without the `Deno` namespace or any other web
+   * APIs, so they can't access the file system or the network.
+   *
+   * @category Linter
+   */
+  export namespace lint {
+    /** The position of a node in the AST. Positions are byte offsets, which
+     * don't start at zero, so only use them for reporting diagnostics and
+     * fixes.
+     *
+     * @category Linter
+     */
+    export interface Span {
+      start: number;
+      end: number;
+    }
+
+    /** A node of the AST. The AST is the one produced by
+     * [swc](https://swc.rs), so `type` is for example `"Identifier"` or
+     * `"CallExpression"`.
+     *
+     * @category Linter
+     */
+    export interface Node {
+      type: string;
+      span: Span;
+      /** The closest enclosing node, or `null` for the root of the AST. */
+      parent: Node | null;
+      // deno-lint-ignore no-explicit-any
+      [key: string]: any;
+    }
+
+    /** A single text change of a fix. Replaces the text of the node or range
+     * with `text`.
+     *
+     * @category Linter
+     */
+    export interface FixChange {
+      node?: Node;
+      range?: Span;
+      text: string;
+    }
+
+    /** A problem found by a rule.
+     *
+     * @category Linter
+     */
+    export interface ReportDescriptor {
+      /** The node to highlight. Either this or `range` is required. */
+      node?: Node;
+      /** The range to highlight. Either this or `node` is required. */
+      range?: Span;
+      message: string;
+      hint?: string;
+      /** Changes that fix the problem. They are applied by `deno lint --fix`
+       * and offered as a quick fix in editors. */
+      fix?: FixChange | FixChange[];
+      /** Describes the fix to the user. */
+      fixDescription?: string;
+    }
+
+    /** Gives a rule access to the file being linted.
+     *
+     * @category Linter
+     */
+    export interface RuleContext {
+      /** The code of the rule, made of the plugin and rule name, e.g.
+       * `"my-plugin/no-foo"`. */
+      readonly id: string;
+      readonly specifier: string;
+      readonly source: string;
+      /** Returns the source text of a node or range. */
+      getText(nodeOrRange: Node | Span): string;
+      /** Reports a problem. */
+      report(descriptor: ReportDescriptor): void;
+    }
+
+    /** Functions that are called for the nodes of the given type while the AST
+     * is traversed. Keys ending in `:exit` are called after the children of a
+     * node have been visited.
+     *
+     * @category Linter
+     */
+    export interface Visitor {
+      [type: string]: ((node: Node) => void) | undefined;
+    }
+
+    /** A lint rule.
+     *
+     * @category Linter
+     */
+    export interface Rule {
+      /** URL of the documentation of the rule. Defaults to the plugin
+       * module. */
+      docsUrl?: string;
+      /** Called once for every linted file. */
+      create(context: RuleContext): Visitor;
+    }
+
+    /** A lint plugin. Its rules are reported as `<plugin name>/<rule name>`.
+     *
+     * ```ts
+     * export default {
+     *   name: "my-plugin",
+     *   rules: {
+     *     "no-foo": {
+     *       create(context) {
+     *         return {
+     *           Identifier(node) {
+     *             if (node.value === "foo") {
+     *               context.report({
+     *                 node,
+     *                 message: "Don't name things foo",
+     *                 fix: { node, text: "bar" },
+     *               });
+     *             }
+     *           },
+     *         };
+     *       },
+     *     },
+     *   },
+     * } satisfies Deno.lint.Plugin;
+     * ```
+     *
+     * @category Linter
+     */
+    export interface Plugin {
+      name: string;
+      rules: Record<string, Rule>;
+    }
+  }
 }
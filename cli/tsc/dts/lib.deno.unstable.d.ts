@@ -981,6 +981,29 @@ declare namespace Deno {
      * @default {false}
      */
     allowHost?: boolean;
+    /** Timeout in milliseconds for establishing a TCP connection. By default
+     * connecting is only bounded by the operating system. */
+    connectTimeout?: number;
+    /** Timeout in milliseconds for the TLS handshake with the server. */
+    tlsHandshakeTimeout?: number;
+    /** Timeout in milliseconds for waiting on the response headers and on
+     * each chunk of the response body. A request that stays silent for longer
+     * fails with an error. */
+    idleTimeout?: number;
+    /** A map of host names to the IP addresses that connections to them
+     * should use instead of resolving the name via DNS, similar to curl's
+     * `--resolve`. Connecting to these addresses requires net permission for
+     * them as well.
+     *
+     * ```ts
+     * const client = Deno.createHttpClient({
+     *   dnsOverrides: { "api.example.com": ["127.0.0.1"] },
+     * });
+     * ```
+     */
+    dnsOverrides?: Record<string, string[]>;
+    /** The local IP address to bind outgoing connections to. */
+    localAddress?: string;
   }
 
   /** **UNSTABLE**: New API, yet to be vetted.
@@ -2341,6 +2364,11 @@ declare interface WebSocketStreamOptions {
   protocols?: string[];
   signal?: AbortSignal;
   headers?: HeadersInit;
+  /** Offer `permessage-deflate` compression to the server. Pass `true` to
+   * use the default parameters, or an object to request specific ones.
+   *
+   * @default {false} */
+  compression?: boolean | Deno.WebSocketCompressionOptions;
 }
 
 /** **UNSTABLE**: New API, yet to be vetted.
@@ -0,0 +1,153 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Writer for the checkstyle XML format, as understood by most CI servers.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckstyleSeverity {
+  Error,
+  Warning,
+  Info,
+}
+
+impl CheckstyleSeverity {
+  fn as_str(&self) -> &'static str {
+    match self {
+      CheckstyleSeverity::Error => "error",
+      CheckstyleSeverity::Warning => "warning",
+      CheckstyleSeverity::Info => "info",
+    }
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct CheckstyleError {
+  /// 1-indexed line number.
+  pub line: Option<usize>,
+  /// 1-indexed column number.
+  pub column: Option<usize>,
+  pub severity: CheckstyleSeverity,
+  pub message: String,
+  pub source: String,
+}
+
+/// Collects errors grouped by file. Files are written out sorted by name and
+/// their errors by position, so that the output is deterministic.
+#[derive(Default)]
+pub struct CheckstyleReport {
+  files: BTreeMap<String, Vec<CheckstyleError>>,
+}
+
+impl CheckstyleReport {
+  pub fn add_error(&mut self, file_name: String, error: CheckstyleError) {
+    self.files.entry(file_name).or_default().push(error);
+  }
+
+  pub fn to_xml(&mut self) -> String {
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml.push_str("<checkstyle version=\"4.3\">\n");
+    for (file_name, errors) in &mut self.files {
+      errors.sort_by_key(|error| (error.line, error.column));
+      writeln!(xml, "  <file name=\"{}\">", escape(file_name)).unwrap();
+      for error in errors.iter() {
+        xml.push_str("    <error");
+        if let Some(line) = error.line {
+          write!(xml, " line=\"{line}\"").unwrap();
+        }
+        if let Some(column) = error.column {
+          write!(xml, " column=\"{column}\"").unwrap();
+        }
+        writeln!(
+          xml,
+          " severity=\"{}\" message=\"{}\" source=\"{}\" />",
+          error.severity.as_str(),
+          escape(&error.message),
+          escape(&error.source),
+        )
+        .unwrap();
+      }
+      xml.push_str("  </file>\n");
+    }
+    xml.push_str("</checkstyle>");
+    xml
+  }
+}
+
+fn escape(text: &str) -> String {
+  let mut escaped = String::with_capacity(text.len());
+  for c in text.chars() {
+    match c {
+      '&' => escaped.push_str("&amp;"),
+      '<' => escaped.push_str("&lt;"),
+      '>' => escaped.push_str("&gt;"),
+      '"' => escaped.push_str("&quot;"),
+      '\'' => escaped.push_str("&apos;"),
+      '\n' => escaped.push_str("&#10;"),
+      '\r' => escaped.push_str("&#13;"),
+      '\t' => escaped.push_str("&#9;"),
+      // not allowed in XML 1.0
+      c if (c as u32) < 0x20 => {}
+      c => escaped.push(c),
+    }
+  }
+  escaped
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn writes_sorted_and_escaped() {
+    let mut report = CheckstyleReport::default();
+    report.add_error(
+      "/b.ts".to_string(),
+      CheckstyleError {
+        line: Some(2),
+        column: Some(1),
+        severity: CheckstyleSeverity::Error,
+        message: "Type 'string' is not assignable to type \"number\"."
+          .to_string(),
+        source: "TS2322".to_string(),
+      },
+    );
+    report.add_error(
+      "/a & b.ts".to_string(),
+      CheckstyleError {
+        line: Some(3),
+        column: Some(5),
+        severity: CheckstyleSeverity::Warning,
+        message: "a <b>\nc".to_string(),
+        source: "no-var".to_string(),
+      },
+    );
+    report.add_error(
+      "/a & b.ts".to_string(),
+      CheckstyleError {
+        line: None,
+        column: None,
+        severity: CheckstyleSeverity::Error,
+        message: "failed".to_string(),
+        source: "no-var".to_string(),
+      },
+    );
+    assert_eq!(
+      report.to_xml(),
+      concat!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n",
+        "<checkstyle version=\"4.3\">\n",
+        "  <file name=\"/a &amp; b.ts\">\n",
+        "    <error severity=\"error\" message=\"failed\" source=\"no-var\" />\n",
+        "    <error line=\"3\" column=\"5\" severity=\"warning\" message=\"a &lt;b&gt;&#10;c\" source=\"no-var\" />\n",
+        "  </file>\n",
+        "  <file name=\"/b.ts\">\n",
+        "    <error line=\"2\" column=\"1\" severity=\"error\" message=\"Type &apos;string&apos; is not assignable to type &quot;number&quot;.\" source=\"TS2322\" />\n",
+        "  </file>\n",
+        "</checkstyle>",
+      )
+    );
+  }
+}
@@ -1,6 +1,7 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
 // Note: Only add code in this folder that has no application specific logic
+pub mod checkstyle;
 pub mod checksum;
 pub mod console;
 pub mod diff;
@@ -12,6 +13,7 @@ pub mod logger;
 pub mod path;
 pub mod progress_bar;
 pub mod result;
+pub mod sarif;
 pub mod sync;
 pub mod text_encoding;
 pub mod unix;
@@ -0,0 +1,328 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Writer for the subset of SARIF 2.1.0 needed to report diagnostics.
+//! https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+
+use std::collections::HashMap;
+
+use deno_core::serde_json;
+use serde::Serialize;
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const VERSION: &str = "2.1.0";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SarifLevel {
+  Error,
+  Warning,
+  Note,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SarifRule {
+  pub id: String,
+  pub short_description: Option<String>,
+  pub help_uri: Option<String>,
+  pub tags: Vec<String>,
+}
+
+/// A 1-indexed region of a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRegion {
+  pub start_line: usize,
+  pub start_column: usize,
+  pub end_line: usize,
+  pub end_column: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct SarifResult {
+  pub rule_id: String,
+  pub level: SarifLevel,
+  pub message: String,
+  pub uri: Option<String>,
+  pub region: Option<SarifRegion>,
+}
+
+/// Collects rules and results of a single tool run.
+pub struct SarifLog {
+  tool_name: String,
+  rules: Vec<SarifRule>,
+  rule_indexes: HashMap<String, usize>,
+  results: Vec<SarifResult>,
+  notifications: Vec<(String, Option<String>)>,
+}
+
+impl SarifLog {
+  pub fn new(tool_name: impl Into<String>) -> Self {
+    Self {
+      tool_name: tool_name.into(),
+      rules: Vec::new(),
+      rule_indexes: HashMap::new(),
+      results: Vec::new(),
+      notifications: Vec::new(),
+    }
+  }
+
+  /// Adds the metadata of a rule, unless a rule with the same id was already
+  /// added.
+  pub fn add_rule(&mut self, rule: SarifRule) {
+    if !self.rule_indexes.contains_key(&rule.id) {
+      self.rule_indexes.insert(rule.id.clone(), self.rules.len());
+      self.rules.push(rule);
+    }
+  }
+
+  /// Adds a result. Rules that weren't added up front are added without
+  /// any metadata.
+  pub fn add_result(&mut self, result: SarifResult) {
+    if !self.rule_indexes.contains_key(&result.rule_id) {
+      self.add_rule(SarifRule {
+        id: result.rule_id.clone(),
+        ..Default::default()
+      });
+    }
+    self.results.push(result);
+  }
+
+  /// Records a problem with the tool run itself, for example a file that
+  /// couldn't be read.
+  pub fn add_notification(&mut self, message: String, uri: Option<String>) {
+    self.notifications.push((message, uri));
+  }
+
+  pub fn to_json(&self) -> String {
+    let rules = self
+      .rules
+      .iter()
+      .map(|rule| RuleJson {
+        id: &rule.id,
+        short_description: rule
+          .short_description
+          .as_deref()
+          .map(|text| MessageJson { text }),
+        help_uri: rule.help_uri.as_deref(),
+        properties: (!rule.tags.is_empty())
+          .then_some(PropertiesJson { tags: &rule.tags }),
+      })
+      .collect();
+    let results = self
+      .results
+      .iter()
+      .map(|result| ResultJson {
+        rule_id: &result.rule_id,
+        rule_index: self.rule_indexes[&result.rule_id],
+        level: result.level,
+        message: MessageJson {
+          text: &result.message,
+        },
+        locations: location_json(result.uri.as_deref(), result.region),
+      })
+      .collect();
+    let notifications = self
+      .notifications
+      .iter()
+      .map(|(message, uri)| NotificationJson {
+        level: SarifLevel::Error,
+        message: MessageJson { text: message },
+        locations: location_json(uri.as_deref(), None),
+      })
+      .collect::<Vec<_>>();
+    let log = LogJson {
+      schema: SCHEMA,
+      version: VERSION,
+      runs: vec![RunJson {
+        tool: ToolJson {
+          driver: DriverJson {
+            name: &self.tool_name,
+            information_uri: "https://deno.com",
+            version: crate::version::deno(),
+            rules,
+          },
+        },
+        invocations: vec![InvocationJson {
+          execution_successful: notifications.is_empty(),
+          tool_execution_notifications: notifications,
+        }],
+        results,
+      }],
+    };
+    serde_json::to_string_pretty(&log).unwrap()
+  }
+}
+
+fn location_json(
+  uri: Option<&str>,
+  region: Option<SarifRegion>,
+) -> Vec<LocationJson> {
+  match uri {
+    Some(uri) => vec![LocationJson {
+      physical_location: PhysicalLocationJson {
+        artifact_location: ArtifactLocationJson { uri },
+        region,
+      },
+    }],
+    None => Vec::new(),
+  }
+}
+
+#[derive(Serialize)]
+struct LogJson<'a> {
+  #[serde(rename = "$schema")]
+  schema: &'static str,
+  version: &'static str,
+  runs: Vec<RunJson<'a>>,
+}
+
+#[derive(Serialize)]
+struct RunJson<'a> {
+  tool: ToolJson<'a>,
+  invocations: Vec<InvocationJson<'a>>,
+  results: Vec<ResultJson<'a>>,
+}
+
+#[derive(Serialize)]
+struct ToolJson<'a> {
+  driver: DriverJson<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DriverJson<'a> {
+  name: &'a str,
+  information_uri: &'static str,
+  version: &'static str,
+  rules: Vec<RuleJson<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RuleJson<'a> {
+  id: &'a str,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  short_description: Option<MessageJson<'a>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  help_uri: Option<&'a str>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  properties: Option<PropertiesJson<'a>>,
+}
+
+#[derive(Serialize)]
+struct PropertiesJson<'a> {
+  tags: &'a [String],
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InvocationJson<'a> {
+  execution_successful: bool,
+  tool_execution_notifications: Vec<NotificationJson<'a>>,
+}
+
+#[derive(Serialize)]
+struct NotificationJson<'a> {
+  level: SarifLevel,
+  message: MessageJson<'a>,
+  locations: Vec<LocationJson<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ResultJson<'a> {
+  rule_id: &'a str,
+  rule_index: usize,
+  level: SarifLevel,
+  message: MessageJson<'a>,
+  locations: Vec<LocationJson<'a>>,
+}
+
+#[derive(Serialize)]
+struct MessageJson<'a> {
+  text: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LocationJson<'a> {
+  physical_location: PhysicalLocationJson<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocationJson<'a> {
+  artifact_location: ArtifactLocationJson<'a>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  region: Option<SarifRegion>,
+}
+
+#[derive(Serialize)]
+struct ArtifactLocationJson<'a> {
+  uri: &'a str,
+}
+
+#[cfg(test)]
+mod tests {
+  use deno_core::serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn results_reference_rules() {
+    let mut log = SarifLog::new("deno lint");
+    log.add_rule(SarifRule {
+      id: "no-var".to_string(),
+      short_description: Some("Disallows var".to_string()),
+      help_uri: Some("https://lint.deno.land/rules/no-var".to_string()),
+      tags: vec!["recommended".to_string()],
+    });
+    log.add_result(SarifResult {
+      rule_id: "my-plugin/no-foo".to_string(),
+      level: SarifLevel::Warning,
+      message: "foo".to_string(),
+      uri: Some("file:///a.ts".to_string()),
+      region: Some(SarifRegion {
+        start_line: 1,
+        start_column: 7,
+        end_line: 1,
+        end_column: 10,
+      }),
+    });
+    let value: serde_json::Value =
+      serde_json::from_str(&log.to_json()).unwrap();
+    let run = &value["runs"][0];
+    assert_eq!(
+      run["tool"]["driver"]["rules"],
+      json!([{
+        "id": "no-var",
+        "shortDescription": { "text": "Disallows var" },
+        "helpUri": "https://lint.deno.land/rules/no-var",
+        "properties": { "tags": ["recommended"] },
+      }, {
+        "id": "my-plugin/no-foo",
+      }])
+    );
+    assert_eq!(
+      run["results"],
+      json!([{
+        "ruleId": "my-plugin/no-foo",
+        "ruleIndex": 1,
+        "level": "warning",
+        "message": { "text": "foo" },
+        "locations": [{
+          "physicalLocation": {
+            "artifactLocation": { "uri": "file:///a.ts" },
+            "region": {
+              "startLine": 1,
+              "startColumn": 7,
+              "endLine": 1,
+              "endColumn": 10,
+            },
+          },
+        }],
+      }])
+    );
+    assert_eq!(run["invocations"][0]["executionSuccessful"], json!(true));
+  }
+}
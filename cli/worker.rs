@@ -142,6 +142,7 @@ struct SharedWorkerState {
   code_cache: Option<Arc<dyn code_cache::CodeCache>>,
   serve_port: Option<u16>,
   serve_host: Option<String>,
+  serve_parallel: bool,
 }
 
 impl SharedWorkerState {
@@ -422,6 +423,7 @@ impl CliMainWorkerFactory {
     node_ipc: Option<i64>,
     serve_port: Option<u16>,
     serve_host: Option<String>,
+    serve_parallel: bool,
     enable_future_features: bool,
     disable_deprecated_api_warning: bool,
     verbose_deprecated_api_warning: bool,
@@ -448,6 +450,7 @@ impl CliMainWorkerFactory {
         node_ipc,
         serve_port,
         serve_host,
+        serve_parallel,
         enable_future_features,
         disable_deprecated_api_warning,
         verbose_deprecated_api_warning,
@@ -592,6 +595,7 @@ impl CliMainWorkerFactory {
         mode,
         serve_port: shared.serve_port,
         serve_host: shared.serve_host.clone(),
+        serve_parallel: shared.serve_parallel,
       },
       extensions: custom_extensions,
       startup_snapshot: crate::js::deno_isolate_init(),
@@ -790,6 +794,7 @@ fn create_web_worker_callback(
         mode,
         serve_port: shared.serve_port,
         serve_host: shared.serve_host.clone(),
+        serve_parallel: shared.serve_parallel,
       },
       extensions: vec![],
       startup_snapshot: crate::js::deno_isolate_init(),
@@ -0,0 +1,82 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::io;
+use std::net::IpAddr;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+
+use hyper_util::client::legacy::connect::dns::GaiResolver;
+use hyper_util::client::legacy::connect::dns::Name;
+use tower_service::Service;
+
+pub type DnsOverrides = HashMap<String, Vec<IpAddr>>;
+
+/// A resolver that answers from a fixed map of host overrides (similar to
+/// curl's `--resolve`) and falls back to the system resolver for every other
+/// host.
+#[derive(Clone, Debug)]
+pub(crate) struct Resolver {
+  overrides: Arc<DnsOverrides>,
+  gai: GaiResolver,
+}
+
+impl Resolver {
+  pub(crate) fn new(overrides: Arc<DnsOverrides>) -> Self {
+    Self {
+      overrides,
+      gai: GaiResolver::new(),
+    }
+  }
+}
+
+type Addrs = std::vec::IntoIter<SocketAddr>;
+type ResolveFuture = Pin<Box<dyn Future<Output = io::Result<Addrs>> + Send>>;
+
+impl Service<Name> for Resolver {
+  type Response = Addrs;
+  type Error = io::Error;
+  type Future = ResolveFuture;
+
+  fn poll_ready(
+    &mut self,
+    cx: &mut Context<'_>,
+  ) -> Poll<Result<(), Self::Error>> {
+    self.gai.poll_ready(cx)
+  }
+
+  fn call(&mut self, name: Name) -> Self::Future {
+    if let Some(addrs) = lookup(&self.overrides, name.as_str()) {
+      // The port is filled in by the connector after resolution.
+      let addrs = addrs
+        .iter()
+        .map(|ip| SocketAddr::new(*ip, 0))
+        .collect::<Vec<_>>();
+      return Box::pin(std::future::ready(Ok(addrs.into_iter())));
+    }
+
+    let resolving = self.gai.call(name);
+    Box::pin(async move {
+      let addrs = resolving.await?;
+      Ok(addrs.collect::<Vec<_>>().into_iter())
+    })
+  }
+}
+
+/// Look up the pinned addresses for `host`. Override keys are expected to be
+/// lowercased already.
+pub(crate) fn lookup<'a>(
+  overrides: &'a DnsOverrides,
+  host: &str,
+) -> Option<&'a [IpAddr]> {
+  if overrides.is_empty() {
+    return None;
+  }
+  overrides
+    .get(&host.to_ascii_lowercase())
+    .map(|addrs| addrs.as_slice())
+}
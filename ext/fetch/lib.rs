@@ -1,5 +1,6 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+mod dns;
 mod fs_fetch_handler;
 mod proxy;
 #[cfg(test)]
@@ -9,6 +10,7 @@ use std::borrow::Cow;
 use std::cell::RefCell;
 use std::cmp::min;
 use std::convert::From;
+use std::net::IpAddr;
 use std::path::Path;
 use std::path::PathBuf;
 use std::pin::Pin;
@@ -16,6 +18,7 @@ use std::rc::Rc;
 use std::sync::Arc;
 use std::task::Context;
 use std::task::Poll;
+use std::time::Duration;
 
 use deno_core::anyhow::anyhow;
 use deno_core::anyhow::Error;
@@ -75,9 +78,11 @@ use tokio::io::AsyncReadExt;
 use tokio::io::AsyncWriteExt;
 use tower::ServiceExt;
 use tower_http::decompression::Decompression;
+use tower_http::timeout::TimeoutBody;
 
 // Re-export data_url
 pub use data_url;
+pub use dns::DnsOverrides;
 pub use proxy::basic_auth;
 
 pub use fs_fetch_handler::FsFetchHandler;
@@ -225,6 +230,11 @@ pub fn create_client_from_options(
       pool_idle_timeout: None,
       http1: true,
       http2: true,
+      connect_timeout: None,
+      tls_handshake_timeout: None,
+      idle_timeout: None,
+      dns_overrides: DnsOverrides::new(),
+      local_address: None,
     },
   )
 }
@@ -386,6 +396,19 @@ where
     "http" | "https" => {
       let permissions = state.borrow_mut::<FP>();
       permissions.check_net_url(&url, "fetch()")?;
+      // Connections to a host with a DNS override go to the pinned
+      // addresses, so those have to be allowed as well.
+      if let Some(addrs) =
+        url.host_str().and_then(|host| client.dns_override(host))
+      {
+        for addr in addrs {
+          let mut pinned_url = url.clone();
+          pinned_url
+            .set_ip_host(*addr)
+            .map_err(|_| type_error("Invalid URL"))?;
+          permissions.check_net_url(&pinned_url, "fetch()")?;
+        }
+      }
 
       let maybe_authority = extract_authority(&mut url);
       let uri = url
@@ -881,6 +904,12 @@ pub struct CreateHttpClientArgs {
   http2: bool,
   #[serde(default)]
   allow_host: bool,
+  connect_timeout: Option<u64>,
+  tls_handshake_timeout: Option<u64>,
+  idle_timeout: Option<u64>,
+  #[serde(default)]
+  dns_overrides: DnsOverrides,
+  local_address: Option<IpAddr>,
 }
 
 fn default_true() -> bool {
@@ -933,6 +962,11 @@ where
       ),
       http1: args.http1,
       http2: args.http2,
+      connect_timeout: args.connect_timeout,
+      tls_handshake_timeout: args.tls_handshake_timeout,
+      idle_timeout: args.idle_timeout,
+      dns_overrides: args.dns_overrides,
+      local_address: args.local_address,
     },
   )?;
 
@@ -953,6 +987,17 @@ pub struct CreateHttpClientOptions {
   pub pool_idle_timeout: Option<Option<u64>>,
   pub http1: bool,
   pub http2: bool,
+  /// Timeout in milliseconds for establishing the TCP connection.
+  pub connect_timeout: Option<u64>,
+  /// Timeout in milliseconds for the TLS handshake with the server.
+  pub tls_handshake_timeout: Option<u64>,
+  /// Timeout in milliseconds for receiving the response head and for each
+  /// subsequent chunk of the response body.
+  pub idle_timeout: Option<u64>,
+  /// Addresses to use for the given host names instead of resolving them.
+  pub dns_overrides: DnsOverrides,
+  /// Local address to bind outgoing connections to.
+  pub local_address: Option<IpAddr>,
 }
 
 impl Default for CreateHttpClientOptions {
@@ -967,6 +1012,11 @@ impl Default for CreateHttpClientOptions {
       pool_idle_timeout: None,
       http1: true,
       http2: true,
+      connect_timeout: None,
+      tls_handshake_timeout: None,
+      idle_timeout: None,
+      dns_overrides: DnsOverrides::new(),
+      local_address: None,
     }
   }
 }
@@ -999,8 +1049,19 @@ pub fn create_http_client(
   tls_config.alpn_protocols = alpn_protocols;
   let tls_config = Arc::from(tls_config);
 
-  let mut http_connector = HttpConnector::new();
+  let dns_overrides = Arc::new(
+    options
+      .dns_overrides
+      .into_iter()
+      .map(|(host, addrs)| (host.to_ascii_lowercase(), addrs))
+      .collect::<DnsOverrides>(),
+  );
+  let mut http_connector =
+    HttpConnector::new_with_resolver(dns::Resolver::new(dns_overrides.clone()));
   http_connector.enforce_http(false);
+  http_connector
+    .set_connect_timeout(options.connect_timeout.map(Duration::from_millis));
+  http_connector.set_local_address(options.local_address);
 
   let user_agent = user_agent
     .parse::<HeaderValue>()
@@ -1026,6 +1087,9 @@ pub fn create_http_client(
     proxies: proxies.clone(),
     tls: tls_config,
     tls_proxy: proxy_tls_config,
+    tls_handshake_timeout: options
+      .tls_handshake_timeout
+      .map(Duration::from_millis),
     user_agent: Some(user_agent.clone()),
   };
 
@@ -1034,9 +1098,7 @@ pub fn create_http_client(
   }
 
   if let Some(pool_idle_timeout) = options.pool_idle_timeout {
-    builder.pool_idle_timeout(
-      pool_idle_timeout.map(std::time::Duration::from_millis),
-    );
+    builder.pool_idle_timeout(pool_idle_timeout.map(Duration::from_millis));
   }
 
   match (options.http1, options.http2) {
@@ -1057,6 +1119,8 @@ pub fn create_http_client(
     inner: decompress,
     proxies,
     user_agent,
+    idle_timeout: options.idle_timeout.map(Duration::from_millis),
+    dns_overrides,
   })
 }
 
@@ -1074,9 +1138,11 @@ pub struct Client {
   // Used to check whether to include a proxy-authorization header
   proxies: Arc<proxy::Proxies>,
   user_agent: HeaderValue,
+  idle_timeout: Option<Duration>,
+  dns_overrides: Arc<DnsOverrides>,
 }
 
-type Connector = proxy::ProxyConnector<HttpConnector>;
+type Connector = proxy::ProxyConnector<HttpConnector<dns::Resolver>>;
 
 // clippy is wrong here
 #[allow(clippy::declare_interior_mutable_const)]
@@ -1098,8 +1164,24 @@ impl Client {
       req.headers_mut().insert(PROXY_AUTHORIZATION, auth.clone());
     }
 
-    let resp = self.inner.oneshot(req).await?;
-    Ok(resp.map(|b| b.map_err(|e| anyhow!(e)).boxed()))
+    let Some(idle_timeout) = self.idle_timeout else {
+      let resp = self.inner.oneshot(req).await?;
+      return Ok(resp.map(|b| b.map_err(|e| anyhow!(e)).boxed()));
+    };
+
+    let resp = tokio::time::timeout(idle_timeout, self.inner.oneshot(req))
+      .await
+      .map_err(|_| type_error("Timed out waiting for response"))??;
+    Ok(resp.map(|b| {
+      TimeoutBody::new(idle_timeout, b)
+        .map_err(|e| anyhow!(e))
+        .boxed()
+    }))
+  }
+
+  /// Returns the addresses that connections to `host` are pinned to, if any.
+  pub fn dns_override(&self, host: &str) -> Option<&[IpAddr]> {
+    dns::lookup(&self.dns_overrides, host)
   }
 }
 
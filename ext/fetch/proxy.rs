@@ -10,8 +10,10 @@ use std::pin::Pin;
 use std::sync::Arc;
 use std::task::Context;
 use std::task::Poll;
+use std::time::Duration;
 
 use deno_core::futures::TryFutureExt;
+use deno_tls::rustls::pki_types::ServerName;
 use deno_tls::rustls::ClientConfig as TlsConfig;
 
 use http::header::HeaderValue;
@@ -23,6 +25,8 @@ use hyper_util::client::legacy::connect::Connected;
 use hyper_util::client::legacy::connect::Connection;
 use hyper_util::rt::TokioIo;
 use ipnet::IpNet;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncWrite;
 use tokio::net::TcpStream;
 use tokio_rustls::client::TlsStream;
 use tokio_rustls::TlsConnector;
@@ -38,6 +42,8 @@ pub(crate) struct ProxyConnector<C> {
   /// TLS config when destination is a proxy
   /// Notably, does not include ALPN
   pub(crate) tls_proxy: Arc<TlsConfig>,
+  /// Upper bound for the TLS handshake with the destination
+  pub(crate) tls_handshake_timeout: Option<Duration>,
   pub(crate) user_agent: Option<HeaderValue>,
 }
 
@@ -451,6 +457,7 @@ where
     if let Some(intercept) = self.intercept(&orig_dst).cloned() {
       let is_https = orig_dst.scheme() == Some(&Scheme::HTTPS);
       let user_agent = self.user_agent.clone();
+      let handshake_timeout = self.tls_handshake_timeout;
       return match intercept.target {
         Target::Http {
           dst: proxy_dst,
@@ -470,12 +477,13 @@ where
             if is_https {
               tunnel(&mut io, &orig_dst, user_agent, auth).await?;
               let tokio_io = TokioIo::new(io);
-              let io = tls
-                .connect(
-                  TryFrom::try_from(orig_dst.host().unwrap().to_owned())?,
-                  tokio_io,
-                )
-                .await?;
+              let io = tls_connect(
+                &tls,
+                orig_dst.host().unwrap(),
+                tokio_io,
+                handshake_timeout,
+              )
+              .await?;
               Ok(Proxied::HttpTunneled(Box::new(TokioIo::new(io))))
             } else {
               Ok(Proxied::HttpForward(io))
@@ -513,9 +521,8 @@ where
 
             if is_https {
               let tokio_io = TokioIo::new(io);
-              let io = tls
-                .connect(TryFrom::try_from(host.to_owned())?, tokio_io)
-                .await?;
+              let io =
+                tls_connect(&tls, host, tokio_io, handshake_timeout).await?;
               Ok(Proxied::SocksTls(TokioIo::new(io)))
             } else {
               Ok(Proxied::Socks(io))
@@ -525,6 +532,27 @@ where
       };
     }
 
+    if let (Some(handshake_timeout), Some(&Scheme::HTTPS)) =
+      (self.tls_handshake_timeout, orig_dst.scheme())
+    {
+      // `HttpsConnector` has no notion of a handshake timeout, so do the
+      // handshake ourselves on top of the plain connection.
+      let connecting = self.http.call(orig_dst.clone());
+      let tls = TlsConnector::from(self.tls.clone());
+      return Box::pin(async move {
+        let io = connecting.await.map_err(Into::<BoxError>::into)?;
+        let host = orig_dst
+          .host()
+          .ok_or("no host in url")?
+          .trim_start_matches('[')
+          .trim_end_matches(']');
+        let io =
+          tls_connect(&tls, host, TokioIo::new(io), Some(handshake_timeout))
+            .await?;
+        Ok(Proxied::PassThrough(MaybeHttpsStream::from(io)))
+      });
+    }
+
     let mut connector =
       HttpsConnector::from((self.http.clone(), self.tls.clone()));
     Box::pin(
@@ -536,6 +564,32 @@ where
   }
 }
 
+async fn tls_connect<T>(
+  tls: &TlsConnector,
+  host: &str,
+  io: T,
+  timeout: Option<Duration>,
+) -> Result<TlsStream<T>, BoxError>
+where
+  T: AsyncRead + AsyncWrite + Unpin,
+{
+  let server_name = ServerName::try_from(host.to_owned())?;
+  let connecting = tls.connect(server_name, io);
+  let Some(timeout) = timeout else {
+    return Ok(connecting.await?);
+  };
+  match tokio::time::timeout(timeout, connecting).await {
+    Ok(res) => Ok(res?),
+    Err(_) => Err(
+      std::io::Error::new(
+        std::io::ErrorKind::TimedOut,
+        "TLS handshake timed out",
+      )
+      .into(),
+    ),
+  }
+}
+
 async fn tunnel<T>(
   io: &mut T,
   dst: &Uri,
@@ -2,6 +2,7 @@
 
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use bytes::Bytes;
 use fast_socks5::server::Config as Socks5Config;
@@ -12,6 +13,7 @@ use tokio::io::AsyncWriteExt;
 
 use super::create_http_client;
 use super::CreateHttpClientOptions;
+use super::DnsOverrides;
 
 static EXAMPLE_CRT: &[u8] = include_bytes!("../tls/testdata/example1_cert.der");
 static EXAMPLE_KEY: &[u8] =
@@ -52,6 +54,113 @@ async fn test_socks_proxy_h2() {
   run_test_client(prx_addr, src_addr, "socks5", http::Version::HTTP_2).await;
 }
 
+#[tokio::test]
+async fn test_dns_override() {
+  let src_addr = create_https_server(false).await;
+  let client = create_http_client(
+    "fetch/test",
+    CreateHttpClientOptions {
+      unsafely_ignore_certificate_errors: Some(vec![]),
+      dns_overrides: DnsOverrides::from([(
+        "Pinned.Example".to_string(),
+        vec![src_addr.ip()],
+      )]),
+      ..Default::default()
+    },
+  )
+  .unwrap();
+  assert_eq!(
+    client.dns_override("pinned.example"),
+    Some([src_addr.ip()].as_slice())
+  );
+  assert_eq!(client.dns_override("example.com"), None);
+
+  let req = http::Request::builder()
+    .uri(format!("https://pinned.example:{}/foo", src_addr.port()))
+    .body(
+      http_body_util::Empty::new()
+        .map_err(|err| match err {})
+        .boxed(),
+    )
+    .unwrap();
+  let resp = client.send(req).await.unwrap();
+  assert_eq!(resp.status(), http::StatusCode::OK);
+  let hello = resp.collect().await.unwrap().to_bytes();
+  assert_eq!(hello, "hello from server");
+}
+
+#[tokio::test]
+async fn test_tls_handshake_timeout() {
+  // A server that accepts connections but never answers the ClientHello.
+  let tcp = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let addr = tcp.local_addr().unwrap();
+  tokio::spawn(async move {
+    let mut conns = vec![];
+    while let Ok((sock, _)) = tcp.accept().await {
+      conns.push(sock);
+    }
+  });
+
+  let client = create_http_client(
+    "fetch/test",
+    CreateHttpClientOptions {
+      tls_handshake_timeout: Some(100),
+      ..Default::default()
+    },
+  )
+  .unwrap();
+  let req = http::Request::builder()
+    .uri(format!("https://{}/foo", addr))
+    .body(
+      http_body_util::Empty::new()
+        .map_err(|err| match err {})
+        .boxed(),
+    )
+    .unwrap();
+  let err = tokio::time::timeout(Duration::from_secs(5), client.send(req))
+    .await
+    .expect("handshake timeout did not fire")
+    .unwrap_err();
+  assert!(format!("{err:?}").contains("TLS handshake timed out"));
+}
+
+#[tokio::test]
+async fn test_idle_timeout() {
+  // A server that reads the request but never sends a response.
+  let tcp = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let addr = tcp.local_addr().unwrap();
+  tokio::spawn(async move {
+    let mut conns = vec![];
+    while let Ok((mut sock, _)) = tcp.accept().await {
+      let mut buf = [0u8; 4096];
+      let _n = sock.read(&mut buf).await.unwrap();
+      conns.push(sock);
+    }
+  });
+
+  let client = create_http_client(
+    "fetch/test",
+    CreateHttpClientOptions {
+      idle_timeout: Some(100),
+      ..Default::default()
+    },
+  )
+  .unwrap();
+  let req = http::Request::builder()
+    .uri(format!("http://{}/foo", addr))
+    .body(
+      http_body_util::Empty::new()
+        .map_err(|err| match err {})
+        .boxed(),
+    )
+    .unwrap();
+  let err = tokio::time::timeout(Duration::from_secs(5), client.send(req))
+    .await
+    .expect("idle timeout did not fire")
+    .unwrap_err();
+  assert!(err.to_string().contains("Timed out waiting for response"));
+}
+
 async fn run_test_client(
   prx_addr: SocketAddr,
   src_addr: SocketAddr,
@@ -73,6 +182,11 @@ async fn run_test_client(
       pool_idle_timeout: None,
       http1: true,
       http2: true,
+      connect_timeout: None,
+      tls_handshake_timeout: None,
+      idle_timeout: None,
+      dns_overrides: DnsOverrides::new(),
+      local_address: None,
     },
   )
   .unwrap();
@@ -11,8 +11,10 @@ import {
   op_http_cancel,
   op_http_close,
   op_http_close_after_finish,
+  op_http_drain,
   op_http_get_request_headers,
   op_http_get_request_method_and_url,
+  op_http_metrics,
   op_http_read_request_body,
   op_http_serve,
   op_http_serve_on,
@@ -30,6 +32,8 @@ import {
 } from "ext:core/ops";
 const {
   ArrayPrototypePush,
+  MathFloor,
+  NumberIsFinite,
   ObjectHasOwn,
   ObjectPrototypeIsPrototypeOf,
   PromisePrototypeCatch,
@@ -376,6 +380,9 @@ class CallbackContext {
   scheme;
   fallbackHost;
   serverRid;
+  metricsRid;
+  /** @type {Deno.ServeMetrics | undefined} */
+  lastMetrics;
   closed;
   /** @type {Promise<void> | undefined} */
   closing;
@@ -394,10 +401,18 @@ class CallbackContext {
     this.serverRid = args[0];
     this.scheme = args[1];
     this.fallbackHost = args[2];
+    this.metricsRid = args[3];
     this.closed = false;
     this.listener = listener;
   }
 
+  metrics() {
+    if (this.lastMetrics === undefined) {
+      return op_http_metrics(this.metricsRid);
+    }
+    return this.lastMetrics;
+  }
+
   close() {
     try {
       this.closed = true;
@@ -405,6 +420,12 @@ class CallbackContext {
     } catch {
       // Pass
     }
+    // Keep the final numbers around so they can still be read after the
+    // server is gone.
+    if (this.lastMetrics === undefined) {
+      this.lastMetrics = op_http_metrics(this.metricsRid);
+      core.tryClose(this.metricsRid);
+    }
   }
 }
 
@@ -577,8 +598,21 @@ type RawServeOptions = {
   onError?: (error: unknown) => Response | Promise<Response>;
   onListen?: (params: { hostname: string; port: number }) => void;
   handler?: RawHandler;
+  maxConnections?: number;
+  maxRequestsPerConnection?: number;
+  maxHeaderSize?: number;
+  maxBodySize?: number;
 };
 
+function serveLimits(options: RawServeOptions) {
+  return {
+    maxConnections: options.maxConnections,
+    maxRequestsPerConnection: options.maxRequestsPerConnection,
+    maxHeaderSize: options.maxHeaderSize,
+    maxBodySize: options.maxBodySize,
+  };
+}
+
 function serve(arg1, arg2) {
   let options: RawServeOptions | undefined;
   let handler: RawHandler | undefined;
@@ -613,6 +647,7 @@ function serve(arg1, arg2) {
     console.error(error);
     return internalServerError();
   };
+  const limits = serveLimits(options);
 
   if (wantsUnix) {
     const listener = listen({
@@ -627,7 +662,7 @@ function serve(arg1, arg2) {
       } else {
         console.log(`Listening on ${path}`);
       }
-    });
+    }, limits);
   }
 
   const listenOpts = {
@@ -685,16 +720,30 @@ function serve(arg1, arg2) {
     }
   };
 
-  return serveHttpOnListener(listener, signal, handler, onError, onListen);
+  return serveHttpOnListener(
+    listener,
+    signal,
+    handler,
+    onError,
+    onListen,
+    limits,
+  );
 }
 
 /**
  * Serve HTTP/1.1 and/or HTTP/2 on an arbitrary listener.
  */
-function serveHttpOnListener(listener, signal, handler, onError, onListen) {
+function serveHttpOnListener(
+  listener,
+  signal,
+  handler,
+  onError,
+  onListen,
+  limits = undefined,
+) {
   const context = new CallbackContext(
     signal,
-    op_http_serve(listener[internalRidSymbol]),
+    op_http_serve(listener[internalRidSymbol], limits),
     listener,
   );
   const callback = mapToCallback(context, handler, onError);
@@ -707,10 +756,17 @@ function serveHttpOnListener(listener, signal, handler, onError, onListen) {
 /**
  * Serve HTTP/1.1 and/or HTTP/2 on an arbitrary connection.
  */
-function serveHttpOnConnection(connection, signal, handler, onError, onListen) {
+function serveHttpOnConnection(
+  connection,
+  signal,
+  handler,
+  onError,
+  onListen,
+  limits = undefined,
+) {
   const context = new CallbackContext(
     signal,
-    op_http_serve_on(connection[internalRidSymbol]),
+    op_http_serve_on(connection[internalRidSymbol], limits),
     null,
   );
   const callback = mapToCallback(context, handler, onError);
@@ -812,6 +868,49 @@ function serveHttpOn(context, addr, callback) {
         context.closed = true;
       }
     },
+    async drain(options = { __proto__: null }) {
+      const timeout = options.timeout;
+      if (
+        timeout !== undefined && (typeof timeout !== "number" || timeout < 0)
+      ) {
+        throw new TypeError("'timeout' must be a non-negative number");
+      }
+      let report = {
+        timedOut: false,
+        abortedRequests: 0,
+        abortedConnections: 0,
+      };
+      try {
+        if (!context.closing && !context.closed) {
+          // Stop accepting and let connections wind down, cutting off
+          // whatever is left once the timeout passes.
+          const draining = op_http_drain(
+            context.serverRid,
+            NumberIsFinite(timeout) ? MathFloor(timeout) : null,
+          );
+          context.closing = draining;
+          report = await draining;
+        } else {
+          await context.closing;
+        }
+      } catch (error) {
+        // The server was interrupted
+        if (ObjectPrototypeIsPrototypeOf(InterruptedPrototype, error)) {
+          return report;
+        }
+        if (ObjectPrototypeIsPrototypeOf(BadResourcePrototype, error)) {
+          return report;
+        }
+
+        throw error;
+      } finally {
+        context.closed = true;
+      }
+      return report;
+    },
+    metrics() {
+      return context.metrics();
+    },
     ref() {
       ref = true;
       if (currentPromise) {
@@ -842,10 +941,12 @@ function registerDeclarativeServer(exports) {
         "Invalid type for fetch: must be a function with a single or no parameter",
       );
     }
-    return ({ servePort, serveHost }) => {
-      Deno.serve({
+    return ({ servePort, serveHost, serveParallel }) => {
+      return Deno.serve({
         port: servePort,
         hostname: serveHost,
+        // `deno serve --parallel` runs several processes on the same port.
+        reusePort: serveParallel,
         onListen: ({ port, hostname }) => {
           console.debug(
             `%cdeno serve%c: Listening on %chttp://${hostname}:${port}/%c`,
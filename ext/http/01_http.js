@@ -361,6 +361,7 @@ function createRespondWith(
       if (ws) {
         const wsRid = await op_http_upgrade_websocket(
           readStreamRid,
+          resp.headers.get("sec-websocket-extensions"),
         );
         ws[_rid] = wsRid;
         ws[_protocol] = resp.headers.get("sec-websocket-protocol");
@@ -1,6 +1,9 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 import { internals, primordials } from "ext:core/mod.js";
-import { op_http_websocket_accept_header } from "ext:core/ops";
+import {
+  op_http_websocket_accept_header,
+  op_ws_negotiate_deflate,
+} from "ext:core/ops";
 const {
   ArrayPrototypeIncludes,
   ArrayPrototypeMap,
@@ -20,6 +23,7 @@ import {
 import { setEventTargetData } from "ext:deno_web/02_event.js";
 import {
   _eventLoop,
+  _extensions,
   _idleTimeoutDuration,
   _idleTimeoutTimeout,
   _protocol,
@@ -88,9 +92,24 @@ function upgradeWebSocket(request, options = { __proto__: null }) {
     }
   }
 
+  let extensions = null;
+  if (options.compression) {
+    extensions = op_ws_negotiate_deflate(
+      request.headers.get("sec-websocket-extensions") ?? "",
+      options.compression === true ? { __proto__: null } : options.compression,
+    );
+    if (extensions !== null) {
+      ArrayPrototypePush(r.headerList, [
+        "sec-websocket-extensions",
+        extensions,
+      ]);
+    }
+  }
+
   const socket = createWebSocketBranded(WebSocket);
   setEventTargetData(socket);
   socket[_server] = true;
+  socket[_extensions] = extensions ?? "";
   // Nginx timeout is 60s, so default to a lower number: https://github.com/denoland/deno/pull/23985
   socket[_idleTimeoutDuration] = options.idleTimeout ?? 30;
   socket[_idleTimeoutTimeout] = null;
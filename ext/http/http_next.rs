@@ -1,6 +1,14 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 use crate::compressible::is_content_compressible;
 use crate::extract_network_stream;
+use crate::limits::ConnectionShutdown;
+use crate::limits::HttpDrainReport;
+use crate::limits::HttpServeLimits;
+use crate::metrics::ConnectionMetricsGuard;
+use crate::metrics::HttpMetricsResource;
+use crate::metrics::HttpProtocol;
+use crate::metrics::HttpServerMetricsSnapshot;
+use crate::metrics::MeteredStream;
 use crate::network_buffered_stream::NetworkStreamPrefixCheck;
 use crate::request_body::HttpRequestBody;
 use crate::request_properties::HttpConnectionProperties;
@@ -22,6 +30,8 @@ use cache_control::CacheControl;
 use deno_core::error::AnyError;
 use deno_core::external;
 use deno_core::futures::future::poll_fn;
+use deno_core::futures::future::select;
+use deno_core::futures::future::Either;
 use deno_core::futures::TryFutureExt;
 use deno_core::op2;
 use deno_core::serde_v8::from_v8;
@@ -53,6 +63,7 @@ use hyper::header::CONTENT_LENGTH;
 use hyper::header::CONTENT_RANGE;
 use hyper::header::CONTENT_TYPE;
 use hyper::header::COOKIE;
+use hyper::header::SEC_WEBSOCKET_EXTENSIONS;
 use hyper::http::HeaderName;
 use hyper::http::HeaderValue;
 use hyper::server::conn::http1;
@@ -68,9 +79,12 @@ use std::cell::RefCell;
 use std::ffi::c_void;
 use std::future::Future;
 use std::io;
+use std::pin::pin;
 use std::pin::Pin;
 use std::ptr::null;
 use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
 
 use super::fly_accept_encoding;
 use fly_accept_encoding::Encoding;
@@ -236,7 +250,7 @@ pub async fn op_http_upgrade_websocket_next(
     unsafe { take_external!(external, "op_http_upgrade_websocket_next") };
   // Stage 1: set the response to 101 Switching Protocols and send it
   let upgrade = http.upgrade()?;
-  {
+  let extensions = {
     let mut response_parts = http.response_parts();
     response_parts.status = StatusCode::SWITCHING_PROTOCOLS;
     for (name, value) in headers {
@@ -245,7 +259,12 @@ pub async fn op_http_upgrade_websocket_next(
         HeaderValue::from_bytes(&value).unwrap(),
       );
     }
-  }
+    response_parts
+      .headers
+      .get(SEC_WEBSOCKET_EXTENSIONS)
+      .and_then(|value| value.to_str().ok())
+      .map(String::from)
+  };
   http.complete();
 
   // Stage 2: wait for the request to finish upgrading
@@ -253,7 +272,12 @@ pub async fn op_http_upgrade_websocket_next(
 
   // Stage 3: take the extracted raw network stream and upgrade it to a websocket, then return it
   let (stream, bytes) = extract_network_stream(upgraded);
-  ws_create_server_stream(&mut state.borrow_mut(), stream, bytes)
+  ws_create_server_stream(
+    &mut state.borrow_mut(),
+    stream,
+    bytes,
+    extensions.as_deref(),
+  )
 }
 
 #[op2(fast)]
@@ -433,7 +457,8 @@ pub fn op_http_read_request_body(
     // SAFETY: op is called with external.
     unsafe { clone_external!(external, "op_http_read_request_body") };
   let rid = if let Some(incoming) = http.take_request_body() {
-    let body_resource = Rc::new(HttpRequestBody::new(incoming));
+    let body_resource =
+      Rc::new(HttpRequestBody::new(incoming, http.max_body_size()));
     state.borrow_mut().resource_table.add_rc(body_resource)
   } else {
     // This should not be possible, but rather than panicking we'll return an invalid
@@ -766,21 +791,31 @@ pub fn op_http_set_response_body_bytes(
 fn serve_http11_unconditional(
   io: impl HttpServeStream,
   svc: impl HttpService<Incoming, ResBody = HttpRecordResponse> + 'static,
-  cancel: Rc<CancelHandle>,
+  shutdown: ConnectionShutdown,
+  max_header_size: Option<usize>,
+  mut guard: ConnectionMetricsGuard,
 ) -> impl Future<Output = Result<(), hyper::Error>> + 'static {
-  let conn = http1::Builder::new()
-    .keep_alive(true)
-    .writev(*USE_WRITEV)
+  guard.set_protocol(HttpProtocol::Http1);
+  let mut builder = http1::Builder::new();
+  builder.keep_alive(true).writev(*USE_WRITEV);
+  if let Some(max_header_size) = max_header_size {
+    builder.max_buf_size(max_header_size);
+  }
+  let conn = builder
     .serve_connection(TokioIo::new(io), svc)
     .with_upgrades();
 
   async {
-    match conn.or_abort(cancel).await {
-      Err(mut conn) => {
-        Pin::new(&mut conn).graceful_shutdown();
+    // Keep the connection counted until hyper is done with it.
+    let _guard = guard;
+    let mut conn = pin!(conn);
+    match select(conn.as_mut(), pin!(shutdown.requested())).await {
+      Either::Left((res, _)) => res,
+      Either::Right((_, mut conn)) => {
+        // Finishes the in-flight request, then closes with `Connection: close`.
+        conn.as_mut().graceful_shutdown();
         conn.await
       }
-      Ok(res) => res,
     }
   }
 }
@@ -788,17 +823,27 @@ fn serve_http11_unconditional(
 fn serve_http2_unconditional(
   io: impl HttpServeStream,
   svc: impl HttpService<Incoming, ResBody = HttpRecordResponse> + 'static,
-  cancel: Rc<CancelHandle>,
+  shutdown: ConnectionShutdown,
+  max_header_size: Option<usize>,
+  mut guard: ConnectionMetricsGuard,
 ) -> impl Future<Output = Result<(), hyper::Error>> + 'static {
-  let conn =
-    http2::Builder::new(LocalExecutor).serve_connection(TokioIo::new(io), svc);
+  guard.set_protocol(HttpProtocol::Http2);
+  let mut builder = http2::Builder::new(LocalExecutor);
+  if let Some(max_header_size) = max_header_size {
+    builder.max_header_list_size(max_header_size as u32);
+  }
+  let conn = builder.serve_connection(TokioIo::new(io), svc);
   async {
-    match conn.or_abort(cancel).await {
-      Err(mut conn) => {
-        Pin::new(&mut conn).graceful_shutdown();
+    // Keep the connection counted until hyper is done with it.
+    let _guard = guard;
+    let mut conn = pin!(conn);
+    match select(conn.as_mut(), pin!(shutdown.requested())).await {
+      Either::Left((res, _)) => res,
+      Either::Right((_, mut conn)) => {
+        // Sends a GOAWAY and lets the open streams finish.
+        conn.as_mut().graceful_shutdown();
         conn.await
       }
-      Ok(res) => res,
     }
   }
 }
@@ -806,16 +851,18 @@ fn serve_http2_unconditional(
 async fn serve_http2_autodetect(
   io: impl HttpServeStream,
   svc: impl HttpService<Incoming, ResBody = HttpRecordResponse> + 'static,
-  cancel: Rc<CancelHandle>,
+  shutdown: ConnectionShutdown,
+  max_header_size: Option<usize>,
+  guard: ConnectionMetricsGuard,
 ) -> Result<(), AnyError> {
   let prefix = NetworkStreamPrefixCheck::new(io, HTTP2_PREFIX);
   let (matches, io) = prefix.match_prefix().await?;
   if matches {
-    serve_http2_unconditional(io, svc, cancel)
+    serve_http2_unconditional(io, svc, shutdown, max_header_size, guard)
       .await
       .map_err(|e| e.into())
   } else {
-    serve_http11_unconditional(io, svc, cancel)
+    serve_http11_unconditional(io, svc, shutdown, max_header_size, guard)
       .await
       .map_err(|e| e.into())
   }
@@ -833,25 +880,36 @@ fn serve_https(
     listen_cancel_handle,
   } = lifetime;
 
+  let metrics = server_state.metrics().clone();
+  let limits = *server_state.limits();
+  let guard = ConnectionMetricsGuard::new(metrics.clone());
+  let shutdown = ConnectionShutdown::new(
+    listen_cancel_handle,
+    limits.max_requests_per_connection,
+  );
+  let svc_shutdown = shutdown.clone();
   let svc = service_fn(move |req: Request| {
+    svc_shutdown.on_request();
     handle_request(req, request_info.clone(), server_state.clone(), tx.clone())
   });
+  let max_header_size = limits.max_header_size;
   spawn(
-    async {
+    async move {
       let handshake = io.handshake().await?;
+      let io = MeteredStream::new(io, metrics);
       // If the client specifically negotiates a protocol, we will use it. If not, we'll auto-detect
       // based on the prefix bytes
       let handshake = handshake.alpn;
       if Some(TLS_ALPN_HTTP_2) == handshake.as_deref() {
-        serve_http2_unconditional(io, svc, listen_cancel_handle)
+        serve_http2_unconditional(io, svc, shutdown, max_header_size, guard)
           .await
           .map_err(|e| e.into())
       } else if Some(TLS_ALPN_HTTP_11) == handshake.as_deref() {
-        serve_http11_unconditional(io, svc, listen_cancel_handle)
+        serve_http11_unconditional(io, svc, shutdown, max_header_size, guard)
           .await
           .map_err(|e| e.into())
       } else {
-        serve_http2_autodetect(io, svc, listen_cancel_handle).await
+        serve_http2_autodetect(io, svc, shutdown, max_header_size, guard).await
       }
     }
     .try_or_cancel(connection_cancel_handle),
@@ -870,11 +928,21 @@ fn serve_http(
     listen_cancel_handle,
   } = lifetime;
 
+  let metrics = server_state.metrics().clone();
+  let limits = *server_state.limits();
+  let guard = ConnectionMetricsGuard::new(metrics.clone());
+  let io = MeteredStream::new(io, metrics);
+  let shutdown = ConnectionShutdown::new(
+    listen_cancel_handle,
+    limits.max_requests_per_connection,
+  );
+  let svc_shutdown = shutdown.clone();
   let svc = service_fn(move |req: Request| {
+    svc_shutdown.on_request();
     handle_request(req, request_info.clone(), server_state.clone(), tx.clone())
   });
   spawn(
-    serve_http2_autodetect(io, svc, listen_cancel_handle)
+    serve_http2_autodetect(io, svc, shutdown, limits.max_header_size, guard)
       .try_or_cancel(connection_cancel_handle),
   )
 }
@@ -923,13 +991,16 @@ struct HttpJoinHandle {
 }
 
 impl HttpJoinHandle {
-  fn new(rx: tokio::sync::mpsc::Receiver<Rc<HttpRecord>>) -> Self {
+  fn new(
+    rx: tokio::sync::mpsc::Receiver<Rc<HttpRecord>>,
+    limits: HttpServeLimits,
+  ) -> Self {
     Self {
       join_handle: AsyncRefCell::new(None),
       connection_cancel_handle: CancelHandle::new_rc(),
       listen_cancel_handle: CancelHandle::new_rc(),
       rx: AsyncRefCell::new(rx),
-      server_state: HttpServerState::new(),
+      server_state: HttpServerState::new(limits),
     }
   }
 
@@ -975,33 +1046,60 @@ impl Drop for HttpJoinHandle {
 pub fn op_http_serve<HTTP>(
   state: Rc<RefCell<OpState>>,
   #[smi] listener_rid: ResourceId,
-) -> Result<(ResourceId, &'static str, String), AnyError>
+  #[serde] limits: Option<HttpServeLimits>,
+) -> Result<(ResourceId, &'static str, String, ResourceId), AnyError>
 where
   HTTP: HttpPropertyExtractor,
 {
+  let limits = limits.unwrap_or_default();
+  limits.validate()?;
+
   let listener =
     HTTP::get_listener_for_rid(&mut state.borrow_mut(), listener_rid)?;
 
   let listen_properties = HTTP::listen_properties_from_listener(&listener)?;
 
   let (tx, rx) = tokio::sync::mpsc::channel(10);
-  let resource: Rc<HttpJoinHandle> = Rc::new(HttpJoinHandle::new(rx));
+  let resource: Rc<HttpJoinHandle> = Rc::new(HttpJoinHandle::new(rx, limits));
   let listen_cancel_clone = resource.listen_cancel_handle();
 
   let lifetime = resource.lifetime();
 
+  // Connections beyond the limit are left in the listen backlog until one of
+  // the open connections closes.
+  let connection_slots = limits
+    .max_connections
+    .map(|max| Arc::new(tokio::sync::Semaphore::new(max)));
+
   let listen_properties_clone: HttpListenProperties = listen_properties.clone();
   let handle = spawn(async move {
     loop {
+      let slot = match &connection_slots {
+        Some(slots) => Some(
+          slots
+            .clone()
+            .acquire_owned()
+            .or_cancel(listen_cancel_clone.clone())
+            .await??,
+        ),
+        None => None,
+      };
       let conn = HTTP::accept_connection_from_listener(&listener)
         .try_or_cancel(listen_cancel_clone.clone())
         .await?;
-      serve_http_on::<HTTP>(
+      let conn_handle = serve_http_on::<HTTP>(
         conn,
         &listen_properties_clone,
         lifetime.clone(),
         tx.clone(),
       );
+      if let Some(slot) = slot {
+        // Hold on to the slot until the connection is done.
+        spawn(async move {
+          let _ = conn_handle.await;
+          drop(slot);
+        });
+      }
     }
     #[allow(unreachable_code)]
     Ok::<_, AnyError>(())
@@ -1012,10 +1110,13 @@ where
     .try_borrow_mut()
     .unwrap() = Some(handle);
 
+  let metrics = HttpMetricsResource(resource.server_state.metrics().clone());
+  let mut state = state.borrow_mut();
   Ok((
-    state.borrow_mut().resource_table.add_rc(resource),
+    state.resource_table.add_rc(resource),
     listen_properties.scheme,
     listen_properties.fallback_host,
+    state.resource_table.add(metrics),
   ))
 }
 
@@ -1024,17 +1125,21 @@ where
 pub fn op_http_serve_on<HTTP>(
   state: Rc<RefCell<OpState>>,
   #[smi] connection_rid: ResourceId,
-) -> Result<(ResourceId, &'static str, String), AnyError>
+  #[serde] limits: Option<HttpServeLimits>,
+) -> Result<(ResourceId, &'static str, String, ResourceId), AnyError>
 where
   HTTP: HttpPropertyExtractor,
 {
+  let limits = limits.unwrap_or_default();
+  limits.validate()?;
+
   let connection =
     HTTP::get_connection_for_rid(&mut state.borrow_mut(), connection_rid)?;
 
   let listen_properties = HTTP::listen_properties_from_connection(&connection)?;
 
   let (tx, rx) = tokio::sync::mpsc::channel(10);
-  let resource: Rc<HttpJoinHandle> = Rc::new(HttpJoinHandle::new(rx));
+  let resource: Rc<HttpJoinHandle> = Rc::new(HttpJoinHandle::new(rx, limits));
 
   let handle: JoinHandle<Result<(), deno_core::anyhow::Error>> =
     serve_http_on::<HTTP>(
@@ -1049,10 +1154,13 @@ where
     .try_borrow_mut()
     .unwrap() = Some(handle);
 
+  let metrics = HttpMetricsResource(resource.server_state.metrics().clone());
+  let mut state = state.borrow_mut();
   Ok((
-    state.borrow_mut().resource_table.add_rc(resource),
+    state.resource_table.add_rc(resource),
     listen_properties.scheme,
     listen_properties.fallback_host,
+    state.resource_table.add(metrics),
   ))
 }
 
@@ -1154,6 +1262,17 @@ pub fn op_http_cancel(
   Ok(())
 }
 
+/// Returns a snapshot of the server's connection and request counters.
+#[op2]
+#[serde]
+pub fn op_http_metrics(
+  state: &mut OpState,
+  #[smi] rid: ResourceId,
+) -> Result<HttpServerMetricsSnapshot, AnyError> {
+  let metrics = state.resource_table.get::<HttpMetricsResource>(rid)?;
+  Ok(metrics.0.snapshot())
+}
+
 #[op2(async)]
 pub async fn op_http_close(
   state: Rc<RefCell<OpState>>,
@@ -1179,9 +1298,64 @@ pub async fn op_http_close(
     tokio::task::yield_now().await;
   }
 
+  await_shutdown(&join_handle).await
+}
+
+/// Stops accepting connections and asks the open ones to wind down: HTTP/1.1
+/// connections close after their current response and HTTP/2 connections are
+/// sent a GOAWAY. Anything still running once `timeout` has passed is cut off
+/// and reported.
+#[op2(async)]
+#[serde]
+pub async fn op_http_drain(
+  state: Rc<RefCell<OpState>>,
+  #[smi] rid: ResourceId,
+  #[serde] timeout: Option<u64>,
+) -> Result<HttpDrainReport, AnyError> {
+  let join_handle = state
+    .borrow_mut()
+    .resource_table
+    .take::<HttpJoinHandle>(rid)?;
+
+  http_general_trace!("draining");
+  join_handle.listen_cancel_handle().cancel();
+  let drained = poll_fn(|cx| join_handle.server_state.poll_complete(cx));
+  let completed = match timeout {
+    Some(timeout) => {
+      tokio::time::timeout(Duration::from_millis(timeout), drained)
+        .await
+        .is_ok()
+    }
+    None => {
+      drained.await;
+      true
+    }
+  };
+
+  let mut report = HttpDrainReport::default();
+  if !completed {
+    http_general_trace!("drain deadline passed, aborting connections");
+    let metrics = join_handle.server_state.metrics().snapshot();
+    report = HttpDrainReport {
+      timed_out: true,
+      aborted_requests: metrics.in_flight_requests,
+      aborted_connections: metrics.active_connections,
+    };
+    join_handle.connection_cancel_handle().cancel();
+    // Give streaming responses a tick to close
+    tokio::task::yield_now().await;
+  }
+
+  await_shutdown(&join_handle).await?;
+  Ok(report)
+}
+
+async fn await_shutdown(
+  join_handle: &Rc<HttpJoinHandle>,
+) -> Result<(), AnyError> {
   http_general_trace!("awaiting shutdown");
 
-  let mut join_handle = RcRef::map(&join_handle, |this| &this.join_handle)
+  let mut join_handle = RcRef::map(join_handle, |this| &this.join_handle)
     .borrow_mut()
     .await;
   if let Some(join_handle) = join_handle.take() {
@@ -80,6 +80,8 @@ use crate::reader_stream::ShutdownHandle;
 pub mod compressible;
 mod fly_accept_encoding;
 mod http_next;
+mod limits;
+mod metrics;
 mod network_buffered_stream;
 mod reader_stream;
 mod request_body;
@@ -129,7 +131,9 @@ deno_core::extension!(
     http_next::op_http_try_wait,
     http_next::op_http_wait,
     http_next::op_http_close,
+    http_next::op_http_drain,
     http_next::op_http_cancel,
+    http_next::op_http_metrics,
   ],
   esm = ["00_serve.ts", "01_http.js", "02_websocket.ts"],
 );
@@ -1037,6 +1041,7 @@ fn op_http_websocket_accept_header(
 async fn op_http_upgrade_websocket(
   state: Rc<RefCell<OpState>>,
   #[smi] rid: ResourceId,
+  #[string] extensions: Option<String>,
 ) -> Result<ResourceId, AnyError> {
   let stream = state
     .borrow_mut()
@@ -1053,8 +1058,12 @@ async fn op_http_upgrade_websocket(
 
   let (transport, bytes) =
     extract_network_stream(hyper_v014::upgrade::on(request).await?);
-  let ws_rid =
-    ws_create_server_stream(&mut state.borrow_mut(), transport, bytes)?;
+  let ws_rid = ws_create_server_stream(
+    &mut state.borrow_mut(),
+    transport,
+    bytes,
+    extensions.as_deref(),
+  )?;
   Ok(ws_rid)
 }
 
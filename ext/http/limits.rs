@@ -0,0 +1,135 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+use deno_core::error::type_error;
+use deno_core::error::AnyError;
+use deno_core::futures::future::select;
+use deno_core::CancelFuture;
+use deno_core::CancelHandle;
+use serde::Deserialize;
+use serde::Serialize;
+use std::cell::Cell;
+use std::pin::pin;
+use std::rc::Rc;
+use tokio::sync::Notify;
+
+/// hyper refuses HTTP/1.1 read buffers smaller than this.
+const MIN_HEADER_SIZE: usize = 8192;
+
+/// Per-server resource limits, as passed in from the `Deno.serve` options.
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpServeLimits {
+  /// Maximum number of concurrently open connections. Further connections
+  /// wait in the listen backlog until a slot frees up.
+  pub max_connections: Option<usize>,
+  /// Number of requests served on a connection before it is asked to close.
+  pub max_requests_per_connection: Option<u64>,
+  /// Maximum size of the request head, in bytes.
+  pub max_header_size: Option<usize>,
+  /// Maximum size of a request body, in bytes.
+  pub max_body_size: Option<u64>,
+}
+
+impl HttpServeLimits {
+  pub fn validate(&self) -> Result<(), AnyError> {
+    if self.max_connections == Some(0) {
+      return Err(type_error("maxConnections must be greater than 0"));
+    }
+    if self.max_requests_per_connection == Some(0) {
+      return Err(type_error(
+        "maxRequestsPerConnection must be greater than 0",
+      ));
+    }
+    if let Some(size) = self.max_header_size {
+      if size < MIN_HEADER_SIZE {
+        return Err(type_error(format!(
+          "maxHeaderSize must be at least {MIN_HEADER_SIZE} bytes"
+        )));
+      }
+    }
+    Ok(())
+  }
+}
+
+/// Outcome of draining a server, reported back to JavaScript.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpDrainReport {
+  /// Whether the deadline passed before all requests completed.
+  pub timed_out: bool,
+  /// Requests that were still in flight when the deadline passed.
+  pub aborted_requests: u64,
+  /// Connections that were still open when the deadline passed.
+  pub aborted_connections: u64,
+}
+
+/// Decides when a single connection should wind down: either the server
+/// stopped listening, or the connection has served its quota of requests.
+#[derive(Clone)]
+pub(crate) struct ConnectionShutdown {
+  listen_cancel_handle: Rc<CancelHandle>,
+  max_requests: Option<u64>,
+  requests: Rc<Cell<u64>>,
+  quota_reached: Rc<Notify>,
+}
+
+impl ConnectionShutdown {
+  pub fn new(
+    listen_cancel_handle: Rc<CancelHandle>,
+    max_requests: Option<u64>,
+  ) -> Self {
+    Self {
+      listen_cancel_handle,
+      max_requests,
+      requests: Default::default(),
+      quota_reached: Default::default(),
+    }
+  }
+
+  /// Count a request received on this connection.
+  pub fn on_request(&self) {
+    let count = self.requests.get() + 1;
+    self.requests.set(count);
+    if Some(count) == self.max_requests {
+      // `notify_one` stores a permit, so this isn't lost if nobody is
+      // waiting yet.
+      self.quota_reached.notify_one();
+    }
+  }
+
+  /// Resolves once the connection should shut down gracefully.
+  pub async fn requested(self) {
+    let listen_closed =
+      std::future::pending::<()>().or_cancel(self.listen_cancel_handle);
+    let quota_reached = self.quota_reached.notified();
+    select(pin!(listen_closed), pin!(quota_reached)).await;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn validate_limits() {
+    assert!(HttpServeLimits::default().validate().is_ok());
+    let limits = HttpServeLimits {
+      max_header_size: Some(1024),
+      ..Default::default()
+    };
+    assert!(limits.validate().is_err());
+    let limits = HttpServeLimits {
+      max_connections: Some(0),
+      ..Default::default()
+    };
+    assert!(limits.validate().is_err());
+  }
+
+  #[tokio::test]
+  async fn shutdown_after_request_quota() {
+    let shutdown = ConnectionShutdown::new(CancelHandle::new_rc(), Some(2));
+    shutdown.on_request();
+    let requested = shutdown.clone().requested();
+    shutdown.on_request();
+    requested.await;
+  }
+}
@@ -0,0 +1,312 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+use deno_core::Resource;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::io;
+use std::pin::Pin;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncWrite;
+use tokio::io::ReadBuf;
+
+/// Upper bounds, in seconds, of the request duration histogram buckets. These
+/// are the Prometheus client library defaults.
+const DURATION_BUCKETS: [f64; 11] = [
+  0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum HttpProtocol {
+  Http1,
+  Http2,
+}
+
+/// Counters for the traffic handled by a single server. Byte counters are
+/// updated from the connection streams, which must be `Send`, so everything
+/// here is atomic.
+#[derive(Default)]
+pub(crate) struct HttpServerMetrics {
+  active_connections: AtomicU64,
+  total_connections: AtomicU64,
+  http1_connections: AtomicU64,
+  http2_connections: AtomicU64,
+  in_flight_requests: AtomicU64,
+  total_requests: AtomicU64,
+  http1_requests: AtomicU64,
+  http2_requests: AtomicU64,
+  bytes_received: AtomicU64,
+  bytes_sent: AtomicU64,
+  duration_buckets: [AtomicU64; DURATION_BUCKETS.len()],
+  duration_count: AtomicU64,
+  duration_sum_micros: AtomicU64,
+}
+
+impl HttpServerMetrics {
+  pub fn request_started(&self, version: http::Version) {
+    self.in_flight_requests.fetch_add(1, Ordering::Relaxed);
+    self.total_requests.fetch_add(1, Ordering::Relaxed);
+    if version == http::Version::HTTP_2 {
+      self.http2_requests.fetch_add(1, Ordering::Relaxed);
+    } else {
+      self.http1_requests.fetch_add(1, Ordering::Relaxed);
+    }
+  }
+
+  pub fn request_finished(&self, duration: Duration) {
+    self.in_flight_requests.fetch_sub(1, Ordering::Relaxed);
+    let seconds = duration.as_secs_f64();
+    if let Some(bucket) = DURATION_BUCKETS.iter().position(|le| seconds <= *le)
+    {
+      self.duration_buckets[bucket].fetch_add(1, Ordering::Relaxed);
+    }
+    self.duration_count.fetch_add(1, Ordering::Relaxed);
+    self
+      .duration_sum_micros
+      .fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+  }
+
+  fn protocol_connections(&self, protocol: HttpProtocol) -> &AtomicU64 {
+    match protocol {
+      HttpProtocol::Http1 => &self.http1_connections,
+      HttpProtocol::Http2 => &self.http2_connections,
+    }
+  }
+
+  pub fn snapshot(&self) -> HttpServerMetricsSnapshot {
+    let load = |counter: &AtomicU64| counter.load(Ordering::Relaxed);
+    // Prometheus histograms are cumulative, so each bucket includes all of
+    // the smaller ones.
+    let mut cumulative = 0;
+    let buckets = DURATION_BUCKETS
+      .iter()
+      .zip(self.duration_buckets.iter())
+      .map(|(le, count)| {
+        cumulative += load(count);
+        (*le, cumulative)
+      })
+      .collect();
+    HttpServerMetricsSnapshot {
+      active_connections: load(&self.active_connections),
+      total_connections: load(&self.total_connections),
+      http1_connections: load(&self.http1_connections),
+      http2_connections: load(&self.http2_connections),
+      in_flight_requests: load(&self.in_flight_requests),
+      total_requests: load(&self.total_requests),
+      http1_requests: load(&self.http1_requests),
+      http2_requests: load(&self.http2_requests),
+      bytes_received: load(&self.bytes_received),
+      bytes_sent: load(&self.bytes_sent),
+      request_duration: RequestDurationSnapshot {
+        buckets,
+        count: load(&self.duration_count),
+        sum: load(&self.duration_sum_micros) as f64 / 1_000_000.0,
+      },
+    }
+  }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpServerMetricsSnapshot {
+  pub active_connections: u64,
+  pub total_connections: u64,
+  pub http1_connections: u64,
+  pub http2_connections: u64,
+  pub in_flight_requests: u64,
+  pub total_requests: u64,
+  pub http1_requests: u64,
+  pub http2_requests: u64,
+  pub bytes_received: u64,
+  pub bytes_sent: u64,
+  pub request_duration: RequestDurationSnapshot,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestDurationSnapshot {
+  /// Pairs of (upper bound in seconds, cumulative count).
+  pub buckets: Vec<(f64, u64)>,
+  pub count: u64,
+  /// Total time spent in requests, in seconds.
+  pub sum: f64,
+}
+
+/// Counts a connection as active for as long as this guard is alive.
+pub(crate) struct ConnectionMetricsGuard {
+  metrics: Arc<HttpServerMetrics>,
+  protocol: Option<HttpProtocol>,
+}
+
+impl ConnectionMetricsGuard {
+  pub fn new(metrics: Arc<HttpServerMetrics>) -> Self {
+    metrics.active_connections.fetch_add(1, Ordering::Relaxed);
+    metrics.total_connections.fetch_add(1, Ordering::Relaxed);
+    Self {
+      metrics,
+      protocol: None,
+    }
+  }
+
+  /// Record the protocol once it has been negotiated or detected.
+  pub fn set_protocol(&mut self, protocol: HttpProtocol) {
+    debug_assert!(self.protocol.is_none());
+    self
+      .metrics
+      .protocol_connections(protocol)
+      .fetch_add(1, Ordering::Relaxed);
+    self.protocol = Some(protocol);
+  }
+}
+
+impl Drop for ConnectionMetricsGuard {
+  fn drop(&mut self) {
+    self
+      .metrics
+      .active_connections
+      .fetch_sub(1, Ordering::Relaxed);
+    if let Some(protocol) = self.protocol {
+      self
+        .metrics
+        .protocol_connections(protocol)
+        .fetch_sub(1, Ordering::Relaxed);
+    }
+  }
+}
+
+/// A stream wrapper that counts the bytes read from and written to the
+/// underlying connection. For TLS connections this wraps the decrypted
+/// stream, so the counts reflect HTTP traffic rather than bytes on the wire.
+pub(crate) struct MeteredStream<S> {
+  inner: S,
+  metrics: Arc<HttpServerMetrics>,
+}
+
+impl<S> MeteredStream<S> {
+  pub fn new(inner: S, metrics: Arc<HttpServerMetrics>) -> Self {
+    Self { inner, metrics }
+  }
+
+  fn count_sent(&self, res: &Poll<io::Result<usize>>) {
+    if let Poll::Ready(Ok(n)) = res {
+      self
+        .metrics
+        .bytes_sent
+        .fetch_add(*n as u64, Ordering::Relaxed);
+    }
+  }
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for MeteredStream<S> {
+  fn poll_read(
+    mut self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+    buf: &mut ReadBuf<'_>,
+  ) -> Poll<io::Result<()>> {
+    let before = buf.filled().len();
+    let res = Pin::new(&mut self.inner).poll_read(cx, buf);
+    if let Poll::Ready(Ok(())) = res {
+      let n = buf.filled().len() - before;
+      self
+        .metrics
+        .bytes_received
+        .fetch_add(n as u64, Ordering::Relaxed);
+    }
+    res
+  }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for MeteredStream<S> {
+  fn poll_write(
+    mut self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+    buf: &[u8],
+  ) -> Poll<io::Result<usize>> {
+    let res = Pin::new(&mut self.inner).poll_write(cx, buf);
+    self.count_sent(&res);
+    res
+  }
+
+  fn poll_write_vectored(
+    mut self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+    bufs: &[io::IoSlice<'_>],
+  ) -> Poll<io::Result<usize>> {
+    let res = Pin::new(&mut self.inner).poll_write_vectored(cx, bufs);
+    self.count_sent(&res);
+    res
+  }
+
+  fn is_write_vectored(&self) -> bool {
+    self.inner.is_write_vectored()
+  }
+
+  fn poll_flush(
+    mut self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+  ) -> Poll<io::Result<()>> {
+    Pin::new(&mut self.inner).poll_flush(cx)
+  }
+
+  fn poll_shutdown(
+    mut self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+  ) -> Poll<io::Result<()>> {
+    Pin::new(&mut self.inner).poll_shutdown(cx)
+  }
+}
+
+/// Keeps a server's metrics readable after the server handle itself has been
+/// taken for shutdown.
+pub(crate) struct HttpMetricsResource(pub Arc<HttpServerMetrics>);
+
+impl Resource for HttpMetricsResource {
+  fn name(&self) -> Cow<str> {
+    "httpMetrics".into()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn duration_histogram_is_cumulative() {
+    let metrics = HttpServerMetrics::default();
+    for ms in [1, 20, 20, 700, 20_000] {
+      metrics.request_started(http::Version::HTTP_11);
+      metrics.request_finished(Duration::from_millis(ms));
+    }
+    let snapshot = metrics.snapshot();
+    assert_eq!(snapshot.in_flight_requests, 0);
+    assert_eq!(snapshot.total_requests, 5);
+    assert_eq!(snapshot.http1_requests, 5);
+    let duration = snapshot.request_duration;
+    assert_eq!(duration.count, 5);
+    assert_eq!(duration.buckets[0], (0.005, 1));
+    assert_eq!(duration.buckets[2], (0.025, 3));
+    assert_eq!(duration.buckets[7], (1.0, 4));
+    // 20s is above the largest bucket and only shows up in the count.
+    assert_eq!(duration.buckets[10], (10.0, 4));
+    assert!((duration.sum - 20.741).abs() < 1e-9);
+  }
+
+  #[test]
+  fn connection_guard_tracks_protocol() {
+    let metrics = Arc::new(HttpServerMetrics::default());
+    let mut guard = ConnectionMetricsGuard::new(metrics.clone());
+    guard.set_protocol(HttpProtocol::Http2);
+    let snapshot = metrics.snapshot();
+    assert_eq!(snapshot.active_connections, 1);
+    assert_eq!(snapshot.http2_connections, 1);
+    drop(guard);
+    let snapshot = metrics.snapshot();
+    assert_eq!(snapshot.active_connections, 0);
+    assert_eq!(snapshot.http2_connections, 0);
+    assert_eq!(snapshot.total_connections, 1);
+  }
+}
@@ -1,5 +1,6 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 use bytes::Bytes;
+use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::futures::stream::Peekable;
 use deno_core::futures::Stream;
@@ -19,7 +20,8 @@ use std::task::ready;
 use std::task::Poll;
 
 /// Converts a hyper incoming body stream into a stream of [`Bytes`] that we can use to read in V8.
-struct ReadFuture(Incoming);
+/// The second field is the number of bytes the client may still send, if the body size is limited.
+struct ReadFuture(Incoming, Option<u64>);
 
 impl Stream for ReadFuture {
   type Item = Result<Bytes, AnyError>;
@@ -37,6 +39,15 @@ impl Stream for ReadFuture {
           if let Ok(data) = frame.into_data() {
             // Ensure that we never yield an empty frame
             if !data.is_empty() {
+              if let Some(remaining) = &mut this.1 {
+                let Some(left) = remaining.checked_sub(data.len() as u64)
+                else {
+                  break Poll::Ready(Some(Err(type_error(
+                    "Request body exceeds the maximum allowed size",
+                  ))));
+                };
+                *remaining = left;
+              }
               break Poll::Ready(Some(Ok::<_, AnyError>(data)));
             }
           }
@@ -53,9 +64,12 @@ impl Stream for ReadFuture {
 pub struct HttpRequestBody(AsyncRefCell<Peekable<ReadFuture>>, SizeHint);
 
 impl HttpRequestBody {
-  pub fn new(body: Incoming) -> Self {
+  pub fn new(body: Incoming, max_size: Option<u64>) -> Self {
     let size_hint = body.size_hint();
-    Self(AsyncRefCell::new(ReadFuture(body).peekable()), size_hint)
+    Self(
+      AsyncRefCell::new(ReadFuture(body, max_size).peekable()),
+      size_hint,
+    )
   }
 
   async fn read(self: Rc<Self>, limit: usize) -> Result<BufView, AnyError> {
@@ -1,4 +1,6 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+use crate::limits::HttpServeLimits;
+use crate::metrics::HttpServerMetrics;
 use crate::request_properties::HttpConnectionProperties;
 use crate::response_body::ResponseBytesInner;
 use crate::response_body::ResponseStreamResult;
@@ -13,7 +15,9 @@ use hyper::body::Frame;
 use hyper::body::Incoming;
 use hyper::body::SizeHint;
 use hyper::header::HeaderMap;
+use hyper::header::CONTENT_LENGTH;
 use hyper::upgrade::OnUpgrade;
+use hyper::StatusCode;
 
 use scopeguard::guard;
 use scopeguard::ScopeGuard;
@@ -25,9 +29,11 @@ use std::future::Future;
 use std::mem::ManuallyDrop;
 use std::pin::Pin;
 use std::rc::Rc;
+use std::sync::Arc;
 use std::task::Context;
 use std::task::Poll;
 use std::task::Waker;
+use std::time::Instant;
 
 pub type Request = hyper::Request<Incoming>;
 pub type Response = hyper::Response<HttpRecordResponse>;
@@ -130,13 +136,27 @@ impl<T> std::ops::Deref for SignallingRc<T> {
   }
 }
 
-pub(crate) struct HttpServerState(RefCell<HttpServerStateInner>);
+pub(crate) struct HttpServerState(
+  RefCell<HttpServerStateInner>,
+  Arc<HttpServerMetrics>,
+  HttpServeLimits,
+);
 
 impl HttpServerState {
-  pub fn new() -> SignallingRc<Self> {
-    SignallingRc::new(Self(RefCell::new(HttpServerStateInner {
-      pool: Vec::new(),
-    })))
+  pub fn new(limits: HttpServeLimits) -> SignallingRc<Self> {
+    SignallingRc::new(Self(
+      RefCell::new(HttpServerStateInner { pool: Vec::new() }),
+      Default::default(),
+      limits,
+    ))
+  }
+
+  pub fn metrics(&self) -> &Arc<HttpServerMetrics> {
+    &self.1
+  }
+
+  pub fn limits(&self) -> &HttpServeLimits {
+    &self.2
   }
 }
 
@@ -186,14 +206,23 @@ pub(crate) async fn handle_request(
   // and execution could stop at any await point.
   // The HttpRecord must live until JavaScript is done processing so is wrapped
   // in an Rc. The guard ensures unneeded resources are freed at cancellation.
+  let max_body_size = server_state.limits().max_body_size;
   let guarded_record = guard(
     HttpRecord::new(request, request_info, server_state),
     HttpRecord::cancel,
   );
 
-  // Clone HttpRecord and send to JavaScript for processing.
-  // Safe to unwrap as channel receiver is never closed.
-  tx.send(guarded_record.clone()).await.unwrap();
+  if exceeds_body_limit(&guarded_record.request_parts().headers, max_body_size)
+  {
+    // Reject oversized bodies up front, without involving JavaScript.
+    http_trace!(*guarded_record, "handle_request body too large");
+    guarded_record.response_parts().status = StatusCode::PAYLOAD_TOO_LARGE;
+    guarded_record.clone().complete();
+  } else {
+    // Clone HttpRecord and send to JavaScript for processing.
+    // Safe to unwrap as channel receiver is never closed.
+    tx.send(guarded_record.clone()).await.unwrap();
+  }
 
   // Wait for JavaScript handler to return request.
   http_trace!(*guarded_record, "handle_request response_ready.await");
@@ -206,11 +235,25 @@ pub(crate) async fn handle_request(
   Ok(response)
 }
 
+/// Whether the declared `Content-Length` is larger than the configured limit.
+/// Bodies without a length are checked as they are read.
+fn exceeds_body_limit(headers: &HeaderMap, max_body_size: Option<u64>) -> bool {
+  let Some(max_body_size) = max_body_size else {
+    return false;
+  };
+  headers
+    .get(CONTENT_LENGTH)
+    .and_then(|value| value.to_str().ok())
+    .and_then(|value| value.parse::<u64>().ok())
+    .is_some_and(|length| length > max_body_size)
+}
+
 struct HttpRecordInner {
   server_state: SignallingRc<HttpServerState>,
   request_info: HttpConnectionProperties,
   request_parts: http::request::Parts,
   request_body: Option<RequestBodyState>,
+  request_started: Instant,
   response_parts: Option<http::response::Parts>,
   response_ready: bool,
   response_waker: Option<Waker>,
@@ -242,6 +285,7 @@ impl HttpRecord {
     request_info: HttpConnectionProperties,
     server_state: SignallingRc<HttpServerState>,
   ) -> Rc<Self> {
+    server_state.metrics().request_started(request.version());
     let (request_parts, request_body) = request.into_parts();
     let request_body = Some(request_body.into());
     let (mut response_parts, _) = http::Response::new(()).into_parts();
@@ -266,6 +310,7 @@ impl HttpRecord {
       request_info,
       request_parts,
       request_body,
+      request_started: Instant::now(),
       response_parts: Some(response_parts),
       response_ready: false,
       response_waker: None,
@@ -283,6 +328,10 @@ impl HttpRecord {
   fn finish(self: Rc<Self>) {
     http_trace!(self, "HttpRecord::finish");
     let mut inner = self.self_mut();
+    inner
+      .server_state
+      .metrics()
+      .request_finished(inner.request_started.elapsed());
     inner.response_body_finished = true;
     let response_body_waker = inner.response_body_waker.take();
     let needs_close_after_finish = inner.needs_close_after_finish;
@@ -367,6 +416,11 @@ impl HttpRecord {
     }
   }
 
+  /// The largest request body the server accepts, if limited.
+  pub fn max_body_size(&self) -> Option<u64> {
+    self.self_ref().server_state.limits().max_body_size
+  }
+
   /// Replace the request body with a resource ID and the OpState we'll need to shut it down.
   /// We cannot keep just the resource itself, as JS code might be reading from the resource ID
   /// to generate the response data (requiring us to keep it in the resource table).
@@ -642,7 +696,7 @@ mod tests {
   #[tokio::test]
   async fn test_handle_request() -> Result<(), AnyError> {
     let (tx, mut rx) = tokio::sync::mpsc::channel(10);
-    let server_state = HttpServerState::new();
+    let server_state = HttpServerState::new(Default::default());
     let server_state_check = server_state.clone();
     let request_info = HttpConnectionProperties {
       peer_address: "".into(),
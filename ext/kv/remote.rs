@@ -206,6 +206,11 @@ impl<P: RemoteDbHandlerPermissions + 'static> DatabaseHandler
         pool_idle_timeout: None,
         http1: false,
         http2: true,
+        connect_timeout: None,
+        tls_handshake_timeout: None,
+        idle_timeout: None,
+        dns_overrides: Default::default(),
+        local_address: None,
       },
     )?;
     let fetch_client = FetchClient(client);
@@ -43,6 +43,54 @@ import {
   headersFromHeaderList,
 } from "ext:deno_fetch/20_headers.js";
 
+webidl.converters.WebSocketCompressionOptions = webidl
+  .createDictionaryConverter(
+    "WebSocketCompressionOptions",
+    [
+      {
+        key: "serverMaxWindowBits",
+        converter: (v, prefix, context, opts) =>
+          webidl.converters.octet(v, prefix, context, {
+            ...opts,
+            enforceRange: true,
+          }),
+      },
+      {
+        key: "clientMaxWindowBits",
+        converter: (v, prefix, context, opts) =>
+          webidl.converters.octet(v, prefix, context, {
+            ...opts,
+            enforceRange: true,
+          }),
+      },
+      {
+        key: "serverNoContextTakeover",
+        converter: webidl.converters.boolean,
+        defaultValue: false,
+      },
+      {
+        key: "clientNoContextTakeover",
+        converter: webidl.converters.boolean,
+        defaultValue: false,
+      },
+    ],
+  );
+webidl.converters["boolean or WebSocketCompressionOptions"] = (
+  V,
+  prefix,
+  context,
+  opts,
+) => {
+  if (webidl.type(V) === "Object") {
+    return webidl.converters.WebSocketCompressionOptions(
+      V,
+      prefix,
+      context,
+      opts,
+    );
+  }
+  return webidl.converters.boolean(V, prefix, context, opts);
+};
 webidl.converters.WebSocketStreamOptions = webidl.createDictionaryConverter(
   "WebSocketStreamOptions",
   [
@@ -61,6 +109,11 @@ webidl.converters.WebSocketStreamOptions = webidl.createDictionaryConverter(
       key: "headers",
       converter: webidl.converters.HeadersInit,
     },
+    {
+      key: "compression",
+      converter: webidl.converters["boolean or WebSocketCompressionOptions"],
+      defaultValue: false,
+    },
   ],
 );
 webidl.converters.WebSocketCloseInfo = webidl.createDictionaryConverter(
@@ -84,6 +137,17 @@ webidl.converters.WebSocketCloseInfo = webidl.createDictionaryConverter(
 
 const CLOSE_RESPONSE_TIMEOUT = 5000;
 
+/** Compression is only offered when asked for. */
+function compressionOptions(compression) {
+  if (compression === false) {
+    return null;
+  }
+  if (compression === true) {
+    return { __proto__: null };
+  }
+  return compression;
+}
+
 const _rid = Symbol("[[rid]]");
 const _url = Symbol("[[url]]");
 const _opened = Symbol("[[opened]]");
@@ -173,6 +237,7 @@ class WebSocketStream {
           options.protocols ? ArrayPrototypeJoin(options.protocols, ", ") : "",
           cancelRid,
           headerListFromHeaders(headers),
+          compressionOptions(options.compression),
         ),
         (create) => {
           options.signal?.[remove](abort);
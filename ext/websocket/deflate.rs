@@ -0,0 +1,701 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! The `permessage-deflate` extension (RFC 7692).
+//!
+//! fastwebsockets has no notion of extensions and rejects frames with any of
+//! the reserved bits set, so compression is layered around it: a
+//! [`FrameRewriter`] sits between fastwebsockets and the socket and moves the
+//! RSV1 ("compressed") bit in and out of frame headers, while [`Deflater`] and
+//! [`Inflater`] transform whole message payloads above it.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use deno_core::anyhow::anyhow;
+use deno_core::anyhow::bail;
+use deno_core::error::type_error;
+use deno_core::error::AnyError;
+use fastwebsockets::Frame;
+use fastwebsockets::OpCode;
+use fastwebsockets::Role;
+use flate2::Compress;
+use flate2::Compression;
+use flate2::Decompress;
+use flate2::FlushCompress;
+use flate2::FlushDecompress;
+use flate2::Status;
+use serde::Deserialize;
+
+pub const EXTENSION_NAME: &str = "permessage-deflate";
+
+/// zlib cannot produce raw deflate streams with a 256 byte window, so 8 is
+/// never negotiated even though the RFC allows it.
+const MIN_WINDOW_BITS: u8 = 9;
+const MAX_WINDOW_BITS: u8 = 15;
+/// Every compressed message ends with an empty stored block, which is
+/// stripped before sending and restored before inflating.
+const DEFLATE_TRAILER: [u8; 4] = [0x00, 0x00, 0xff, 0xff];
+/// Matches the limit fastwebsockets applies to uncompressed messages.
+const MAX_MESSAGE_SIZE: usize = 64 << 20;
+
+const FIN_BIT: u8 = 0x80;
+const RSV1_BIT: u8 = 0x40;
+const OPCODE_MASK: u8 = 0x0f;
+const OPCODE_TEXT: u8 = 0x1;
+const OPCODE_BINARY: u8 = 0x2;
+
+/// Compression settings requested by the application, as passed in from
+/// JavaScript. They make up the client's offer, or bound what the server
+/// accepts from one.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeflateOptions {
+  pub server_max_window_bits: Option<u8>,
+  pub client_max_window_bits: Option<u8>,
+  #[serde(default)]
+  pub server_no_context_takeover: bool,
+  #[serde(default)]
+  pub client_no_context_takeover: bool,
+}
+
+impl DeflateOptions {
+  pub fn validate(&self) -> Result<(), AnyError> {
+    for (name, bits) in [
+      ("serverMaxWindowBits", self.server_max_window_bits),
+      ("clientMaxWindowBits", self.client_max_window_bits),
+    ] {
+      if let Some(bits) = bits {
+        if !(MIN_WINDOW_BITS..=MAX_WINDOW_BITS).contains(&bits) {
+          return Err(type_error(format!(
+            "{name} must be between {MIN_WINDOW_BITS} and {MAX_WINDOW_BITS}"
+          )));
+        }
+      }
+    }
+    Ok(())
+  }
+
+  /// The `Sec-WebSocket-Extensions` value a client sends to offer compression.
+  pub fn client_offer(&self) -> String {
+    let mut offer = EXTENSION_NAME.to_string();
+    if self.server_no_context_takeover {
+      offer.push_str("; server_no_context_takeover");
+    }
+    if self.client_no_context_takeover {
+      offer.push_str("; client_no_context_takeover");
+    }
+    if let Some(bits) = self.server_max_window_bits {
+      offer.push_str(&format!("; server_max_window_bits={bits}"));
+    }
+    // Only advertise client_max_window_bits when a limit was asked for:
+    // otherwise the server could ask for a window zlib can't honour.
+    if let Some(bits) = self.client_max_window_bits {
+      offer.push_str(&format!("; client_max_window_bits={bits}"));
+    }
+    offer
+  }
+
+  /// Pick the first acceptable `permessage-deflate` offer from a client's
+  /// `Sec-WebSocket-Extensions` header, returning the value to answer with.
+  /// Offers with parameters we don't understand or can't honour are skipped,
+  /// as the RFC requires.
+  pub fn server_accept(&self, offers: &str) -> Option<String> {
+    parse_extensions(offers)
+      .filter(|(name, _)| name.eq_ignore_ascii_case(EXTENSION_NAME))
+      .find_map(|(_, params)| self.accept_offer(&params))
+  }
+
+  fn accept_offer(&self, params: &[(&str, Option<&str>)]) -> Option<String> {
+    let offer = DeflateParams::parse(params).ok()?;
+
+    let mut response = EXTENSION_NAME.to_string();
+    if offer.server_no_context_takeover || self.server_no_context_takeover {
+      response.push_str("; server_no_context_takeover");
+    }
+    if offer.client_no_context_takeover || self.client_no_context_takeover {
+      response.push_str("; client_no_context_takeover");
+    }
+
+    let server_bits =
+      match (offer.server_max_window_bits, self.server_max_window_bits) {
+        (Some(offered), _) if offered < MIN_WINDOW_BITS => return None,
+        (Some(offered), Some(ours)) => Some(offered.min(ours)),
+        (offered, ours) => offered.or(ours),
+      };
+    if let Some(bits) = server_bits {
+      response.push_str(&format!("; server_max_window_bits={bits}"));
+    }
+
+    // The client may only be limited if it said it supports the parameter.
+    if let (Some(offered), Some(ours)) =
+      (offer.client_max_window_bits, self.client_max_window_bits)
+    {
+      let bits = offered.map_or(ours, |offered| offered.min(ours));
+      response.push_str(&format!("; client_max_window_bits={bits}"));
+    }
+
+    Some(response)
+  }
+
+  /// Validate a server's `Sec-WebSocket-Extensions` response against the
+  /// offer made with these options.
+  pub fn client_accept(
+    &self,
+    response: &str,
+  ) -> Result<Option<DeflateConfig>, AnyError> {
+    let mut extensions = parse_extensions(response);
+    let Some((name, params)) = extensions.next() else {
+      return Ok(None);
+    };
+    if !name.eq_ignore_ascii_case(EXTENSION_NAME) || extensions.next().is_some()
+    {
+      bail!("Server accepted an extension that was not offered: {response}");
+    }
+
+    let accepted = DeflateParams::parse(&params)?;
+    if let Some(offered) = self.server_max_window_bits {
+      match accepted.server_max_window_bits {
+        Some(bits) if bits <= offered => {}
+        _ => bail!("Server did not accept server_max_window_bits={offered}"),
+      }
+    }
+    if self.server_no_context_takeover && !accepted.server_no_context_takeover {
+      bail!("Server did not accept server_no_context_takeover");
+    }
+    match accepted.client_max_window_bits {
+      Some(_) if self.client_max_window_bits.is_none() => {
+        bail!("Server sent client_max_window_bits, which was not offered")
+      }
+      Some(None) => bail!("client_max_window_bits requires a value"),
+      Some(Some(bits)) if bits < MIN_WINDOW_BITS => {
+        bail!("Unsupported client_max_window_bits={bits}")
+      }
+      _ => {}
+    }
+
+    Ok(Some(accepted.into()))
+  }
+}
+
+/// The parameters of a negotiated `permessage-deflate` extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DeflateConfig {
+  pub server_max_window_bits: u8,
+  pub client_max_window_bits: u8,
+  pub server_no_context_takeover: bool,
+  pub client_no_context_takeover: bool,
+}
+
+impl DeflateConfig {
+  /// Read back the parameters from a `Sec-WebSocket-Extensions` response
+  /// produced by [`DeflateOptions::server_accept`].
+  pub fn from_response(response: &str) -> Result<Option<Self>, AnyError> {
+    let Some((_, params)) = parse_extensions(response)
+      .find(|(name, _)| name.eq_ignore_ascii_case(EXTENSION_NAME))
+    else {
+      return Ok(None);
+    };
+    Ok(Some(DeflateParams::parse(&params)?.into()))
+  }
+}
+
+impl From<DeflateParams> for DeflateConfig {
+  fn from(params: DeflateParams) -> Self {
+    Self {
+      server_max_window_bits: params
+        .server_max_window_bits
+        .unwrap_or(MAX_WINDOW_BITS),
+      client_max_window_bits: params
+        .client_max_window_bits
+        .flatten()
+        .unwrap_or(MAX_WINDOW_BITS),
+      server_no_context_takeover: params.server_no_context_takeover,
+      client_no_context_takeover: params.client_no_context_takeover,
+    }
+  }
+}
+
+#[derive(Debug, Default)]
+struct DeflateParams {
+  server_no_context_takeover: bool,
+  client_no_context_takeover: bool,
+  server_max_window_bits: Option<u8>,
+  /// In an offer, `client_max_window_bits` may appear without a value.
+  client_max_window_bits: Option<Option<u8>>,
+}
+
+impl DeflateParams {
+  fn parse(params: &[(&str, Option<&str>)]) -> Result<Self, AnyError> {
+    let mut parsed = Self::default();
+    for (name, value) in params {
+      let duplicate = match name.to_ascii_lowercase().as_str() {
+        "server_no_context_takeover" if value.is_none() => {
+          std::mem::replace(&mut parsed.server_no_context_takeover, true)
+        }
+        "client_no_context_takeover" if value.is_none() => {
+          std::mem::replace(&mut parsed.client_no_context_takeover, true)
+        }
+        "server_max_window_bits" => {
+          let bits = parse_window_bits(value.ok_or_else(|| {
+            anyhow!("server_max_window_bits requires a value")
+          })?)?;
+          parsed.server_max_window_bits.replace(bits).is_some()
+        }
+        "client_max_window_bits" => {
+          let bits = value.map(parse_window_bits).transpose()?;
+          parsed.client_max_window_bits.replace(bits).is_some()
+        }
+        _ => bail!("Invalid {EXTENSION_NAME} parameter: {name}"),
+      };
+      if duplicate {
+        bail!("Duplicate {EXTENSION_NAME} parameter: {name}");
+      }
+    }
+    Ok(parsed)
+  }
+}
+
+fn parse_window_bits(value: &str) -> Result<u8, AnyError> {
+  match value.parse::<u8>() {
+    Ok(bits)
+      if (8..=MAX_WINDOW_BITS).contains(&bits) && !value.starts_with('0') =>
+    {
+      Ok(bits)
+    }
+    _ => bail!("Invalid window bits: {value}"),
+  }
+}
+
+/// Split a `Sec-WebSocket-Extensions` value into extension names and their
+/// parameters. Quoted parameter values are unquoted.
+fn parse_extensions(
+  value: &str,
+) -> impl Iterator<Item = (&str, Vec<(&str, Option<&str>)>)> {
+  value
+    .split(',')
+    .map(str::trim)
+    .filter(|extension| !extension.is_empty())
+    .map(|extension| {
+      let mut parts = extension.split(';').map(str::trim);
+      let name = parts.next().unwrap_or_default();
+      let params = parts
+        .filter(|param| !param.is_empty())
+        .map(|param| match param.split_once('=') {
+          Some((name, value)) => {
+            let value = value.trim();
+            let value = value
+              .strip_prefix('"')
+              .and_then(|value| value.strip_suffix('"'))
+              .unwrap_or(value);
+            (name.trim(), Some(value))
+          }
+          None => (param, None),
+        })
+        .collect();
+      (name, params)
+    })
+}
+
+/// Set up compression for one end of a connection.
+pub(crate) fn per_message_deflate(
+  config: &DeflateConfig,
+  role: Role,
+) -> (FrameRewriter, Deflater, Inflater) {
+  let (own_bits, own_reset, peer_reset) = match role {
+    Role::Server => (
+      config.server_max_window_bits,
+      config.server_no_context_takeover,
+      config.client_no_context_takeover,
+    ),
+    Role::Client => (
+      config.client_max_window_bits,
+      config.client_no_context_takeover,
+      config.server_no_context_takeover,
+    ),
+  };
+  let received = Arc::new(Mutex::new(VecDeque::new()));
+  let rewriter = FrameRewriter {
+    received: received.clone(),
+    ..Default::default()
+  };
+  let deflater = Deflater {
+    compress: Compress::new_with_window_bits(
+      Compression::default(),
+      false,
+      own_bits,
+    ),
+    reset: own_reset,
+  };
+  // A full size window can inflate anything the peer sends, whatever it
+  // agreed to.
+  let inflater = Inflater {
+    decompress: Decompress::new_with_window_bits(false, MAX_WINDOW_BITS),
+    reset: peer_reset,
+    received,
+  };
+  (rewriter, deflater, inflater)
+}
+
+/// Compresses outgoing messages. Every data message is compressed.
+pub(crate) struct Deflater {
+  compress: Compress,
+  reset: bool,
+}
+
+impl Deflater {
+  pub fn deflate<'f>(
+    &mut self,
+    frame: Frame<'f>,
+  ) -> Result<Frame<'f>, AnyError> {
+    if !matches!(frame.opcode, OpCode::Text | OpCode::Binary) {
+      return Ok(frame);
+    }
+
+    let input = &frame.payload[..];
+    let start = self.compress.total_in();
+    let mut output = Vec::with_capacity(input.len() / 2 + 64);
+    loop {
+      if output.len() == output.capacity() {
+        output.reserve(output.capacity());
+      }
+      let consumed = (self.compress.total_in() - start) as usize;
+      self.compress.compress_vec(
+        &input[consumed..],
+        &mut output,
+        FlushCompress::Sync,
+      )?;
+      let consumed = (self.compress.total_in() - start) as usize;
+      // A sync flush is complete once zlib leaves room in the output.
+      if consumed == input.len() && output.len() < output.capacity() {
+        break;
+      }
+    }
+    if output.ends_with(&DEFLATE_TRAILER) {
+      output.truncate(output.len() - DEFLATE_TRAILER.len());
+    }
+    if self.reset {
+      self.compress.reset();
+    }
+
+    Ok(Frame::new(true, frame.opcode, None, output.into()))
+  }
+}
+
+/// How a received data message was sent, as recorded by the
+/// [`FrameRewriter`] when its first frame went by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Received {
+  Plain,
+  Compressed { text: bool },
+}
+
+/// Decompresses incoming messages that the peer marked as compressed.
+pub(crate) struct Inflater {
+  decompress: Decompress,
+  reset: bool,
+  received: Arc<Mutex<VecDeque<Received>>>,
+}
+
+impl Inflater {
+  pub fn inflate<'f>(
+    &mut self,
+    frame: Frame<'f>,
+  ) -> Result<Frame<'f>, AnyError> {
+    if !matches!(frame.opcode, OpCode::Text | OpCode::Binary) {
+      return Ok(frame);
+    }
+    let received = self.received.lock().unwrap().pop_front();
+    let Some(Received::Compressed { text }) = received else {
+      return Ok(frame);
+    };
+
+    let mut input = Vec::with_capacity(frame.payload.len() + 4);
+    input.extend_from_slice(&frame.payload);
+    input.extend_from_slice(&DEFLATE_TRAILER);
+    let start = self.decompress.total_in();
+    let mut output =
+      Vec::with_capacity((input.len() * 2).min(MAX_MESSAGE_SIZE));
+    loop {
+      if output.len() == output.capacity() {
+        if output.len() >= MAX_MESSAGE_SIZE {
+          bail!("Message too big");
+        }
+        output.reserve(output.capacity().min(MAX_MESSAGE_SIZE));
+      }
+      let consumed = (self.decompress.total_in() - start) as usize;
+      let status = self.decompress.decompress_vec(
+        &input[consumed..],
+        &mut output,
+        FlushDecompress::Sync,
+      )?;
+      let consumed = (self.decompress.total_in() - start) as usize;
+      if status == Status::StreamEnd {
+        // The peer ended the deflate stream, so the next message starts a
+        // fresh one.
+        self.decompress.reset(false);
+        break;
+      }
+      if consumed == input.len() && output.len() < output.capacity() {
+        break;
+      }
+    }
+    if output.len() > MAX_MESSAGE_SIZE {
+      bail!("Message too big");
+    }
+    if self.reset {
+      self.decompress.reset(false);
+    }
+
+    let opcode = if text { OpCode::Text } else { OpCode::Binary };
+    Ok(Frame::new(true, opcode, None, output.into()))
+  }
+}
+
+/// Moves the RSV1 bit in and out of frame headers as they pass between
+/// fastwebsockets and the socket.
+///
+/// Outgoing data frames are all compressed, so RSV1 is set on each of them.
+/// Incoming data frames have RSV1 cleared before fastwebsockets sees them,
+/// and whether they were compressed is queued for the [`Inflater`].
+/// Compressed text frames are also relabelled as binary, since their payload
+/// isn't valid UTF-8 until inflated.
+#[derive(Default)]
+pub(crate) struct FrameRewriter {
+  read: FrameScanner,
+  write: FrameScanner,
+  write_buf: Vec<u8>,
+  received: Arc<Mutex<VecDeque<Received>>>,
+}
+
+impl FrameRewriter {
+  /// Rewrite bytes that were just read from the socket.
+  pub fn on_read(&mut self, data: &mut [u8]) {
+    let received = &self.received;
+    self.read.scan(data, |head| {
+      let opcode = *head & OPCODE_MASK;
+      if opcode != OPCODE_TEXT && opcode != OPCODE_BINARY {
+        // RSV1 on control or continuation frames is a protocol error, which
+        // fastwebsockets reports as long as the bit is left alone.
+        return;
+      }
+      let entry = if *head & RSV1_BIT != 0 {
+        *head = (*head & !(RSV1_BIT | OPCODE_MASK)) | OPCODE_BINARY;
+        Received::Compressed {
+          text: opcode == OPCODE_TEXT,
+        }
+      } else {
+        Received::Plain
+      };
+      received.lock().unwrap().push_back(entry);
+    });
+  }
+
+  /// Rewrite bytes about to be written to the socket. Nothing is recorded
+  /// until [`FrameRewriter::on_written`] says how many were actually sent.
+  pub fn prepare_write(&mut self, data: &[u8]) -> &[u8] {
+    self.write_buf.clear();
+    self.write_buf.extend_from_slice(data);
+    let mut scanner = self.write;
+    scanner.scan(&mut self.write_buf, mark_compressed);
+    &self.write_buf
+  }
+
+  pub fn on_written(&mut self, n: usize) {
+    self.write.scan(&mut self.write_buf[..n], mark_compressed);
+  }
+}
+
+fn mark_compressed(head: &mut u8) {
+  let opcode = *head & OPCODE_MASK;
+  if opcode == OPCODE_TEXT || opcode == OPCODE_BINARY {
+    *head |= RSV1_BIT;
+  }
+}
+
+/// Follows frame boundaries through a raw WebSocket byte stream, however it
+/// is split into chunks.
+#[derive(Clone, Copy, Default)]
+struct FrameScanner {
+  header: [u8; 14],
+  header_len: usize,
+  payload_remaining: u64,
+}
+
+impl FrameScanner {
+  /// Advance over `data`, calling `on_frame` with the first byte of every
+  /// frame that starts in it.
+  fn scan(&mut self, data: &mut [u8], mut on_frame: impl FnMut(&mut u8)) {
+    let mut i = 0;
+    while i < data.len() {
+      if self.payload_remaining > 0 {
+        let skip = self.payload_remaining.min((data.len() - i) as u64);
+        self.payload_remaining -= skip;
+        i += skip as usize;
+        continue;
+      }
+      if self.header_len == 0 {
+        on_frame(&mut data[i]);
+      }
+      self.header[self.header_len] = data[i];
+      self.header_len += 1;
+      i += 1;
+      if let Some(payload_len) = self.payload_len() {
+        self.header_len = 0;
+        self.payload_remaining = payload_len;
+      }
+    }
+  }
+
+  /// The payload length, once the whole header has been seen.
+  fn payload_len(&self) -> Option<u64> {
+    if self.header_len < 2 {
+      return None;
+    }
+    let masked = self.header[1] & 0x80 != 0;
+    let (extended, len) = match self.header[1] & 0x7f {
+      126 => (2, None),
+      127 => (8, None),
+      len => (0, Some(len as u64)),
+    };
+    let header_len = 2 + extended + if masked { 4 } else { 0 };
+    if self.header_len < header_len {
+      return None;
+    }
+    Some(len.unwrap_or_else(|| {
+      let mut bytes = [0; 8];
+      bytes[8 - extended..].copy_from_slice(&self.header[2..2 + extended]);
+      u64::from_be_bytes(bytes)
+    }))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn negotiate_offer() {
+    let options = DeflateOptions::default();
+    assert_eq!(
+      options
+        .server_accept("permessage-deflate; client_max_window_bits")
+        .as_deref(),
+      Some("permessage-deflate")
+    );
+    // The first acceptable offer wins.
+    assert_eq!(
+      options
+        .server_accept(
+          "x-webkit-deflate-frame, permessage-deflate; server_max_window_bits=8, permessage-deflate; server_max_window_bits=10; client_no_context_takeover"
+        )
+        .as_deref(),
+      Some("permessage-deflate; client_no_context_takeover; server_max_window_bits=10")
+    );
+    assert_eq!(options.server_accept("permessage-deflate; foo"), None);
+
+    let options = DeflateOptions {
+      client_max_window_bits: Some(12),
+      server_no_context_takeover: true,
+      ..Default::default()
+    };
+    let response = options
+      .server_accept("permessage-deflate; client_max_window_bits=\"14\"")
+      .unwrap();
+    assert_eq!(
+      response,
+      "permessage-deflate; server_no_context_takeover; client_max_window_bits=12"
+    );
+    assert_eq!(
+      DeflateConfig::from_response(&response).unwrap(),
+      Some(DeflateConfig {
+        server_max_window_bits: 15,
+        client_max_window_bits: 12,
+        server_no_context_takeover: true,
+        client_no_context_takeover: false,
+      })
+    );
+  }
+
+  #[test]
+  fn validate_response() {
+    let options = DeflateOptions::default();
+    assert_eq!(options.client_accept("").unwrap(), None);
+    assert!(options
+      .client_accept("permessage-deflate")
+      .unwrap()
+      .is_some());
+    assert!(options
+      .client_accept("permessage-deflate; client_max_window_bits=10")
+      .is_err());
+    assert!(options
+      .client_accept("permessage-deflate, permessage-deflate")
+      .is_err());
+
+    let options = DeflateOptions {
+      server_max_window_bits: Some(10),
+      ..Default::default()
+    };
+    assert!(options.client_accept("permessage-deflate").is_err());
+    assert!(options
+      .client_accept("permessage-deflate; server_max_window_bits=10")
+      .unwrap()
+      .is_some());
+  }
+
+  #[test]
+  fn round_trip() {
+    let config = DeflateConfig::from_response(
+      "permessage-deflate; server_max_window_bits=10",
+    )
+    .unwrap()
+    .unwrap();
+    let (mut client_frames, _, mut client) =
+      per_message_deflate(&config, Role::Client);
+    let (mut server_frames, mut server, _) =
+      per_message_deflate(&config, Role::Server);
+
+    for message in ["Hello", "Hello", ""] {
+      let frame = Frame::new(
+        true,
+        OpCode::Text,
+        None,
+        message.as_bytes().to_vec().into(),
+      );
+      let compressed = server.deflate(frame).unwrap();
+
+      // Write the frame header as fastwebsockets would, through both ends'
+      // rewriters.
+      let mut wire =
+        vec![FIN_BIT | OPCODE_TEXT, compressed.payload.len() as u8];
+      wire.extend_from_slice(&compressed.payload);
+      let mut wire = server_frames.prepare_write(&wire).to_vec();
+      server_frames.on_written(wire.len());
+      assert_eq!(wire[0], FIN_BIT | RSV1_BIT | OPCODE_TEXT);
+      client_frames.on_read(&mut wire);
+      assert_eq!(wire[0], FIN_BIT | OPCODE_BINARY);
+
+      let frame =
+        Frame::new(true, OpCode::Binary, None, wire[2..].to_vec().into());
+      let inflated = client.inflate(frame).unwrap();
+      assert!(matches!(inflated.opcode, OpCode::Text));
+      assert_eq!(&inflated.payload[..], message.as_bytes());
+    }
+  }
+
+  #[test]
+  fn scanner_follows_split_headers() {
+    let mut scanner = FrameScanner::default();
+    let mut frames = 0;
+    // A masked 300 byte frame, then an unmasked empty one, fed byte by byte.
+    let mut data = vec![0x82, 0x80 | 126, 0x01, 0x2c, 1, 2, 3, 4];
+    data.extend(std::iter::repeat(0).take(300));
+    data.extend([0x89, 0x00]);
+    for byte in data.chunks_mut(1) {
+      scanner.scan(byte, |_| frames += 1);
+    }
+    assert_eq!(frames, 2);
+    assert_eq!(scanner.header_len, 0);
+    assert_eq!(scanner.payload_remaining, 0);
+  }
+}
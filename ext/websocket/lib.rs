@@ -1,4 +1,8 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+use crate::deflate::DeflateConfig;
+use crate::deflate::DeflateOptions;
+use crate::deflate::Deflater;
+use crate::deflate::Inflater;
 use crate::stream::WebSocketStream;
 use bytes::Bytes;
 use deno_core::anyhow::bail;
@@ -63,6 +67,7 @@ use fastwebsockets::Role;
 use fastwebsockets::WebSocket;
 use fastwebsockets::WebSocketWrite;
 
+mod deflate;
 mod stream;
 
 static USE_WRITEV: Lazy<bool> = Lazy::new(|| {
@@ -167,8 +172,9 @@ async fn handshake_websocket(
   state: &Rc<RefCell<OpState>>,
   uri: &Uri,
   protocols: &str,
+  extensions: &str,
   headers: Option<Vec<(ByteString, ByteString)>>,
-) -> Result<(WebSocket<WebSocketStream>, http::HeaderMap), AnyError> {
+) -> Result<(WebSocketStream, http::HeaderMap), AnyError> {
   let mut request = Request::builder().method(Method::GET).uri(
     uri
       .path_and_query()
@@ -191,8 +197,13 @@ async fn handshake_websocket(
     );
 
   let user_agent = state.borrow().borrow::<WsUserAgent>().0.clone();
-  request =
-    populate_common_request_headers(request, &user_agent, protocols, &headers)?;
+  request = populate_common_request_headers(
+    request,
+    &user_agent,
+    protocols,
+    extensions,
+    &headers,
+  )?;
 
   let request = request.body(http_body_util::Empty::new())?;
   let domain = &uri.host().unwrap().to_string();
@@ -215,6 +226,7 @@ async fn handshake_websocket(
             authority,
             &user_agent,
             protocols,
+            extensions,
             domain,
             &headers,
             &addr,
@@ -231,7 +243,7 @@ async fn handshake_websocket(
 async fn handshake_http1_ws(
   request: Request<http_body_util::Empty<Bytes>>,
   addr: &String,
-) -> Result<(WebSocket<WebSocketStream>, http::HeaderMap), AnyError> {
+) -> Result<(WebSocketStream, http::HeaderMap), AnyError> {
   let tcp_socket = TcpStream::connect(addr).await?;
   handshake_connection(request, tcp_socket).await
 }
@@ -241,7 +253,7 @@ async fn handshake_http1_wss(
   request: Request<http_body_util::Empty<Bytes>>,
   domain: &str,
   addr: &str,
-) -> Result<(WebSocket<WebSocketStream>, http::HeaderMap), AnyError> {
+) -> Result<(WebSocketStream, http::HeaderMap), AnyError> {
   let tcp_socket = TcpStream::connect(addr).await?;
   let tls_config = create_ws_client_config(state, SocketUse::Http1Only)?;
   let dnsname = ServerName::try_from(domain.to_string())
@@ -263,10 +275,11 @@ async fn handshake_http2_wss(
   authority: &str,
   user_agent: &str,
   protocols: &str,
+  extensions: &str,
   domain: &str,
   headers: &Option<Vec<(ByteString, ByteString)>>,
   addr: &str,
-) -> Result<(WebSocket<WebSocketStream>, http::HeaderMap), AnyError> {
+) -> Result<(WebSocketStream, http::HeaderMap), AnyError> {
   let tcp_socket = TcpStream::connect(addr).await?;
   let tls_config = create_ws_client_config(state, SocketUse::Http2Only)?;
   let dnsname = ServerName::try_from(domain.to_string())
@@ -292,8 +305,9 @@ async fn handshake_http2_wss(
     .scheme("https")
     .build()?;
   request = request.uri(uri);
-  request =
-    populate_common_request_headers(request, user_agent, protocols, headers)?;
+  request = populate_common_request_headers(
+    request, user_agent, protocols, extensions, headers,
+  )?;
   request = request.extension(h2::ext::Protocol::from("websocket"));
   let (resp, send) = send.send_request(request.body(())?, false)?;
   let resp = resp.await?;
@@ -301,14 +315,7 @@ async fn handshake_http2_wss(
     bail!("Invalid status code: {}", resp.status());
   }
   let (http::response::Parts { headers, .. }, recv) = resp.into_parts();
-  let mut stream = WebSocket::after_handshake(
-    WebSocketStream::new(stream::WsStreamKind::H2(send, recv), None),
-    Role::Client,
-  );
-  // We currently don't support vectored writes in the H2 streams
-  stream.set_writev(false);
-  // TODO(mmastrac): we should be able to use a zero masking key over HTTPS
-  // stream.set_auto_apply_mask(false);
+  let stream = WebSocketStream::new(stream::WsStreamKind::H2(send, recv), None);
   Ok((stream, headers))
 }
 
@@ -317,14 +324,13 @@ async fn handshake_connection<
 >(
   request: Request<http_body_util::Empty<Bytes>>,
   socket: S,
-) -> Result<(WebSocket<WebSocketStream>, http::HeaderMap), AnyError> {
+) -> Result<(WebSocketStream, http::HeaderMap), AnyError> {
   let (upgraded, response) =
     fastwebsockets::handshake::client(&LocalExecutor, request, socket).await?;
 
   let upgraded = upgraded.into_inner();
   let stream =
     WebSocketStream::new(stream::WsStreamKind::Upgraded(upgraded), None);
-  let stream = WebSocket::after_handshake(stream, Role::Client);
 
   Ok((stream, response.into_parts().0.headers))
 }
@@ -356,6 +362,7 @@ fn populate_common_request_headers(
   mut request: http::request::Builder,
   user_agent: &str,
   protocols: &str,
+  extensions: &str,
   headers: &Option<Vec<(ByteString, ByteString)>>,
 ) -> Result<http::request::Builder, AnyError> {
   request = request
@@ -366,6 +373,10 @@ fn populate_common_request_headers(
     request = request.header("Sec-WebSocket-Protocol", protocols);
   }
 
+  if !extensions.is_empty() {
+    request = request.header("Sec-WebSocket-Extensions", extensions);
+  }
+
   if let Some(headers) = headers {
     for (key, value) in headers {
       let name = HeaderName::from_bytes(key)
@@ -401,10 +412,14 @@ pub async fn op_ws_create<WP>(
   #[string] protocols: String,
   #[smi] cancel_handle: Option<ResourceId>,
   #[serde] headers: Option<Vec<(ByteString, ByteString)>>,
+  #[serde] compression: Option<DeflateOptions>,
 ) -> Result<CreateResponse, AnyError>
 where
   WP: WebSocketPermissions + 'static,
 {
+  if let Some(compression) = &compression {
+    compression.validate()?;
+  }
   {
     let mut s = state.borrow_mut();
     s.borrow_mut::<WP>()
@@ -426,12 +441,18 @@ where
 
   let uri: Uri = url.parse()?;
 
-  let handshake = handshake_websocket(&state, &uri, &protocols, headers)
-    .map_err(|err| {
-      AnyError::from(DomExceptionNetworkError::new(&format!(
-        "failed to connect to WebSocket: {err}"
-      )))
-    });
+  let offer = compression
+    .as_ref()
+    .map(DeflateOptions::client_offer)
+    .unwrap_or_default();
+  let handshake = handshake_websocket(
+    &state, &uri, &protocols, &offer, headers,
+  )
+  .map_err(|err| {
+    AnyError::from(DomExceptionNetworkError::new(&format!(
+      "failed to connect to WebSocket: {err}"
+    )))
+  });
   let (stream, response) = match cancel_resource {
     Some(rc) => handshake.try_or_cancel(rc).await,
     None => handshake.await,
@@ -443,18 +464,37 @@ where
     }
   }
 
+  let extensions = response
+    .get_all("Sec-WebSocket-Extensions")
+    .iter()
+    .map(|header| header.to_str().unwrap())
+    .collect::<Vec<_>>()
+    .join(", ");
+  let deflate = match &compression {
+    Some(compression) => {
+      compression.client_accept(&extensions).map_err(|err| {
+        AnyError::from(DomExceptionNetworkError::new(&format!(
+          "failed to connect to WebSocket: {err}"
+        )))
+      })?
+    }
+    None => None,
+  };
+
+  // TODO(mmastrac): we should be able to use a zero masking key over HTTPS
+  // stream.set_auto_apply_mask(false);
+  let (ws, compression) =
+    after_handshake(stream, Role::Client, deflate.as_ref());
+
   let mut state = state.borrow_mut();
-  let rid = state.resource_table.add(ServerWebSocket::new(stream));
+  let rid = state
+    .resource_table
+    .add(ServerWebSocket::new(ws, compression));
 
   let protocol = match response.get("Sec-WebSocket-Protocol") {
     Some(header) => header.to_str().unwrap(),
     None => "",
   };
-  let extensions = response
-    .get_all("Sec-WebSocket-Extensions")
-    .iter()
-    .map(|header| header.to_str().unwrap())
-    .collect::<String>();
   Ok(CreateResponse {
     rid,
     protocol: protocol.to_string(),
@@ -481,11 +521,22 @@ pub struct ServerWebSocket {
   string: Cell<Option<String>>,
   ws_read: AsyncRefCell<FragmentCollectorRead<ReadHalf<WebSocketStream>>>,
   ws_write: AsyncRefCell<WebSocketWrite<WriteHalf<WebSocketStream>>>,
+  deflater: Option<RefCell<Deflater>>,
+  inflater: Option<RefCell<Inflater>>,
 }
 
 impl ServerWebSocket {
-  fn new(ws: WebSocket<WebSocketStream>) -> Self {
+  fn new(
+    ws: WebSocket<WebSocketStream>,
+    compression: Option<(Deflater, Inflater)>,
+  ) -> Self {
     let (ws_read, ws_write) = ws.split(tokio::io::split);
+    let (deflater, inflater) = match compression {
+      Some((deflater, inflater)) => {
+        (Some(RefCell::new(deflater)), Some(RefCell::new(inflater)))
+      }
+      None => (None, None),
+    };
     Self {
       buffered: Cell::new(0),
       error: Cell::new(None),
@@ -495,6 +546,8 @@ impl ServerWebSocket {
       string: Cell::new(None),
       ws_read: AsyncRefCell::new(FragmentCollectorRead::new(ws_read)),
       ws_write: AsyncRefCell::new(ws_write),
+      deflater,
+      inflater,
     }
   }
 
@@ -525,6 +578,12 @@ impl ServerWebSocket {
     if ws.is_closed() {
       return Ok(());
     }
+    // Compress only once we hold the lock, so that messages go through the
+    // compression context in the order they are sent.
+    let frame = match &self.deflater {
+      Some(deflater) => deflater.borrow_mut().deflate(frame)?,
+      None => frame,
+    };
     ws.write_frame(frame)
       .await
       .map_err(|err| type_error(err.to_string()))?;
@@ -538,26 +597,73 @@ impl Resource for ServerWebSocket {
   }
 }
 
+/// Wrap a stream whose handshake has completed, setting up compression if an
+/// extension was negotiated.
+fn after_handshake(
+  mut stream: WebSocketStream,
+  role: Role,
+  deflate: Option<&DeflateConfig>,
+) -> (WebSocket<WebSocketStream>, Option<(Deflater, Inflater)>) {
+  let compression = deflate.map(|config| {
+    let (rewriter, deflater, inflater) =
+      deflate::per_message_deflate(config, role);
+    stream.set_deflate(rewriter);
+    (deflater, inflater)
+  });
+  let is_h2 = stream.is_h2();
+  let mut ws = WebSocket::after_handshake(stream, role);
+  // We currently don't support vectored writes in the H2 streams, and frames
+  // that need rewriting for compression are written one buffer at a time.
+  if is_h2 || compression.is_some() {
+    ws.set_writev(false);
+  }
+  (ws, compression)
+}
+
+/// `extensions` is the `Sec-WebSocket-Extensions` header sent with the
+/// handshake response, if any.
 pub fn ws_create_server_stream(
   state: &mut OpState,
   transport: NetworkStream,
   read_buf: Bytes,
+  extensions: Option<&str>,
 ) -> Result<ResourceId, AnyError> {
-  let mut ws = WebSocket::after_handshake(
+  let deflate = extensions
+    .map(DeflateConfig::from_response)
+    .transpose()?
+    .flatten();
+  let (mut ws, compression) = after_handshake(
     WebSocketStream::new(
       stream::WsStreamKind::Network(transport),
       Some(read_buf),
     ),
     Role::Server,
+    deflate.as_ref(),
   );
-  ws.set_writev(*USE_WRITEV);
+  if compression.is_none() {
+    ws.set_writev(*USE_WRITEV);
+  }
   ws.set_auto_close(true);
   ws.set_auto_pong(true);
 
-  let rid = state.resource_table.add(ServerWebSocket::new(ws));
+  let rid = state
+    .resource_table
+    .add(ServerWebSocket::new(ws, compression));
   Ok(rid)
 }
 
+/// Answer a client's `permessage-deflate` offer, returning the
+/// `Sec-WebSocket-Extensions` value to respond with if one was accepted.
+#[op2]
+#[string]
+pub fn op_ws_negotiate_deflate(
+  #[string] offers: String,
+  #[serde] options: DeflateOptions,
+) -> Result<Option<String>, AnyError> {
+  options.validate()?;
+  Ok(options.server_accept(&offers))
+}
+
 fn send_binary(state: &mut OpState, rid: ResourceId, data: &[u8]) {
   let resource = state.resource_table.get::<ServerWebSocket>(rid).unwrap();
   let data = data.to_vec();
@@ -790,6 +896,16 @@ pub async fn op_ws_next_event(
         return MessageKind::Error as u16;
       }
     };
+    let val = match &resource.inflater {
+      Some(inflater) => match inflater.borrow_mut().inflate(val) {
+        Ok(val) => val,
+        Err(err) => {
+          resource.set_error(Some(err.to_string()));
+          return MessageKind::Error as u16;
+        }
+      },
+      None => val,
+    };
 
     break match val.opcode {
       OpCode::Text => match String::from_utf8(val.payload.to_vec()) {
@@ -847,6 +963,7 @@ deno_core::extension!(deno_websocket,
     op_ws_send_text_async,
     op_ws_send_ping,
     op_ws_get_buffered_amount,
+    op_ws_negotiate_deflate,
   ],
   esm = [ "01_websocket.js", "02_websocketstream.js" ],
   options = {
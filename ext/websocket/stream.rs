@@ -1,4 +1,5 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+use crate::deflate::FrameRewriter;
 use bytes::Buf;
 use bytes::Bytes;
 use deno_net::raw::NetworkStream;
@@ -24,6 +25,7 @@ pub(crate) enum WsStreamKind {
 pub(crate) struct WebSocketStream {
   stream: WsStreamKind,
   pre: Option<Bytes>,
+  deflate: Option<FrameRewriter>,
 }
 
 impl WebSocketStream {
@@ -31,14 +33,21 @@ impl WebSocketStream {
     Self {
       stream,
       pre: buffer,
+      deflate: None,
     }
   }
-}
 
-impl AsyncRead for WebSocketStream {
-  // From hyper's Rewind (https://github.com/hyperium/hyper), MIT License, Copyright (c) Sean McArthur
-  fn poll_read(
-    mut self: Pin<&mut Self>,
+  /// Rewrite frame headers for `permessage-deflate` from here on.
+  pub fn set_deflate(&mut self, rewriter: FrameRewriter) {
+    self.deflate = Some(rewriter);
+  }
+
+  pub fn is_h2(&self) -> bool {
+    matches!(self.stream, WsStreamKind::H2(..))
+  }
+
+  fn poll_read_raw(
+    &mut self,
     cx: &mut std::task::Context<'_>,
     buf: &mut ReadBuf<'_>,
   ) -> Poll<std::io::Result<()>> {
@@ -85,41 +94,75 @@ impl AsyncRead for WebSocketStream {
   }
 }
 
-impl AsyncWrite for WebSocketStream {
-  fn poll_write(
+impl AsyncRead for WebSocketStream {
+  // From hyper's Rewind (https://github.com/hyperium/hyper), MIT License, Copyright (c) Sean McArthur
+  fn poll_read(
     mut self: Pin<&mut Self>,
     cx: &mut std::task::Context<'_>,
-    buf: &[u8],
-  ) -> std::task::Poll<Result<usize, std::io::Error>> {
-    match &mut self.stream {
-      WsStreamKind::Network(stream) => Pin::new(stream).poll_write(cx, buf),
-      WsStreamKind::Upgraded(stream) => Pin::new(stream).poll_write(cx, buf),
-      WsStreamKind::H2(send, _) => {
-        // Zero-length write succeeds
-        if buf.is_empty() {
-          return Poll::Ready(Ok(0));
-        }
+    buf: &mut ReadBuf<'_>,
+  ) -> Poll<std::io::Result<()>> {
+    let before = buf.filled().len();
+    ready!(self.poll_read_raw(cx, buf))?;
+    if let Some(deflate) = &mut self.deflate {
+      deflate.on_read(&mut buf.filled_mut()[before..]);
+    }
+    Poll::Ready(Ok(()))
+  }
+}
 
-        send.reserve_capacity(buf.len());
-        let res = ready!(send.poll_capacity(cx));
+fn poll_write_stream(
+  stream: &mut WsStreamKind,
+  cx: &mut std::task::Context<'_>,
+  buf: &[u8],
+) -> std::task::Poll<Result<usize, std::io::Error>> {
+  match stream {
+    WsStreamKind::Network(stream) => Pin::new(stream).poll_write(cx, buf),
+    WsStreamKind::Upgraded(stream) => Pin::new(stream).poll_write(cx, buf),
+    WsStreamKind::H2(send, _) => {
+      // Zero-length write succeeds
+      if buf.is_empty() {
+        return Poll::Ready(Ok(0));
+      }
 
-        // TODO(mmastrac): the documentation is not entirely clear what to do here, so we'll continue
-        _ = res;
+      send.reserve_capacity(buf.len());
+      let res = ready!(send.poll_capacity(cx));
 
-        // We'll try to send whatever we have capacity for
-        let size = std::cmp::min(buf.len(), send.capacity());
-        assert!(size > 0);
+      // TODO(mmastrac): the documentation is not entirely clear what to do here, so we'll continue
+      _ = res;
 
-        let buf: Bytes = Bytes::copy_from_slice(&buf[0..size]);
-        let len = buf.len();
-        // TODO(mmastrac): surface the h2 error?
-        let res = send
-          .send_data(buf, false)
-          .map_err(|_| std::io::Error::from(ErrorKind::Other));
-        Poll::Ready(res.map(|_| len))
-      }
+      // We'll try to send whatever we have capacity for
+      let size = std::cmp::min(buf.len(), send.capacity());
+      assert!(size > 0);
+
+      let buf: Bytes = Bytes::copy_from_slice(&buf[0..size]);
+      let len = buf.len();
+      // TODO(mmastrac): surface the h2 error?
+      let res = send
+        .send_data(buf, false)
+        .map_err(|_| std::io::Error::from(ErrorKind::Other));
+      Poll::Ready(res.map(|_| len))
     }
   }
+}
+
+impl AsyncWrite for WebSocketStream {
+  fn poll_write(
+    mut self: Pin<&mut Self>,
+    cx: &mut std::task::Context<'_>,
+    buf: &[u8],
+  ) -> std::task::Poll<Result<usize, std::io::Error>> {
+    let this = &mut *self;
+    let Some(deflate) = &mut this.deflate else {
+      return poll_write_stream(&mut this.stream, cx, buf);
+    };
+    let n = ready!(poll_write_stream(
+      &mut this.stream,
+      cx,
+      deflate.prepare_write(buf)
+    ))?;
+    deflate.on_written(n);
+    Poll::Ready(Ok(n))
+  }
 
   fn poll_flush(
     mut self: Pin<&mut Self>,
@@ -150,6 +193,9 @@ impl AsyncWrite for WebSocketStream {
   }
 
   fn is_write_vectored(&self) -> bool {
+    if self.deflate.is_some() {
+      return false;
+    }
     match &self.stream {
       WsStreamKind::Network(stream) => stream.is_write_vectored(),
       WsStreamKind::Upgraded(stream) => stream.is_write_vectored(),
@@ -162,6 +208,14 @@ impl AsyncWrite for WebSocketStream {
     cx: &mut std::task::Context<'_>,
     bufs: &[std::io::IoSlice<'_>],
   ) -> std::task::Poll<Result<usize, std::io::Error>> {
+    if self.deflate.is_some() {
+      // Frame headers are rewritten in a copy, one buffer at a time.
+      let buf = bufs
+        .iter()
+        .find(|buf| !buf.is_empty())
+        .map_or(&[][..], |buf| &**buf);
+      return self.poll_write(cx, buf);
+    }
     match &mut self.stream {
       WsStreamKind::Network(stream) => {
         Pin::new(stream).poll_write_vectored(cx, bufs)
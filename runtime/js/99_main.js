@@ -55,6 +55,10 @@ const {
   isNativeError,
 } = core;
 import { registerDeclarativeServer } from "ext:deno_http/00_serve.ts";
+import {
+  addSignalListener,
+  removeSignalListener,
+} from "ext:runtime/40_signals.js";
 import * as event from "ext:deno_web/02_event.js";
 import * as location from "ext:deno_web/12_location.js";
 import * as version from "ext:runtime/01_version.ts";
@@ -709,6 +713,7 @@ function bootstrapMainRuntime(runtimeOptions, warmup = false) {
       11: mode,
       12: servePort,
       13: serveHost,
+      14: serveParallel,
     } = runtimeOptions;
 
     if (mode === executionModes.run || mode === executionModes.serve) {
@@ -746,7 +751,20 @@ function bootstrapMainRuntime(runtimeOptions, warmup = false) {
             );
           }
           if (mode === executionModes.serve) {
-            serve({ servePort, serveHost });
+            const server = serve({ servePort, serveHost, serveParallel });
+            if (serveParallel) {
+              // The `deno serve --parallel` supervisor sends SIGTERM to ask
+              // workers to finish their in-flight requests and exit.
+              const onTerminate = () => {
+                removeSignalListener("SIGTERM", onTerminate);
+                server.shutdown();
+              };
+              addSignalListener("SIGTERM", onTerminate);
+              PromisePrototypeThen(
+                server.finished,
+                () => removeSignalListener("SIGTERM", onTerminate),
+              );
+            }
           }
         }
       });
@@ -95,6 +95,9 @@ pub struct BootstrapOptions {
   // Used by `deno serve`
   pub serve_port: Option<u16>,
   pub serve_host: Option<String>,
+  /// Set when this is one of the processes started by `deno serve --parallel`,
+  /// which share the port through `SO_REUSEPORT`.
+  pub serve_parallel: bool,
 }
 
 impl Default for BootstrapOptions {
@@ -132,6 +135,7 @@ impl Default for BootstrapOptions {
       mode: WorkerExecutionMode::None,
       serve_port: Default::default(),
       serve_host: Default::default(),
+      serve_parallel: false,
     }
   }
 }
@@ -175,6 +179,8 @@ struct BootstrapV8<'a>(
   u16,
   // serve host
   Option<&'a str>,
+  // serve parallel
+  bool,
 );
 
 impl BootstrapOptions {
@@ -201,6 +207,7 @@ impl BootstrapOptions {
       self.mode as u8 as _,
       self.serve_port.unwrap_or_default(),
       self.serve_host.as_deref(),
+      self.serve_parallel,
     );
 
     bootstrap.serialize(ser).unwrap()
@@ -833,7 +833,7 @@ testing[WILDCARD]this
     .args("compile --output binary main.ts")
     .run()
     .assert_exit_code(0)
-    .assert_matches_text("Check file:///[WILDLINE]/main.ts\nCompile file:///[WILDLINE]/main.ts to binary[WILDLINE]\n");
+    .assert_matches_text("Check file:///[WILDLINE]/main.ts\nCompile file:///[WILDLINE]/main.ts to binary[WILDLINE]\nSize:[WILDCARD]");
 
   context
     .new_command()
@@ -1115,6 +1115,7 @@ Compile file:///[WILDCARD]/main.ts to [WILDCARD]
 Warning Failed resolving symlink. Ignoring.
     Path: [WILDCARD]
     Message: [WILDCARD])
+Size:[WILDCARD]
 "#,
   );
 
@@ -1296,3 +1297,67 @@ fn standalone_jsr_dynamic_import() {
   output.assert_exit_code(0);
   output.assert_matches_text("Hello world\n");
 }
+
+#[test]
+fn standalone_code_cache() {
+  let context = TestContextBuilder::new().build();
+  let dir = context.temp_dir();
+  let exe = if cfg!(windows) {
+    dir.path().join("standalone_code_cache.exe")
+  } else {
+    dir.path().join("standalone_code_cache")
+  };
+  context
+    .new_command()
+    .args_vec([
+      "compile",
+      "--log-level=debug",
+      "--output",
+      &exe.to_string_lossy(),
+      "./compile/standalone_code_cache/main.ts",
+    ])
+    .run()
+    .skip_output_check()
+    .assert_exit_code(0);
+
+  // the first run populates the cache
+  let output = context.new_command().name(&exe).run();
+  output.assert_exit_code(0);
+  assert_contains!(output.combined_output(), "Hello world");
+  assert_contains!(
+    output.combined_output(),
+    "Updating V8 code cache for ES module"
+  );
+  assert_not_contains!(output.combined_output(), "V8 code cache hit");
+  // the cache is stored in the user's DENO_DIR
+  assert!(context
+    .deno_dir()
+    .path()
+    .join("compile_code_cache_v1")
+    .exists());
+
+  // the second run uses it
+  let output = context.new_command().name(&exe).run();
+  output.assert_exit_code(0);
+  assert_contains!(output.combined_output(), "Hello world");
+  assert_contains!(output.combined_output(), "V8 code cache hit for ES module");
+
+  // the cache is not used with --no-code-cache
+  context
+    .new_command()
+    .args_vec([
+      "compile",
+      "--log-level=debug",
+      "--no-code-cache",
+      "--output",
+      &exe.to_string_lossy(),
+      "./compile/standalone_code_cache/main.ts",
+    ])
+    .run()
+    .skip_output_check()
+    .assert_exit_code(0);
+  let output = context.new_command().name(&exe).run();
+  output.assert_exit_code(0);
+  assert_contains!(output.combined_output(), "Hello world");
+  assert_not_contains!(output.combined_output(), "V8 code cache");
+}
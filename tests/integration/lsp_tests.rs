@@ -3566,6 +3566,107 @@ fn lsp_selection_range() {
   client.shutdown();
 }
 
+#[test]
+fn lsp_linked_editing_range() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let mut client = context.new_lsp_command().build();
+  client.initialize_default();
+  client.did_open(json!({
+    "textDocument": {
+      "uri": "file:///a/file.tsx",
+      "languageId": "typescriptreact",
+      "version": 1,
+      "text": "const a = <div>hello</div>;\n",
+    }
+  }));
+  let res = client.write_request(
+    "textDocument/linkedEditingRange",
+    json!({
+      "textDocument": { "uri": "file:///a/file.tsx" },
+      "position": { "line": 0, "character": 12 },
+    }),
+  );
+  assert_eq!(
+    res,
+    json!({
+      "ranges": [
+        {
+          "start": { "line": 0, "character": 11 },
+          "end": { "line": 0, "character": 14 },
+        },
+        {
+          "start": { "line": 0, "character": 22 },
+          "end": { "line": 0, "character": 25 },
+        },
+      ],
+      "wordPattern": "[a-zA-Z0-9:\\-\\._$]*",
+    })
+  );
+  client.shutdown();
+}
+
+#[test]
+fn lsp_inline_value() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let mut client = context.new_lsp_command().build();
+  client.initialize_default();
+  client.did_open(json!({
+    "textDocument": {
+      "uri": "file:///a/file.ts",
+      "languageId": "typescript",
+      "version": 1,
+      "text": "const a = 1;\nconst b = { c: a };\nconsole.log(b.c);\n",
+    }
+  }));
+  let res = client.write_request(
+    "textDocument/inlineValue",
+    json!({
+      "textDocument": { "uri": "file:///a/file.ts" },
+      "range": {
+        "start": { "line": 0, "character": 0 },
+        "end": { "line": 3, "character": 0 },
+      },
+      "context": {
+        "frameId": 1,
+        "stoppedLocation": {
+          "start": { "line": 1, "character": 0 },
+          "end": { "line": 1, "character": 19 },
+        },
+      },
+    }),
+  );
+  assert_eq!(
+    res,
+    json!([
+      {
+        "range": {
+          "start": { "line": 0, "character": 6 },
+          "end": { "line": 0, "character": 7 },
+        },
+        "variableName": "a",
+        "caseSensitiveLookup": true,
+      },
+      {
+        "range": {
+          "start": { "line": 1, "character": 6 },
+          "end": { "line": 1, "character": 7 },
+        },
+        "variableName": "b",
+        "caseSensitiveLookup": true,
+      },
+      {
+        "range": {
+          "start": { "line": 1, "character": 15 },
+          "end": { "line": 1, "character": 16 },
+        },
+        "variableName": "a",
+        "caseSensitiveLookup": true,
+      },
+    ])
+  );
+  client.shutdown();
+}
+
 #[test]
 fn lsp_semantic_tokens() {
   let context = TestContextBuilder::new().use_temp_cwd().build();
@@ -9313,6 +9414,222 @@ fn lsp_npmrc() {
   client.shutdown();
 }
 
+#[test]
+fn lsp_pull_diagnostics() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let mut client = context.new_lsp_command().build();
+  client.initialize(|builder| {
+    builder.with_capabilities(|capabilities| {
+      capabilities.text_document.as_mut().unwrap().diagnostic =
+        Some(Default::default());
+      capabilities.workspace.as_mut().unwrap().diagnostic =
+        Some(lsp::DiagnosticWorkspaceClientCapabilities {
+          refresh_support: Some(true),
+        });
+    });
+  });
+  // diagnostics are generated in the background, but not published
+  let diagnostics = client.did_open(json!({
+    "textDocument": {
+      "uri": "file:///a/file.ts",
+      "languageId": "typescript",
+      "version": 1,
+      "text": "export const a: number = \"a\";\n"
+    }
+  }));
+  assert_eq!(diagnostics.all_messages(), vec![]);
+  let (_, method, _) = client.read_request::<Value>();
+  assert_eq!(method, "workspace/diagnostic/refresh");
+  let res = client.write_request(
+    "textDocument/diagnostic",
+    json!({
+      "textDocument": {
+        "uri": "file:///a/file.ts",
+      },
+    }),
+  );
+  assert_eq!(res["kind"], json!("full"));
+  assert_eq!(
+    res["items"],
+    json!([{
+      "range": {
+        "start": { "line": 0, "character": 13 },
+        "end": { "line": 0, "character": 14 },
+      },
+      "severity": 1,
+      "code": 2322,
+      "source": "deno-ts",
+      "message": "Type 'string' is not assignable to type 'number'.",
+    }])
+  );
+  let result_id = res["resultId"].clone();
+  let res = client.write_request(
+    "textDocument/diagnostic",
+    json!({
+      "textDocument": {
+        "uri": "file:///a/file.ts",
+      },
+      "previousResultId": result_id,
+    }),
+  );
+  assert_eq!(res, json!({ "kind": "unchanged", "resultId": result_id }));
+  client.shutdown();
+}
+
+#[test]
+fn lsp_document_links() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let mut client = context.new_lsp_command().build();
+  client.initialize_default();
+  client.did_open(json!({
+    "textDocument": {
+      "uri": "file:///a/file.ts",
+      "languageId": "typescript",
+      "version": 1,
+      "text": "import \"./b.ts\";\nimport \"https://deno.land/x/a/mod.ts\";\nimport \"npm:chalk@5\";\n"
+    }
+  }));
+  let res = client.write_request(
+    "textDocument/documentLink",
+    json!({
+      "textDocument": {
+        "uri": "file:///a/file.ts",
+      },
+    }),
+  );
+  assert_eq!(
+    res,
+    json!([
+      {
+        "range": {
+          "start": { "line": 0, "character": 8 },
+          "end": { "line": 0, "character": 14 },
+        },
+        "target": "file:///a/b.ts",
+      },
+      {
+        "range": {
+          "start": { "line": 1, "character": 8 },
+          "end": { "line": 1, "character": 36 },
+        },
+        "target": "https://deno.land/x/a/mod.ts",
+      },
+      {
+        "range": {
+          "start": { "line": 2, "character": 8 },
+          "end": { "line": 2, "character": 19 },
+        },
+        "target": "https://www.npmjs.com/package/chalk",
+      },
+    ])
+  );
+  client.shutdown();
+}
+
+#[test]
+fn lsp_code_actions_add_missing_dependency() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write("deno.json", json!({ "imports": {} }).to_string());
+  let mut client = context.new_lsp_command().build();
+  client.initialize_default();
+  let diagnostics = client.did_open(json!({
+    "textDocument": {
+      "uri": temp_dir.uri().join("file.ts").unwrap(),
+      "languageId": "typescript",
+      "version": 1,
+      "text": "import \"@std/fs\";\n",
+    }
+  }));
+  let diagnostic = diagnostics
+    .messages_with_file_and_source(
+      temp_dir.uri().join("file.ts").unwrap().as_str(),
+      "deno",
+    )
+    .diagnostics
+    .into_iter()
+    .find(|d| {
+      d.code == Some(lsp::NumberOrString::String("resolver-error".to_string()))
+    })
+    .unwrap();
+  assert_eq!(diagnostic.data, Some(json!({ "specifier": "@std/fs" })));
+  let res = client.write_request(
+    "textDocument/codeAction",
+    json!({
+      "textDocument": {
+        "uri": temp_dir.uri().join("file.ts").unwrap(),
+      },
+      "range": diagnostic.range,
+      "context": {
+        "diagnostics": [diagnostic],
+        "only": ["quickfix"],
+      },
+    }),
+  );
+  assert_eq!(
+    res[0]["title"],
+    json!("Add \"jsr:@std/fs\" to the imports of deno.json.")
+  );
+  assert_eq!(res[0]["kind"], json!("quickfix"));
+  assert!(res[0]["edit"]["changes"]
+    [temp_dir.uri().join("deno.json").unwrap().as_str()]
+  .is_array());
+  client.shutdown();
+}
+
+#[test]
+fn lsp_code_actions_remove_unused_dependencies() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write(
+    "deno.json",
+    json!({
+      "imports": {
+        "@std/fs": "jsr:@std/fs@^1",
+        "@std/path": "jsr:@std/path@^1",
+      },
+    })
+    .to_string(),
+  );
+  let mut client = context.new_lsp_command().build();
+  client.initialize_default();
+  client.did_open(json!({
+    "textDocument": {
+      "uri": temp_dir.uri().join("file.ts").unwrap(),
+      "languageId": "typescript",
+      "version": 1,
+      "text": "import \"@std/path/posix\";\n",
+    }
+  }));
+  let res = client.write_request(
+    "textDocument/codeAction",
+    json!({
+      "textDocument": {
+        "uri": temp_dir.uri().join("file.ts").unwrap(),
+      },
+      "range": {
+        "start": { "line": 0, "character": 0 },
+        "end": { "line": 0, "character": 0 },
+      },
+      "context": {
+        "diagnostics": [],
+        "only": ["source.removeUnusedDependencies"],
+      },
+    }),
+  );
+  let action = res
+    .as_array()
+    .unwrap()
+    .iter()
+    .find(|a| a["kind"] == json!("source.removeUnusedDependencies"))
+    .unwrap();
+  assert_eq!(action["title"], json!("Remove unused dependencies"));
+  assert!(action["edit"]["changes"]
+    [temp_dir.uri().join("deno.json").unwrap().as_str()]
+  .is_array());
+  client.shutdown();
+}
+
 #[test]
 fn lsp_diagnostics_warn_redirect() {
   let context = TestContextBuilder::new()
@@ -10226,6 +10543,105 @@ fn lsp_format_json() {
   client.shutdown();
 }
 
+#[test]
+fn lsp_format_range() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let mut client = context.new_lsp_command().build();
+  client.initialize_default();
+  client.did_open(json!({
+    "textDocument": {
+      "uri": "file:///a/file.ts",
+      "languageId": "typescript",
+      "version": 1,
+      "text": "const a=1;\nfunction f() {\n  const b=2;\n    const c=3;\n}\n"
+    }
+  }));
+  // only the selected statement of the function body is formatted
+  let res = client.write_request(
+    "textDocument/rangeFormatting",
+    json!({
+      "textDocument": {
+        "uri": "file:///a/file.ts",
+      },
+      "range": {
+        "start": { "line": 2, "character": 9 },
+        "end": { "line": 2, "character": 9 },
+      },
+      "options": {
+        "tabSize": 2,
+        "insertSpaces": true,
+      },
+    }),
+  );
+  assert_eq!(
+    res,
+    json!([
+      {
+        "range": {
+          "start": { "line": 2, "character": 9 },
+          "end": { "line": 2, "character": 9 }
+        },
+        "newText": " "
+      }, {
+        "range": {
+          "start": { "line": 2, "character": 10 },
+          "end": { "line": 2, "character": 10 }
+        },
+        "newText": " "
+      }
+    ])
+  );
+  client.shutdown();
+}
+
+#[test]
+fn lsp_format_on_type() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let mut client = context.new_lsp_command().build();
+  client.initialize_default();
+  client.did_open(json!({
+    "textDocument": {
+      "uri": "file:///a/file.ts",
+      "languageId": "typescript",
+      "version": 1,
+      "text": "const a=1;\nconst b=2;\n"
+    }
+  }));
+  let res = client.write_request(
+    "textDocument/onTypeFormatting",
+    json!({
+      "textDocument": {
+        "uri": "file:///a/file.ts",
+      },
+      "position": { "line": 1, "character": 10 },
+      "ch": ";",
+      "options": {
+        "tabSize": 2,
+        "insertSpaces": true,
+      },
+    }),
+  );
+  assert_eq!(
+    res,
+    json!([
+      {
+        "range": {
+          "start": { "line": 1, "character": 7 },
+          "end": { "line": 1, "character": 7 }
+        },
+        "newText": " "
+      }, {
+        "range": {
+          "start": { "line": 1, "character": 8 },
+          "end": { "line": 1, "character": 8 }
+        },
+        "newText": " "
+      }
+    ])
+  );
+  client.shutdown();
+}
+
 #[test]
 fn lsp_format_editor_options() {
   let context = TestContextBuilder::new().use_temp_cwd().build();
@@ -11607,6 +12023,8 @@ struct TestData {
   label: String,
   steps: Option<Vec<TestData>>,
   range: Option<lsp::Range>,
+  #[serde(default)]
+  is_bench: bool,
 }
 
 #[derive(Debug, Deserialize, PartialEq)]
@@ -11889,6 +12307,124 @@ Deno.test({
   client.shutdown();
 }
 
+#[test]
+fn lsp_testing_api_bench() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+
+  let contents = r#"
+Deno.bench("bench a", () => {});
+"#;
+  temp_dir.write("./bench.ts", contents);
+  temp_dir.write("./deno.jsonc", "{}");
+  let specifier = temp_dir.uri().join("bench.ts").unwrap();
+
+  let mut client = context.new_lsp_command().build();
+  client.initialize_default();
+
+  client.did_open(json!({
+    "textDocument": {
+      "uri": specifier,
+      "languageId": "typescript",
+      "version": 1,
+      "text": contents,
+    }
+  }));
+
+  let notification =
+    client.read_notification_with_method::<Value>("deno/testModule");
+  let params: TestModuleNotificationParams =
+    serde_json::from_value(notification.unwrap()).unwrap();
+  assert_eq!(params.text_document.uri, specifier);
+  assert_eq!(params.kind, TestModuleNotificationKind::Replace);
+  assert_eq!(params.tests.len(), 1);
+  let bench = &params.tests[0];
+  assert_eq!(bench.label, "bench a");
+  assert!(bench.is_bench);
+  assert_eq!(
+    bench.range,
+    Some(lsp::Range {
+      start: lsp::Position {
+        line: 1,
+        character: 5,
+      },
+      end: lsp::Position {
+        line: 1,
+        character: 10,
+      }
+    })
+  );
+
+  let res = client.write_request_with_res_as::<TestRunResponseParams>(
+    "deno/testRun",
+    json!({
+      "id": 1,
+      "kind": "run",
+    }),
+  );
+  assert_eq!(res.enqueued.len(), 1);
+  assert_eq!(res.enqueued[0].ids, vec![bench.id.clone()]);
+  let test = json!({
+    "textDocument": {
+      "uri": specifier,
+    },
+    "id": bench.id,
+  });
+
+  let notification =
+    client.read_notification_with_method::<Value>("deno/testRunProgress");
+  assert_eq!(
+    notification,
+    Some(json!({
+      "id": 1,
+      "message": {
+        "type": "started",
+        "test": test,
+      }
+    }))
+  );
+
+  let notification = client
+    .read_notification_with_method::<Value>("deno/testRunProgress")
+    .unwrap();
+  let message = notification.get("message").unwrap();
+  assert_eq!(message.get("type"), Some(&json!("output")));
+  assert_eq!(message.get("test"), Some(&test));
+  assert!(message
+    .get("value")
+    .unwrap()
+    .as_str()
+    .unwrap()
+    .contains("/iter"));
+
+  let notification =
+    client.read_notification_with_method::<Value>("deno/testRunProgress");
+  assert_eq!(
+    notification,
+    Some(json!({
+      "id": 1,
+      "message": {
+        "type": "passed",
+        "test": test,
+      }
+    }))
+  );
+
+  let notification =
+    client.read_notification_with_method::<Value>("deno/testRunProgress");
+  assert_eq!(
+    notification,
+    Some(json!({
+      "id": 1,
+      "message": {
+        "type": "end",
+      }
+    }))
+  );
+
+  client.shutdown();
+}
+
 #[test]
 fn lsp_closed_file_find_references() {
   let context = TestContextBuilder::new().use_temp_cwd().build();
@@ -91,3 +91,53 @@ async fn deno_serve_no_args() {
   child.kill().unwrap();
   child.wait().unwrap();
 }
+
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn deno_serve_parallel() {
+  use nix::sys::signal::kill;
+  use nix::sys::signal::Signal;
+  use nix::unistd::Pid;
+
+  let port = 4561;
+  let mut child = util::deno_cmd()
+    .current_dir(util::testdata_path())
+    .arg("serve")
+    .arg("--parallel=2")
+    .arg("--port")
+    .arg(port.to_string())
+    .arg("./serve/no_args.ts")
+    .stdout_piped()
+    .spawn()
+    .unwrap();
+
+  let client = reqwest::Client::new();
+  let mut body = None;
+  for _ in 0..100 {
+    if let Ok(res) = client.get(format!("http://127.0.0.1:{port}")).send().await
+    {
+      body = Some(res.text().await.unwrap());
+      break;
+    }
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+  }
+  assert_eq!(
+    body.as_deref(),
+    Some("deno serve with no args in fetch() works!")
+  );
+
+  // Shutting down the supervisor gracefully stops all of the workers.
+  kill(Pid::from_raw(child.id() as i32), Signal::SIGTERM).unwrap();
+  let status = child.wait().unwrap();
+  assert!(status.success());
+
+  let mut stdout = String::new();
+  child
+    .stdout
+    .as_mut()
+    .unwrap()
+    .read_to_string(&mut stdout)
+    .unwrap();
+  assert!(stdout.contains("[worker 0]"), "{stdout}");
+  assert!(stdout.contains("[worker 1]"), "{stdout}");
+}
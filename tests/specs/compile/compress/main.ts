@@ -0,0 +1,5 @@
+const text = Deno.readTextFileSync(import.meta.dirname + "/data/lines.txt");
+const lines = text.trimEnd().split("\n");
+console.log(lines.length);
+console.log(lines[0]);
+console.log(lines[199]);
@@ -0,0 +1,5 @@
+const dataDir = import.meta.dirname + "/data";
+console.log(Deno.readTextFileSync(dataDir + "/a.txt").trim());
+console.log(JSON.parse(Deno.readTextFileSync(dataDir + "/nested/b.json")));
+const names = Array.from(Deno.readDirSync(dataDir)).map((entry) => entry.name);
+console.log(names.sort());
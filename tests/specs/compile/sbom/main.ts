@@ -0,0 +1,3 @@
+import { join } from "jsr:@std/url@0.220/join";
+
+console.log(join("https://deno.land", "std").href);
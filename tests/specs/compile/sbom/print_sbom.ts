@@ -0,0 +1,14 @@
+const document = JSON.parse(Deno.readTextFileSync(Deno.args[0]));
+if (document.bomFormat === "CycloneDX") {
+  for (const component of document.components) {
+    console.log(component.purl, component.hashes?.[0].alg ?? "no hash");
+  }
+} else {
+  for (const pkg of document.packages) {
+    console.log(
+      pkg.name,
+      pkg.versionInfo ?? "-",
+      pkg.checksums?.[0].algorithm ?? "no checksum",
+    );
+  }
+}
@@ -0,0 +1,4 @@
+const card = html`<div   class="card"><h1>${title}</h1>
+<p>${body}</p></div>`;
+const query = sql`select id, name from users where id = ${id}`;
+const user = gql`query { user(id: ${id}) { name } }`;
@@ -0,0 +1,2 @@
+console.log(Deno.readTextFileSync("./my_app/deno.json"));
+console.log(Deno.readTextFileSync("./my_app/src/my_app.ts"));
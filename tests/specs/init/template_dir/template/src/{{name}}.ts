@@ -0,0 +1 @@
+console.log("{{name}} by {{author}}");
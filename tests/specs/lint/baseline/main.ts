@@ -0,0 +1,2 @@
+if (Math.random()) {}
+if (Math.random()) {}
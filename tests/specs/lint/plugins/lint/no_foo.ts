@@ -0,0 +1,22 @@
+export default {
+  name: "my-plugin",
+  rules: {
+    "no-foo": {
+      create(context) {
+        return {
+          Identifier(node) {
+            if (node.value === "foo") {
+              context.report({
+                node,
+                message: `Don't name things "${context.getText(node)}"`,
+                hint: 'Use "bar" instead',
+                fix: { node, text: "bar" },
+                fixDescription: 'Rename to "bar"',
+              });
+            }
+          },
+        };
+      },
+    },
+  },
+} satisfies Deno.lint.Plugin;
@@ -0,0 +1,5 @@
+const foo = "foo";
+console.log(foo);
+
+// deno-lint-ignore my-plugin/no-foo
+export const exported = (foo: string) => foo;
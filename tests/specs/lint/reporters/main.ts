@@ -0,0 +1 @@
+if (Math.random()) {}
@@ -0,0 +1,3 @@
+import { lib } from "@scope/lib";
+
+export const app = `app ${lib}`;
@@ -1171,6 +1171,24 @@ Deno.test(
   },
 );
 
+Deno.test(
+  { permissions: { net: true } },
+  async function fetchCustomClientDnsOverrides() {
+    using client = Deno.createHttpClient({
+      connectTimeout: 1000,
+      idleTimeout: 5000,
+      dnsOverrides: { "pinned.localhost.test": ["127.0.0.1"] },
+      localAddress: "127.0.0.1",
+    });
+    const response = await fetch(
+      "http://pinned.localhost.test:4545/assets/fixture.json",
+      { client },
+    );
+    assertEquals(response.status, 200);
+    await response.text();
+  },
+);
+
 Deno.test(
   { permissions: { net: true } },
   async function fetchCustomClientUserAgent(): Promise<
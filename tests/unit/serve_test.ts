@@ -92,6 +92,182 @@ Deno.test(async function httpServerShutsDownPortBeforeResolving() {
   listener!.close();
 });
 
+Deno.test(
+  { permissions: { net: true } },
+  async function httpServerMetrics() {
+    const ac = new AbortController();
+    const { promise, resolve } = Promise.withResolvers<void>();
+    const server = Deno.serve({
+      handler: () => new Response("hello"),
+      port: servePort,
+      signal: ac.signal,
+      onListen: onListen(resolve),
+    });
+    await promise;
+
+    const initial = server.metrics();
+    assertEquals(initial.totalRequests, 0);
+    assertEquals(initial.activeConnections, 0);
+
+    for (let i = 0; i < 3; i++) {
+      const resp = await fetch(`http://127.0.0.1:${servePort}/`);
+      assertEquals(await resp.text(), "hello");
+    }
+
+    const metrics = server.metrics();
+    assertEquals(metrics.totalRequests, 3);
+    assertEquals(metrics.http1Requests, 3);
+    assertEquals(metrics.http2Requests, 0);
+    assertEquals(metrics.inFlightRequests, 0);
+    assertEquals(metrics.requestDuration.count, 3);
+    assertEquals(metrics.requestDuration.buckets.length, 11);
+    assert(metrics.totalConnections >= 1);
+    assert(metrics.bytesReceived > 0);
+    assert(metrics.bytesSent > 0);
+
+    ac.abort();
+    await server.finished;
+
+    // The final numbers are still available once the server is closed.
+    assertEquals(server.metrics().totalRequests, 3);
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  async function httpServerDrainReportsAbortedRequests() {
+    const { promise: listening, resolve: onListening } = Promise
+      .withResolvers<void>();
+    const { promise: received, resolve: onReceived } = Promise.withResolvers<
+      void
+    >();
+    const { promise: release, resolve: doRelease } = Promise.withResolvers<
+      void
+    >();
+    const server = Deno.serve({
+      handler: async () => {
+        onReceived();
+        await release;
+        return new Response("late");
+      },
+      port: servePort,
+      onListen: onListen(onListening),
+    });
+    await listening;
+
+    const conn = await Deno.connect({ port: servePort });
+    await conn.write(
+      new TextEncoder().encode("GET / HTTP/1.1\r\nHost: example.com\r\n\r\n"),
+    );
+    await received;
+
+    const report = await server.drain({ timeout: 100 });
+    assertEquals(report, {
+      timedOut: true,
+      abortedRequests: 1,
+      abortedConnections: 1,
+    });
+    await server.finished;
+
+    doRelease();
+    conn.close();
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  async function httpServerDrainWaitsForInFlightRequests() {
+    const { promise: listening, resolve: onListening } = Promise
+      .withResolvers<void>();
+    const { promise: received, resolve: onReceived } = Promise.withResolvers<
+      void
+    >();
+    const server = Deno.serve({
+      handler: async () => {
+        onReceived();
+        await new Promise((resolve) => setTimeout(resolve, 50));
+        return new Response("done");
+      },
+      port: servePort,
+      onListen: onListen(onListening),
+    });
+    await listening;
+
+    const response = fetch(`http://127.0.0.1:${servePort}/`);
+    await received;
+    const report = await server.drain({ timeout: 5000 });
+    assertEquals(report.timedOut, false);
+    assertEquals(await (await response).text(), "done");
+    await server.finished;
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  async function httpServerMaxBodySize() {
+    const ac = new AbortController();
+    const { promise, resolve } = Promise.withResolvers<void>();
+    let called = false;
+    const server = Deno.serve({
+      handler: () => {
+        called = true;
+        return new Response("ok");
+      },
+      port: servePort,
+      signal: ac.signal,
+      maxBodySize: 10,
+      onListen: onListen(resolve),
+    });
+    await promise;
+
+    const resp = await fetch(`http://127.0.0.1:${servePort}/`, {
+      method: "POST",
+      body: "x".repeat(100),
+    });
+    assertEquals(resp.status, 413);
+    await resp.body?.cancel();
+    assert(!called);
+
+    ac.abort();
+    await server.finished;
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  async function httpServerMaxRequestsPerConnection() {
+    const ac = new AbortController();
+    const { promise, resolve } = Promise.withResolvers<void>();
+    const server = Deno.serve({
+      handler: () => new Response("ok"),
+      port: servePort,
+      signal: ac.signal,
+      maxRequestsPerConnection: 1,
+      onListen: onListen(resolve),
+    });
+    await promise;
+
+    const conn = await Deno.connect({ port: servePort });
+    await conn.write(
+      new TextEncoder().encode("GET / HTTP/1.1\r\nHost: example.com\r\n\r\n"),
+    );
+    // The server closes the connection after the first response.
+    let response = "";
+    const decoder = new TextDecoder();
+    const buf = new Uint8Array(1024);
+    while (true) {
+      const n = await conn.read(buf);
+      if (n === null) break;
+      response += decoder.decode(buf.subarray(0, n));
+    }
+    conn.close();
+    assertStringIncludes(response.toLowerCase(), "connection: close");
+
+    ac.abort();
+    await server.finished;
+  },
+);
+
 // When shutting down abruptly, we require that all in-progress connections are aborted,
 // no new connections are allowed, and no new transactions are allowed on existing connections.
 Deno.test(
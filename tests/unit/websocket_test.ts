@@ -805,3 +805,84 @@ Deno.test("Close connection", async () => {
   await server.finished;
   conn.close();
 });
+
+Deno.test(async function websocketPermessageDeflate() {
+  const ac = new AbortController();
+  const listeningDeferred = Promise.withResolvers<void>();
+  const message = "Hello ".repeat(1000);
+
+  const server = Deno.serve({
+    handler: (req) => {
+      const { socket, response } = Deno.upgradeWebSocket(req, {
+        compression: { serverNoContextTakeover: true },
+      });
+      assertEquals(
+        socket.extensions,
+        "permessage-deflate; server_no_context_takeover",
+      );
+      socket.onmessage = (e) => socket.send(e.data);
+      socket.onclose = () => ac.abort();
+      socket.onerror = () => fail();
+      return response;
+    },
+    signal: ac.signal,
+    onListen: () => listeningDeferred.resolve(),
+    hostname: "localhost",
+    port: servePort,
+  });
+
+  await listeningDeferred.promise;
+
+  // Compression is opt-in on the client.
+  const wss = new WebSocketStream(serveUrl, { compression: true });
+  const { extensions, readable, writable } = await wss.opened;
+  assertEquals(extensions, "permessage-deflate; server_no_context_takeover");
+
+  // Several messages, so that the compression context is reused.
+  const writer = writable.getWriter();
+  await writer.write(message);
+  await writer.write(new TextEncoder().encode(message));
+  await writer.write("");
+  const reader = readable.getReader();
+  assertEquals((await reader.read()).value, message);
+  assertEquals(
+    new TextDecoder().decode((await reader.read()).value as Uint8Array),
+    message,
+  );
+  assertEquals((await reader.read()).value, "");
+
+  wss.close();
+  await server.finished;
+});
+
+Deno.test(async function websocketNoCompressionByDefault() {
+  const ac = new AbortController();
+  const listeningDeferred = Promise.withResolvers<void>();
+
+  const server = Deno.serve({
+    handler: (req) => {
+      const { socket, response } = Deno.upgradeWebSocket(req, {
+        compression: true,
+      });
+      socket.onclose = () => ac.abort();
+      socket.onerror = () => fail();
+      return response;
+    },
+    signal: ac.signal,
+    onListen: () => listeningDeferred.resolve(),
+    hostname: "localhost",
+    port: servePort,
+  });
+
+  await listeningDeferred.promise;
+
+  const ws = new WebSocket(serveUrl);
+  ws.onerror = () => fail();
+  const opened = Promise.withResolvers<void>();
+  ws.onopen = () => opened.resolve();
+  await opened.promise;
+  assertEquals(ws.extensions, "");
+
+  ws.close();
+  await server.finished;
+});